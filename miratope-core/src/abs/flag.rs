@@ -11,6 +11,7 @@ use std::{
 
 use crate::{
     abs::{ranked::Ranked, Abstract},
+    float::Real,
     Polytope,
 };
 
@@ -33,10 +34,13 @@ fn assert_sorted(p: &Abstract) {
 /// This algorithm is basically a modified version of the merging step in
 /// mergesort.
 ///
+/// If the lists have a single common element, as happens at the boundary of an
+/// open polytope, this element is returned twice.
+///
 /// # Panics
 /// This method will behave erroneously and might panic if the lists are not
-/// sorted. Furthermore, the method will panic if the lists have less than two
-/// common elements.
+/// sorted. Furthermore, the method will panic if the lists have no common
+/// elements.
 fn common<T: AsRef<[usize]>, U: AsRef<[usize]>>(list1: T, list2: U) -> (usize, usize) {
     let list1 = list1.as_ref();
     let list2 = list2.as_ref();
@@ -44,9 +48,7 @@ fn common<T: AsRef<[usize]>, U: AsRef<[usize]>>(list1: T, list2: U) -> (usize, u
     let mut j = 0;
     let mut prev = None;
 
-    loop {
-        // We could make these unchecked if we knew for a fact that there are
-        // two common elements.
+    while i < list1.len() && j < list2.len() {
         let sub0 = list1[i];
         let sub1 = list2[j];
 
@@ -64,6 +66,9 @@ fn common<T: AsRef<[usize]>, U: AsRef<[usize]>>(list1: T, list2: U) -> (usize, u
             Ordering::Less => i += 1,
         }
     }
+
+    let only = prev.expect("The lists have no common elements.");
+    (only, only)
 }
 
 /// Represents a [flag](https://polytope.miraheze.org/wiki/Flag) in a polytope.
@@ -81,7 +86,8 @@ impl Flag {
     ///
     /// Recall that an `i`-flag change sends a flag to another that shares all
     /// elements except for the `i`-th one. In a valid (dyadic) polytope, the
-    /// resulting flag always exists and is unique.
+    /// resulting flag always exists and is unique. At the boundary of an open
+    /// polytope, where it doesn't exist, the flag is left unchanged.
     ///
     /// # Panics
     /// You must call [`Polytope::element_sort`] before calling this method.
@@ -140,7 +146,7 @@ impl Orientation {
 
     /// Returns the "sign" associated with a flag, which is either `1.0` or
     /// `-1.0`.
-    pub fn sign(&self) -> f64 {
+    pub fn sign(&self) -> Real {
         match self {
             Self::Even => 1.0,
             Self::Odd => -1.0,
@@ -455,6 +461,9 @@ impl<'a> OrientedFlagIter<'a> {
             let flag_change = self.flag_changes[self.flag_idx];
             let new_flag = current.change(self.polytope, flag_change);
 
+            // At the boundary of an open polytope, the flag change does nothing.
+            let boundary = new_flag == *current;
+
             // Increments the flag index.
             self.flag_idx = if self.flag_idx + 1 == self.flag_changes.len() {
                 self.queue.pop_front();
@@ -463,6 +472,10 @@ impl<'a> OrientedFlagIter<'a> {
                 self.flag_idx + 1
             };
 
+            if boundary {
+                return FlagNext::Repeat;
+            }
+
             let new_orientation = new_flag.orientation;
             match self.found.entry(new_flag) {
                 // If the flag is already in the found dictionary:
@@ -7,13 +7,13 @@ pub mod ranked;
 pub mod valid;
 
 use std::{
-    collections::{BTreeSet, HashMap},
+    collections::{BTreeSet, HashMap, HashSet},
     convert::Infallible,
     ops::{Index, IndexMut},
     slice, vec, iter,
 };
 
-use self::flag::{Flag, FlagSet};
+use self::flag::{Flag, FlagSet, Orientation};
 use super::Polytope;
 
 use vec_like::VecLike;
@@ -124,6 +124,10 @@ pub struct Abstract {
 
     /// Some metadata about the [`Ranks`].
     meta: Metadata,
+
+    /// The orientation of the first flag, which the orientations of all other
+    /// flags are relative to.
+    orientation: Orientation,
 }
 
 impl From<Abstract> for Ranks {
@@ -177,6 +181,7 @@ impl Abstract {
         Self {
             ranks,
             meta: Default::default(),
+            orientation: Default::default(),
         }
     }
 
@@ -207,6 +212,18 @@ impl Abstract {
         self.meta.sorted = sorted;
     }
 
+    /// Returns the orientation of the first flag of the polytope, which the
+    /// orientations of all other flags are relative to.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Reverses the orientation of the polytope, flipping the orientation of
+    /// every flag, and hence that of every facet.
+    pub fn reverse_orientation(&mut self) {
+        self.orientation = self.orientation.flip();
+    }
+
     /// Returns an iterator over the [`ElementLists`](ElementList) of each rank.
     pub fn iter(&self) -> slice::Iter<'_, ElementList> {
         self.ranks.iter()
@@ -265,6 +282,52 @@ impl Abstract {
         Some((element_hash.to_vertices(), element_hash.to_polytope(self)))
     }
 
+    /// Returns the incidence matrix between the elements of two ranks. The
+    /// entry at row `i` and column `j` says whether the `i`-th element of rank
+    /// `r0` and the `j`-th element of rank `r1` are incident, that is, whether
+    /// one of them contains the other. Returns `None` if either rank is out of
+    /// bounds.
+    pub fn incidence_matrix(&self, r0: usize, r1: usize) -> Option<Vec<Vec<bool>>> {
+        let rank = self.rank();
+        if r0 > rank || r1 > rank {
+            return None;
+        }
+
+        let (lo, hi) = if r0 <= r1 { (r0, r1) } else { (r1, r0) };
+        let mut rows = Vec::with_capacity(self.el_count(lo));
+
+        // Walks up the superelements of every element of the lower rank until
+        // reaching the higher rank.
+        for idx in 0..self.el_count(lo) {
+            let mut row = vec![false; self.el_count(lo)];
+            row[idx] = true;
+
+            for r in lo..hi {
+                let mut next = vec![false; self.el_count(r + 1)];
+
+                for (i, _) in row.iter().enumerate().filter(|(_, inc)| **inc) {
+                    for &sup in self[(r, i)].sups.iter() {
+                        next[sup] = true;
+                    }
+                }
+
+                row = next;
+            }
+
+            rows.push(row);
+        }
+
+        if r0 <= r1 {
+            Some(rows)
+        } else {
+            Some(
+                (0..self.el_count(hi))
+                    .map(|j| rows.iter().map(|row| row[j]).collect())
+                    .collect(),
+            )
+        }
+    }
+
     /// Returns the omnitruncate of a polytope, along with the flags that make
     /// up its respective vertices.
     ///
@@ -461,6 +524,91 @@ impl Abstract {
         (unsafe { builder.build() }, verts_subflags)
     }
 	
+    /// Returns the [Kleetope](https://polytope.miraheze.org/wiki/Kleetope) of
+    /// a polytope, which raises a pyramid over every facet. The apex of the
+    /// pyramid over the facet with index `f` is the vertex with index
+    /// `vertex_count + f`.
+    ///
+    /// Dyads and polytopes of lower rank are returned unchanged.
+    pub fn kis(&self) -> Self {
+        let rank = self.rank();
+        if rank < 3 {
+            return self.clone();
+        }
+
+        let vertex_count = self.vertex_count();
+        let facet_count = self.facet_count();
+
+        // The elements contained in each facet, rank by rank.
+        let mut contents = Vec::with_capacity(facet_count);
+        for f in 0..facet_count {
+            let mut facet_contents = vec![Vec::new(); rank - 1];
+            let mut els = vec![f];
+
+            for r in (1..rank - 1).rev() {
+                let mut subs: Vec<usize> = els
+                    .iter()
+                    .flat_map(|&el| self[(r + 1, el)].subs.iter().copied())
+                    .collect();
+                subs.sort_unstable();
+                subs.dedup();
+
+                facet_contents[r] = subs.clone();
+                els = subs;
+            }
+
+            contents.push(facet_contents);
+        }
+
+        let mut builder = AbstractBuilder::new();
+        builder.push_min();
+        builder.push_vertices(vertex_count + facet_count);
+
+        // Maps every element of a facet to the pyramid over it in the previous
+        // rank.
+        let mut prev_pyramids = HashMap::new();
+
+        for r in 2..rank {
+            let mut subelements = SubelementList::new();
+
+            // The facets themselves get replaced by pyramids.
+            if r < rank - 1 {
+                for el in &self[r] {
+                    subelements.push(el.subs.clone());
+                }
+            }
+
+            // Adds a pyramid over every (r - 1)-element of every facet.
+            let mut pyramids = HashMap::new();
+            for (f, facet_contents) in contents.iter().enumerate() {
+                for &el in &facet_contents[r - 1] {
+                    let mut subs = Subelements::new();
+                    subs.push(el);
+
+                    if r == 2 {
+                        subs.push(vertex_count + f);
+                    } else {
+                        for sub in &self[(r - 1, el)].subs {
+                            subs.push(prev_pyramids[&(f, *sub)]);
+                        }
+                    }
+
+                    pyramids.insert((f, el), subelements.len());
+                    subelements.push(subs);
+                }
+            }
+
+            builder.push(subelements);
+            prev_pyramids = pyramids;
+        }
+
+        builder.push_max();
+
+        // Safety: the Kleetope is obtained by replacing every facet by a
+        // pyramid over its boundary, which yields a valid polytope.
+        unsafe { builder.build() }
+    }
+
 	/// Returns whether a polytope is compound
     ///
     /// # Panics
@@ -469,6 +617,371 @@ impl Abstract {
 		let flag_set = FlagSet::new_all(self);
         flag_set.len() != self.flags().count()
     }
+
+    /// Returns the number of connected components of a polytope, where two
+    /// facets are connected whenever they share a ridge.
+    pub fn component_count(&self) -> usize {
+        let rank = self.rank();
+        match rank {
+            0 => return 0,
+            1 | 2 => return 1,
+            _ => {}
+        }
+
+        // A union-find structure on the facets.
+        fn find(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        let facet_count = self.el_count(rank - 1);
+        let mut parent: Vec<usize> = (0..facet_count).collect();
+
+        // Joins all of the facets through each ridge.
+        for ridge in &self[rank - 2] {
+            let mut sups = ridge.sups.iter();
+            if let Some(&first) = sups.next() {
+                for &facet in sups {
+                    let root = find(&mut parent, first);
+                    let other = find(&mut parent, facet);
+                    parent[other] = root;
+                }
+            }
+        }
+
+        (0..facet_count)
+            .filter(|&i| find(&mut parent, i) == i)
+            .count()
+    }
+
+    /// Returns the genus of the surface of a polyhedron, or `None` if it isn't
+    /// a connected, closed 2-manifold. For non-orientable surfaces, this is the
+    /// non-orientable genus, the number of cross-caps.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    pub fn surface_genus(&self) -> Option<i64> {
+        if self.rank() != 4 || self.component_count() != 1 {
+            return None;
+        }
+
+        // Every edge must be shared by exactly two faces.
+        if self[2].iter().any(|edge| edge.sups.len() != 2) {
+            return None;
+        }
+
+        // The faces around every vertex must form a single cycle.
+        fn find(parent: &mut HashMap<usize, usize>, mut i: usize) -> usize {
+            while parent[&i] != i {
+                let grandparent = parent[&parent[&i]];
+                parent.insert(i, grandparent);
+                i = grandparent;
+            }
+            i
+        }
+
+        for vertex in &self[1] {
+            let mut parent = HashMap::new();
+            for &edge in &vertex.sups {
+                for &face in &self[2][edge].sups {
+                    parent.insert(face, face);
+                }
+            }
+
+            for &edge in &vertex.sups {
+                let faces = &self[2][edge].sups;
+                let root = find(&mut parent, faces[0]);
+                let other = find(&mut parent, faces[1]);
+                parent.insert(other, root);
+            }
+
+            let faces: Vec<_> = parent.keys().copied().collect();
+            let roots: HashSet<_> = faces.into_iter().map(|f| find(&mut parent, f)).collect();
+            if roots.len() != 1 {
+                return None;
+            }
+        }
+
+        let euler_characteristic = self.euler_characteristic();
+
+        if self.orientable() {
+            let double_genus = 2 - euler_characteristic;
+            (double_genus >= 0 && double_genus % 2 == 0).then(|| double_genus / 2)
+        } else {
+            let genus = 2 - euler_characteristic;
+            (genus > 0).then(|| genus)
+        }
+    }
+
+    /// Returns the Euler characteristic of the boundary of a polytope, that is,
+    /// the alternating sum of its proper element counts, starting from the
+    /// vertices. This is also defined for open polytopes.
+    pub fn euler_characteristic(&self) -> i64 {
+        (1..self.rank())
+            .map(|r| {
+                let count = self.el_count(r) as i64;
+                if r % 2 == 1 {
+                    count
+                } else {
+                    -count
+                }
+            })
+            .sum()
+    }
+
+    /// Removes a facet from the polytope in place, leaving an open polytope
+    /// whose boundary consists of the ridges of the removed facet. Every other
+    /// element is kept. Returns `false` and leaves the polytope untouched if
+    /// the facet doesn't exist, if it's the only facet, or if the polytope has
+    /// rank less than 3.
+    ///
+    /// Note that the result doesn't satisfy the diamond property at its
+    /// boundary. Flag changes simply do nothing there.
+    pub fn remove_facet(&mut self, idx: usize) -> bool {
+        let rank = self.rank();
+        if rank < 3 || idx >= self.facet_count() || self.facet_count() == 1 {
+            return false;
+        }
+
+        let ranks = &mut self.ranks;
+        ranks[rank - 1].remove(idx);
+
+        // Removing and shifting the indices keeps the lists sorted.
+        for ridge in &mut ranks[rank - 2] {
+            ridge.sups.as_inner_mut().retain(|&f| f != idx);
+            for f in ridge.sups.iter_mut() {
+                if *f > idx {
+                    *f -= 1;
+                }
+            }
+        }
+
+        let facet_count = ranks[rank - 1].len();
+        ranks.max_mut().subs = (0..facet_count).collect();
+
+        true
+    }
+
+    /// Returns the indices of the ridges shared by more than two facets, such
+    /// as the edges of a polyhedron where three or more faces meet. Such a
+    /// polytope can't be a manifold.
+    pub fn non_manifold_ridges(&self) -> Vec<usize> {
+        let rank = self.rank();
+        if rank < 3 {
+            return Vec::new();
+        }
+
+        self[rank - 2]
+            .iter()
+            .enumerate()
+            .filter(|(_, ridge)| ridge.sups.len() > 2)
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Removes facets until no ridge is shared by more than two of them. At
+    /// every non-manifold ridge, the two remaining facets with the lowest
+    /// indices are kept. Returns the number of facets removed.
+    ///
+    /// As with [`Self::remove_facet`], the result may be an open polytope.
+    pub fn remove_non_manifold_facets(&mut self) -> usize {
+        let rank = self.rank();
+        let mut removed = BTreeSet::new();
+
+        for idx in self.non_manifold_ridges() {
+            let kept: Vec<_> = self[(rank - 2, idx)]
+                .sups
+                .iter()
+                .copied()
+                .filter(|f| !removed.contains(f))
+                .collect();
+
+            for &facet in kept.iter().skip(2) {
+                removed.insert(facet);
+            }
+        }
+
+        // Removing the facets from the back keeps the other indices valid.
+        removed
+            .into_iter()
+            .rev()
+            .filter(|&facet| self.remove_facet(facet))
+            .count()
+    }
+
+    /// Returns a canonical form of the polytope up to combinatorial
+    /// isomorphism. It consists of the element counts, followed by the flag
+    /// graph as seen from the starting flag that makes it lexicographically
+    /// smallest: the flags are numbered in the order a breadth-first search
+    /// finds them, and each one lists the numbers of its neighbors under every
+    /// flag change.
+    ///
+    /// Two polytopes with connected flag graphs are isomorphic if and only if
+    /// their canonical forms coincide. This takes quadratic time in the number
+    /// of flags.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    pub fn canonical_form(&self) -> Vec<usize> {
+        let rank = self.rank();
+        let mut form: Vec<usize> = self.el_count_iter().collect();
+        if rank < 2 {
+            return form;
+        }
+
+        let (_, changes) = self.flag_graph();
+        let mut best: Option<Vec<usize>> = None;
+        for start in 0..changes.len() {
+            let (_, code) = bfs_code(&changes, start);
+            if best.as_ref().map_or(true, |best| code < *best) {
+                best = Some(code);
+            }
+        }
+
+        form.extend(best.unwrap_or_default());
+        form
+    }
+
+    /// Returns the flags of the polytope, along with the indices of their
+    /// neighbors under every flag change.
+    fn flag_graph(&self) -> (Vec<Flag>, Vec<Vec<usize>>) {
+        let flags: Vec<Flag> = self.flags().collect();
+        let flag_idx: HashMap<&Flag, usize> =
+            flags.iter().enumerate().map(|(i, f)| (f, i)).collect();
+        let changes = flags
+            .iter()
+            .map(|flag| {
+                (1..self.rank())
+                    .map(|r| flag_idx[&flag.change(self, r)])
+                    .collect()
+            })
+            .collect();
+
+        (flags, changes)
+    }
+
+    /// Returns every isomorphism from the polytope to another one, as the
+    /// images of its vertices. Both polytopes must have connected flag graphs.
+    /// See [`Self::canonical_form`].
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] on both polytopes before
+    /// calling this method.
+    pub fn vertex_isomorphisms(&self, other: &Self) -> Vec<Vec<usize>> {
+        if !self.el_count_iter().eq(other.el_count_iter()) {
+            return Vec::new();
+        } else if self.rank() < 2 {
+            return vec![(0..self.vertex_count()).collect()];
+        }
+
+        // Any isomorphism is determined by the image of a single flag, and
+        // maps it to a flag from which the flag graph looks the same.
+        let (flags, changes) = self.flag_graph();
+        let (other_flags, other_changes) = other.flag_graph();
+        let (order, code) = bfs_code(&changes, 0);
+
+        (0..other_flags.len())
+            .filter_map(|start| {
+                let (other_order, other_code) = bfs_code(&other_changes, start);
+                if code != other_code {
+                    return None;
+                }
+
+                let mut vertices = vec![0; self.vertex_count()];
+                for (&i, &j) in order.iter().zip(&other_order) {
+                    vertices[flags[i][1]] = other_flags[j][1];
+                }
+                Some(vertices)
+            })
+            .collect()
+    }
+
+    /// Returns whether two polytopes with connected flag graphs are
+    /// combinatorially isomorphic. See [`Self::canonical_form`].
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] on both polytopes before
+    /// calling this method.
+    pub fn is_isomorphic(&self, other: &Self) -> bool {
+        self.el_count_iter().eq(other.el_count_iter())
+            && self.flag_count() == other.flag_count()
+            && self.canonical_form() == other.canonical_form()
+    }
+
+    /// Groups the facets of a polytope by combinatorial isomorphism. Returns
+    /// an example of each type, the index of the first facet of that type, and
+    /// the number of facets of that type, in the order they're first found.
+    pub fn facet_types(&self) -> Vec<FacetType> {
+        let rank = self.rank();
+        if rank == 0 {
+            return Vec::new();
+        }
+
+        let mut types: Vec<FacetType> = Vec::new();
+        let mut forms: HashMap<_, usize> = HashMap::new();
+
+        for idx in 0..self.el_count(rank - 1) {
+            let mut facet = self.element(rank - 1, idx).unwrap();
+            facet.element_sort();
+
+            let form = facet.canonical_form();
+            match forms.get(&form) {
+                Some(&i) => types[i].count += 1,
+                None => {
+                    forms.insert(form, types.len());
+                    types.push(FacetType {
+                        facet,
+                        example: idx,
+                        count: 1,
+                    });
+                }
+            }
+        }
+
+        types
+    }
+}
+
+/// Walks through a flag graph in breadth-first order from a starting flag.
+/// Returns the order in which the flags are found, along with the code that
+/// lists the labels of the neighbors of every flag in that order.
+fn bfs_code(changes: &[Vec<usize>], start: usize) -> (Vec<usize>, Vec<usize>) {
+    let mut label = vec![usize::MAX; changes.len()];
+    let mut order = vec![start];
+    label[start] = 0;
+
+    let mut code = Vec::with_capacity(changes.len() * changes[start].len());
+    let mut i = 0;
+    while i < order.len() {
+        for &neighbor in &changes[order[i]] {
+            if label[neighbor] == usize::MAX {
+                label[neighbor] = order.len();
+                order.push(neighbor);
+            }
+
+            code.push(label[neighbor]);
+        }
+
+        i += 1;
+    }
+
+    (order, code)
+}
+
+/// A type of facet in a polytope, up to combinatorial isomorphism.
+#[derive(Clone, Debug)]
+pub struct FacetType {
+    /// A facet of this type.
+    pub facet: Abstract,
+
+    /// The index of the first facet of this type.
+    pub example: usize,
+
+    /// The number of facets of this type.
+    pub count: usize,
 }
 
 impl Polytope for Abstract {
@@ -1061,6 +1574,84 @@ mod tests {
         }
     }
 
+    /// Checks the incidences of a square through the accessor methods.
+    #[test]
+    fn incidences() {
+        let square = Abstract::polygon(4);
+
+        assert_eq!(square.subelements(2, 3), Some(&[0, 3][..]));
+        assert_eq!(square.superelements(1, 0), Some(&[0, 3][..]));
+        assert_eq!(square.subelements(3, 0), Some(&[0, 1, 2, 3][..]));
+        assert_eq!(square.superelements(0, 0), Some(&[0, 1, 2, 3][..]));
+        assert_eq!(square.subelements(2, 4), None);
+        assert_eq!(square.superelements(4, 0), None);
+    }
+
+    /// Checks the incidence matrices of a square.
+    #[test]
+    fn incidence_matrix() {
+        let square = Abstract::polygon(4);
+
+        let vertex_edge = square.incidence_matrix(1, 2).unwrap();
+        assert_eq!(vertex_edge.len(), 4);
+        assert!(vertex_edge.iter().all(|row| row.iter().filter(|&&inc| inc).count() == 2));
+        assert_eq!(square.incidence_matrix(2, 1).unwrap()[3], vec![true, false, false, true]);
+
+        assert_eq!(square.incidence_matrix(1, 3).unwrap(), vec![vec![true]; 4]);
+        assert_eq!(square.incidence_matrix(0, 1).unwrap(), vec![vec![true; 4]]);
+        assert_eq!(square.incidence_matrix(1, 4), None);
+    }
+
+    /// Removes a face from a cube, and checks that the open polytope is still
+    /// orientable and has the expected Euler characteristic.
+    #[test]
+    fn remove_facet() {
+        let mut cube = Abstract::cube();
+        assert_eq!(cube.euler_characteristic(), 2);
+        assert!(cube.remove_facet(2));
+
+        assert_eq!(cube.el_count_iter().collect::<Vec<_>>(), vec![1, 8, 12, 5, 1]);
+        assert_eq!(cube.euler_characteristic(), 1);
+        assert!(cube.orientable_mut());
+        assert_eq!(cube.surface_genus(), None);
+        assert_eq!(cube.flags().count(), 40);
+
+        assert!(!cube.remove_facet(5));
+        assert!(!Abstract::dyad().remove_facet(0));
+    }
+
+    /// Builds three triangles sharing an edge, and removes the third one.
+    #[test]
+    fn non_manifold() {
+        let mut builder = AbstractBuilder::new();
+        builder.push_min();
+        builder.push_vertices(5);
+        builder.push(SubelementList::from(
+            [[0, 1], [0, 2], [1, 2], [0, 3], [1, 3], [0, 4], [1, 4]]
+                .iter()
+                .map(|edge| Subelements::from(edge.to_vec()))
+                .collect::<Vec<_>>(),
+        ));
+        builder.push(SubelementList::from(
+            [[0, 1, 2], [0, 3, 4], [0, 5, 6]]
+                .iter()
+                .map(|face| Subelements::from(face.to_vec()))
+                .collect::<Vec<_>>(),
+        ));
+        builder.push_max();
+
+        // Safety: this isn't a valid polytope, but it's what a broken OFF file
+        // might load as.
+        let mut fan = unsafe { builder.build() };
+        assert_eq!(fan.non_manifold_ridges(), vec![0]);
+        assert_eq!(fan.remove_non_manifold_facets(), 1);
+        assert!(fan.non_manifold_ridges().is_empty());
+        assert_eq!(fan.facet_count(), 2);
+
+        assert!(Abstract::cube().non_manifold_ridges().is_empty());
+        assert_eq!(Abstract::cube().remove_non_manifold_facets(), 0);
+    }
+
     /// Checks a tetrahedron.
     #[test]
     fn tetrahedron() {
@@ -1140,4 +1731,150 @@ mod tests {
         test(&Abstract::polygon(6).into_dual(), [1, 6, 6, 1]);
         test(&Abstract::cube().into_dual(), [1, 6, 12, 8, 1]);
     }
+
+    /// Checks the Kleetopes of a few polytopes.
+    #[test]
+    fn kis() {
+        test(&Abstract::polygon(4).kis(), [1, 8, 8, 1]);
+        test(&Abstract::tetrahedron().kis(), [1, 8, 18, 12, 1]);
+        test(&Abstract::cube().kis(), [1, 14, 36, 24, 1]);
+        test(&Abstract::simplex(5).kis(), [1, 10, 30, 40, 20, 1]);
+    }
+
+    /// Checks the number of components of a few polytopes.
+    #[test]
+    fn component_count() {
+        assert_eq!(Abstract::nullitope().component_count(), 0);
+        assert_eq!(Abstract::dyad().component_count(), 1);
+        assert_eq!(Abstract::cube().component_count(), 1);
+
+        let mut hexagram = Abstract::polygon(3);
+        hexagram.comp_append(Abstract::polygon(3));
+        assert_eq!(hexagram.component_count(), 2);
+
+        let mut stella_octangula = Abstract::tetrahedron();
+        stella_octangula.comp_append(Abstract::tetrahedron());
+        assert_eq!(stella_octangula.component_count(), 2);
+    }
+
+    /// Checks the genus of a few surfaces.
+    #[test]
+    fn surface_genus() {
+        let mut cube = Abstract::cube();
+        cube.element_sort();
+        assert_eq!(cube.surface_genus(), Some(0));
+
+        // A torus made out of a 3 × 3 grid of squares.
+        let idx = |i: usize, j: usize| (i % 3) * 3 + j % 3;
+        let mut builder = AbstractBuilder::new();
+        builder.push_min();
+        builder.push_vertices(9);
+
+        let mut edges = SubelementList::new();
+        for i in 0..3 {
+            for j in 0..3 {
+                edges.push(vec![idx(i, j), idx(i, j + 1)].into());
+            }
+        }
+        for i in 0..3 {
+            for j in 0..3 {
+                edges.push(vec![idx(i, j), idx(i + 1, j)].into());
+            }
+        }
+        builder.push(edges);
+
+        let mut faces = SubelementList::new();
+        for i in 0..3 {
+            for j in 0..3 {
+                faces.push(vec![idx(i, j), idx(i + 1, j), 9 + idx(i, j), 9 + idx(i, j + 1)].into());
+            }
+        }
+        builder.push(faces);
+        builder.push_max();
+
+        // Safety: the 3 × 3 square torus is a valid abstract polyhedron.
+        let mut torus = unsafe { builder.build() };
+        torus.element_sort();
+        assert_eq!(torus.surface_genus(), Some(1));
+
+        let mut compound = Abstract::cube();
+        compound.comp_append(Abstract::cube());
+        compound.element_sort();
+        assert_eq!(compound.surface_genus(), None);
+        assert_eq!(Abstract::polygon(4).surface_genus(), None);
+    }
+
+    /// Checks combinatorial isomorphism between a few polytopes.
+    #[test]
+    fn is_isomorphic() {
+        let mut cube = Abstract::cube();
+        cube.element_sort();
+        let mut octahedron = Abstract::orthoplex(4);
+        octahedron.element_sort();
+        let mut dual = cube.dual();
+        dual.element_sort();
+
+        assert!(cube.is_isomorphic(&cube));
+        assert!(octahedron.is_isomorphic(&dual));
+        assert!(!cube.is_isomorphic(&octahedron));
+
+        // These have the same element counts.
+        let mut hexagon = Abstract::polygon(6);
+        hexagon.element_sort();
+        let mut triangles = Abstract::polygon(3);
+        triangles.comp_append(Abstract::polygon(3));
+        triangles.element_sort();
+        assert!(!hexagon.is_isomorphic(&triangles));
+    }
+
+    /// Counts the isomorphisms between a few polytopes.
+    #[test]
+    fn vertex_isomorphisms() {
+        let mut cube = Abstract::cube();
+        cube.element_sort();
+        let mut octahedron = Abstract::orthoplex(4);
+        octahedron.element_sort();
+        let mut pentagon = Abstract::polygon(5);
+        pentagon.element_sort();
+
+        assert_eq!(cube.vertex_isomorphisms(&cube).len(), 48);
+        assert_eq!(pentagon.vertex_isomorphisms(&pentagon).len(), 10);
+        assert!(cube.vertex_isomorphisms(&octahedron).is_empty());
+
+        // Every isomorphism maps the vertices bijectively.
+        for mut vertices in cube.vertex_isomorphisms(&cube) {
+            vertices.sort_unstable();
+            assert_eq!(vertices, (0..8).collect::<Vec<_>>());
+        }
+    }
+
+    /// Checks the facet types of a few polytopes.
+    #[test]
+    fn facet_types() {
+        let counts = |poly: &Abstract| -> Vec<usize> {
+            let mut counts: Vec<_> = poly.facet_types().iter().map(|t| t.count).collect();
+            counts.sort_unstable();
+            counts
+        };
+
+        assert_eq!(counts(&Abstract::cube()), vec![6]);
+        assert_eq!(counts(&Abstract::simplex(5)), vec![5]);
+        assert_eq!(counts(&Abstract::polygon(3).prism()), vec![2, 3]);
+        assert_eq!(counts(&Abstract::polygon(5).pyramid()), vec![1, 5]);
+    }
+
+    /// Checks the flag counts of a few polytopes.
+    #[test]
+    fn flag_count() {
+        assert_eq!(Abstract::nullitope().flag_count(), 1);
+        assert_eq!(Abstract::point().flag_count(), 1);
+        assert_eq!(Abstract::dyad().flag_count(), 2);
+        assert_eq!(Abstract::polygon(5).flag_count(), 10);
+        assert_eq!(Abstract::cube().flag_count(), 48);
+
+        let mut hypercube = Abstract::cube().prism();
+        hypercube.element_sort();
+        assert_eq!(hypercube.flag_count(), 384);
+        assert_eq!(hypercube.flag_count(), hypercube.flags().count());
+    }
 }
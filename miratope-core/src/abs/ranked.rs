@@ -360,6 +360,20 @@ pub trait Ranked:
         self.ranks().get(rank)
     }
 
+    /// Returns the indices of the subelements of an element, i.e. the
+    /// elements of the previous rank incident to it. Returns `None` if the
+    /// element doesn't exist.
+    fn subelements(&self, rank: usize, idx: usize) -> Option<&[usize]> {
+        Some(self.get_element(rank, idx)?.subs.as_slice())
+    }
+
+    /// Returns the indices of the superelements of an element, i.e. the
+    /// elements of the next rank incident to it. Returns `None` if the element
+    /// doesn't exist.
+    fn superelements(&self, rank: usize, idx: usize) -> Option<&[usize]> {
+        Some(self.get_element(rank, idx)?.sups.as_slice())
+    }
+
     /// Returns a reference to the minimal element of the polytope.
     ///
     /// # Panics
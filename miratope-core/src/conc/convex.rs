@@ -1,7 +1,7 @@
 use std::collections::BTreeSet;
-use std::f64::NEG_INFINITY;
 
 use super::Concrete;
+use crate::float::Real;
 use petgraph::{graph::NodeIndex, Directed, Direction, Graph};
 
 /// An entry in the priority queue used in Shell.
@@ -10,7 +10,7 @@ enum QueueEntry<'a> {
     /// contains a certain point becomes visible. This facet will have the
     /// specified vertices and normal vector.
     Point {
-        time: f64,
+        time: Real,
         normal: Vector,
         point: Point,
         vertices: Vec<Point>,
@@ -20,7 +20,7 @@ enum QueueEntry<'a> {
     /// horizon peak and the horizon ridges specified by an element's neighbors
     /// becomes visible. This facet will have the specified normal vector.
     Peak {
-        time: f64,
+        time: Real,
         normal: Vector,
         element: ShellElement<'a>,
     },
@@ -28,7 +28,7 @@ enum QueueEntry<'a> {
 
 impl<'a> QueueEntry<'a> {
     /// Returns the time associated with an event.
-    pub fn time(&self) -> f64 {
+    pub fn time(&self) -> Real {
         match self {
             QueueEntry::Point { time: t, .. } => *t,
             QueueEntry::Peak { time: t, .. } => *t,
@@ -61,7 +61,7 @@ impl<'a> Ord for QueueEntry<'a> {
 /// [`Point`](ShellQueueEntry::Point) and [`Peak`](ShellQueueEntry::Peak)
 /// entries at this time, and their common normal vector.
 struct QueueData<'a> {
-    time: f64,
+    time: Real,
     normal: Vector,
     vertices: Vec<Point>,
     elements: Vec<ShellElement<'a>>,
@@ -109,7 +109,7 @@ impl<'a> ShellPolytope<'a> {
         // A vector that is contained in s, but is in "general position."
         let y = 0.57 * &s.basis[0] + 0.43 * &s.basis[1];
         let a = s.orthogonal_comp();
-        let x: Point = vertices.iter().sum::<Point>() / vertices.len() as f64;
+        let x: Point = vertices.iter().sum::<Point>() / vertices.len() as Real;
 
         let vertices = vertices.iter().collect::<Vec<_>>();
 
@@ -117,7 +117,7 @@ impl<'a> ShellPolytope<'a> {
 
         poly.shell(
             Line(y, x),
-            NEG_INFINITY,
+            Real::NEG_INFINITY,
             Vec::new(),
             Vec::new(),
             vertices.clone(),
@@ -131,7 +131,7 @@ impl<'a> ShellPolytope<'a> {
     fn shell(
         &mut self,
         line: Line,
-        time: f64,
+        time: Real,
         ff: Vec<NodeIndex>,
         hr: Vec<NodeIndex>,
         u: Vec<&'a Point>,
@@ -0,0 +1,278 @@
+//! Implements [Conway's operators](https://en.wikipedia.org/wiki/Conway_polyhedron_notation)
+//! on polytopes, and a way to apply several of them at once by their letters.
+//!
+//! Most of these are particular truncations, and as such are defined for
+//! polytopes of any rank. The chamfer is only defined for polyhedra. Snubs
+//! aren't supported, since they require alternating a polytope.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Display,
+};
+
+use super::{hull::from_vertex_sets, Concrete, ConcretePolytope};
+use crate::{
+    abs::Ranked,
+    float::{Float, Real},
+    geometry::Point,
+    DualError, Polytope,
+};
+
+use vec_like::VecLike;
+
+/// Represents an error while applying a sequence of Conway operators.
+#[derive(Clone, Copy, Debug)]
+pub enum ConwayError {
+    /// A character that isn't the letter of a supported operator.
+    UnknownOperator(char),
+
+    /// One of the duals failed.
+    DualError(DualError),
+}
+
+impl From<DualError> for ConwayError {
+    fn from(err: DualError) -> Self {
+        Self::DualError(err)
+    }
+}
+
+impl Display for ConwayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownOperator(c) => write!(f, "unknown Conway operator {:?}", c),
+            Self::DualError(err) => write!(f, "dual failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ConwayError {}
+
+/// The letters of the supported Conway operators, along with their names.
+pub const CONWAY_OPERATORS: [(char, &str); 7] = [
+    ('d', "Dual"),
+    ('a', "Ambo"),
+    ('t', "Truncate"),
+    ('k', "Kis"),
+    ('e', "Expand"),
+    ('b', "Bevel"),
+    ('c', "Chamfer"),
+];
+
+impl Concrete {
+    /// Truncates a polytope with some given nodes ringed and unit depths, or
+    /// returns it unchanged if it has no ridges.
+    fn truncate_nodes(&self, nodes: Vec<usize>) -> Self {
+        let rank = self.rank();
+        if rank < 3 {
+            return self.clone();
+        }
+
+        let mut poly = self.clone();
+        poly.element_sort();
+        poly.truncate_with(nodes, vec![1.0; rank - 1])
+    }
+
+    /// Builds the ambo of a polytope, Conway's `a` operator, which is the same
+    /// as its rectification. Its vertices are the midpoints of the edges.
+    pub fn ambo(&self) -> Self {
+        self.truncate_nodes(vec![1])
+    }
+
+    /// Builds the truncation of a polytope, Conway's `t` operator, which cuts
+    /// off every vertex.
+    pub fn truncate(&self) -> Self {
+        self.truncate_nodes(vec![0, 1])
+    }
+
+    /// Builds the expansion of a polytope, Conway's `e` operator, which pulls
+    /// its facets apart and fills the gaps with new elements at its vertices
+    /// and edges. For polyhedra, this is the same as their cantellation.
+    pub fn expand(&self) -> Self {
+        let rank = self.rank();
+        self.truncate_nodes(vec![0, rank.saturating_sub(2)])
+    }
+
+    /// Builds the bevel of a polytope, Conway's `b` operator, which is the
+    /// same as its omnitruncation.
+    pub fn bevel(&self) -> Self {
+        let rank = self.rank();
+        self.truncate_nodes((0..rank.saturating_sub(1)).collect())
+    }
+
+    /// Applies a sequence of Conway operators to a polytope, written by their
+    /// letters from right to left as in Conway's notation. For instance, `tk`
+    /// builds the truncation of the Kleetope. Whitespace is ignored.
+    pub fn conway(&self, notation: &str) -> Result<Self, ConwayError> {
+        let mut poly = self.clone();
+
+        for c in notation.chars().rev().filter(|c| !c.is_whitespace()) {
+            poly = match c {
+                'd' => poly.try_dual()?,
+                'a' => poly.ambo(),
+                't' => poly.truncate(),
+                'k' => poly.kis(),
+                'e' => poly.expand(),
+                'b' => poly.bevel(),
+                'c' => poly.chamfer(),
+                _ => return Err(ConwayError::UnknownOperator(c)),
+            };
+        }
+
+        Ok(poly)
+    }
+
+    /// Builds the chamfer of a polyhedron, with a given depth between 0 and 1.
+    /// Every face is inset towards its centroid by the depth, and every edge
+    /// is replaced by a hexagon joining its endpoints with the inset copies of
+    /// the faces through it. The original vertices are moved towards the
+    /// gravicenter so that these hexagons are planar whenever the polyhedron
+    /// is edge-transitive.
+    ///
+    /// Anything other than a polyhedron is returned unchanged.
+    pub fn chamfer_with(&self, depth: Real) -> Self {
+        if self.rank() != 4 {
+            return self.clone();
+        }
+
+        let gravicenter = self.gravicenter().unwrap();
+        let vertex_count = self.vertex_count();
+
+        // The vertices of every face, and their centroids.
+        let face_vertices: Vec<BTreeSet<usize>> = (0..self[3].len())
+            .map(|f| {
+                self[(3, f)]
+                    .subs
+                    .iter()
+                    .flat_map(|&e| self[(2, e)].subs.iter().copied())
+                    .collect()
+            })
+            .collect();
+        let centroids: Vec<Point<Real>> = face_vertices
+            .iter()
+            .map(|vertices| {
+                vertices.iter().map(|&v| &self.vertices[v]).sum::<Point<Real>>()
+                    / vertices.len() as Real
+            })
+            .collect();
+
+        // Moves every original vertex so that it lies on the planes of the
+        // hexagons through it, averaging when these don't agree.
+        let mut vertices = Vec::with_capacity(vertex_count);
+        for (v, vertex) in self.vertices.iter().enumerate() {
+            let mut sum = 0.0;
+            let mut count = 0;
+
+            for &e in self[(1, v)].sups.iter() {
+                let subs = &self[(2, e)].subs;
+                let mid = (&self.vertices[subs[0]] + &self.vertices[subs[1]]) / 2.0 - &gravicenter;
+                let dot = (vertex - &gravicenter).dot(&mid);
+                if dot.abs() < Real::EPS {
+                    continue;
+                }
+
+                for &f in self[(2, e)].sups.iter() {
+                    sum += 1.0 - depth + depth * (&centroids[f] - &gravicenter).dot(&mid) / dot;
+                    count += 1;
+                }
+            }
+
+            let scale = if count == 0 { 1.0 } else { sum / count as Real };
+            vertices.push(&gravicenter + (vertex - &gravicenter) * scale);
+        }
+
+        // The inset copy of every vertex in every face through it.
+        let mut inset = HashMap::new();
+        for (f, face) in face_vertices.iter().enumerate() {
+            for &v in face {
+                inset.insert((f, v), vertices.len());
+                vertices.push(&self.vertices[v] + (&centroids[f] - &self.vertices[v]) * depth);
+            }
+        }
+
+        let mut edges = Vec::new();
+        for (f, face) in face_vertices.iter().enumerate() {
+            for &v in face {
+                edges.push(vec![v, inset[&(f, v)]]);
+            }
+
+            // Every edge of a face has an inset copy.
+            for &e in self[(3, f)].subs.iter() {
+                let subs = &self[(2, e)].subs;
+                edges.push(vec![inset[&(f, subs[0])], inset[&(f, subs[1])]]);
+            }
+        }
+
+        let mut faces: Vec<Vec<usize>> = face_vertices
+            .iter()
+            .enumerate()
+            .map(|(f, face)| face.iter().map(|&v| inset[&(f, v)]).collect())
+            .collect();
+        for e in 0..self[2].len() {
+            let subs = &self[(2, e)].subs;
+            let mut hexagon = vec![subs[0], subs[1]];
+            for &f in self[(2, e)].sups.iter() {
+                hexagon.push(inset[&(f, subs[0])]);
+                hexagon.push(inset[&(f, subs[1])]);
+            }
+            faces.push(hexagon);
+        }
+
+        for el in edges.iter_mut().chain(faces.iter_mut()) {
+            el.sort_unstable();
+        }
+
+        let elements = [(0..vertices.len()).map(|v| vec![v]).collect(), edges, faces];
+
+        // Safety: the faces of the chamfer are determined by their vertices,
+        // and so are its edges.
+        unsafe { from_vertex_sets(vertices, &elements) }
+    }
+
+    /// Builds the chamfer of a polyhedron, insetting every face halfway
+    /// towards its centroid.
+    pub fn chamfer(&self) -> Self {
+        self.chamfer_with(0.5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{conc::Concrete, test, Polytope};
+
+    /// Applies the truncations to a cube.
+    #[test]
+    fn truncations() {
+        let cube = Concrete::cube();
+
+        test(&cube.ambo(), [1, 12, 24, 14, 1]);
+        test(&cube.truncate(), [1, 24, 36, 14, 1]);
+        test(&cube.expand(), [1, 24, 48, 26, 1]);
+        test(&cube.bevel(), [1, 48, 72, 26, 1]);
+    }
+
+    /// Applies sequences of operators to a cube.
+    #[test]
+    fn conway() {
+        let cube = Concrete::cube();
+
+        test(&cube.conway("").unwrap(), [1, 8, 12, 6, 1]);
+        test(&cube.conway("d").unwrap(), [1, 6, 12, 8, 1]);
+        test(&cube.conway("ad").unwrap(), [1, 12, 24, 14, 1]);
+        test(&cube.conway("k d").unwrap(), [1, 14, 36, 24, 1]);
+        assert!(cube.conway("x").is_err());
+    }
+
+    /// Chamfers a few polyhedra.
+    #[test]
+    fn chamfer() {
+        test(&Concrete::cube().chamfer(), [1, 32, 48, 18, 1]);
+        test(&Concrete::simplex(4).chamfer(), [1, 16, 24, 10, 1]);
+        test(&Concrete::hypercube(4).chamfer_with(0.3), [1, 32, 48, 18, 1]);
+    }
+
+    /// Polygons can't be chamfered.
+    #[test]
+    fn polygon() {
+        test(&Concrete::polygon(5).chamfer(), [1, 5, 5, 1]);
+    }
+}
@@ -5,7 +5,7 @@ use std::{collections::BTreeMap, cmp::Ordering};
 use crate::{
     abs::{ElementMap, Ranked},
     conc::Concrete,
-    float::Float,
+    float::{Float, Real},
     geometry::{Point, Subspace},
 };
 
@@ -36,7 +36,7 @@ struct TypeData {
 
     /// Various heuristics that distinguish types of elements in concrete polytopes.
     /// Currently just distance from the origin
-    heuristics: OrderedFloat<f64>,
+    heuristics: OrderedFloat<Real>,
 }
 
 /// Names of elements of each rank.
@@ -53,14 +53,14 @@ pub const EL_SUFFIXES: [&str; 25] = [
     "yodakon", "nedakon", "ikon", "ikenon", "ikodon",
 ];
 
-impl Subspace<f64> {
-    fn distance_heuristic(&self, list: &mut Vec<f64>) -> f64 {
+impl Subspace<Real> {
+    fn distance_heuristic(&self, list: &mut Vec<Real>) -> Real {
         let dim = self.offset.len();
         let mut dist = self.distance(&Point::zeros(dim));
 
         match list.binary_search_by(|x| {
             let diff = x-&dist;
-            if diff.abs() < f64::EPS {Ordering::Equal}
+            if diff.abs() < Real::EPS {Ordering::Equal}
             else if diff > 0. {Ordering::Greater}
             else {Ordering::Less}
         }) {
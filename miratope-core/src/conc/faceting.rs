@@ -1,11 +1,11 @@
 //! The faceting algorithm.
 
-use std::{collections::{BTreeMap, HashMap, HashSet, VecDeque}, vec, iter::FromIterator, io::Write, time::Instant, path::PathBuf};
+use std::{collections::{BTreeMap, HashMap, HashSet, VecDeque}, fmt::Display, vec, iter::FromIterator, io::Write, time::Instant, path::PathBuf};
 
 use crate::{
     abs::{Abstract, Element, ElementList, Ranked, Ranks, Subelements, Superelements, AbstractBuilder},
     conc::{Concrete, ConcretePolytope},
-    float::Float,
+    float::{Float, Real},
     group::{Group}, geometry::{Matrix, PointOrd, Subspace, Point}, Polytope
 };
 
@@ -16,7 +16,7 @@ use vec_like::*;
 /// Input for the faceting function
 pub enum GroupEnum {
     /// Group of matrices
-    ConcGroup(Group<vec::IntoIter<Matrix<f64>>>),
+    ConcGroup(Group<vec::IntoIter<Matrix<Real>>>),
     /// Group of vertex mappings
     VertexMap(Vec<Vec<usize>>),
     /// True: take chiral group
@@ -24,6 +24,22 @@ pub enum GroupEnum {
     Chiral(bool),
 }
 
+/// The error returned when the enumeration of facetings gives up, since it
+/// found more elements than allowed.
+#[derive(Clone, Debug)]
+pub struct FacetingAborted {
+    /// How far the enumeration got before giving up.
+    pub report: String,
+}
+
+impl Display for FacetingAborted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.report)
+    }
+}
+
+impl std::error::Error for FacetingAborted {}
+
 const CL: &str = "\r                                                                                                                   \r";
 
 const DELAY: u128 = 200;
@@ -226,11 +242,11 @@ fn filter_irc(vec: &Vec<Vec<(usize,usize)>>) -> Vec<usize> {
 
 fn faceting_subdim(
     rank: usize,
-    plane: Subspace<f64>,
-    points: Vec<PointOrd<f64>>,
+    plane: Subspace<Real>,
+    points: Vec<PointOrd<Real>>,
     vertex_map: Vec<Vec<usize>>,
-    min_edge_length: Option<f64>,
-    max_edge_length: Option<f64>,
+    min_edge_length: Option<Real>,
+    max_edge_length: Option<Real>,
     max_per_hyperplane: Option<usize>,
 	uniform: bool,
     noble_package: Option<(&Vec<Vec<usize>>, &Vec<usize>, usize)>,
@@ -340,12 +356,12 @@ fn faceting_subdim(
             if !checked[rep][vertex] {
                 let edge_length = (&points[vertex].0-&points[rep].0).norm();
                 if let Some(min) = min_edge_length {
-                    if edge_length < min - f64::EPS {
+                    if edge_length < min - Real::EPS {
                         continue
                     }
                 }
                 if let Some(max) = max_edge_length {
-                    if edge_length > max + f64::EPS {
+                    if edge_length > max + Real::EPS {
                         continue
                     }
                 }
@@ -390,13 +406,13 @@ fn faceting_subdim(
                 for (v_i, v) in new_vertices.iter().enumerate() {
                     let edge_length = (&points[*v].0-&points[rep[0]].0).norm();
                     if let Some(min) = min_edge_length {
-                        if edge_length < min - f64::EPS {
+                        if edge_length < min - Real::EPS {
                             update = v_i;
                             break 'c;
                         }
                     }
                     if let Some(max) = max_edge_length {
-                        if edge_length > max + f64::EPS {
+                        if edge_length > max + Real::EPS {
                             update = v_i;
                             break 'c;
                         }
@@ -416,7 +432,7 @@ fn faceting_subdim(
 
                     let mut hyperplane_vertices = Vec::new();
                     for (idx, v) in flat_points.iter().enumerate() {
-                        if hyperplane.distance(&v.0) < f64::EPS {
+                        if hyperplane.distance(&v.0) < Real::EPS {
                             hyperplane_vertices.push(idx);
                         }
                     }
@@ -947,10 +963,7 @@ fn faceting_subdim(
                                 new_vertices.push(flat_points[i].0.clone());
                             }
 
-                            let mut poly = Concrete {
-                                vertices: new_vertices,
-                                abs: abs.clone(),
-                            };
+                            let mut poly = Concrete::new(new_vertices, abs.clone());
                             poly.recenter();
                             
                             let amount = poly.element_types()[1].len();
@@ -1026,18 +1039,21 @@ fn faceting_subdim(
 impl Concrete {
     /// Enumerates the facetings of a polytope under a provided symmetry group or vertex map.
     /// If the symmetry group is not provided, it uses the full symmetry of the polytope.
+    /// Gives up once more than `max_elements` hyperplanes, possible facets or facetings
+    /// are found, returning how far it got.
     pub fn faceting(
         &mut self,
-        vertices: Vec<Point<f64>>,
+        vertices: Vec<Point<Real>>,
         symmetry: GroupEnum,
-        min_edge_length: Option<f64>,
-        max_edge_length: Option<f64>,
-        min_inradius: Option<f64>,
-        max_inradius: Option<f64>,
+        min_edge_length: Option<Real>,
+        max_edge_length: Option<Real>,
+        min_inradius: Option<Real>,
+        max_inradius: Option<Real>,
         exclude_hemis: bool,
         only_below_vertex: bool,
         noble: Option<usize>,
         max_per_hyperplane: Option<usize>,
+        max_elements: Option<usize>,
 		uniform: bool,
         include_compounds: bool,
         mark_fissary: bool,
@@ -1046,16 +1062,16 @@ impl Concrete {
         save_facets: bool,
         save_to_file: bool,
         file_path: String
-    ) -> Vec<(Concrete, Option<String>)> {
+    ) -> Result<Vec<(Concrete, Option<String>)>, FacetingAborted> {
         let rank = self.rank();
         let mut now = Instant::now();
 
         if rank < 4 {
             println!("\nFaceting polytopes of rank less than 3 is not supported!\n");
-            return Vec::new()
+            return Ok(Vec::new())
         }
 
-        let mut vertices_ord = Vec::<PointOrd<f64>>::new();
+        let mut vertices_ord = Vec::<PointOrd<Real>>::new();
         for v in &vertices {
             vertices_ord.push(PointOrd::new(v.clone()));
         }
@@ -1117,7 +1133,7 @@ impl Concrete {
 
         if only_below_vertex {
             for v_orbit in &vertex_orbits {
-                let mut map = BTreeMap::<OrderedFloat<f64>, Vec<usize>>::new();
+                let mut map = BTreeMap::<OrderedFloat<Real>, Vec<usize>>::new();
                 let rep = v_orbit[0];
                 let point = &vertices[rep];
 
@@ -1149,12 +1165,12 @@ impl Concrete {
                     for v in &list[1..] {
                         let edge_length = (&vertices[*v]-&vertices[list[0]]).norm();
                         if let Some(min) = min_edge_length {
-                            if edge_length < min - f64::EPS {
+                            if edge_length < min - Real::EPS {
                                 continue 'd;
                             }
                         }
                         if let Some(max) = max_edge_length {
-                            if edge_length > max + f64::EPS {
+                            if edge_length > max + Real::EPS {
                                 continue 'd;
                             }
                         }
@@ -1168,24 +1184,24 @@ impl Concrete {
                     if hyperplane.is_hyperplane() {
                         let inradius = hyperplane.distance(&Point::zeros(self.dim().unwrap()));
                         if let Some(min) = min_inradius {
-                            if inradius < min - f64::EPS {
+                            if inradius < min - Real::EPS {
                                 continue
                             }
                         }
                         if let Some(max) = max_inradius {
-                            if inradius > max + f64::EPS {
+                            if inradius > max + Real::EPS {
                                 continue
                             }
                         }
                         if exclude_hemis {
-                            if inradius.abs() < f64::EPS {
+                            if inradius.abs() < Real::EPS {
                                 continue
                             }
                         }
 
                         let mut hyperplane_vertices = Vec::new();
                         for (idx, v) in vertices.iter().enumerate() {
-                            if hyperplane.distance(&v) < f64::EPS {
+                            if hyperplane.distance(&v) < Real::EPS {
                                 hyperplane_vertices.push(idx);
                             }
                         }
@@ -1232,12 +1248,12 @@ impl Concrete {
                     if !checked[rep][vertex] {
                         let edge_length = (&vertices[vertex]-&vertices[rep]).norm();
                         if let Some(min) = min_edge_length {
-                            if edge_length < min - f64::EPS {
+                            if edge_length < min - Real::EPS {
                                 continue;
                             }
                         }
                         if let Some(max) = max_edge_length {
-                            if edge_length > max + f64::EPS {
+                            if edge_length > max + Real::EPS {
                                 continue;
                             }
                         }
@@ -1278,12 +1294,12 @@ impl Concrete {
 
                         let edge_length = (&vertices[tuple[0]]-&vertices[new_vertex]).norm();
                         if let Some(min) = min_edge_length {
-                            if edge_length < min - f64::EPS {
+                            if edge_length < min - Real::EPS {
                                 wrong_edge = true;
                             }
                         }
                         if let Some(max) = max_edge_length {
-                            if edge_length > max + f64::EPS {
+                            if edge_length > max + Real::EPS {
                                 wrong_edge = true;
                             }
                         }
@@ -1340,12 +1356,12 @@ impl Concrete {
 
                     let edge_length = (&vertices[new_vertex]-&vertices[rep[0]]).norm();
                     if let Some(min) = min_edge_length {
-                        if edge_length < min - f64::EPS {
+                        if edge_length < min - Real::EPS {
                             continue;
                         }
                     }
                     if let Some(max) = max_edge_length {
-                        if edge_length > max + f64::EPS {
+                        if edge_length > max + Real::EPS {
                             continue;
                         }
                     }
@@ -1360,24 +1376,24 @@ impl Concrete {
                     if hyperplane.is_hyperplane() {
                         let inradius = hyperplane.distance(&Point::zeros(self.dim().unwrap()));
                         if let Some(min) = min_inradius {
-                            if inradius < min - f64::EPS {
+                            if inradius < min - Real::EPS {
                                 break
                             }
                         }
                         if let Some(max) = max_inradius {
-                            if inradius > max + f64::EPS {
+                            if inradius > max + Real::EPS {
                                 break
                             }
                         }
                         if exclude_hemis {
-                            if inradius.abs() < f64::EPS {
+                            if inradius.abs() < Real::EPS {
                                 break
                             }
                         }
 
                         let mut hyperplane_vertices = Vec::new();
                         for (idx, v) in vertices.iter().enumerate() {
-                            if hyperplane.distance(&v) < f64::EPS {
+                            if hyperplane.distance(&v) < Real::EPS {
                                 hyperplane_vertices.push(idx);
                             }
                         }
@@ -1419,6 +1435,14 @@ impl Concrete {
 
         println!("{}{} hyperplanes in {} orbit{}", CL, sum, hyperplane_orbits.len(), if hyperplane_orbits.len() == 1 {""} else {"s"});
 
+        if let Some(max) = max_elements {
+            if sum > max as u64 {
+                return Err(FacetingAborted {
+                    report: format!("Aborted: found {} hyperplanes, more than the limit of {} elements.", sum, max),
+                })
+            }
+        }
+
         println!("\nFaceting hyperplanes...");
 
         // Facet the hyperplanes
@@ -1427,6 +1451,7 @@ impl Concrete {
         let mut compound_facets: Vec<HashMap<usize, (usize,usize)>> = Vec::new();
         let mut ridges: Vec<Vec<Vec<Ranks>>> = Vec::new();
         let mut ff_counts = Vec::new();
+        let mut total_facets = 0;
 
         for (idx, orbit) in hyperplane_orbits.iter().enumerate() {
             let (hp, hp_v) = (orbit.0.clone(), orbit.1.clone());
@@ -1495,6 +1520,18 @@ impl Concrete {
 
             println!("{}{}: {} facets, {} verts, {} copies", CL, idx, possible_facets_row.len(), hp_v.len(), orbit.2);
             std::io::stdout().flush().unwrap();
+
+            total_facets += possible_facets_row.len();
+            if let Some(max) = max_elements {
+                if total_facets > max {
+                    return Err(FacetingAborted {
+                        report: format!(
+                            "Aborted after faceting {} of {} hyperplane orbits: found {} possible facets, more than the limit of {} elements.",
+                            idx + 1, hyperplane_orbits.len(), total_facets, max
+                        ),
+                    })
+                }
+            }
         }
 
         println!("\nComputing ridges...");
@@ -1548,7 +1585,7 @@ impl Concrete {
                     let mut all_vertices_idx = HashSet::new();
 
                     for (i, vertex) in vertices.iter().enumerate() {
-                        if subspace.distance(&vertex) < f64::EPS {
+                        if subspace.distance(&vertex) < Real::EPS {
                             all_vertices_idx.insert(i);
                         }
                     }
@@ -1752,6 +1789,18 @@ impl Concrete {
     
                     output_facets.push(new_facets);
 
+                    if let Some(max) = max_elements {
+                        if output_facets.len() > max {
+                            print!("{}", CL);
+                            return Err(FacetingAborted {
+                                report: format!(
+                                    "Aborted while combining, at {:?}: found {} facetings, more than the limit of {} elements.",
+                                    facets, output_facets.len(), max
+                                ),
+                            })
+                        }
+                    }
+
                     if let Some(max_facets) = noble {
                         if facets.len() == max_facets {
                             let t = facets.last_mut().unwrap();
@@ -2003,10 +2052,7 @@ impl Concrete {
                         new_vertices.push(vertices[i].clone());
                     }
 
-                    let poly = Concrete {
-                        vertices: new_vertices,
-                        abs: abs.clone(),
-                    };
+                    let poly = Concrete::new(new_vertices, abs.clone());
 
 					let mut fissary_status = "";
                     if mark_fissary {
@@ -2088,6 +2134,6 @@ impl Concrete {
         }
 
         println!("\nFaceting complete\n");
-        return output
+        return Ok(output)
     }
 }
\ No newline at end of file
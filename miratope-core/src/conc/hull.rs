@@ -0,0 +1,442 @@
+//! Builds the convex hull of a set of points, by
+//! [gift wrapping](https://en.wikipedia.org/wiki/Gift_wrapping_algorithm) the
+//! facets of every element recursively.
+
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::{fmt::Display, vec};
+
+use super::{symmetry::Vertices, Concrete};
+use crate::{
+    abs::{AbstractBuilder, SubelementList, Subelements},
+    float::{Float, Real},
+    geometry::{Matrix, Point, PointOrd, Subspace, Vector},
+    group::Group,
+};
+
+use vec_like::VecLike;
+
+/// The facets of the faces found so far. Every face is stored as the sorted
+/// list of the indices of its vertices.
+type FacetCache = HashMap<Vec<usize>, Vec<Vec<usize>>>;
+
+/// The state of the search for the elements of a convex hull.
+struct Search {
+    /// The facets of the faces found so far.
+    cache: FacetCache,
+
+    /// Every distinct element of the hull found so far.
+    elements: HashSet<Vec<usize>>,
+
+    /// The number of elements above which the search gives up.
+    cap: usize,
+}
+
+impl Search {
+    /// Starts the search for the elements of the hull with a given vertex set.
+    fn new(hull: Vec<usize>, cap: usize) -> Self {
+        let mut elements = HashSet::new();
+        elements.insert(hull);
+
+        Self {
+            cache: FacetCache::new(),
+            elements,
+            cap,
+        }
+    }
+
+    /// Records the facets of a face of a given dimension, giving up if there's
+    /// now more elements than allowed.
+    fn record(&mut self, facets: &[Vec<usize>], face_dim: usize) -> Result<(), TooManyElements> {
+        for facet in facets {
+            if self.elements.insert(facet.clone()) && self.elements.len() > self.cap {
+                return Err(TooManyElements {
+                    found: self.elements.len(),
+                    dim: face_dim - 1,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The error returned when the convex hull of some points has more elements
+/// than allowed.
+#[derive(Clone, Copy, Debug)]
+pub struct TooManyElements {
+    /// The number of distinct elements found before giving up.
+    pub found: usize,
+
+    /// The dimension of the element that went over the limit.
+    pub dim: usize,
+}
+
+impl Display for TooManyElements {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "gave up after finding {} elements, at an element of dimension {}",
+            self.found, self.dim
+        )
+    }
+}
+
+impl std::error::Error for TooManyElements {}
+
+/// Builds a polytope out of its vertices and the vertex sets of its elements.
+/// The list `elements[k]` contains the elements of rank `k + 1`, each one as a
+/// sorted list of vertex indices, and `elements[0]` must list the vertices in
+/// order.
+///
+/// # Safety
+/// The vertex sets must be the elements of an actual polytope, each of which
+/// is determined by its vertices.
+pub(super) unsafe fn from_vertex_sets(
+    vertices: Vec<Point<Real>>,
+    elements: &[Vec<Vec<usize>>],
+) -> Concrete {
+    let dim = elements.len();
+    let mut builder = AbstractBuilder::with_rank_capacity(dim + 1);
+    builder.push_min();
+    builder.push_vertices(vertices.len());
+
+    for k in 1..dim {
+        // The elements of the current rank that contain each vertex.
+        let mut containing = HashMap::new();
+        for (idx, el) in elements[k].iter().enumerate() {
+            for &v in el {
+                containing.entry(v).or_insert_with(Vec::new).push(idx);
+            }
+        }
+
+        let mut subs = vec![Vec::new(); elements[k].len()];
+        for (sub_idx, sub) in elements[k - 1].iter().enumerate() {
+            for &idx in containing.get(&sub[0]).into_iter().flatten() {
+                let el = &elements[k][idx];
+                if sub.iter().all(|v| el.binary_search(v).is_ok()) {
+                    subs[idx].push(sub_idx);
+                }
+            }
+        }
+
+        let mut list = SubelementList::new();
+        for el_subs in subs {
+            list.push(Subelements::from(el_subs));
+        }
+        builder.push(list);
+    }
+
+    builder.push_max();
+    Concrete::new(vertices, builder.build())
+}
+
+/// Returns the indices of the points that lie on the hyperplane through `base`
+/// with a given unit normal.
+fn on_hyperplane(points: &[Point<Real>], normal: &Vector<Real>, base: &Point<Real>) -> Vec<usize> {
+    (0..points.len())
+        .filter(|&i| normal.dot(&(&points[i] - base)).abs() < Real::EPS)
+        .collect()
+}
+
+/// Rotates a supporting hyperplane through `base` with a given unit normal
+/// towards a unit direction orthogonal to it, until it hits another point. The
+/// normal points towards the points, and is updated to that of the new
+/// hyperplane.
+fn rotate_hyperplane(
+    points: &[Point<Real>],
+    normal: &mut Vector<Real>,
+    dir: &Vector<Real>,
+    base: &Point<Real>,
+) {
+    // The angle at which the hyperplane hits each point off of it.
+    let mut angle = Real::PI;
+    for p in points {
+        let p = p - base;
+        let a = normal.dot(&p);
+
+        if a > Real::EPS {
+            angle = angle.min(dir.dot(&p).atan2(a) + Real::PI / 2.0);
+        }
+    }
+
+    let (s, c) = angle.fsin_cos();
+    *normal = &*normal * c + dir * s;
+}
+
+/// Finds some facet of the convex hull of a set of points of full rank, along
+/// with its normal, pointing inwards.
+fn first_facet(points: &[Point<Real>]) -> (Vec<usize>, Vector<Real>) {
+    let dim = points[0].len();
+
+    // Starts with the hyperplane through the leftmost point.
+    let mut normal = Vector::zeros(dim);
+    normal[0] = 1.0;
+    let base = points
+        .iter()
+        .min_by(|p, q| p[0].partial_cmp(&q[0]).unwrap())
+        .unwrap()
+        .clone();
+    let mut facet = on_hyperplane(points, &normal, &base);
+
+    // Rotates the hyperplane around the points on it until it becomes a facet.
+    loop {
+        let space = Subspace::from_points(facet.iter().map(|&i| &points[i]));
+        if space.rank() + 1 == dim {
+            return (facet, normal);
+        }
+
+        // A direction orthogonal to the normal and to the points found so far.
+        let mut spanned = space.basis;
+        spanned.push(normal.clone());
+        let dir = (0..dim)
+            .find_map(|i| {
+                let mut e = Vector::zeros(dim);
+                e[i] = 1.0;
+                for b in &spanned {
+                    let along = b.dot(&e);
+                    e -= b * along;
+                }
+                e.try_normalize(Real::EPS)
+            })
+            .unwrap();
+
+        rotate_hyperplane(points, &mut normal, &dir, &base);
+        facet = on_hyperplane(points, &normal, &base);
+    }
+}
+
+/// Finds the facets of the convex hull of some points of rank at least 2, by
+/// starting at some facet and rotating around its ridges. Gives up if the hull
+/// has too many elements.
+fn wrap(
+    points: &[Point<Real>],
+    face: &[usize],
+    local: &[Point<Real>],
+    search: &mut Search,
+) -> Result<Vec<Vec<usize>>, TooManyElements> {
+    let (first, normal) = first_facet(local);
+    let mut found = HashSet::new();
+    found.insert(first.clone());
+    let mut queue = VecDeque::new();
+    queue.push_back((first, normal));
+    let mut facets = Vec::new();
+
+    while let Some((facet, normal)) = queue.pop_front() {
+        let global: Vec<_> = facet.iter().map(|&i| face[i]).collect();
+
+        for ridge in facets_of(points, &global, search)? {
+            let ridge: Vec<_> = ridge
+                .iter()
+                .map(|v| face.binary_search(v).unwrap())
+                .collect();
+            let base = &local[ridge[0]];
+
+            // A direction within the facet, orthogonal to the ridge, pointing
+            // into the facet.
+            let ridge_space = Subspace::from_points(ridge.iter().map(|&i| &local[i]));
+            let inside = *facet.iter().find(|&&i| ridge.binary_search(&i).is_err()).unwrap();
+            let mut dir = &local[inside] - ridge_space.project(&local[inside]);
+            let along = normal.dot(&dir);
+            dir -= &normal * along;
+            dir.normalize_mut();
+
+            // Rotates the facet around the ridge to get the adjacent facet.
+            let mut next_normal = normal.clone();
+            rotate_hyperplane(local, &mut next_normal, &dir, base);
+            let next = on_hyperplane(local, &next_normal, base);
+
+            if found.insert(next.clone()) {
+                queue.push_back((next, next_normal));
+            }
+        }
+
+        facets.push(global);
+    }
+
+    Ok(facets)
+}
+
+/// Finds the facets of the convex hull of the points with the given sorted
+/// indices. Gives up if the hull has too many elements.
+fn facets_of(
+    points: &[Point<Real>],
+    face: &[usize],
+    search: &mut Search,
+) -> Result<Vec<Vec<usize>>, TooManyElements> {
+    if let Some(facets) = search.cache.get(face) {
+        return Ok(facets.clone());
+    }
+
+    // Works in the coordinates of the subspace spanned by the points.
+    let space = Subspace::from_points(face.iter().map(|&i| &points[i]));
+    let local: Vec<_> = face.iter().map(|&i| space.flatten(&points[i])).collect();
+
+    let facets = match space.rank() {
+        0 => Vec::new(),
+        1 => {
+            let cmp = |&i: &usize, &j: &usize| local[i][0].partial_cmp(&local[j][0]).unwrap();
+            let min = (0..face.len()).min_by(cmp).unwrap();
+            let max = (0..face.len()).max_by(cmp).unwrap();
+            vec![vec![face[min]], vec![face[max]]]
+        }
+        _ => wrap(points, face, &local, search)?,
+    };
+
+    search.record(&facets, space.rank())?;
+    search.cache.insert(face.to_vec(), facets.clone());
+    Ok(facets)
+}
+
+impl Concrete {
+    /// Builds the convex hull of a set of points. Repeated points and points
+    /// in the interior of the hull are ignored.
+    ///
+    /// # Panics
+    /// This method will panic if no points are given.
+    pub fn convex_hull(points: Vec<Point<Real>>) -> Self {
+        Self::convex_hull_capped(points, usize::MAX).unwrap()
+    }
+
+    /// Builds the convex hull of a set of points, giving up once more than
+    /// `max_elements` elements have been found. This keeps pathological inputs
+    /// from taking up all of the memory.
+    ///
+    /// # Panics
+    /// This method will panic if no points are given.
+    pub fn convex_hull_capped(
+        points: Vec<Point<Real>>,
+        max_elements: usize,
+    ) -> Result<Self, TooManyElements> {
+        let mut seen = BTreeSet::new();
+        let points: Vec<_> = points
+            .into_iter()
+            .filter(|p| seen.insert(PointOrd::new(p.clone())))
+            .collect();
+        let rank = Subspace::from_points(points.iter()).rank();
+
+        // Finds the faces of every rank, starting from the hull itself.
+        let hull: Vec<_> = (0..points.len()).collect();
+        let mut search = Search::new(hull.clone(), max_elements);
+        let mut elements = vec![Vec::new(); rank + 1];
+        elements[rank] = vec![hull];
+
+        for k in (1..=rank).rev() {
+            let mut found = HashSet::new();
+            let mut faces = Vec::new();
+
+            for face in &elements[k] {
+                for facet in facets_of(&points, face, &mut search)? {
+                    if found.insert(facet.clone()) {
+                        faces.push(facet);
+                    }
+                }
+            }
+
+            elements[k - 1] = faces;
+        }
+
+        // Only keeps the points that are vertices of the hull.
+        let mut new_idx = HashMap::new();
+        let mut vertices = Vec::new();
+        for vertex in &elements[0] {
+            new_idx.insert(vertex[0], vertices.len());
+            vertices.push(points[vertex[0]].clone());
+        }
+
+        for faces in &mut elements[..rank] {
+            for face in faces {
+                for v in face.iter_mut() {
+                    *v = new_idx[v];
+                }
+                face.sort_unstable();
+            }
+        }
+
+        // Safety: the faces of a convex polytope form a polytope, and they're
+        // determined by their vertices.
+        Ok(unsafe { from_vertex_sets(vertices, &elements[..rank]) })
+    }
+
+    /// Builds the convex hull of the orbit of a set of points under a group of
+    /// isometries.
+    pub fn orbit_hull(seeds: Vec<Point<Real>>, group: Group<vec::IntoIter<Matrix<Real>>>) -> Self {
+        Self::convex_hull(Vertices(seeds).copy_by_symmetry(group).0 .0)
+    }
+
+    /// Builds the convex hull of the orbit of a set of points under a group of
+    /// isometries, giving up once more than `max_elements` elements have been
+    /// found.
+    pub fn orbit_hull_capped(
+        seeds: Vec<Point<Real>>,
+        group: Group<vec::IntoIter<Matrix<Real>>>,
+        max_elements: usize,
+    ) -> Result<Self, TooManyElements> {
+        Self::convex_hull_capped(Vertices(seeds).copy_by_symmetry(group).0 .0, max_elements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test, Polytope};
+
+    use nalgebra::dvector;
+
+    /// Builds the hulls of the vertices of a few polytopes.
+    #[test]
+    fn convex_hull() {
+        test(&Concrete::convex_hull(Concrete::polygon(7).vertices), [1, 7, 7, 1]);
+        test(&Concrete::convex_hull(Concrete::cube().vertices), [1, 8, 12, 6, 1]);
+        test(
+            &Concrete::convex_hull(Concrete::hypercube(5).vertices),
+            [1, 16, 32, 24, 8, 1],
+        );
+
+        // Interior and repeated points are ignored.
+        let mut points = Concrete::cube().vertices;
+        points.push(dvector![0.0, 0.0, 0.0]);
+        points.push(points[0].clone());
+        test(&Concrete::convex_hull(points), [1, 8, 12, 6, 1]);
+
+        // A square in 3D space.
+        let square = vec![
+            dvector![0.0, 0.0, 1.0],
+            dvector![1.0, 0.0, 1.0],
+            dvector![0.0, 1.0, 1.0],
+            dvector![1.0, 1.0, 1.0],
+        ];
+        test(&Concrete::convex_hull(square), [1, 4, 4, 1]);
+    }
+
+    /// Builds the octahedron and the cuboctahedron as orbits under the
+    /// symmetry of a cube.
+    #[test]
+    fn orbit_hull() {
+        let group = || Concrete::cube().get_symmetry_group().unwrap().0;
+
+        test(
+            &Concrete::orbit_hull(vec![dvector![0.5, 0.0, 0.0]], group()),
+            [1, 6, 12, 8, 1],
+        );
+        test(
+            &Concrete::orbit_hull(vec![dvector![0.5, 0.5, 0.0]], group()),
+            [1, 12, 24, 14, 1],
+        );
+    }
+
+    /// Gives up on hulls with too many elements.
+    #[test]
+    fn capped() {
+        let points = || Concrete::hypercube(5).vertices;
+
+        // The hull itself and the first 10 elements found are within the cap.
+        let err = Concrete::convex_hull_capped(points(), 10).unwrap_err();
+        assert_eq!(err.found, 11);
+        assert!(err.dim < 4);
+
+        test(
+            &Concrete::convex_hull_capped(points(), 1000).unwrap(),
+            [1, 16, 32, 24, 8, 1],
+        );
+    }
+}
@@ -0,0 +1,534 @@
+//! Guesses which well-known polytope a polytope is, by comparing its
+//! invariants against a small table.
+
+use std::{cmp::Reverse, collections::BTreeSet, fmt::Display};
+
+use super::{Concrete, ConcretePolytope};
+use crate::abs::{Ranked, Superelements};
+
+use vec_like::VecLike;
+
+/// A well-known polytope, together with the invariants used to recognize it.
+struct Known {
+    /// The name of the polytope.
+    name: &'static str,
+
+    /// The number of elements of each rank, from the vertices up to the
+    /// facets.
+    counts: &'static [usize],
+
+    /// The order of the symmetry group of the polytope.
+    symmetry: usize,
+
+    /// The configuration of every vertex. See [`vertex_config`].
+    vertex_config: &'static [usize],
+}
+
+/// The polytopes we compare against. Every one of them is uniform, and so
+/// convex and equilateral.
+const KNOWN: &[Known] = &[
+    // Polygons.
+    Known {
+        name: "triangle",
+        counts: &[3, 3],
+        symmetry: 6,
+        vertex_config: &[3],
+    },
+    Known {
+        name: "square",
+        counts: &[4, 4],
+        symmetry: 8,
+        vertex_config: &[4],
+    },
+    Known {
+        name: "pentagon",
+        counts: &[5, 5],
+        symmetry: 10,
+        vertex_config: &[5],
+    },
+    Known {
+        name: "hexagon",
+        counts: &[6, 6],
+        symmetry: 12,
+        vertex_config: &[6],
+    },
+    Known {
+        name: "octagon",
+        counts: &[8, 8],
+        symmetry: 16,
+        vertex_config: &[8],
+    },
+    Known {
+        name: "decagon",
+        counts: &[10, 10],
+        symmetry: 20,
+        vertex_config: &[10],
+    },
+    Known {
+        name: "dodecagon",
+        counts: &[12, 12],
+        symmetry: 24,
+        vertex_config: &[12],
+    },
+    // Platonic solids.
+    Known {
+        name: "tetrahedron",
+        counts: &[4, 6, 4],
+        symmetry: 24,
+        vertex_config: &[3, 3, 3],
+    },
+    Known {
+        name: "cube",
+        counts: &[8, 12, 6],
+        symmetry: 48,
+        vertex_config: &[4, 4, 4],
+    },
+    Known {
+        name: "octahedron",
+        counts: &[6, 12, 8],
+        symmetry: 48,
+        vertex_config: &[3, 3, 3, 3],
+    },
+    Known {
+        name: "dodecahedron",
+        counts: &[20, 30, 12],
+        symmetry: 120,
+        vertex_config: &[5, 5, 5],
+    },
+    Known {
+        name: "icosahedron",
+        counts: &[12, 30, 20],
+        symmetry: 120,
+        vertex_config: &[3, 3, 3, 3, 3],
+    },
+    // Archimedean solids.
+    Known {
+        name: "truncated tetrahedron",
+        counts: &[12, 18, 8],
+        symmetry: 24,
+        vertex_config: &[3, 6, 6],
+    },
+    Known {
+        name: "cuboctahedron",
+        counts: &[12, 24, 14],
+        symmetry: 48,
+        vertex_config: &[3, 4, 3, 4],
+    },
+    Known {
+        name: "truncated cube",
+        counts: &[24, 36, 14],
+        symmetry: 48,
+        vertex_config: &[3, 8, 8],
+    },
+    Known {
+        name: "truncated octahedron",
+        counts: &[24, 36, 14],
+        symmetry: 48,
+        vertex_config: &[4, 6, 6],
+    },
+    Known {
+        name: "rhombicuboctahedron",
+        counts: &[24, 48, 26],
+        symmetry: 48,
+        vertex_config: &[3, 4, 4, 4],
+    },
+    Known {
+        name: "truncated cuboctahedron",
+        counts: &[48, 72, 26],
+        symmetry: 48,
+        vertex_config: &[4, 6, 8],
+    },
+    Known {
+        name: "snub cube",
+        counts: &[24, 60, 38],
+        symmetry: 24,
+        vertex_config: &[3, 3, 3, 3, 4],
+    },
+    Known {
+        name: "icosidodecahedron",
+        counts: &[30, 60, 32],
+        symmetry: 120,
+        vertex_config: &[3, 5, 3, 5],
+    },
+    Known {
+        name: "truncated dodecahedron",
+        counts: &[60, 90, 32],
+        symmetry: 120,
+        vertex_config: &[3, 10, 10],
+    },
+    Known {
+        name: "truncated icosahedron",
+        counts: &[60, 90, 32],
+        symmetry: 120,
+        vertex_config: &[5, 6, 6],
+    },
+    Known {
+        name: "rhombicosidodecahedron",
+        counts: &[60, 120, 62],
+        symmetry: 120,
+        vertex_config: &[3, 4, 5, 4],
+    },
+    Known {
+        name: "truncated icosidodecahedron",
+        counts: &[120, 180, 62],
+        symmetry: 120,
+        vertex_config: &[4, 6, 10],
+    },
+    Known {
+        name: "snub dodecahedron",
+        counts: &[60, 150, 92],
+        symmetry: 60,
+        vertex_config: &[3, 3, 3, 3, 5],
+    },
+    // Prisms and antiprisms.
+    Known {
+        name: "triangular prism",
+        counts: &[6, 9, 5],
+        symmetry: 12,
+        vertex_config: &[3, 4, 4],
+    },
+    Known {
+        name: "pentagonal prism",
+        counts: &[10, 15, 7],
+        symmetry: 20,
+        vertex_config: &[4, 4, 5],
+    },
+    Known {
+        name: "hexagonal prism",
+        counts: &[12, 18, 8],
+        symmetry: 24,
+        vertex_config: &[4, 4, 6],
+    },
+    Known {
+        name: "square antiprism",
+        counts: &[8, 16, 10],
+        symmetry: 16,
+        vertex_config: &[3, 3, 3, 4],
+    },
+    Known {
+        name: "pentagonal antiprism",
+        counts: &[10, 20, 12],
+        symmetry: 20,
+        vertex_config: &[3, 3, 3, 5],
+    },
+    // Polychora.
+    Known {
+        name: "pentachoron",
+        counts: &[5, 10, 10, 5],
+        symmetry: 120,
+        vertex_config: &[3; 6],
+    },
+    Known {
+        name: "tesseract",
+        counts: &[16, 32, 24, 8],
+        symmetry: 384,
+        vertex_config: &[4; 6],
+    },
+    Known {
+        name: "hexadecachoron",
+        counts: &[8, 24, 32, 16],
+        symmetry: 384,
+        vertex_config: &[3; 12],
+    },
+    Known {
+        name: "icositetrachoron",
+        counts: &[24, 96, 96, 24],
+        symmetry: 1152,
+        vertex_config: &[3; 12],
+    },
+    Known {
+        name: "hecatonicosachoron",
+        counts: &[600, 1200, 720, 120],
+        symmetry: 14400,
+        vertex_config: &[5; 6],
+    },
+    Known {
+        name: "hexacosichoron",
+        counts: &[120, 720, 1200, 600],
+        symmetry: 14400,
+        vertex_config: &[3; 30],
+    },
+    Known {
+        name: "rectified pentachoron",
+        counts: &[10, 30, 30, 10],
+        symmetry: 120,
+        vertex_config: &[3; 9],
+    },
+    Known {
+        name: "triangular duoprism",
+        counts: &[9, 18, 15, 6],
+        symmetry: 72,
+        vertex_config: &[3, 3, 4, 4, 4, 4],
+    },
+];
+
+/// The closest match to a polytope among some well-known ones, together with
+/// which of their invariants agree.
+#[derive(Clone, Copy, Debug)]
+pub struct Guess {
+    /// The name of the well-known polytope.
+    pub name: &'static str,
+
+    /// Whether the numbers of elements of each rank agree.
+    pub counts: bool,
+
+    /// Whether the orders of the symmetry groups agree.
+    pub symmetry: bool,
+
+    /// Whether every vertex has the same configuration as those of the
+    /// well-known polytope.
+    pub vertex_config: bool,
+
+    /// Whether every edge has the same length.
+    pub equilateral: bool,
+
+    /// Whether the polytope is convex.
+    pub convex: bool,
+}
+
+impl Guess {
+    /// Returns every invariant, together with whether it agrees.
+    fn invariants(&self) -> [(&'static str, bool); 5] {
+        [
+            ("element counts", self.counts),
+            ("symmetry order", self.symmetry),
+            ("vertex configuration", self.vertex_config),
+            ("edge lengths", self.equilateral),
+            ("convexity", self.convex),
+        ]
+    }
+
+    /// Returns the number of invariants that agree.
+    pub fn score(&self) -> usize {
+        self.invariants()
+            .iter()
+            .filter(|(_, agrees)| *agrees)
+            .count()
+    }
+
+    /// Returns whether every invariant agrees, in which case the polytope is
+    /// almost certainly the well-known one.
+    pub fn is_certain(&self) -> bool {
+        self.score() == self.invariants().len()
+    }
+}
+
+/// Joins some words into an English list, like `a, b and c`.
+fn english_list(words: &[&str]) -> String {
+    match words.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+impl Display for Guess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let invariants = self.invariants();
+        let (agree, disagree): (Vec<_>, Vec<_>) =
+            invariants.iter().partition(|(_, agrees)| *agrees);
+        let agree: Vec<_> = agree.into_iter().map(|(name, _)| name).collect();
+        let disagree: Vec<_> = disagree.into_iter().map(|(name, _)| name).collect();
+
+        if disagree.is_empty() {
+            return write!(
+                f,
+                "This is the {}: its {} all match.",
+                self.name,
+                english_list(&agree)
+            );
+        }
+
+        let confidence = match self.score() {
+            4 => "high",
+            2 | 3 => "medium",
+            _ => "low",
+        };
+        write!(
+            f,
+            "The closest match is the {} ({} confidence).",
+            self.name, confidence
+        )?;
+        if !agree.is_empty() {
+            write!(f, " The {} match.", english_list(&agree))?;
+        }
+        write!(f, " The {} don't.", english_list(&disagree))
+    }
+}
+
+/// Orders the faces around a vertex of a polyhedron, given the edges through
+/// it, or returns `None` if they don't form a single cycle.
+fn face_cycle(poly: &Concrete, edges: &Superelements) -> Option<Vec<usize>> {
+    // The two faces through an edge.
+    let faces = |edge: usize| {
+        let sups = &poly[(2, edge)].sups;
+        (sups.len() == 2).then(|| (sups[0], sups[1]))
+    };
+
+    let mut edge = *edges.iter().next()?;
+    let (start, mut face) = faces(edge)?;
+    let mut cycle = vec![start];
+
+    while face != start {
+        if cycle.len() >= edges.len() {
+            return None;
+        }
+        cycle.push(face);
+
+        // Crosses the other edge through the vertex on this face.
+        edge = *edges
+            .iter()
+            .find(|&&e| e != edge && poly[(2, e)].sups.contains(&face))?;
+        let (face0, face1) = faces(edge)?;
+        face = if face0 == face { face1 } else { face0 };
+    }
+
+    (cycle.len() == edges.len()).then(|| cycle)
+}
+
+/// Returns the least sequence among the rotations and reflections of a cyclic
+/// sequence.
+fn least_rotation(seq: Vec<usize>) -> Vec<usize> {
+    let mut reversed = seq.clone();
+    reversed.reverse();
+
+    let mut least = seq.clone();
+    for seq in [seq, reversed].iter() {
+        for i in 0..seq.len() {
+            let mut rotated = seq.clone();
+            rotated.rotate_left(i);
+            if rotated < least {
+                least = rotated;
+            }
+        }
+    }
+
+    least
+}
+
+/// Returns the configuration of a vertex, that is, the numbers of sides of
+/// the polygons through it. For polyhedra, these are listed in the order they
+/// go around the vertex, starting wherever makes the sequence the least, as in
+/// `3.4.3.4` for the cuboctahedron. Otherwise, they're sorted.
+///
+/// This should only be called for polytopes of rank at least 3.
+fn vertex_config(poly: &Concrete, vertex: usize) -> Vec<usize> {
+    let edges = &poly[(1, vertex)].sups;
+    let sides = |face: usize| poly[(3, face)].subs.len();
+
+    if poly.rank() == 4 {
+        if let Some(cycle) = face_cycle(poly, edges) {
+            return least_rotation(cycle.into_iter().map(sides).collect());
+        }
+    }
+
+    let faces: BTreeSet<_> = edges
+        .iter()
+        .flat_map(|&edge| poly[(2, edge)].sups.iter().copied())
+        .collect();
+    let mut config: Vec<_> = faces.into_iter().map(sides).collect();
+    config.sort_unstable();
+    config
+}
+
+impl Concrete {
+    /// Compares the polytope against a small table of well-known polytopes,
+    /// and returns the closest match among those of the same rank. Returns
+    /// `None` if there are none.
+    pub fn identify(&self) -> Option<Guess> {
+        let rank = self.rank();
+        let candidates: Vec<_> = KNOWN
+            .iter()
+            .filter(|known| known.counts.len() + 1 == rank)
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let counts: Vec<_> = (1..rank).map(|r| self.el_count(r)).collect();
+
+        // The symmetry group is calculated about the origin.
+        let mut centered = self.clone();
+        centered.recenter();
+        let symmetry = centered
+            .get_symmetry_group()
+            .map(|(group, _)| group.count());
+
+        // The configuration shared by every vertex, if any.
+        let configs: BTreeSet<_> = (0..self.vertex_count())
+            .map(|v| vertex_config(self, v))
+            .collect();
+        let config = if configs.len() == 1 {
+            configs.into_iter().next()
+        } else {
+            None
+        };
+
+        let equilateral = self.is_equilateral();
+        let convex = self.is_convex();
+
+        candidates
+            .into_iter()
+            .map(|known| Guess {
+                name: known.name,
+                counts: counts == known.counts,
+                symmetry: symmetry == Some(known.symmetry),
+                vertex_config: config.as_deref() == Some(known.vertex_config),
+                equilateral,
+                convex,
+            })
+            .min_by_key(|guess| Reverse(guess.score()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    /// Checks the configurations of the vertices of a few polytopes.
+    #[test]
+    fn vertex_configs() {
+        let cuboctahedron = Concrete::from_cd("o3x4o").unwrap();
+        assert_eq!(vertex_config(&cuboctahedron, 0), vec![3, 4, 3, 4]);
+
+        let rhombicuboctahedron = Concrete::from_cd("x3o4x").unwrap();
+        assert_eq!(vertex_config(&rhombicuboctahedron, 0), vec![3, 4, 4, 4]);
+
+        let tesseract = Concrete::hypercube(5);
+        assert_eq!(vertex_config(&tesseract, 0), vec![4; 6]);
+    }
+
+    /// Checks that a few polytopes are recognized.
+    #[test]
+    fn identify() {
+        for (poly, name) in [
+            (Concrete::polygon(5), "pentagon"),
+            (Concrete::cube(), "cube"),
+            (Concrete::from_cd("x3x3o").unwrap(), "truncated tetrahedron"),
+            (Concrete::polygon(6).prism(), "hexagonal prism"),
+            (Concrete::hypercube(5), "tesseract"),
+        ]
+        .iter()
+        {
+            let guess = poly.identify().unwrap();
+            assert_eq!(guess.name, *name);
+            assert!(guess.is_certain(), "{}", guess);
+        }
+    }
+
+    /// Checks that a stretched cube is still closest to the cube, but isn't
+    /// recognized as one.
+    #[test]
+    fn cuboid() {
+        let mut cuboid = Concrete::cube();
+        for v in cuboid.vertices_mut() {
+            v[0] *= 2.0;
+        }
+
+        let guess = cuboid.identify().unwrap();
+        assert_eq!(guess.name, "cube");
+        assert!(guess.counts && guess.vertex_config && guess.convex);
+        assert!(!guess.symmetry && !guess.equilateral);
+    }
+}
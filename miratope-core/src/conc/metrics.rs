@@ -0,0 +1,364 @@
+//! Declares the [`Metrics`] of a polytope: its centers and the hyperspheres
+//! around and inside of it, which get cached in the polytope so that they
+//! don't have to be recalculated every time they're asked for.
+
+use super::{Concrete, ConcretePolytope};
+use crate::{
+    abs::Ranked,
+    float::{Float, Real},
+    geometry::{Hypersphere, Point, Subspace},
+    Polytope,
+};
+
+/// The centers and radii of a polytope.
+#[derive(Clone, Debug)]
+pub struct Metrics {
+    /// The hypersphere through every vertex, if there's one.
+    pub circumsphere: Option<Hypersphere<Real>>,
+
+    /// The smallest hypersphere containing every vertex. This is the
+    /// circumsphere whenever the circumcenter lies within the polytope.
+    pub bounding_sphere: Option<Hypersphere<Real>>,
+
+    /// The largest hypersphere within the polytope. This is only calculated
+    /// for convex polytopes, and is the insphere whenever the polytope has one.
+    pub insphere: Option<Hypersphere<Real>>,
+
+    /// The average of the vertices.
+    pub gravicenter: Option<Point<Real>>,
+
+    /// The center of mass of the polytope.
+    pub volume_centroid: Option<Point<Real>>,
+}
+
+impl Metrics {
+    /// Calculates the metrics of a polytope.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    pub(super) fn new(poly: &Concrete) -> Self {
+        Self {
+            circumsphere: poly.circumsphere(),
+            bounding_sphere: bounding_sphere(poly.vertices()),
+            insphere: insphere(poly),
+            gravicenter: poly.gravicenter(),
+            volume_centroid: poly.volume_centroid(),
+        }
+    }
+}
+
+/// Returns the smallest hypersphere with every one of some points on its
+/// surface, or `None` if there's no such hypersphere.
+fn sphere_through(points: &[&Point<Real>]) -> Option<Hypersphere<Real>> {
+    let (&first, rest) = points.split_first()?;
+    let mut center = first.clone();
+    let mut subspace = Subspace::new(first.clone());
+
+    for &p in rest {
+        let basis_vector = subspace.add(p)?;
+        let distance = ((&center - p).norm_squared() - (&center - first).norm_squared())
+            / (2.0 * (p - first).dot(basis_vector));
+        center += basis_vector * distance;
+    }
+
+    Some(Hypersphere {
+        squared_radius: (&center - first).norm_squared(),
+        center,
+    })
+}
+
+/// Returns whether a point lies within a hypersphere, up to floating-point
+/// error.
+fn contains(sphere: &Option<Hypersphere<Real>>, p: &Point<Real>) -> bool {
+    sphere.as_ref().map_or(false, |sphere| {
+        (p - &sphere.center).norm() <= sphere.radius() + Real::EPS
+    })
+}
+
+/// Finds the smallest hypersphere containing the first `end` points and with
+/// every point on the boundary on its surface, by the move-to-front variant
+/// of [Welzl's algorithm](https://en.wikipedia.org/wiki/Smallest-circle_problem#Welzl's_algorithm).
+fn move_to_front<'a>(
+    points: &mut [&'a Point<Real>],
+    end: usize,
+    boundary: &mut Vec<&'a Point<Real>>,
+) -> Option<Hypersphere<Real>> {
+    let mut sphere = sphere_through(boundary);
+
+    // The hypersphere is determined by the boundary alone.
+    if boundary.len() == points[0].len() + 1 {
+        return sphere;
+    }
+
+    for i in 0..end {
+        let p = points[i];
+        if !contains(&sphere, p) {
+            boundary.push(p);
+            // Points that don't actually determine a hypersphere because of
+            // floating-point error are skipped.
+            if let Some(new_sphere) = move_to_front(points, i, boundary) {
+                sphere = Some(new_sphere);
+            }
+            boundary.pop();
+
+            points[..=i].rotate_right(1);
+        }
+    }
+
+    sphere
+}
+
+/// Returns the smallest hypersphere containing a set of points, or `None` if
+/// there are no points.
+fn bounding_sphere(vertices: &[Point<Real>]) -> Option<Hypersphere<Real>> {
+    let mut points: Vec<_> = vertices.iter().collect();
+    if points.is_empty() {
+        return None;
+    }
+
+    let end = points.len();
+    move_to_front(&mut points, end, &mut Vec::new())
+}
+
+/// Returns the largest hypersphere within a convex polytope, or `None` if the
+/// polytope isn't convex or doesn't have any volume.
+///
+/// This is the hypersphere whose center is the furthest away from the facet
+/// hyperplanes, which is found by linear programming.
+fn insphere(poly: &Concrete) -> Option<Hypersphere<Real>> {
+    let rank = poly.rank();
+    if rank < 2 {
+        return None;
+    }
+
+    // We work within the subspace the polytope spans.
+    let subspace = Subspace::from_points(poly.vertices().iter());
+    let flat_vertices = subspace.flatten_vec(poly.vertices());
+    let dim = rank - 1;
+    if flat_vertices[0].len() != dim {
+        return None;
+    }
+    let gravicenter = flat_vertices.iter().sum::<Point<Real>>() / flat_vertices.len() as Real;
+
+    // Every facet hyperplane, as a normal pointing inwards and the distance to
+    // the gravicenter. For a convex polytope, the gravicenter is inside of it.
+    let mut facets = Vec::with_capacity(poly.facet_count());
+    for idx in 0..poly.facet_count() {
+        let facet_vertices = poly.abs().element_vertices(rank - 1, idx)?;
+        let hyperplane = Subspace::from_points(facet_vertices.iter().map(|&v| &flat_vertices[v]));
+        if !hyperplane.is_hyperplane() {
+            return None;
+        }
+
+        let base = hyperplane.project(&gravicenter);
+        let mut normal = &gravicenter - &base;
+        let distance = normal.normalize_mut();
+        if distance < Real::EPS
+            || flat_vertices
+                .iter()
+                .any(|v| (v - &base).dot(&normal) < -Real::EPS)
+        {
+            return None;
+        }
+
+        facets.push((normal, distance));
+    }
+
+    // We maximize the radius r of a hypersphere with center c = g + y, where g
+    // is the gravicenter, subject to -n·y + r ≤ d for every facet with normal n
+    // at distance d. Since y can be negative, it's split as y⁺ - y⁻.
+    let constraints = facets
+        .iter()
+        .map(|(normal, _)| {
+            normal
+                .iter()
+                .map(|&x| -x)
+                .chain(normal.iter().copied())
+                .chain(std::iter::once(1.0))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let bounds = facets.iter().map(|&(_, distance)| distance).collect();
+    let mut objective = vec![0.0; 2 * dim + 1];
+    objective[2 * dim] = 1.0;
+
+    let solution = simplex(constraints, bounds, objective)?;
+    let radius = solution[2 * dim];
+    if radius < Real::EPS {
+        return None;
+    }
+
+    let center =
+        gravicenter + Point::from_iterator(dim, (0..dim).map(|i| solution[i] - solution[dim + i]));
+    let center = if subspace.is_full_rank() {
+        center
+    } else {
+        subspace
+            .basis
+            .iter()
+            .zip(center.iter())
+            .fold(subspace.offset.clone(), |p, (b, &c)| p + b * c)
+    };
+
+    Some(Hypersphere::with_radius(center, radius))
+}
+
+/// Maximizes `c · x` subject to `a x ≤ b` and `x ≥ 0`, for some nonnegative
+/// `b`, by the [simplex method](https://en.wikipedia.org/wiki/Simplex_algorithm)
+/// with Bland's rule. Returns `None` if the maximum is unbounded.
+///
+/// The linear program is stored as a dictionary, where every row holds the
+/// coefficients of the nonbasic variables in the equation for a basic one.
+fn simplex(mut a: Vec<Vec<Real>>, mut b: Vec<Real>, mut c: Vec<Real>) -> Option<Vec<Real>> {
+    let n = c.len();
+    let mut nonbasic: Vec<_> = (0..n).collect();
+    let mut basic: Vec<_> = (n..n + b.len()).collect();
+
+    // Since b is nonnegative, setting every original variable to zero is a
+    // feasible starting point.
+    while let Some(e) = (0..n)
+        .filter(|&j| c[j] > Real::EPS)
+        .min_by_key(|&j| nonbasic[j])
+    {
+        // The row whose constraint is the tightest on the entering variable.
+        let mut leaving: Option<(usize, Real)> = None;
+        for (i, row) in a.iter().enumerate() {
+            if row[e] > Real::EPS {
+                let ratio = b[i] / row[e];
+                let better = match leaving {
+                    None => true,
+                    Some((l, best)) => {
+                        ratio < best - Real::EPS
+                            || (ratio < best + Real::EPS && basic[i] < basic[l])
+                    }
+                };
+                if better {
+                    leaving = Some((i, ratio));
+                }
+            }
+        }
+        let (l, _) = leaving?;
+
+        // Swaps the entering and leaving variables.
+        let pivot = a[l][e];
+        for (j, x) in a[l].iter_mut().enumerate() {
+            *x = if j == e { 1.0 / pivot } else { *x / pivot };
+        }
+        b[l] /= pivot;
+
+        let pivot_row = a[l].clone();
+        for (i, row) in a.iter_mut().enumerate() {
+            let factor = row[e];
+            if i != l {
+                for (j, x) in row.iter_mut().enumerate() {
+                    *x = if j == e {
+                        -factor * pivot_row[e]
+                    } else {
+                        *x - factor * pivot_row[j]
+                    };
+                }
+                b[i] -= factor * b[l];
+            }
+        }
+
+        let factor = c[e];
+        for (j, x) in c.iter_mut().enumerate() {
+            *x = if j == e {
+                -factor * pivot_row[e]
+            } else {
+                *x - factor * pivot_row[j]
+            };
+        }
+
+        std::mem::swap(&mut basic[l], &mut nonbasic[e]);
+    }
+
+    let mut x = vec![0.0; n];
+    for (i, &var) in basic.iter().enumerate() {
+        if var < n {
+            x[var] = b[i];
+        }
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use approx::assert_abs_diff_eq;
+
+    /// Checks the hyperspheres of a cube.
+    #[test]
+    fn cube() {
+        let mut cube = Concrete::cube();
+        let metrics = cube.metrics_mut().clone();
+
+        let circumsphere = metrics.circumsphere.unwrap();
+        let bounding_sphere = metrics.bounding_sphere.unwrap();
+        let insphere = metrics.insphere.unwrap();
+        assert_abs_diff_eq!(
+            circumsphere.radius(),
+            (0.75 as Real).sqrt(),
+            epsilon = Real::EPS
+        );
+        assert_abs_diff_eq!(
+            bounding_sphere.radius(),
+            (0.75 as Real).sqrt(),
+            epsilon = Real::EPS
+        );
+        assert_abs_diff_eq!(insphere.radius(), 0.5, epsilon = Real::EPS);
+        assert_abs_diff_eq!(insphere.center.norm(), 0.0, epsilon = Real::EPS);
+    }
+
+    /// Checks the hyperspheres of a 1 × 2 rectangle in 3D, which has no
+    /// insphere and whose bounding sphere doesn't touch every side.
+    #[test]
+    fn rectangle() {
+        let mut rectangle = Concrete::dyad().duoprism(&Concrete::dyad_with(2.0));
+        for v in rectangle.vertices_mut() {
+            *v = Point::from_iterator(3, v.iter().copied().chain(std::iter::once(1.0)));
+        }
+        let metrics = rectangle.metrics_mut().clone();
+
+        let bounding_sphere = metrics.bounding_sphere.unwrap();
+        let insphere = metrics.insphere.unwrap();
+        assert_abs_diff_eq!(
+            bounding_sphere.radius(),
+            (1.25 as Real).sqrt(),
+            epsilon = Real::EPS
+        );
+        assert_abs_diff_eq!(insphere.radius(), 0.5, epsilon = Real::EPS);
+        assert_abs_diff_eq!(insphere.center[2], 1.0, epsilon = Real::EPS);
+    }
+
+    /// Checks that the bounding sphere of an obtuse triangle is the one on its
+    /// longest side.
+    #[test]
+    fn obtuse() {
+        let points = vec![
+            Point::from_vec(vec![-1.0, 0.0]),
+            Point::from_vec(vec![1.0, 0.0]),
+            Point::from_vec(vec![0.0, 0.2]),
+        ];
+        let sphere = bounding_sphere(&points).unwrap();
+        assert_abs_diff_eq!(sphere.radius(), 1.0, epsilon = Real::EPS);
+        assert_abs_diff_eq!(sphere.center.norm(), 0.0, epsilon = Real::EPS);
+    }
+
+    /// Checks that the metrics are discarded once the polytope changes.
+    #[test]
+    fn invalidate() {
+        let mut cube = Concrete::cube();
+        cube.metrics_mut();
+        assert!(cube.metrics().is_some());
+
+        cube.scale(2.0);
+        assert!(cube.metrics().is_none());
+        assert_abs_diff_eq!(
+            cube.metrics_mut().insphere.as_ref().unwrap().radius(),
+            1.0,
+            epsilon = Real::EPS
+        );
+    }
+}
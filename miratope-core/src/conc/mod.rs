@@ -1,25 +1,32 @@
 //! Declares the [`Concrete`] polytope type and all associated data structures.
 
+pub mod conway;
 pub mod cycle;
 pub mod element_types;
 pub mod faceting;
+pub mod hull;
+pub mod identify;
+pub mod metrics;
 pub mod symmetry;
+pub mod wythoff;
 
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
     ops::{Index, IndexMut}, iter,
 };
 
 use super::{
     abs::{
-        flag::{Flag, FlagChanges, FlagEvent, OrientedFlagIter},
+        flag::{Flag, FlagChanges, FlagEvent, Orientation, OrientedFlagIter},
         Abstract, ElementList, Ranked, SubelementList,
     },
     DualError, Polytope,
 };
+use metrics::Metrics;
 use crate::{
     abs::{AbstractBuilder, Element, ElementMap, Subelements, Superelements, Ranks},
-    float::Float,
+    exact::{self, Exact, ExactPoint},
+    float::{Float, Real},
     geometry::*,
 };
 
@@ -28,6 +35,49 @@ use partitions::{PartitionVec, partition_vec};
 use rayon::prelude::*;
 use vec_like::*;
 
+/// An RGBA color, with every component between 0 and 1.
+pub type Rgba = [f32; 4];
+
+/// The point of a polytope that gets moved to the origin when recentering it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecenterMode {
+    /// The average of the vertices.
+    Centroid,
+
+    /// The point equidistant from all vertices, or the closest thing to it in
+    /// the least-squares sense if there's no such point.
+    Circumcenter,
+
+    /// The center of the bounding box of the vertices.
+    BoundingBoxCenter,
+
+    /// The center of mass of the solid polytope, see
+    /// [`ConcretePolytope::volume_centroid`].
+    CenterOfMass,
+}
+
+impl Default for RecenterMode {
+    fn default() -> Self {
+        Self::Centroid
+    }
+}
+
+/// The shape of a cap built over a facet by [`ConcretePolytope::facet_cap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CapType {
+    /// A pyramid with its apex above the centroid of the facet.
+    Pyramid,
+
+    /// A prism extruded outwards from the facet.
+    Prism,
+}
+
+impl Default for CapType {
+    fn default() -> Self {
+        Self::Pyramid
+    }
+}
+
 /// Represents a [concrete polytope](https://polytope.miraheze.org/wiki/Polytope),
 /// which is an [`Abstract`] together with its corresponding vertices.
 #[derive(Debug, Clone)]
@@ -35,10 +85,23 @@ pub struct Concrete {
     /// The list of vertices as points in Euclidean space.
     // todo: come up with a more compact representation, making use of the fact
     // all points have the same length?
-    pub vertices: Vec<Point<f64>>,
+    pub vertices: Vec<Point<Real>>,
 
     /// The underlying abstract polytope.
     pub abs: Abstract,
+
+    /// The colors of the 2-elements of the polytope, if they were given by
+    /// the file it was loaded from.
+    pub face_colors: Option<Vec<Rgba>>,
+
+    /// The exact coordinates of the vertices, if the polytope was built from
+    /// primitives by operations that keep them exact. These are cleared
+    /// whenever the vertices move; see [`Self::exact_vertices`].
+    pub exact: Option<Vec<ExactPoint>>,
+
+    /// The centers and radii of the polytope, if they've been calculated. These
+    /// are cleared whenever the polytope changes; see [`Self::clear_metrics`].
+    pub metrics: Option<Metrics>,
 }
 
 impl Index<usize> for Concrete {
@@ -53,6 +116,7 @@ impl Index<usize> for Concrete {
 impl IndexMut<usize> for Concrete {
     /// Gets the list of elements with a given rank.
     fn index_mut(&mut self, rank: usize) -> &mut Self::Output {
+        self.clear_metrics();
         &mut self.abs[rank]
     }
 }
@@ -69,6 +133,7 @@ impl Index<(usize, usize)> for Concrete {
 impl IndexMut<(usize, usize)> for Concrete {
     /// Gets the list of elements with a given rank.
     fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        self.clear_metrics();
         &mut self.abs[index]
     }
 }
@@ -76,7 +141,7 @@ impl IndexMut<(usize, usize)> for Concrete {
 impl Concrete {
     /// Initializes a new concrete polytope from a set of vertices and an
     /// underlying abstract polytope. Does some debug assertions on the input.
-    pub fn new(vertices: Vec<Point<f64>>, abs: Abstract) -> Self {
+    pub fn new(vertices: Vec<Point<Real>>, abs: Abstract) -> Self {
         // There must be as many abstract vertices as concrete ones.
         debug_assert_eq!(
             abs.vertex_count(),
@@ -94,7 +159,299 @@ impl Concrete {
         }
 
         // With no further info, we create a generic name for the polytope.
-        Self { vertices, abs }
+        Self {
+            vertices,
+            abs,
+            face_colors: None,
+            exact: None,
+            metrics: None,
+        }
+    }
+
+    /// Returns the exact coordinates of the vertices, if the polytope has them.
+    /// Operations that move the vertices without updating the exact
+    /// coordinates fall back to floating-point.
+    pub fn exact_vertices(&self) -> Option<&[ExactPoint]> {
+        self.exact.as_deref()
+    }
+
+    /// Returns whether the polytope has exact coordinates. See
+    /// [`Self::exact_vertices`].
+    pub fn is_exact(&self) -> bool {
+        self.exact_vertices().is_some()
+    }
+
+    /// Returns the centers and radii of the polytope, if they've been
+    /// calculated since it last changed.
+    pub fn metrics(&self) -> Option<&Metrics> {
+        self.metrics.as_ref()
+    }
+
+    /// Forgets the centers and radii of the polytope. Every method that changes
+    /// the polytope does this, but it must be done by hand after changing
+    /// [`Self::vertices`] or [`Self::abs`] directly.
+    pub fn clear_metrics(&mut self) {
+        self.metrics = None;
+    }
+
+    /// Returns the centers and radii of the polytope, only calculating them
+    /// again if the polytope has changed since the last time. Sorts the
+    /// elements of the polytope.
+    pub fn metrics_mut(&mut self) -> &Metrics {
+        if !self.abs.sorted() {
+            self.element_sort();
+        }
+        if self.metrics().is_none() {
+            self.metrics = Some(Metrics::new(self));
+        }
+
+        self.metrics.as_ref().unwrap()
+    }
+
+    /// Sets the exact coordinates of the polytope, as long as they match the
+    /// floating-point ones. This is checked only once, when they're set.
+    fn set_exact(&mut self, exact: Option<Vec<ExactPoint>>) {
+        self.exact = exact.filter(|exact| {
+            exact.len() == self.vertices.len()
+                && exact.iter().zip(&self.vertices).all(|(e, v)| {
+                    e.len() == v.len() && (exact::to_point(e) - v).norm() < Real::EPS
+                })
+        });
+    }
+
+    /// Sets the exact coordinates of the polytope. See [`Self::set_exact`].
+    fn with_exact(mut self, exact: Option<Vec<ExactPoint>>) -> Self {
+        self.set_exact(exact);
+        self
+    }
+
+    /// Returns the exact vertices of the dual of a polytope, as long as it has
+    /// exact coordinates and the reciprocation sphere is the unit hypersphere
+    /// centered at the origin. Mirrors [`ConcretePolytope::try_dual_mut_with`].
+    fn exact_dual_vertices(&self, sphere: &Hypersphere<Real>) -> Option<Vec<ExactPoint>> {
+        #[allow(clippy::float_cmp)]
+        let unit = sphere.squared_radius == 1.0 && sphere.center.iter().all(|&x| x == 0.0);
+        if !unit {
+            return None;
+        }
+
+        let exact = self.exact_vertices()?;
+        let rank = self.rank();
+
+        // We project the origin onto the polytope's hyperplane, and then onto
+        // each of the facets.
+        let origin = vec![Exact::ZERO; exact.first()?.len()];
+        let o = exact::project(&origin, &exact.iter().collect::<Vec<_>>())?;
+        let projections = if rank >= 2 {
+            (0..self.facet_count())
+                .map(|idx| {
+                    let facet: Vec<_> = self
+                        .abs
+                        .element_vertices(rank - 1, idx)?
+                        .into_iter()
+                        .map(|v| &exact[v])
+                        .collect();
+                    exact::project(&o, &facet)
+                })
+                .collect::<Option<Vec<_>>>()?
+        } else {
+            exact.to_vec()
+        };
+
+        // Reciprocates the projected points.
+        projections
+            .into_iter()
+            .map(|v| {
+                let inv = exact::dot(&v, &v)?.checked_inv()?;
+                v.iter().map(|x| x.checked_mul(inv)).collect()
+            })
+            .collect()
+    }
+
+    /// Returns the colors of the 2-elements of the polytope, as long as there's
+    /// exactly one for every face.
+    pub fn face_colors(&self) -> Option<&[Rgba]> {
+        let colors = self.face_colors.as_ref()?;
+
+        if self.rank() >= 4 && colors.len() == self.el_count(3) {
+            Some(colors)
+        } else {
+            None
+        }
+    }
+
+    /// Builds a compound of the simplices given by
+    /// [`ConcretePolytope::triangulate_full`], each of them pushed away from the
+    /// gravicenter by a given fraction of the distance from its own gravicenter.
+    pub fn exploded_simplices(&self, factor: Real) -> Self {
+        let center = self
+            .gravicenter()
+            .unwrap_or_else(|| Point::zeros(self.dim_or()));
+        let simplex = Abstract::simplex(self.rank());
+
+        Self::compound(self.triangulate_full().into_iter().map(|indices| {
+            let vertices: Vec<_> = indices.iter().map(|&idx| self.vertices[idx].clone()).collect();
+            let offset = (vertices.iter().sum::<Point<Real>>() / indices.len() as Real - &center) * factor;
+
+            Self::new(
+                vertices.into_iter().map(|v| v + &offset).collect(),
+                simplex.clone(),
+            )
+        }))
+    }
+
+    /// Returns whether two polytopes with connected flag graphs are congruent,
+    /// meaning that some combinatorial isomorphism between them preserves the
+    /// distances between all vertices, up to a given tolerance.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] on both polytopes before
+    /// calling this method.
+    pub fn is_congruent(&self, other: &Self, tolerance: Real) -> bool {
+        let dist = |p: &Self, i: usize, j: usize| (&p.vertices[i] - &p.vertices[j]).norm();
+        let n = self.vertex_count();
+
+        self.abs.vertex_isomorphisms(&other.abs).iter().any(|map| {
+            (0..n).all(|i| {
+                (0..i).all(|j| (dist(self, i, j) - dist(other, map[i], map[j])).abs() <= tolerance)
+            })
+        })
+    }
+
+    /// Returns the faces of a polyhedron as cycles of vertices, all of them
+    /// oriented consistently, so that any two faces run through their common
+    /// edge in opposite directions. This is done by a breadth-first search
+    /// over the faces, starting anew on every component. In 3D, every
+    /// component is then oriented so that its faces point outwards.
+    ///
+    /// Returns `None` if the polytope isn't a polyhedron, if some edge isn't
+    /// shared by exactly two faces, or if the polyhedron is non-orientable.
+    pub fn oriented_face_cycles(&self) -> Option<Vec<cycle::CycleList>> {
+        if self.rank() != 4 {
+            return None;
+        }
+
+        let mut faces: Vec<_> = self[3]
+            .iter()
+            .map(|face| {
+                cycle::CycleList::from_edges(face.subs.iter().map(|&i| &self[(2, i)].subs))
+            })
+            .collect();
+
+        // Every cycle, as the index of its face and its index within the face.
+        let cycles: Vec<(usize, usize)> = faces
+            .iter()
+            .enumerate()
+            .flat_map(|(f, cycles)| (0..cycles.len()).map(move |c| (f, c)))
+            .collect();
+
+        /// The directed edges of a cycle.
+        fn directed_edges(cycle: &cycle::Cycle) -> impl Iterator<Item = (usize, usize)> + '_ {
+            let len = cycle.len();
+            (0..len).map(move |i| (cycle[i], cycle[(i + 1) % len]))
+        }
+
+        // The cycles through every edge, and whether each of them runs through
+        // it from its lower vertex to its higher one.
+        let mut edges: HashMap<_, Vec<_>> = HashMap::new();
+        for (idx, &(f, c)) in cycles.iter().enumerate() {
+            for (a, b) in directed_edges(&faces[f][c]) {
+                edges.entry((a.min(b), a.max(b))).or_default().push((idx, a < b));
+            }
+        }
+
+        if edges.values().any(|through| through.len() != 2) {
+            return None;
+        }
+
+        // Whether every cycle must be reversed, found component by component.
+        let mut reverse = vec![None; cycles.len()];
+        let mut components = Vec::new();
+
+        for start in 0..cycles.len() {
+            if reverse[start].is_some() {
+                continue;
+            }
+
+            reverse[start] = Some(false);
+            let mut component = vec![start];
+            let mut queue = VecDeque::from(vec![start]);
+
+            while let Some(idx) = queue.pop_front() {
+                let (f, c) = cycles[idx];
+
+                for (a, b) in directed_edges(&faces[f][c]) {
+                    // Whether the cycle runs upwards through the edge, once
+                    // oriented. Its neighbor must run downwards.
+                    let up = (a < b) != reverse[idx].unwrap();
+
+                    for &(other, other_up) in &edges[&(a.min(b), a.max(b))] {
+                        if other == idx {
+                            continue;
+                        }
+
+                        let other_reverse = other_up == up;
+                        match reverse[other] {
+                            None => {
+                                reverse[other] = Some(other_reverse);
+                                component.push(other);
+                                queue.push_back(other);
+                            }
+                            Some(old) if old != other_reverse => return None,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        // Makes the faces of every component point outwards, which is the case
+        // when the signed volume they enclose is positive.
+        if self.dim_or() == 3 {
+            let det = |a: &Point<Real>, b: &Point<Real>, c: &Point<Real>| {
+                a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+                    + a[2] * (b[0] * c[1] - b[1] * c[0])
+            };
+
+            for component in &components {
+                let volume: Real = component
+                    .iter()
+                    .map(|&idx| {
+                        let (f, c) = cycles[idx];
+                        let cycle = &faces[f][c];
+                        let p = &self.vertices[cycle[0]];
+                        let cone: Real = (1..cycle.len() - 1)
+                            .map(|i| det(p, &self.vertices[cycle[i]], &self.vertices[cycle[i + 1]]))
+                            .sum();
+
+                        if reverse[idx].unwrap() {
+                            -cone
+                        } else {
+                            cone
+                        }
+                    })
+                    .sum();
+
+                if volume < 0.0 {
+                    for &idx in component {
+                        reverse[idx] = reverse[idx].map(|r| !r);
+                    }
+                }
+            }
+        }
+
+        // A polytope with its orientation reversed has every face run the
+        // other way.
+        let reversed = self.abs.orientation() == Orientation::Odd;
+        for (idx, &(f, c)) in cycles.iter().enumerate() {
+            if reverse[idx].unwrap() != reversed {
+                faces[f][c].reverse();
+            }
+        }
+
+        Some(faces)
     }
 }
 
@@ -106,6 +463,7 @@ impl Polytope for Concrete {
     }
 
     fn abs_mut(&mut self) -> &mut Abstract {
+        self.clear_metrics();
         &mut self.abs
     }
 
@@ -120,12 +478,13 @@ impl Polytope for Concrete {
 
     /// Builds the unique polytope of rank 0.
     fn point() -> Self {
-        Self::new(vec![Vec::new().into()], Abstract::point())
+        Self::new(vec![Vec::new().into()], Abstract::point()).with_exact(Some(vec![Vec::new()]))
     }
 
     /// Builds a dyad with unit edge length, centered at the origin.
     fn dyad() -> Self {
-        Self::dyad_with(1.0)
+        let half = Exact::frac(1, 2);
+        Self::dyad_with(1.0).with_exact(exact_dyad(half))
     }
 
     /// Builds a convex regular polygon with `n` sides and unit edge length,
@@ -154,6 +513,8 @@ impl Polytope for Concrete {
     /// polytope in place. If unsuccessful, leaves the polytope unchanged and
     /// returns `false`.
     fn petrial_mut(&mut self) -> bool {
+        self.face_colors = None;
+        self.clear_metrics();
         self.abs.petrial_mut()
     }
 
@@ -177,6 +538,17 @@ impl Polytope for Concrete {
     /// # Panics
     /// This method will panic if the polytopes have different ranks.
     fn comp_append(&mut self, mut p: Self) {
+        // We only keep the colors if both polytopes had them.
+        self.face_colors = match (self.face_colors(), p.face_colors()) {
+            (Some(c), Some(d)) => Some(c.iter().chain(d).copied().collect()),
+            _ => None,
+        };
+        self.exact = match (self.exact_vertices(), p.exact_vertices()) {
+            (Some(e), Some(f)) => Some(e.iter().chain(f).cloned().collect()),
+            _ => None,
+        };
+
+        self.clear_metrics();
         self.abs.comp_append(p.abs);
         self.vertices.append(&mut p.vertices);
     }
@@ -201,10 +573,15 @@ impl Polytope for Concrete {
             // todo: this is quite inefficient for a small element figure since
             // we take the dual of the entire thing.
             if let Some(mut element_fig) = self.try_dual()?.element(self.rank() - rank, idx) {
+                // The nullitope has no vertices to flatten.
+                if element_fig.is_nullitope() {
+                    return Ok(Some(element_fig));
+                }
+
                 let subspace = Subspace::from_points(element_fig.vertices.iter());
                 element_fig.flatten();
                 element_fig.recenter_with(
-                    &subspace.flatten(&subspace.project(&Point::zeros(self.dim().unwrap()))),
+                    &subspace.flatten(&subspace.project(&Point::zeros(self.dim_or()))),
                 );
                 element_fig.try_dual_mut()?;
                 return Ok(Some(element_fig));
@@ -299,7 +676,7 @@ impl Polytope for Concrete {
             unsafe {
                 if builder.ranks().is_dyadic().is_ok() {
                     let abs = builder.build();
-                    let conc = Concrete{abs, vertices};
+                    let conc = Concrete::new(vertices, abs);
                     output.push(conc);
                 }
             }
@@ -344,10 +721,16 @@ impl Polytope for Concrete {
     /// Builds a [duoprism](https://polytope.miraheze.org/wiki/Prism_product)
     /// from two polytopes.
     fn duoprism(&self, p: &Self) -> Self {
+        let exact = match (self.exact_vertices(), p.exact_vertices()) {
+            (Some(e), Some(f)) => Some(exact_duoprism_vertices(e, f)),
+            _ => None,
+        };
+
         Self::new(
             duoprism_vertices(&self.vertices, &p.vertices),
             self.abs.duoprism(&p.abs),
         )
+        .with_exact(exact)
     }
 
     /// Builds a [duotegum](https://polytope.miraheze.org/wiki/Tegum_product)
@@ -373,6 +756,8 @@ impl Polytope for Concrete {
     /// Builds a [ditope](https://polytope.miraheze.org/wiki/Ditope) of a given
     /// polytope in place.
     fn ditope_mut(&mut self) {
+        self.face_colors = None;
+        self.clear_metrics();
         self.abs.ditope_mut();
     }
 
@@ -389,6 +774,9 @@ impl Polytope for Concrete {
     /// given polytope in place.
     fn hosotope_mut(&mut self) {
         self.vertices = vec![vec![-0.5].into(), vec![0.5].into()];
+        self.face_colors = None;
+        self.exact = None;
+        self.clear_metrics();
         self.abs.hosotope_mut();
     }
 
@@ -425,18 +813,18 @@ impl Polytope for Concrete {
             // equal to 0.
             for i in 0..dim {
                 let mut v = Point::zeros(dim);
-                v[i] = f64::HALF_SQRT_2;
+                v[i] = Real::HALF_SQRT_2;
                 vertices.push(v);
             }
 
             // Adds the remaining vertex, all of whose coordinates are equal.
-            let dim_f = dim as f64;
-            let a = (1.0 - (dim_f + 1.0).fsqrt()) * f64::HALF_SQRT_2 / dim_f;
+            let dim_f = dim as Real;
+            let a = (1.0 - (dim_f + 1.0).fsqrt()) * Real::HALF_SQRT_2 / dim_f;
             vertices.push(vec![a; dim].into());
 
             let mut simplex = Concrete::new(vertices, Abstract::simplex(rank));
             simplex.recenter();
-            simplex
+            simplex.with_exact(exact_simplex_vertices(rank))
         }
     }
 
@@ -446,29 +834,74 @@ impl Polytope for Concrete {
         if rank == 0 {
             Self::nullitope()
         } else {
-            Self::multitegum(iter::repeat(&Self::dyad_with(f64::SQRT_2)).take(rank - 1))
+            let half = Exact::sqrt(2).and_then(|x| x.checked_div(Exact::int(2)));
+            let dyad = Self::dyad_with(Real::SQRT_2).with_exact(exact_dyad(half));
+            Self::multitegum(iter::repeat(&dyad).take(rank - 1))
         }
     }
 
     /// Splits compound faces into their components.
     fn untangle_faces(&mut self) {
+        let face_count = self.abs.get_element_list(3).map(ElementList::len);
         self.abs.untangle_faces();
+
+        // Any split faces invalidate the face colors.
+        if self.abs.get_element_list(3).map(ElementList::len) != face_count {
+            self.face_colors = None;
+        }
     }
 }
 
+/// Returns the centroid and the unit outer normal of every facet of a
+/// polytope. The normals are taken within the subspace of the polytope, and
+/// are zero for facets passing through its gravicenter.
+fn facet_normals(poly: &Concrete) -> Vec<(Point<Real>, Vector<Real>)> {
+    let center = poly.gravicenter().unwrap();
+
+    (0..poly.facet_count())
+        .map(|idx| facet_normal(poly, &center, idx))
+        .collect()
+}
+
+/// Returns the centroid and the unit outer normal of a single facet of a
+/// polytope, given the gravicenter of the polytope.
+fn facet_normal(poly: &Concrete, center: &Point<Real>, idx: usize) -> (Point<Real>, Vector<Real>) {
+    let vertices = poly.element_vertices_ref(poly.rank() - 1, idx).unwrap();
+    let centroid = vertices.iter().copied().sum::<Point<Real>>() / vertices.len() as Real;
+    let normal = Subspace::from_points(vertices.into_iter()).project(center) - center;
+
+    let normal = match normal.try_normalize(Real::EPS) {
+        Some(normal) => normal,
+        None => Vector::zeros(center.len()),
+    };
+
+    (centroid, normal)
+}
+
+/// Returns whether the vertices of a facet of a polytope coincide with a given
+/// set of points, up to a given tolerance.
+fn facet_coincides(poly: &Concrete, idx: usize, points: &[Point<Real>], tolerance: Real) -> bool {
+    let vertices = poly.element_vertices_ref(poly.rank() - 1, idx).unwrap();
+
+    vertices.len() == points.len()
+        && vertices
+            .iter()
+            .all(|&v| points.iter().any(|p| (v - p).norm() <= tolerance))
+}
+
 /// Generates the vertices for either a tegum or a pyramid product with two
 /// given vertex sets and a given height.
 ///
 /// The vertices are the padded vertices of `p`, followed by the padded
 /// vertices of `q`.
 fn duopyramid_vertices(
-    p: &[Point<f64>],
-    q: &[Point<f64>],
-    p_pad: &Point<f64>,
-    q_pad: &Point<f64>,
-    height: f64,
+    p: &[Point<Real>],
+    q: &[Point<Real>],
+    p_pad: &Point<Real>,
+    q_pad: &Point<Real>,
+    height: Real,
     tegum: bool,
-) -> Vec<Point<f64>> {
+) -> Vec<Point<Real>> {
     // Duotegums with points should just return the original polytopes.
     if tegum {
         if p.get(0).map(|vp| vp.len()) == Some(0) {
@@ -500,8 +933,86 @@ fn duopyramid_vertices(
         .collect()
 }
 
+/// Generates the exact vertices of a dyad whose vertices are at plus or minus a
+/// given value.
+fn exact_dyad(half: Option<Exact>) -> Option<Vec<ExactPoint>> {
+    let half = half?;
+    Some(vec![vec![half.checked_neg()?], vec![half]])
+}
+
+/// Generates the exact vertices of a simplex as built by
+/// [`Concrete::simplex`], or returns `None` if they aren't exact.
+fn exact_simplex_vertices(rank: usize) -> Option<Vec<ExactPoint>> {
+    let dim = rank - 1;
+    if dim == 0 {
+        return Some(vec![Vec::new()]);
+    }
+
+    let h = Exact::sqrt(2)?.checked_div(Exact::int(2))?;
+    let a = Exact::ONE
+        .checked_sub(Exact::sqrt(rank as u64)?)?
+        .checked_mul(h)?
+        .checked_div(Exact::int(dim as i64))?;
+
+    let mut vertices: Vec<ExactPoint> = (0..dim)
+        .map(|i| {
+            let mut v = vec![Exact::ZERO; dim];
+            v[i] = h;
+            v
+        })
+        .collect();
+    vertices.push(vec![a; dim]);
+
+    // Moves the centroid to the origin.
+    let n = Exact::int(rank as i64);
+    let centroid = (0..dim)
+        .map(|i| {
+            vertices
+                .iter()
+                .try_fold(Exact::ZERO, |acc, v| acc.checked_add(v[i]))?
+                .checked_div(n)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    vertices
+        .into_iter()
+        .map(|v| v.iter().zip(&centroid).map(|(x, c)| x.checked_sub(*c)).collect())
+        .collect()
+}
+
+/// Generates the exact vertices for a duoprism with two given vertex sets.
+fn exact_duoprism_vertices(p: &[ExactPoint], q: &[ExactPoint]) -> Vec<ExactPoint> {
+    itertools::iproduct!(p.iter(), q.iter())
+        .map(|(vp, vq)| vp.iter().chain(vq.iter()).copied().collect())
+        .collect()
+}
+
+/// Generates the exact vertices for a duotegum with two given vertex sets,
+/// following [`duopyramid_vertices`].
+fn exact_duotegum_vertices(p: &[ExactPoint], q: &[ExactPoint]) -> Vec<ExactPoint> {
+    // Duotegums with points should just return the original polytopes.
+    if p.get(0).map(Vec::len) == Some(0) {
+        return q.to_owned();
+    } else if q.get(0).map(Vec::len) == Some(0) {
+        return p.to_owned();
+    }
+
+    let p_dim = p.get(0).map_or(0, Vec::len);
+    let q_dim = q.get(0).map_or(0, Vec::len);
+
+    p.iter()
+        .map(|vp| vp.iter().copied().chain(iter::repeat(Exact::ZERO).take(q_dim)).collect())
+        .chain(q.iter().map(|vq| {
+            iter::repeat(Exact::ZERO)
+                .take(p_dim)
+                .chain(vq.iter().copied())
+                .collect()
+        }))
+        .collect()
+}
+
 /// Generates the vertices for a duoprism with two given vertex sets.
-fn duoprism_vertices(p: &[Point<f64>], q: &[Point<f64>]) -> Vec<Point<f64>> {
+fn duoprism_vertices(p: &[Point<Real>], q: &[Point<Real>]) -> Vec<Point<Real>> {
     // The dimension of the points in p.
     let p_dim = if let Some(vp) = p.get(0) {
         vp.len()
@@ -539,13 +1050,16 @@ pub trait ConcretePolytope: Polytope {
     fn con_mut(&mut self) -> &mut Concrete;
 
     /// Returns a reference to the concrete vertices of the polytope.
-    fn vertices(&self) -> &Vec<Point<f64>> {
+    fn vertices(&self) -> &Vec<Point<Real>> {
         &self.con().vertices
     }
 
     /// Returns a mutable reference to the concrete vertices of the polytope.
-    fn vertices_mut(&mut self) -> &mut Vec<Point<f64>> {
-        &mut self.con_mut().vertices
+    fn vertices_mut(&mut self) -> &mut Vec<Point<Real>> {
+        let con = self.con_mut();
+        con.exact = None;
+        con.clear_metrics();
+        &mut con.vertices
     }
 
     /// Returns the number of dimensions of the space the polytope lives in,
@@ -561,11 +1075,11 @@ pub trait ConcretePolytope: Polytope {
     }
 
     /// Builds a dyad with a specified height.
-    fn dyad_with(height: f64) -> Self;
+    fn dyad_with(height: Real) -> Self;
 
     /// Builds the Grünbaumian star polygon `{n / d}` with unit circumradius,
     /// rotated by an angle.
-    fn grunbaum_star_polygon_with_rot(n: usize, d: usize, rot: f64) -> Self;
+    fn grunbaum_star_polygon_with_rot(n: usize, d: usize, rot: Real) -> Self;
 
     /// Builds the Grünbaumian star polygon `{n / d}` with unit circumradius. If
     /// `n` and `d` have a common factor, the result is a multiply-wound
@@ -587,11 +1101,11 @@ pub trait ConcretePolytope: Polytope {
         use gcd::Gcd;
 
         let gcd = n.gcd(d);
-        let angle = f64::TAU / f64::usize(n);
+        let angle = Real::TAU / Real::usize(n);
 
         Self::compound(
             (0..gcd).into_iter().map(|k| {
-                Self::grunbaum_star_polygon_with_rot(n / gcd, d / gcd, f64::usize(k) * angle)
+                Self::grunbaum_star_polygon_with_rot(n / gcd, d / gcd, Real::usize(k) * angle)
             }),
         )
     }
@@ -602,35 +1116,164 @@ pub trait ConcretePolytope: Polytope {
     /// # Panics
     /// Will panic if either `n < 2` or if `d < 1`, in which case there's
     /// nothing sensible to do.
-    fn star_polygon_with_edge(n: usize, d: usize, a: f64) -> Self {
+    fn star_polygon_with_edge(n: usize, d: usize, a: Real) -> Self {
         let mut p = Self::star_polygon(n, d);
-        p.scale(a / 2.0 / (f64::PI * d as f64 / n as f64).fsin());
+        p.scale(a / 2.0 / (Real::PI * d as Real / n as Real).fsin());
         p
     }
 
     /// Scales a polytope by a given factor.
-    fn scale(&mut self, k: f64) {
+    fn scale(&mut self, k: Real) {
         for v in self.vertices_mut() {
             *v *= k;
         }
     }
 
+    /// Reverses the orientation of every facet of a polytope, leaving its
+    /// vertices untouched.
+    fn reverse_orientation(&mut self) {
+        self.abs_mut().reverse_orientation();
+    }
+
+    /// Reflects a polytope across a hyperplane. For a chiral polytope, this
+    /// gives its enantiomorph.
+    fn reflect(&mut self, hyperplane: &Hyperplane<Real>) {
+        for v in self.vertices_mut() {
+            *v = hyperplane.project(v) * 2.0 - &*v;
+        }
+    }
+
+    /// Embeds a polytope into one more dimension, by appending a zero
+    /// coordinate to every vertex.
+    fn embed(&mut self) {
+        for v in self.vertices_mut() {
+            *v = v.clone().push(0.0);
+        }
+    }
+
+    /// Projects a polytope into one less dimension, by dropping the last
+    /// coordinate of every vertex. Does nothing for polytopes in 0D.
+    ///
+    /// Returns whether any two vertices coincide afterwards.
+    fn project(&mut self) -> bool {
+        if self.dim_or() == 0 {
+            return false;
+        }
+
+        for v in self.vertices_mut() {
+            let last = v.len() - 1;
+            *v = v.clone().remove_row(last);
+        }
+
+        let mut seen = BTreeSet::new();
+        !self
+            .vertices()
+            .iter()
+            .all(|v| seen.insert(PointOrd::new(v.clone())))
+    }
+
+    /// Returns an orthonormal basis for the plane spanned by the lowest
+    /// frequency Fourier coefficient of a polygon about the gravicenter, where
+    /// the polygon is given by the indices of its vertices in cyclic order.
+    /// Returns `None` if this coefficient doesn't span a plane.
+    ///
+    /// For a Petrie polygon of a regular polytope, this is the Coxeter plane,
+    /// onto which the polygon projects as a regular polygon. Nothing checks
+    /// that the polytope is regular, and the projection of any other polygon
+    /// needn't be regular.
+    fn coxeter_plane(&self, petrie: &[usize]) -> Option<[Vector<Real>; 2]> {
+        let center = self.gravicenter()?;
+        let n = petrie.len() as Real;
+
+        // The real and imaginary parts of the discrete Fourier coefficient of
+        // the Petrie polygon with the lowest frequency.
+        let mut re = Vector::zeros(self.dim_or());
+        let mut im = re.clone();
+        for (k, &idx) in petrie.iter().enumerate() {
+            let angle = Real::TAU * k as Real / n;
+            let v = &self.vertices()[idx] - &center;
+            re += &v * angle.cos();
+            im += v * angle.sin();
+        }
+
+        let mut plane = Subspace::new(Point::zeros(self.dim_or()));
+        let u = plane.add(&re)?.clone();
+        let w = plane.add(&im)?.clone();
+        Some([u, w])
+    }
+
+    /// Rotates a polytope about the origin, so that a plane spanned by two
+    /// orthonormal vectors becomes the plane of the first two coordinates.
+    fn align_plane(&mut self, plane: &[Vector<Real>; 2]) {
+        let dim = self.dim_or();
+
+        // Completes the plane into an orthonormal basis for the space.
+        let mut basis = Subspace::new(Point::zeros(dim));
+        basis.basis.extend(plane.iter().cloned());
+        for i in 0..dim {
+            if basis.is_full_rank() {
+                break;
+            }
+            let mut e = Point::zeros(dim);
+            e[i] = 1.0;
+            basis.add(&e);
+        }
+
+        let mut m = Matrix::from_fn(dim, dim, |i, j| basis.basis[i][j]);
+
+        // We want a rotation, not a reflection.
+        if dim >= 3 && m.determinant() < 0.0 {
+            m.row_mut(dim - 1).neg_mut();
+        }
+
+        for v in self.vertices_mut() {
+            *v = &m * &*v;
+        }
+    }
+
     /// Recenters a polytope so that the gravicenter is at the origin.
     fn recenter(&mut self) {
-        if let Some(gravicenter) = self.gravicenter() {
-            self.recenter_with(&gravicenter);
+        self.recenter_by(RecenterMode::default());
+    }
+
+    /// Recenters a polytope so that the center given by some mode is at the
+    /// origin. Does nothing in the case of the nullitope.
+    fn recenter_by(&mut self, mode: RecenterMode) {
+        if let Some(center) = self.center(mode) {
+            self.recenter_with(&center);
+        }
+    }
+
+    /// Returns the center of a polytope given by some mode, or `None` in the
+    /// case of the nullitope. The center of mass is also `None` whenever the
+    /// polytope has no volume.
+    fn center(&self, mode: RecenterMode) -> Option<Point<Real>> {
+        match mode {
+            RecenterMode::Centroid => self.gravicenter(),
+            RecenterMode::Circumcenter => match self.circumsphere() {
+                Some(sphere) => Some(sphere.center),
+                None => self.least_squares_circumcenter(),
+            },
+            RecenterMode::BoundingBoxCenter => self.bounding_box_center(),
+            RecenterMode::CenterOfMass => {
+                if self.abs().sorted() {
+                    self.volume_centroid()
+                } else {
+                    self.clone().volume_centroid_mut()
+                }
+            }
         }
     }
 
     /// Recenters a polytope so that a certain point is at the origin.
-    fn recenter_with(&mut self, p: &Point<f64>) {
+    fn recenter_with(&mut self, p: &Point<Real>) {
         for v in self.vertices_mut() {
             *v -= p;
         }
     }
 
     /// Applies a linear transformation to all vertices of a polytope.
-    fn apply(mut self, m: &Matrix<f64>) -> Self {
+    fn apply(mut self, m: &Matrix<Real>) -> Self {
         for v in self.vertices_mut() {
             *v = m * v as &_;
         }
@@ -639,11 +1282,11 @@ pub trait ConcretePolytope: Polytope {
     }
 
     /// Returns an arbitrary truncate of a polytope.
-    fn truncate_with(&self, truncate_type: Vec<usize>, depth: Vec<f64>) -> Self;
+    fn truncate_with(&self, truncate_type: Vec<usize>, depth: Vec<Real>) -> Self;
 
     /// Calculates the circumsphere of a polytope. Returns `None` if the
     /// polytope isn't circumscribable.
-    fn circumsphere(&self) -> Option<Hypersphere<f64>> {
+    fn circumsphere(&self) -> Option<Hypersphere<Real>> {
         let mut vertices = self.vertices().iter();
 
         let first_vertex = vertices.next()?.clone();
@@ -654,7 +1297,7 @@ pub trait ConcretePolytope: Polytope {
             // If the new vertex does not lie on the hyperplane of the others:
             if let Some(basis_vector) = subspace.add(vertex) {
                 // Calculates the new circumcenter.
-                let distance: f64 = ((&center - vertex).norm_squared()
+                let distance: Real = ((&center - vertex).norm_squared()
                     - (&center - &first_vertex).norm_squared())
                     / (2.0 * (vertex - &first_vertex).dot(basis_vector));
 
@@ -665,7 +1308,7 @@ pub trait ConcretePolytope: Polytope {
             else if abs_diff_ne!(
                 (&center - &first_vertex).norm(),
                 (&center - vertex).norm(),
-                epsilon = f64::EPS
+                epsilon = Real::EPS
             ) {
                 return None;
             }
@@ -677,17 +1320,56 @@ pub trait ConcretePolytope: Polytope {
         })
     }
 
+    /// Calculates the point within the affine hull of the vertices whose
+    /// squared distances to them are the closest to being equal, in the
+    /// least-squares sense. Agrees with the circumcenter whenever the polytope
+    /// has a circumsphere. Returns `None` in the case of the nullitope.
+    fn least_squares_circumcenter(&self) -> Option<Point<Real>> {
+        let mut vertices = self.vertices().iter();
+        let first_vertex = vertices.next()?;
+        let diffs: Vec<_> = vertices.map(|v| v - first_vertex).collect();
+
+        if diffs.is_empty() {
+            return Some(first_vertex.clone());
+        }
+
+        // The center c = v₀ + x satisfies 2 (vᵢ - v₀) · x = |vᵢ - v₀|² for
+        // every vertex vᵢ. The minimal solution lies in the affine hull.
+        let dim = first_vertex.len();
+        let a = Matrix::from_fn(diffs.len(), dim, |i, j| 2.0 * diffs[i][j]);
+        let b = Vector::from_iterator(diffs.len(), diffs.iter().map(|d| d.norm_squared()));
+        let x = a.svd(true, true).solve(&b, Real::EPS).ok()?;
+
+        Some(first_vertex + x)
+    }
+
+    /// Calculates the center of the bounding box of a polytope, or returns
+    /// `None` in the case of the nullitope.
+    fn bounding_box_center(&self) -> Option<Point<Real>> {
+        let mut vertices = self.vertices().iter();
+        let first_vertex = vertices.next()?;
+        let mut min = first_vertex.clone();
+        let mut max = first_vertex.clone();
+
+        for v in vertices {
+            min = min.inf(v);
+            max = max.sup(v);
+        }
+
+        Some((min + max) / 2.0)
+    }
+
     /// Calculates the gravicenter of a polytope, or returns `None` in the case
     /// of the nullitope.
-    fn gravicenter(&self) -> Option<Point<f64>> {
+    fn gravicenter(&self) -> Option<Point<Real>> {
         (!self.is_nullitope())
-            .then(|| self.vertices().iter().sum::<Point<f64>>() / (self.vertex_count()) as f64)
+            .then(|| self.vertices().iter().sum::<Point<Real>>() / (self.vertex_count()) as Real)
     }
 
     /// Gets the least and greatest distance of a vertex of the polytope,
     /// measuring from a specified direction, or returns `None` in the case of
     /// the nullitope.
-    fn minmax(&self, direction: Vector<f64>) -> Option<(f64, f64)> {
+    fn minmax(&self, direction: Vector<Real>) -> Option<(Real, Real)> {
         use itertools::{Itertools, MinMaxResult::*};
 
         let hyperplane = Hyperplane::new(direction, 0.0);
@@ -709,11 +1391,28 @@ pub trait ConcretePolytope: Polytope {
         }
     }
 
+    /// Returns the index of the vertex closest to a given point, or `None` in
+    /// the case of the nullitope. Any coordinates missing from the point are
+    /// treated as zero.
+    fn nearest_vertex(&self, p: &Point<Real>) -> Option<usize> {
+        let dist = |v: &Point<Real>| {
+            (0..v.len())
+                .map(|i| (v[i] - p.get(i).copied().unwrap_or_default()).powi(2))
+                .sum::<Real>()
+        };
+
+        self.vertices()
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, v)| ordered_float::OrderedFloat(dist(v)))
+            .map(|(idx, _)| idx)
+    }
+
     /// Returns a map from the elements in a polytope to a crude average of
     /// their vertices. Specifically, every vertex is mapped to itself, and
     /// every other element is mapped to the average of the images of its
     /// subelements.
-    fn avg_vertex_map(&self) -> ElementMap<Point<f64>> {
+    fn avg_vertex_map(&self) -> ElementMap<Point<Real>> {
         // Maps every element of the polytope to one of its vertices.
         let mut map = ElementMap::new();
         map.push(Vec::new());
@@ -733,8 +1432,8 @@ pub trait ConcretePolytope: Polytope {
                         el.subs
                             .iter()
                             .map(|&idx| &map[(r - 1, idx)])
-                            .sum::<Point<f64>>()
-                            / f64::usize(el.subs.len())
+                            .sum::<Point<Real>>()
+                            / Real::usize(el.subs.len())
                     })
                     .collect(),
             );
@@ -744,16 +1443,16 @@ pub trait ConcretePolytope: Polytope {
     }
 
     /// Returns the length of a given edge.
-    fn edge_len(&self, idx: usize) -> Option<f64> {
+    fn edge_len(&self, idx: usize) -> Option<Real> {
         let edge = self.get_element(2, idx)?;
         Some((&self.vertices()[edge.subs[0]] - &self.vertices()[edge.subs[1]]).norm())
     }
 
     /// Checks whether a polytope is equilateral to a fixed precision, and with
     /// a specified edge length.
-    fn is_equilateral_with(&self, len: f64) -> bool {
+    fn is_equilateral_with(&self, len: Real) -> bool {
         (0..self.edge_count())
-            .all(|idx| abs_diff_eq!(self.edge_len(idx).unwrap(), len, epsilon = f64::EPS))
+            .all(|idx| abs_diff_eq!(self.edge_len(idx).unwrap(), len, epsilon = Real::EPS))
     }
 
     /// Checks whether a polytope is equilateral to a fixed precision.
@@ -761,11 +1460,79 @@ pub trait ConcretePolytope: Polytope {
         self.edge_count() == 0 || self.is_equilateral_with(self.edge_len(0).unwrap())
     }
 
+    /// Returns the lengths of all edges of the polytope.
+    fn edge_lengths(&self) -> Vec<Real> {
+        (0..self.edge_count())
+            .map(|idx| self.edge_len(idx).unwrap())
+            .collect()
+    }
+
+    /// Returns the mean edge length of a polytope. For an equilateral polytope,
+    /// this is just its edge length. Returns `None` if the polytope has no
+    /// edges of positive length.
+    fn mean_edge_length(&self) -> Option<Real> {
+        let lengths = self.edge_lengths();
+        if lengths.is_empty() {
+            return None;
+        }
+
+        let mean = lengths.iter().sum::<Real>() / lengths.len() as Real;
+        (mean >= Real::EPS).then(|| mean)
+    }
+
+    /// Scales a polytope so that its mean edge length is 1. For an equilateral
+    /// polytope, this is just its edge length. Returns the scale factor, or
+    /// `None` if the polytope has no edges of positive length.
+    fn normalize_edges(&mut self) -> Option<Real> {
+        let k = 1.0 / self.mean_edge_length()?;
+        self.scale(k);
+        Some(k)
+    }
+
+    /// Returns whether a polytope is convex, i.e. whether every vertex lies on
+    /// the same side of every facet's hyperplane as the gravicenter.
+    fn is_convex(&self) -> bool {
+        self.is_convex_with(Real::EPS)
+    }
+
+    /// Returns whether a polytope is convex, allowing vertices to lie on the
+    /// wrong side of a facet's hyperplane by up to a given tolerance.
+    /// Polytopes that don't span their ambient space are checked within their
+    /// own subspace.
+    fn is_convex_with(&self, tolerance: Real) -> bool {
+        // Points and the nullitope are trivially convex.
+        if self.rank() <= 1 {
+            return true;
+        }
+
+        let mut flat = self.con().clone();
+        flat.flatten();
+        let gravicenter = flat.gravicenter().unwrap();
+
+        (0..flat.facet_count()).all(|idx| {
+            let facet_vertices = flat.element_vertices_ref(flat.rank() - 1, idx).unwrap();
+            let hyperplane = Subspace::from_points(facet_vertices.into_iter());
+            if !hyperplane.is_hyperplane() {
+                return false;
+            }
+
+            // The normal vector pointing towards the inside of the polytope.
+            let mut normal = &gravicenter - hyperplane.project(&gravicenter);
+            if normal.normalize_mut() < Real::EPS {
+                return false;
+            }
+
+            flat.vertices()
+                .iter()
+                .all(|v| (v - hyperplane.project(v)).dot(&normal) >= -tolerance)
+        })
+    }
+
     /// I haven't actually implemented this in the general case.
     ///
     /// # Todo
     /// Maybe make this work in the general case?
-    fn midradius(&self) -> f64 {
+    fn midradius(&self) -> Real {
         let edge_subs = &self[(2, 0)].subs;
         (&self.vertices()[edge_subs[0]] + &self.vertices()[edge_subs[1]]).norm() / 2.0
     }
@@ -774,20 +1541,41 @@ pub trait ConcretePolytope: Polytope {
     /// place, or does nothing in case any facets go through the reciprocation
     /// center. In case of failure, returns the index of the facet through the
     /// projection center.
-    fn try_dual_mut_with(&mut self, sphere: &Hypersphere<f64>) -> Result<(), Self::DualError>;
+    fn try_dual_mut_with(&mut self, sphere: &Hypersphere<Real>) -> Result<(), Self::DualError>;
 
     /// Returns the dual of a polytope with a given reciprocation sphere, or
     /// `None` if any facets pass through the reciprocation center.
-    fn try_dual_with(&self, sphere: &Hypersphere<f64>) -> Result<Self, Self::DualError> {
+    fn try_dual_with(&self, sphere: &Hypersphere<Real>) -> Result<Self, Self::DualError> {
         let mut clone = self.clone();
         clone.try_dual_mut_with(sphere).map(|_| clone)
     }
 
+    /// Builds a [Kleetope](https://polytope.miraheze.org/wiki/Kleetope) of a
+    /// polytope, raising a pyramid with a given height over every facet.
+    fn kis_with(&self, height: Real) -> Self;
+
+    /// Returns a height for [`Self::kis_with`] that keeps the Kleetope of a
+    /// convex polytope convex.
+    fn kis_height(&self) -> Real;
+
+    /// Builds a [Kleetope](https://polytope.miraheze.org/wiki/Kleetope) of a
+    /// polytope, with a height that keeps it convex whenever the original
+    /// polytope is.
+    fn kis(&self) -> Self {
+        self.kis_with(self.kis_height())
+    }
+
     /// Builds a pyramid with a specified apex.
-    fn pyramid_with(&self, apex: Point<f64>) -> Self;
+    fn pyramid_with(&self, apex: Point<Real>) -> Self;
 
     /// Builds a prism with a specified height.
-    fn prism_with(&self, height: f64) -> Self;
+    fn prism_with(&self, height: Real) -> Self;
+
+    /// Builds a [lace prism](https://polytope.miraheze.org/wiki/Lace_prism)
+    /// with a specified height, whose top base is a copy of the polytope
+    /// scaled about the origin by a given factor. A scale of 1 gives the
+    /// usual prism, while other scales give frusta.
+    fn lace(&self, top_scale: Real, height: Real) -> Self;
 
     /// Builds a uniform prism from an {n/d} polygon.
     fn uniform_prism(n: usize, d: usize) -> Self {
@@ -795,7 +1583,7 @@ pub trait ConcretePolytope: Polytope {
     }
 
     /// Builds a tegum with two specified apices.
-    fn tegum_with(&self, apex1: Point<f64>, apex2: Point<f64>) -> Self;
+    fn tegum_with(&self, apex1: Point<Real>, apex2: Point<Real>) -> Self;
 
     /// Builds an [antiprism](https://polytope.miraheze.org/wiki/Antiprism),
     /// using the specified sets of vertices for the base and the dual base.
@@ -803,7 +1591,7 @@ pub trait ConcretePolytope: Polytope {
     /// The vertices of the base should be specified in the same order as those
     /// of the original polytope. The vertices of the dual face should be
     /// specified in the same order as the facets of the original polytope.
-    fn antiprism_with_vertices<I: Iterator<Item = Point<f64>>, J: Iterator<Item = Point<f64>>>(
+    fn antiprism_with_vertices<I: Iterator<Item = Point<Real>>, J: Iterator<Item = Point<Real>>>(
         &self,
         vertices: I,
         dual_vertices: J,
@@ -814,8 +1602,8 @@ pub trait ConcretePolytope: Polytope {
     /// the dual base, and separates the bases by the given height.
     fn try_antiprism_with(
         &self,
-        sphere: &Hypersphere<f64>,
-        height: f64,
+        sphere: &Hypersphere<Real>,
+        height: Real,
     ) -> Result<Self, Self::DualError> {
         let half_height = height / 2.0;
         let vertices = self.vertices().iter().map(|v| v.push(-half_height));
@@ -831,7 +1619,7 @@ pub trait ConcretePolytope: Polytope {
     /// # Panics
     /// Panics if any facets pass through the inversion center. If you want to
     /// handle this possibility, use [`Self::try_antiprism_with`] instead.
-    fn antiprism_with(&self, sphere: &Hypersphere<f64>, height: f64) -> Self {
+    fn antiprism_with(&self, sphere: &Hypersphere<Real>, height: Real) -> Self {
         self.try_antiprism_with(sphere, height).unwrap()
     }
 
@@ -841,7 +1629,7 @@ pub trait ConcretePolytope: Polytope {
 
         // Appropriately scaled antiprism.
         if n != 2 * d {
-            let angle = f64::PI * f64::usize(d) / f64::usize(n);
+            let angle = Real::PI * Real::usize(d) / Real::usize(n);
             let cos = angle.fcos();
             let height = ((cos - (2.0 * angle).fcos()) * 2.0).fsqrt();
 
@@ -849,13 +1637,13 @@ pub trait ConcretePolytope: Polytope {
                 &Hypersphere::with_squared_radius(Point::zeros(2), cos),
                 height,
             );
-            antiprism.scale(0.5 / (f64::PI * d as f64 / n as f64).fsin());
+            antiprism.scale(0.5 / (Real::PI * d as Real / n as Real).fsin());
 
             antiprism
         }
         // Digon compounds are a special case.
         else {
-            let half_height = f64::HALF_SQRT_2;
+            let half_height = Real::HALF_SQRT_2;
             let vertices = polygon.vertices().iter().map(|v| v.push(-half_height));
             let dual_vertices = polygon
                 .vertices()
@@ -871,7 +1659,7 @@ pub trait ConcretePolytope: Polytope {
 
     /// Gets the references to the (geometric) vertices of an element on the
     /// polytope.
-    fn element_vertices_ref(&self, rank: usize, idx: usize) -> Option<Vec<&Point<f64>>> {
+    fn element_vertices_ref(&self, rank: usize, idx: usize) -> Option<Vec<&Point<Real>>> {
         Some(
             self.abs()
                 .element_vertices(rank, idx)?
@@ -886,20 +1674,28 @@ pub trait ConcretePolytope: Polytope {
     fn duopyramid_with(
         &self,
         other: &Self,
-        self_offset: &Point<f64>,
-        other_offset: &Point<f64>,
-        height: f64,
+        self_offset: &Point<Real>,
+        other_offset: &Point<Real>,
+        height: Real,
     ) -> Self;
 
     /// Generates a duopyramid from two given polytopes with a given offset.
-    fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<f64>, q_offset: &Point<f64>) -> Self;
+    fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<Real>, q_offset: &Point<Real>) -> Self;
 
     /// Computes the volume of a polytope by adding up the contributions of all
     /// flags. Returns `None` if the volume is undefined.
     ///
+    /// Every flag spans a simplex together with the origin, whose volume is
+    /// counted with the sign of the flag's orientation. This is a discrete form
+    /// of the divergence theorem, so the result doesn't depend on the origin,
+    /// and it doesn't assume convexity. For self-intersecting polytopes, every
+    /// region is counted as many times as the boundary winds around it, so that
+    /// the core of a pentagram is counted twice. Non-orientable polytopes have
+    /// no volume.
+    ///
     /// # Panics
     /// You must call [`Polytope::element_sort`] before calling this method.
-    fn volume(&self) -> Option<f64> {
+    fn volume(&self) -> Option<Real> {
         let rank = self.rank();
 
         // We leave the nullitope's volume undefined.
@@ -925,7 +1721,7 @@ pub trait ConcretePolytope: Polytope {
 
         // Maps every element of the polytope to one of its vertices.
         let vertex_map = self.vertex_map();
-        let mut volume = 0.0;
+        let mut volume = 0.0f64;
 
         // All of the flags we've found so far.
         let mut all_flags = HashSet::new();
@@ -935,7 +1731,9 @@ pub trait ConcretePolytope: Polytope {
             // If this flag forms a new component of the polytope, we iterate
             // over the oriented flags in this component.
             if !all_flags.contains(&flag) {
-                let mut component_volume = 0.0;
+                // Always accumulated in double precision, since many of these
+                // terms may cancel out.
+                let mut component_volume = 0.0f64;
 
                 for flag_event in
                     OrientedFlagIter::with_flags(self.abs(), FlagChanges::all(rank), flag.into())
@@ -947,7 +1745,7 @@ pub trait ConcretePolytope: Polytope {
                         // For each flag, there's a simplex defined by any vertices in its
                         // elements and the origin. We add up the volumes of all of these
                         // simplices times the sign of the flag that generated them.
-                        component_volume += oriented_flag.orientation.sign()
+                        component_volume += (oriented_flag.orientation.sign()
                             * Matrix::from_iterator(
                                 rank - 1,
                                 rank - 1,
@@ -960,7 +1758,7 @@ pub trait ConcretePolytope: Polytope {
                                     .flatten()
                                     .copied(),
                             )
-                            .determinant();
+                            .determinant()) as f64;
                     }
                     // A non-orientable polytope doesn't have a volume.
                     else {
@@ -973,31 +1771,276 @@ pub trait ConcretePolytope: Polytope {
             }
         }
 
-        Some(volume / f64::u32(crate::factorial(rank - 1)))
+        Some(Real::f64(volume) / Real::u32(crate::factorial(rank - 1)))
     }
 
     /// Computes the volume of a polytope by adding up the contributions of all
     /// flags. Returns `None` if the volume is undefined.
-    fn volume_mut(&mut self) -> Option<f64> {
+    fn volume_mut(&mut self) -> Option<Real> {
         self.element_sort();
         self.volume()
     }
 
-    /// Projects the vertices of the polytope into the lowest dimension possible.
-    /// If the polytope's subspace is already of full rank, this is a no-op.
-    fn flatten(&mut self);
-
-    /// Flattens the vertices of a polytope into a specified subspace.
-    fn flatten_into(&mut self, subspace: &Subspace<f64>);
+    /// Computes the center of mass of a polytope, thought of as a solid of
+    /// uniform density. Returns `None` if the polytope has no volume, or if
+    /// its volume is zero.
+    ///
+    /// This uses the same simplices as [`Self::volume`], weighting each of
+    /// their centroids by their signed volume. Unlike the
+    /// [`gravicenter`](Self::gravicenter), which averages the vertices, this
+    /// doesn't depend on how the vertices are spread out over the polytope.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    fn volume_centroid(&self) -> Option<Point<Real>> {
+        let rank = self.rank();
+        if rank < 2 {
+            return None;
+        }
+
+        let subspace = Subspace::from_points(self.vertices().iter());
+        let flat_vertices = subspace.flatten_vec(self.vertices());
+        let dim = rank - 1;
+        if flat_vertices.get(0)?.len() != dim {
+            return None;
+        }
+
+        let vertex_map = self.vertex_map();
+        let mut volume = 0.0;
+        let mut moment = Point::zeros(dim);
+        let mut all_flags = HashSet::new();
+
+        for flag in self.flags() {
+            if !all_flags.contains(&flag) {
+                let mut component_volume = 0.0;
+                let mut component_moment = Point::zeros(dim);
+
+                for flag_event in
+                    OrientedFlagIter::with_flags(self.abs(), FlagChanges::all(rank), flag.into())
+                {
+                    if let FlagEvent::Flag(oriented_flag) = flag_event {
+                        all_flags.insert(oriented_flag.flag.clone());
+                        let sign = oriented_flag.orientation.sign();
+                        let simplex: Vec<_> = oriented_flag
+                            .into_iter()
+                            .enumerate()
+                            .skip(1)
+                            .take(dim)
+                            .map(|(rank, idx)| &flat_vertices[vertex_map[(rank, idx)]])
+                            .collect();
+
+                        // The simplex spanned by the flag and the origin, and
+                        // its centroid.
+                        let simplex_volume = sign
+                            * Matrix::from_iterator(
+                                dim,
+                                dim,
+                                simplex.iter().copied().flatten().copied(),
+                            )
+                            .determinant();
+                        let centroid = simplex.into_iter().sum::<Point<Real>>() / rank as Real;
+
+                        component_volume += simplex_volume;
+                        component_moment += centroid * simplex_volume;
+                    } else {
+                        return None;
+                    }
+                }
+
+                // Every component counts positively, whatever its orientation.
+                if component_volume < 0.0 {
+                    component_volume = -component_volume;
+                    component_moment.neg_mut();
+                }
+
+                volume += component_volume;
+                moment += component_moment;
+            }
+        }
+
+        if volume.fabs() < Real::EPS {
+            return None;
+        }
+
+        let center = moment / volume;
+        Some(if subspace.is_full_rank() {
+            center
+        } else {
+            subspace
+                .basis
+                .iter()
+                .zip(center.iter())
+                .fold(subspace.offset.clone(), |p, (b, &c)| p + b * c)
+        })
+    }
+
+    /// Computes the center of mass of a polytope. See
+    /// [`Self::volume_centroid`].
+    fn volume_centroid_mut(&mut self) -> Option<Point<Real>> {
+        self.element_sort();
+        self.volume_centroid()
+    }
+
+    /// Decomposes a polytope into simplices, each given by the indices of its
+    /// vertices. Every element is split into pyramids with their apex at its
+    /// lowest-indexed vertex, over the simplices of its facets that don't
+    /// contain it. For convex polytopes, this partitions the solid polytope.
+    fn triangulate_full(&self) -> Vec<Vec<usize>> {
+        let rank = self.rank();
+        if rank == 0 {
+            return Vec::new();
+        }
+
+        // The vertices and the simplices of every element of the last rank.
+        let mut elements: Vec<(Vec<usize>, Vec<Vec<usize>>)> = (0..self.vertex_count())
+            .map(|v| (vec![v], vec![vec![v]]))
+            .collect();
+
+        for r in 2..=rank {
+            elements = self[r]
+                .iter()
+                .map(|el| {
+                    let mut vertices: Vec<_> = el
+                        .subs
+                        .iter()
+                        .flat_map(|&sub| elements[sub].0.iter().copied())
+                        .collect();
+                    vertices.sort_unstable();
+                    vertices.dedup();
+                    let apex = vertices[0];
+
+                    let simplices = el
+                        .subs
+                        .iter()
+                        .filter(|&&sub| elements[sub].0.binary_search(&apex).is_err())
+                        .flat_map(|&sub| {
+                            elements[sub].1.iter().map(move |simplex| {
+                                let mut simplex = simplex.clone();
+                                simplex.push(apex);
+                                simplex
+                            })
+                        })
+                        .collect();
+
+                    (vertices, simplices)
+                })
+                .collect();
+        }
+
+        elements.pop().map(|(_, simplices)| simplices).unwrap_or_default()
+    }
+
+    /// Computes the surface area of a polytope, i.e. the sum of the volumes of
+    /// its facets. Returns `None` if the volume of any facet is undefined.
+    fn surface_area(&self) -> Option<Real> {
+        if self.rank() <= 1 {
+            return None;
+        }
+
+        (0..self.facet_count())
+            .map(|idx| self.facet(idx)?.volume_mut())
+            .sum()
+    }
+
+    /// Computes the volume of a polytope scaled to have unit mean edge length,
+    /// which is how volumes of uniform polytopes are usually tabulated.
+    ///
+    /// # Panics
+    /// You must call [`Polytope::element_sort`] before calling this method.
+    fn unit_edge_volume(&self) -> Option<Real> {
+        let edge = self.mean_edge_length()?;
+        Some(self.volume()? / edge.powi(self.rank() as i32 - 1))
+    }
+
+    /// Computes the surface area of a polytope scaled to have unit mean edge
+    /// length.
+    fn unit_edge_surface_area(&self) -> Option<Real> {
+        let edge = self.mean_edge_length()?;
+        Some(self.surface_area()? / edge.powi(self.rank() as i32 - 2))
+    }
+
+    /// Returns the angular defect at every vertex of a polyhedron, i.e. a full
+    /// turn minus the angles of the faces at the vertex. By Descartes'
+    /// theorem, these add up to 2π times the Euler characteristic of the
+    /// polyhedron. Returns `None` if the polytope isn't a polyhedron.
+    fn angular_defects(&self) -> Option<Vec<Real>> {
+        if self.rank() != 4 {
+            return None;
+        }
+
+        let abs = self.abs();
+        let vertices = self.vertices();
+        let mut defects = vec![Real::TAU; vertices.len()];
+
+        for face in abs[3].iter() {
+            let edges = face.subs.iter().map(|&i| &abs[(2, i)].subs);
+
+            for cycle in cycle::CycleList::from_edges(edges).iter() {
+                let len = cycle.len();
+
+                for i in 0..len {
+                    let v = &vertices[cycle[i]];
+                    let prev = &vertices[cycle[(i + len - 1) % len]] - v;
+                    let next = &vertices[cycle[(i + 1) % len]] - v;
+                    defects[cycle[i]] -= prev.angle(&next);
+                }
+            }
+        }
+
+        Some(defects)
+    }
+
+    /// Projects the vertices of the polytope into the lowest dimension possible.
+    /// If the polytope's subspace is already of full rank, this is a no-op.
+    fn flatten(&mut self);
+
+    /// Flattens the vertices of a polytope into a specified subspace.
+    fn flatten_into(&mut self, subspace: &Subspace<Real>);
 
     /// Slices the polytope through a given plane.
-    fn cross_section(&self, slice: &Hyperplane<f64>) -> Self;
+    fn cross_section(&self, slice: &Hyperplane<Real>) -> Self;
 	
 	/// Checks if is fissary.
     fn is_fissary(&self) -> bool;
 	
 	/// Compounds coplanar facets
-    fn fuse_facets(&self) -> Self;
+    fn fuse_facets(&self) -> Self {
+        self.fuse_facets_with(Real::EPS)
+    }
+
+    /// Compounds coplanar facets, treating vertices within a given distance of
+    /// a facet's hyperplane as lying on it.
+    fn fuse_facets_with(&self, tolerance: Real) -> Self;
+
+    /// Merges every set of adjacent facets lying on the same hyperplane, up to
+    /// a given tolerance, into a single facet. The ridges between them are
+    /// removed, along with any elements that are left without
+    /// superelements. Returns the number of facets that were removed.
+    fn merge_coplanar_facets(&mut self, tolerance: Real) -> usize;
+
+    /// Merges all vertices within a given distance of each other into their
+    /// average, rewiring the elements accordingly. Elements that become
+    /// degenerate are dropped, and elements that become equal are merged.
+    /// Returns the number of vertices that were removed.
+    fn weld_vertices(&mut self, tolerance: Real) -> usize;
+
+    /// Builds a cap of a given type over a facet of the polytope, pointing
+    /// outwards with a given height. The cap shares the facet's vertices, so
+    /// that it can be glued on with [`Self::augment_with`]. Returns `None` if
+    /// the facet doesn't exist or passes through the gravicenter.
+    fn facet_cap(&self, idx: usize, cap_type: CapType, height: Real) -> Option<Self>;
+
+    /// Glues a cap onto a facet of the polytope. One of the facets of the cap
+    /// must coincide with the given facet. The coincident vertices are welded
+    /// together with a given tolerance, and the shared facet is removed.
+    /// Returns `None` if the cap doesn't fit onto the facet.
+    fn augment_with(&self, idx: usize, cap: &Self, tolerance: Real) -> Option<Self>;
+
+    /// Glues a cap of a given type and height onto a facet of the polytope.
+    /// Returns `None` if this isn't possible.
+    fn augment(&self, idx: usize, cap_type: CapType, height: Real) -> Option<Self> {
+        self.augment_with(idx, &self.facet_cap(idx, cap_type, height)?, Real::EPS)
+    }
 	
 }
 
@@ -1011,7 +2054,7 @@ impl ConcretePolytope for Concrete {
     }
 
     /// Builds a dyad with a specified height.
-    fn dyad_with(height: f64) -> Self {
+    fn dyad_with(height: Real) -> Self {
         let half_height = height / 2.0;
 
         Self::new(
@@ -1022,22 +2065,34 @@ impl ConcretePolytope for Concrete {
 
     /// Builds the Grünbaumian star polygon `{n / d}` with unit circumradius,
     /// rotated by an angle.
-    fn grunbaum_star_polygon_with_rot(n: usize, d: usize, rot: f64) -> Self {
+    fn grunbaum_star_polygon_with_rot(n: usize, d: usize, rot: Real) -> Self {
         assert!(n >= 2);
         assert!(d >= 1);
 
-        let angle = f64::TAU * f64::usize(d) / f64::usize(n);
+        let angle = Real::TAU * Real::usize(d) / Real::usize(n);
+
+        // The vertices are exact whenever they're at multiples of 15°.
+        #[allow(clippy::float_cmp)]
+        let exact = (rot == 0.0 && (24 * d) % n == 0).then(|| {
+            (0..n)
+                .map(|k| {
+                    let (sin, cos) = Exact::sin_cos_15(k * 24 * d / n);
+                    vec![sin, cos]
+                })
+                .collect()
+        });
 
         Self::new(
             (0..n)
                 .into_iter()
                 .map(|k| {
-                    let (sin, cos) = (f64::usize(k) * angle + rot).fsin_cos();
+                    let (sin, cos) = (Real::usize(k) * angle + rot).fsin_cos();
                     vec![sin, cos].into()
                 })
                 .collect(),
             Abstract::polygon(n),
         )
+        .with_exact(exact)
     }
 
     /// Builds the dual of a polytope with a given reciprocation sphere in
@@ -1047,7 +2102,7 @@ impl ConcretePolytope for Concrete {
     ///
     /// # Panics
     /// This method shouldn't panic. If it does, please file a bug.
-    fn try_dual_mut_with(&mut self, sphere: &Hypersphere<f64>) -> Result<(), Self::DualError> {
+    fn try_dual_mut_with(&mut self, sphere: &Hypersphere<Real>) -> Result<(), Self::DualError> {
         // If we're dealing with a nullitope, the dual is itself.
         let rank = self.rank();
         if rank == 0 {
@@ -1088,29 +2143,102 @@ impl ConcretePolytope for Concrete {
             }
         }
 
+        let exact = self.exact_dual_vertices(sphere);
         self.vertices = projections;
+        self.face_colors = None;
+        self.set_exact(exact);
+        self.clear_metrics();
         self.abs.dual_mut();
         Ok(())
     }
 
+    /// Builds a [Kleetope](https://polytope.miraheze.org/wiki/Kleetope) of a
+    /// polytope, raising a pyramid with a given height over every facet.
+    fn kis_with(&self, height: Real) -> Self {
+        if self.rank() < 3 {
+            return self.clone();
+        }
+
+        let mut vertices = self.vertices.clone();
+        for (centroid, normal) in facet_normals(self) {
+            vertices.push(centroid + normal * height);
+        }
+
+        Self::new(vertices, self.abs.kis())
+    }
+
+    /// Returns half the greatest height for which the Kleetope of a convex
+    /// polytope remains convex, i.e. for which every apex lies beneath the
+    /// hyperplanes of all other facets.
+    fn kis_height(&self) -> Real {
+        if self.rank() < 3 {
+            return 1.0;
+        }
+
+        let facets = facet_normals(self);
+        let mut height = Real::INFINITY;
+
+        for (i, (c0, n0)) in facets.iter().enumerate() {
+            for (j, (c1, n1)) in facets.iter().enumerate() {
+                let cos = n0.dot(n1);
+
+                if i != j && cos > Real::EPS {
+                    let h = n1.dot(&(c1 - c0)) / cos;
+                    if h > Real::EPS {
+                        height = height.min(h);
+                    }
+                }
+            }
+        }
+
+        if height.is_finite() {
+            height / 2.0
+        } else {
+            1.0
+        }
+    }
+
     /// Builds a pyramid with a specified apex.
-    fn pyramid_with(&self, apex: Point<f64>) -> Self {
+    fn pyramid_with(&self, apex: Point<Real>) -> Self {
         let mut poly = self.pyramid();
         *poly.vertices.last_mut().unwrap() = apex;
         poly
     }
 
     /// Builds a prism with a specified height.
-    fn prism_with(&self, height: f64) -> Self {
+    fn prism_with(&self, height: Real) -> Self {
         self.duoprism(&Self::dyad_with(height))
     }
 
+    /// Builds a lace prism with a specified height, whose top base is scaled
+    /// about the origin by a given factor.
+    fn lace(&self, top_scale: Real, height: Real) -> Self {
+        let mut lace = self.prism();
+        let half_height = height / 2.0;
+
+        // The bottom base sits at -1/2 and the top base at 1/2.
+        for v in lace.vertices_mut() {
+            let last = v.len() - 1;
+            if v[last] > 0.0 {
+                for x in v.iter_mut().take(last) {
+                    *x *= top_scale;
+                }
+                v[last] = half_height;
+            } else {
+                v[last] = -half_height;
+            }
+        }
+
+        lace
+    }
+
     /// Builds a tegum with two specified apices.
-    fn tegum_with(&self, apex1: Point<f64>, apex2: Point<f64>) -> Self {
+    fn tegum_with(&self, apex1: Point<Real>, apex2: Point<Real>) -> Self {
         let mut poly = self.tegum();
-        let v = poly.vertices.len();
-        poly.vertices[v - 1] = apex1;
-        poly.vertices[v - 2] = apex2;
+        let vertices = poly.vertices_mut();
+        let v = vertices.len();
+        vertices[v - 1] = apex1;
+        vertices[v - 2] = apex2;
         poly
     }
 
@@ -1120,7 +2248,7 @@ impl ConcretePolytope for Concrete {
     /// The vertices of the base should be specified in the same order as those
     /// of the original polytope. The vertices of the dual face should be
     /// specified in the same order as the facets of the original polytope.
-    fn antiprism_with_vertices<I: Iterator<Item = Point<f64>>, J: Iterator<Item = Point<f64>>>(
+    fn antiprism_with_vertices<I: Iterator<Item = Point<Real>>, J: Iterator<Item = Point<Real>>>(
         &self,
         vertices: I,
         dual_vertices: J,
@@ -1147,9 +2275,9 @@ impl ConcretePolytope for Concrete {
     fn duopyramid_with(
         &self,
         other: &Self,
-        self_offset: &Point<f64>,
-        other_offset: &Point<f64>,
-        height: f64,
+        self_offset: &Point<Real>,
+        other_offset: &Point<Real>,
+        height: Real,
     ) -> Self {
         Self::new(
             duopyramid_vertices(
@@ -1166,11 +2294,20 @@ impl ConcretePolytope for Concrete {
 
     /// Builds a [duotegum](https://polytope.miraheze.org/wiki/Tegum_product)
     /// from two polytopes.
-    fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<f64>, q_offset: &Point<f64>) -> Self {
+    fn duotegum_with(p: &Self, q: &Self, p_offset: &Point<Real>, q_offset: &Point<Real>) -> Self {
+        // Offsets aren't exact, so we only keep exact coordinates without them.
+        #[allow(clippy::float_cmp)]
+        let offset = p_offset.iter().chain(q_offset.iter()).any(|&x| x != 0.0);
+        let exact = match (p.exact_vertices(), q.exact_vertices()) {
+            (Some(e), Some(f)) if !offset => Some(exact_duotegum_vertices(e, f)),
+            _ => None,
+        };
+
         Self::new(
             duopyramid_vertices(&p.vertices, &q.vertices, p_offset, q_offset, 0.0, true),
             p.abs.duotegum(&q.abs),
         )
+        .with_exact(exact)
     }
 
     /// Projects the vertices of the polytope into the lowest dimension possible.
@@ -1182,9 +2319,9 @@ impl ConcretePolytope for Concrete {
     }
 
     /// Flattens the vertices of a polytope into a specified subspace.
-    fn flatten_into(&mut self, subspace: &Subspace<f64>) {
+    fn flatten_into(&mut self, subspace: &Subspace<Real>) {
         if !subspace.is_full_rank() {
-            for v in &mut self.vertices {
+            for v in self.vertices_mut() {
                 *v = subspace.flatten(v);
             }
         }
@@ -1194,7 +2331,12 @@ impl ConcretePolytope for Concrete {
     ///
     /// # Todo
     /// We should make this function take a general [`Subspace`] instead.
-    fn cross_section(&self, slice: &Hyperplane<f64>) -> Self {
+    fn cross_section(&self, slice: &Hyperplane<Real>) -> Self {
+        // Slicing nothing gives nothing.
+        if self.is_nullitope() {
+            return Self::nullitope();
+        }
+
         if self.rank() < 4 {
             unreachable!();
         }
@@ -1322,7 +2464,7 @@ impl ConcretePolytope for Concrete {
         }
     }
 
-    fn truncate_with(&self, truncate_type: Vec<usize>, depth: Vec<f64>) -> Self {
+    fn truncate_with(&self, truncate_type: Vec<usize>, depth: Vec<Real>) -> Self {
         if truncate_type.is_empty() {
             println!("Cannot truncate with no active nodes!");
             return self.clone()
@@ -1330,9 +2472,9 @@ impl ConcretePolytope for Concrete {
         let (abs, subflags) = self.abs().truncate_and_flags(truncate_type.clone());
         let element_vertices = self.avg_vertex_map();
 
-        let mut vertex_coords = Vec::<Point<f64>>::new();
+        let mut vertex_coords = Vec::<Point<Real>>::new();
         for subflag in subflags {
-            let mut vector = Point::<f64>::from_vec(vec![0.0; self.rank() - 1]);
+            let mut vector = Point::<Real>::from_vec(vec![0.0; self.rank() - 1]);
             for (r, i) in subflag.iter().enumerate() {
                 vector += element_vertices[truncate_type[r] + 1][*i].clone() * depth[truncate_type[r]];
             }
@@ -1376,7 +2518,7 @@ impl ConcretePolytope for Concrete {
     }
 	
 	/// Fuses coplanar facets
-	fn fuse_facets(&self) -> Self {
+	fn fuse_facets_with(&self, tolerance: Real) -> Self {
 		let mut i = 0 as usize;
 		
 		let mut builder = AbstractBuilder::new();
@@ -1400,7 +2542,7 @@ impl ConcretePolytope for Concrete {
 			let facet = self.abs.ranks()[self.rank() - 1][i].clone();
 			let subspace = Subspace::from_points(facetvert);
 			
-			let mut contained_vertices = self.vertices.clone().into_iter().enumerate().filter(|x| subspace.is_outer(&x.1)).map(|x| x.0).collect::<Vec<usize>>();
+			let mut contained_vertices = self.vertices.clone().into_iter().enumerate().filter(|x| subspace.is_outer_with(&x.1, tolerance)).map(|x| x.0).collect::<Vec<usize>>();
 			contained_vertices.sort();
 			if compound.contains_key(&contained_vertices) {
 				compound.get_mut(&contained_vertices).unwrap().1.extend(facet.subs.clone());
@@ -1417,17 +2559,331 @@ impl ConcretePolytope for Concrete {
 		builder.push_max();
 		unsafe { Self::new(self.vertices.clone(),builder.build()) }
 	}
+
+    fn merge_coplanar_facets(&mut self, tolerance: Real) -> usize {
+        let rank = self.rank();
+        if rank < 3 {
+            return 0;
+        }
+
+        // The hyperplane of each facet, or `None` if it's degenerate.
+        let facet_count = self.el_count(rank - 1);
+        let facet_vertices: Vec<_> = (0..facet_count)
+            .map(|f| self.abs.element_vertices(rank - 1, f).unwrap())
+            .collect();
+        let hyperplanes: Vec<_> = facet_vertices
+            .iter()
+            .map(|vertices| {
+                let subspace =
+                    Subspace::from_points(vertices.iter().map(|&v| &self.vertices[v]));
+                (subspace.rank() + 2 == rank).then(|| subspace)
+            })
+            .collect();
+
+        // Finds the root of a facet in the union-find forest.
+        fn find(parent: &mut [usize], mut f: usize) -> usize {
+            while parent[f] != f {
+                parent[f] = parent[parent[f]];
+                f = parent[f];
+            }
+            f
+        }
+
+        // Joins every two facets through a ridge if they lie on the same
+        // hyperplane.
+        let mut parent: Vec<_> = (0..facet_count).collect();
+        let mut merged = false;
+        for ridge in self[rank - 2].iter() {
+            if let [f, g] = ridge.sups.as_slice() {
+                let (f, g) = (*f, *g);
+                if let (Some(hf), Some(_)) = (&hyperplanes[f], &hyperplanes[g]) {
+                    if facet_vertices[g]
+                        .iter()
+                        .all(|&v| hf.distance(&self.vertices[v]) <= tolerance)
+                    {
+                        let (f, g) = (find(&mut parent, f), find(&mut parent, g));
+                        if f != g {
+                            parent[f] = g;
+                            merged = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        if !merged {
+            return 0;
+        }
+
+        // The ridges between facets that were merged are removed.
+        let mut kept: Vec<Vec<bool>> = Vec::with_capacity(rank);
+        kept.push(vec![true]);
+        for r in 1..rank - 2 {
+            kept.push(vec![false; self.el_count(r)]);
+        }
+        let ridges_kept: Vec<_> = self[rank - 2]
+            .iter()
+            .map(|ridge| match ridge.sups.as_slice() {
+                [f, g] => find(&mut parent, *f) != find(&mut parent, *g),
+                _ => true,
+            })
+            .collect();
+        kept.push(ridges_kept);
+
+        // Any element left without superelements is removed.
+        for r in (1..rank - 2).rev() {
+            for (idx, el) in self[r + 1].iter().enumerate() {
+                if kept[r + 1][idx] {
+                    for &sub in el.subs.iter() {
+                        kept[r][sub] = true;
+                    }
+                }
+            }
+        }
+
+        // Maps the indices of the kept elements to their new indices.
+        let maps: Vec<Vec<Option<usize>>> = kept
+            .iter()
+            .map(|kept| {
+                let mut count = 0;
+                kept.iter()
+                    .map(|&k| {
+                        k.then(|| {
+                            count += 1;
+                            count - 1
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let vertices: Vec<_> = self
+            .vertices
+            .iter()
+            .zip(&kept[1])
+            .filter(|(_, &k)| k)
+            .map(|(v, _)| v.clone())
+            .collect();
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank + 1);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+
+        for r in 2..rank - 1 {
+            let mut list = SubelementList::new();
+            for (idx, el) in self[r].iter().enumerate() {
+                if kept[r][idx] {
+                    list.push(el.subs.iter().filter_map(|&sub| maps[r - 1][sub]).collect());
+                }
+            }
+            builder.push(list);
+        }
+
+        // Each set of merged facets becomes a single facet, bounded by the
+        // ridges that weren't removed.
+        let mut groups = HashMap::new();
+        let mut facets: Vec<Vec<usize>> = Vec::new();
+        for f in 0..facet_count {
+            let root = find(&mut parent, f);
+            let len = facets.len();
+            let idx = *groups.entry(root).or_insert(len);
+            if idx == len {
+                facets.push(Vec::new());
+            }
+
+            facets[idx].extend(self[(rank - 1, f)].subs.iter().filter_map(|&sub| maps[rank - 2][sub]));
+        }
+
+        let removed = facet_count - facets.len();
+        let mut list = SubelementList::new();
+        for mut subs in facets {
+            subs.sort_unstable();
+            subs.dedup();
+            list.push(Subelements::from(subs));
+        }
+        builder.push(list);
+        builder.push_max();
+
+        // Safety: merging a set of adjacent facets on a hyperplane yields
+        // another facet, whose boundary consists of the ridges of the set
+        // that aren't shared between two of its facets.
+        *self = Self::new(vertices, unsafe { builder.build() });
+        removed
+    }
+
+    fn weld_vertices(&mut self, tolerance: Real) -> usize {
+        let rank = self.rank();
+        let vertex_count = self.vertex_count();
+        if rank < 3 {
+            return 0;
+        }
+
+        // Finds the root of a vertex in the union-find forest.
+        fn find(parent: &mut [usize], mut v: usize) -> usize {
+            while parent[v] != v {
+                parent[v] = parent[parent[v]];
+                v = parent[v];
+            }
+            v
+        }
+
+        // We sort the vertices by their first coordinate, so that we only need
+        // to compare the vertices that are close along it.
+        let first = |v: usize| self.vertices[v].get(0).copied().unwrap_or(0.0);
+        let mut order: Vec<_> = (0..vertex_count).collect();
+        order.sort_unstable_by(|&v, &w| first(v).partial_cmp(&first(w)).unwrap());
+
+        let mut parent: Vec<_> = (0..vertex_count).collect();
+        for (i, &v) in order.iter().enumerate() {
+            for &w in &order[i + 1..] {
+                if first(w) - first(v) > tolerance {
+                    break;
+                }
+
+                if (&self.vertices[v] - &self.vertices[w]).norm() <= tolerance {
+                    let (v, w) = (find(&mut parent, v), find(&mut parent, w));
+                    parent[v] = w;
+                }
+            }
+        }
+
+        // Every new vertex is the average of the old vertices merged into it.
+        let mut roots = HashMap::new();
+        let mut vertices: Vec<Point<Real>> = Vec::new();
+        let mut counts = Vec::new();
+        let mut map = Vec::with_capacity(vertex_count);
+
+        for v in 0..vertex_count {
+            let root = find(&mut parent, v);
+            let idx = *roots.entry(root).or_insert_with(|| {
+                vertices.push(Point::zeros(self.vertices[v].len()));
+                counts.push(0);
+                vertices.len() - 1
+            });
+
+            vertices[idx] += &self.vertices[v];
+            counts[idx] += 1;
+            map.push(Some(idx));
+        }
+
+        let welded = vertex_count - vertices.len();
+        if welded == 0 {
+            return 0;
+        }
+
+        for (v, count) in vertices.iter_mut().zip(counts) {
+            *v /= count as Real;
+        }
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank + 1);
+        builder.push_min();
+        builder.push_vertices(vertices.len());
+
+        // An element of rank r needs at least r distinct subelements.
+        for r in 2..rank {
+            let mut found = HashMap::new();
+            let mut list = SubelementList::new();
+            let mut new_map = Vec::with_capacity(self[r].len());
+
+            for el in self[r].iter() {
+                let mut subs: Vec<_> = el.subs.iter().filter_map(|&sub| map[sub]).collect();
+                subs.sort_unstable();
+                subs.dedup();
+
+                if subs.len() < r {
+                    new_map.push(None);
+                    continue;
+                }
+
+                let len = found.len();
+                let idx = *found.entry(subs.clone()).or_insert(len);
+                if idx == len {
+                    list.push(Subelements::from(subs));
+                }
+                new_map.push(Some(idx));
+            }
+
+            builder.push(list);
+            map = new_map;
+        }
+
+        builder.push_max();
+
+        // Safety: we're trusting that merging the vertices doesn't break the
+        // diamond property, which holds whenever the welded vertices were
+        // really meant to be the same.
+        *self = Self::new(vertices, unsafe { builder.build() });
+        welded
+    }
+
+    fn facet_cap(&self, idx: usize, cap_type: CapType, height: Real) -> Option<Self> {
+        let rank = self.rank();
+        if rank < 3 {
+            return None;
+        }
+
+        let facet = self.facet(idx)?;
+        let (centroid, normal) = facet_normal(self, &self.gravicenter()?, idx);
+        if normal.norm() < Real::EPS {
+            return None;
+        }
+
+        let offset = normal * height;
+        Some(match cap_type {
+            CapType::Pyramid => {
+                let mut vertices = facet.vertices.clone();
+                vertices.push(centroid + offset);
+                Self::new(vertices, facet.abs.pyramid())
+            }
+            CapType::Prism => {
+                let vertices = facet
+                    .vertices
+                    .iter()
+                    .flat_map(|v| [v.clone(), v + &offset])
+                    .collect();
+                Self::new(vertices, facet.abs.prism())
+            }
+        })
+    }
+
+    fn augment_with(&self, idx: usize, cap: &Self, tolerance: Real) -> Option<Self> {
+        let rank = self.rank();
+        if rank < 3 || cap.rank() != rank || cap.dim() != self.dim() {
+            return None;
+        }
+
+        let facet: Vec<_> = self
+            .element_vertices_ref(rank - 1, idx)?
+            .into_iter()
+            .cloned()
+            .collect();
+
+        // The cap must have a facet where it can be glued.
+        if !(0..cap.facet_count()).any(|f| facet_coincides(cap, f, &facet, tolerance)) {
+            return None;
+        }
+
+        // Welding merges both copies of the facet into a single one, which
+        // then ends up in the interior.
+        let mut poly = Self::compound(vec![self.clone(), cap.clone()].into_iter());
+        poly.weld_vertices(tolerance);
+        let glued = (0..poly.facet_count()).find(|&f| facet_coincides(&poly, f, &facet, tolerance))?;
+        poly.remove_facet(glued);
+
+        Some(poly)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Concrete, ConcretePolytope};
-    use crate::{float::Float, Polytope};
+    use super::{CapType, Concrete, ConcretePolytope, RecenterMode};
+    use crate::{abs::{flag::Orientation, Abstract}, float::{Float, Real}, geometry::{Hyperplane, Matrix, Point, Vector}, Polytope};
 
     use approx::abs_diff_eq;
+    use vec_like::VecLike;
 
     /// Tests that a polytope has an expected volume.
-    fn test_volume(mut poly: Concrete, volume: Option<f64>) {
+    fn test_volume(mut poly: Concrete, volume: Option<Real>) {
         poly.element_sort();
 
         if let Some(poly_volume) = poly.volume() {
@@ -1437,7 +2893,7 @@ mod tests {
             ));
 
             assert!(
-                abs_diff_eq!(poly_volume, volume, epsilon = f64::EPS),
+                abs_diff_eq!(poly_volume, volume, epsilon = Real::EPS),
                 "Expected volume {} for {}, found volume {}.",
                 volume,
                 "TBA: name",
@@ -1466,13 +2922,13 @@ mod tests {
         test_volume(Concrete::dyad(), Some(1.0));
     }
 
-    fn polygon_area(n: usize, d: usize) -> f64 {
-        let n = n as f64;
-        let d = d as f64;
-        n * (d * f64::TAU / n).sin() / 2.0
+    fn polygon_area(n: usize, d: usize) -> Real {
+        let n = n as Real;
+        let d = d as Real;
+        n * (d * Real::TAU / n).sin() / 2.0
     }
 
-    fn test_compound(mut p: Concrete, volume: Option<f64>) {
+    fn test_compound(mut p: Concrete, volume: Option<Real>) {
         p.comp_append(p.clone());
         test_volume(p, volume)
     }
@@ -1494,7 +2950,7 @@ mod tests {
         }
     }
 
-    fn polygons_areas() -> (Vec<Concrete>, Vec<f64>) {
+    fn polygons_areas() -> (Vec<Concrete>, Vec<Real>) {
         let mut polygons = Vec::new();
         let mut areas = Vec::new();
         for n in 2..=5 {
@@ -1568,7 +3024,7 @@ mod tests {
         for n in 1..=6 {
             test_volume(
                 Concrete::simplex(n),
-                Some((n as f64 / (1 << (n - 1)) as f64).sqrt() / crate::factorial(n - 1) as f64),
+                Some((n as Real / (1 << (n - 1)) as Real).sqrt() / crate::factorial(n - 1) as Real),
             );
         }
     }
@@ -1585,8 +3041,576 @@ mod tests {
         for n in 1..=6 {
             test_volume(
                 Concrete::orthoplex(n),
-                Some(((1 << (n - 1)) as f64).sqrt() / crate::factorial(n - 1) as f64),
+                Some(((1 << (n - 1)) as Real).sqrt() / crate::factorial(n - 1) as Real),
             );
         }
     }
+
+    /// Checks the volumes of some nonconvex polytopes, which don't depend on
+    /// where the origin is.
+    #[test]
+    fn nonconvex() {
+        // An L-shaped hexagon.
+        let mut l_shape = Concrete::new(
+            vec![
+                vec![0.0, 0.0].into(),
+                vec![2.0, 0.0].into(),
+                vec![2.0, 1.0].into(),
+                vec![1.0, 1.0].into(),
+                vec![1.0, 2.0].into(),
+                vec![0.0, 2.0].into(),
+            ],
+            Abstract::polygon(6),
+        );
+        test_volume(l_shape.clone(), Some(3.0));
+        l_shape.recenter_with(&vec![5.0, -3.0].into());
+        test_volume(l_shape, Some(3.0));
+
+        // A cube with a pyramid dug into one of its faces.
+        let cube = Concrete::hypercube(4);
+        let dented = cube.augment(0, CapType::Pyramid, -0.5).unwrap();
+        crate::test(&dented, [1, 9, 16, 9, 1]);
+        test_volume(dented, Some(1.0 - 0.5 / 3.0));
+    }
+
+    /// Checks that the Kleetope of a tetrahedron is a triakis tetrahedron.
+    #[test]
+    fn kis() {
+        let tet = Concrete::simplex(4);
+        let kis = tet.kis();
+        crate::test(&kis, [1, 8, 18, 12, 1]);
+
+        // Every face of the tetrahedron gets a pyramid of the given height.
+        let height = tet.kis_height();
+        let face_area = Real::SQRT_3 / 4.0;
+        test_volume(
+            kis,
+            Some(Real::SQRT_2 / 12.0 + 4.0 * face_area * height / 3.0),
+        );
+    }
+
+    /// Slices a cube outside of its extent, and checks that the resulting
+    /// nullitope is handled gracefully.
+    #[test]
+    fn empty_cross_section() {
+        let mut direction = Vector::zeros(3);
+        direction[0] = 1.0;
+        let mut empty = Concrete::cube().cross_section(&Hyperplane::new(direction.clone(), 2.0));
+
+        crate::test(&empty, [1]);
+        assert!(empty.is_nullitope());
+        assert_eq!(empty.dim(), None);
+        assert_eq!(empty.dim_or(), 0);
+        assert!(empty.gravicenter().is_none());
+        assert!(empty.circumsphere().is_none());
+        assert!(empty.minmax(direction.clone()).is_none());
+        assert!(empty.volume().is_none());
+        assert!(empty.surface_area().is_none());
+        assert!(empty.is_convex());
+        assert_eq!(empty.flag_count(), 1);
+        assert!(empty.facet(0).is_none());
+        assert!(empty.get_symmetry_group().is_none());
+
+        // Slicing it again gives nothing either.
+        assert!(empty
+            .cross_section(&Hyperplane::new(direction, 0.0))
+            .is_nullitope());
+
+        // The facet of a point is also empty.
+        assert!(Concrete::point().facet(0).unwrap().is_nullitope());
+    }
+
+    /// Builds a square frustum and compares its volume with the usual formula.
+    #[test]
+    fn lace() {
+        let frustum = Concrete::polygon(4).lace(0.5, 2.0);
+        crate::test(&frustum, [1, 8, 12, 6, 1]);
+
+        // A square of circumradius 1 has area 2.
+        let (bottom, top) = (2.0, 0.5);
+        test_volume(
+            frustum,
+            Some(2.0 * (bottom + top + (bottom * top).fsqrt()) / 3.0),
+        );
+
+        test_volume(Concrete::polygon(4).lace(1.0, 2.0), Some(4.0));
+    }
+
+    /// Checks that edges are normalized to unit length, or to unit mean length.
+    #[test]
+    fn normalize_edges() {
+        let mut cube = Concrete::hypercube(4);
+        cube.scale(2.0);
+        assert!(abs_diff_eq!(cube.normalize_edges().unwrap(), 0.5, epsilon = Real::EPS));
+        assert!(cube.is_equilateral_with(1.0));
+
+        let mut rectangle = Concrete::dyad_with(1.0).duoprism(&Concrete::dyad_with(3.0));
+        assert!(abs_diff_eq!(rectangle.normalize_edges().unwrap(), 0.5, epsilon = Real::EPS));
+        assert!(!rectangle.is_equilateral());
+
+        assert_eq!(Concrete::point().normalize_edges(), None);
+    }
+
+    /// Orients the faces of a cube outwards, and fails to orient the Petrial
+    /// of a tetrahedron, which is a hemi-cube.
+    #[test]
+    fn oriented_face_cycles() {
+        let cube = Concrete::hypercube(4);
+        let faces = cube.oriented_face_cycles().unwrap();
+        let mut directed = std::collections::HashSet::new();
+
+        for cycle in faces.iter().flat_map(|cycles| cycles.iter()) {
+            let len = cycle.len();
+            for i in 0..len {
+                assert!(directed.insert((cycle[i], cycle[(i + 1) % len])));
+            }
+
+            // The face points away from the center of the cube.
+            let [p, q, r] = [0, 1, 2].map(|i| &cube.vertices[cycle[i]]);
+            assert!((q - p).cross(&(r - p)).dot(p) > 0.0);
+        }
+        assert_eq!(directed.len(), 24);
+
+        let mut hemicube = Concrete::simplex(4);
+        assert!(hemicube.petrial_mut());
+        assert!(hemicube.oriented_face_cycles().is_none());
+    }
+
+    /// Checks Descartes' theorem on a few polyhedra.
+    #[test]
+    fn angular_defects() {
+        for poly in [Concrete::simplex(4), Concrete::hypercube(4), Concrete::orthoplex(4)] {
+            let total: Real = poly.angular_defects().unwrap().iter().sum();
+            assert!(abs_diff_eq!(total, 2.0 * Real::TAU, epsilon = Real::EPS));
+        }
+
+        let defects = Concrete::hypercube(4).angular_defects().unwrap();
+        assert!(defects.iter().all(|&d| abs_diff_eq!(d, Real::PI / 2.0, epsilon = Real::EPS)));
+
+        assert_eq!(Concrete::hypercube(5).angular_defects(), None);
+    }
+
+    /// Checks that the center of mass doesn't depend on where the vertices
+    /// are, unlike the gravicenter.
+    #[test]
+    fn volume_centroid() {
+        use nalgebra::dvector;
+
+        // A square with an extra vertex on its bottom edge.
+        let vertices = vec![
+            dvector![0.0, 0.0],
+            dvector![1.0, 0.0],
+            dvector![2.0, 0.0],
+            dvector![2.0, 2.0],
+            dvector![0.0, 2.0],
+        ];
+        let mut square = Concrete::new(vertices.clone(), Abstract::polygon(5));
+        assert!((square.gravicenter().unwrap() - dvector![1.0, 0.8]).norm() < Real::EPS);
+        assert!((square.volume_centroid_mut().unwrap() - dvector![1.0, 1.0]).norm() < Real::EPS);
+
+        // The same square on a plane in 3D.
+        let mut square = Concrete::new(
+            vertices.iter().map(|v| dvector![v[0], v[1], 1.0]).collect(),
+            Abstract::polygon(5),
+        );
+        assert!(
+            (square.volume_centroid_mut().unwrap() - dvector![1.0, 1.0, 1.0]).norm() < Real::EPS
+        );
+
+        assert!(Concrete::cube().volume_centroid_mut().unwrap().norm() < Real::EPS);
+        assert_eq!(Concrete::point().volume_centroid_mut(), None);
+    }
+
+    /// Checks the centers given by every recentering mode.
+    #[test]
+    fn recenter_modes() {
+        use nalgebra::dvector;
+
+        let triangle = Concrete::new(
+            vec![dvector![0.0, 0.0], dvector![4.0, 0.0], dvector![0.0, 2.0]],
+            Abstract::polygon(3),
+        );
+
+        for (mode, center) in [
+            (RecenterMode::Centroid, dvector![4.0 / 3.0, 2.0 / 3.0]),
+            (RecenterMode::Circumcenter, dvector![2.0, 1.0]),
+            (RecenterMode::BoundingBoxCenter, dvector![2.0, 1.0]),
+            (RecenterMode::CenterOfMass, dvector![4.0 / 3.0, 2.0 / 3.0]),
+        ] {
+            assert!((triangle.center(mode).unwrap() - center).norm() < Real::EPS);
+
+            let mut recentered = triangle.clone();
+            recentered.recenter_by(mode);
+            assert!(recentered.center(mode).unwrap().norm() < Real::EPS);
+        }
+
+        // Four points that aren't cocircular still get a circumcenter.
+        let quad = Concrete::new(
+            vec![
+                dvector![1.0, 0.0],
+                dvector![0.0, 1.0],
+                dvector![-1.0, 0.0],
+                dvector![0.0, -2.0],
+            ],
+            Abstract::polygon(4),
+        );
+        assert!(quad.circumsphere().is_none());
+        assert!(quad.center(RecenterMode::Circumcenter).is_some());
+        assert_eq!(Concrete::nullitope().center(RecenterMode::Circumcenter), None);
+    }
+
+    /// Checks that reflecting a polytope twice gives back the original one.
+    #[test]
+    fn reflect() {
+        let square = Concrete::polygon(4);
+        let hyperplane = Hyperplane::new(Vector::from_vec(vec![0.6, 0.8]), 0.5);
+
+        let mut reflected = square.clone();
+        reflected.reflect(&hyperplane);
+        assert!(reflected.circumsphere().unwrap().center.norm() > 0.5);
+
+        reflected.reflect(&hyperplane);
+        for (v, w) in reflected.vertices.iter().zip(&square.vertices) {
+            assert!((v - w).norm() < Real::EPS);
+        }
+
+        // Reflecting across x = 0 only changes the sign of a coordinate.
+        let mut flipped = square.clone();
+        flipped.reflect(&Hyperplane::new(Vector::from_vec(vec![1.0, 0.0]), 0.0));
+        for (v, w) in flipped.vertices.iter().zip(&square.vertices) {
+            assert!((v[0] + w[0]).abs() < Real::EPS);
+            assert!((v[1] - w[1]).abs() < Real::EPS);
+        }
+    }
+
+    /// Embeds polytopes into one more dimension and projects them back.
+    #[test]
+    fn embed_project() {
+        let mut square = Concrete::polygon(4);
+        square.embed();
+        assert_eq!(square.dim(), Some(3));
+        crate::test(&square, [1, 4, 4, 1]);
+
+        assert!(!square.project());
+        assert_eq!(square.dim(), Some(2));
+        assert_eq!(square.vertices, Concrete::polygon(4).vertices);
+
+        // The vertices of a cube coincide in pairs.
+        let mut cube = Concrete::hypercube(4);
+        assert!(cube.project());
+        assert_eq!(cube.dim(), Some(2));
+
+        let mut point = Concrete::point();
+        assert!(!point.project());
+        assert_eq!(point.dim(), Some(0));
+    }
+
+    /// Computes the volume of a simplex given by the indices of its vertices.
+    fn simplex_volume(poly: &Concrete, simplex: &[usize]) -> Real {
+        let first = &poly.vertices[simplex[0]];
+        let d = simplex.len() - 1;
+        let m = Matrix::from_fn(d, d, |i, j| poly.vertices[simplex[j + 1]][i] - first[i]);
+        m.determinant().abs() / (1..=d).product::<usize>() as Real
+    }
+
+    /// Checks that the simplices of full triangulations add up to the volume
+    /// of the polytope.
+    #[test]
+    fn triangulate_full() {
+        for (mut poly, count) in [
+            (Concrete::polygon(7), 5),
+            (Concrete::cube(), 6),
+            (Concrete::hypercube(5), 24),
+        ] {
+            poly.element_sort();
+            let simplices = poly.triangulate_full();
+            assert_eq!(simplices.len(), count);
+
+            let volume: Real = simplices.iter().map(|s| simplex_volume(&poly, s)).sum();
+            assert!(abs_diff_eq!(volume, poly.volume().unwrap(), epsilon = Real::EPS));
+        }
+    }
+
+    /// Checks the volume and surface area of a cube, before and after
+    /// normalizing its edges.
+    #[test]
+    fn unit_edge_volume() {
+        let mut cube = Concrete::cube();
+        cube.element_sort();
+        cube.scale(3.0 / cube.mean_edge_length().unwrap());
+
+        assert!(abs_diff_eq!(cube.volume().unwrap(), 27.0, epsilon = Real::EPS));
+        assert!(abs_diff_eq!(cube.surface_area().unwrap(), 54.0, epsilon = Real::EPS));
+        assert!(abs_diff_eq!(cube.unit_edge_volume().unwrap(), 1.0, epsilon = Real::EPS));
+        assert!(abs_diff_eq!(cube.unit_edge_surface_area().unwrap(), 6.0, epsilon = Real::EPS));
+    }
+
+    /// Finds the vertices of a cube closest to some points.
+    #[test]
+    fn nearest_vertex() {
+        let cube = Concrete::cube();
+        assert_eq!(Concrete::nullitope().nearest_vertex(&Point::zeros(0)), None);
+
+        for v in &cube.vertices {
+            let p = v * 0.9;
+            let idx = cube.nearest_vertex(&p).unwrap();
+            assert_eq!(&cube.vertices[idx], v);
+        }
+    }
+
+    /// Checks that the Petrie polygons of the cube and the tesseract project
+    /// onto their Coxeter planes as regular polygons.
+    #[test]
+    fn coxeter_plane() {
+        for (mut poly, sides) in [(Concrete::cube(), 6), (Concrete::hypercube(5), 8)] {
+            let petrie = poly.first_petrie_polygon().unwrap();
+            assert_eq!(petrie.len(), sides);
+
+            let plane = poly.coxeter_plane(&petrie).unwrap();
+            poly.align_plane(&plane);
+
+            let shadow: Vec<_> = petrie
+                .iter()
+                .map(|&idx| Vector::from_vec(vec![poly.vertices[idx][0], poly.vertices[idx][1]]))
+                .collect();
+
+            let radius = shadow[0].norm();
+            let side = (&shadow[1] - &shadow[0]).norm();
+            for k in 0..sides {
+                assert!(abs_diff_eq!(shadow[k].norm(), radius, epsilon = Real::EPS));
+                assert!(abs_diff_eq!(
+                    (&shadow[(k + 1) % sides] - &shadow[k]).norm(),
+                    side,
+                    epsilon = Real::EPS
+                ));
+            }
+        }
+    }
+
+    /// Checks that convex polytopes are told apart from nonconvex ones.
+    #[test]
+    fn is_convex() {
+        assert!(Concrete::point().is_convex());
+        assert!(Concrete::dyad().is_convex());
+        assert!(Concrete::polygon(5).is_convex());
+        assert!(Concrete::hypercube(4).is_convex());
+        assert!(Concrete::orthoplex(5).is_convex());
+
+        assert!(!Concrete::star_polygon(5, 2).is_convex());
+        assert!(!Concrete::star_polygon(6, 2).is_convex());
+
+        // A polygon embedded in 3D space is checked in its own plane.
+        let mut square = Concrete::polygon(4);
+        square.vertices_mut().iter_mut().for_each(|v| *v = v.clone().push(0.5));
+        assert!(square.is_convex());
+
+        // A pentagon with a vertex pushed inwards.
+        let mut dented = Concrete::polygon(5);
+        dented.vertices_mut()[0] *= 0.2;
+        assert!(!dented.is_convex());
+        assert!(dented.is_convex_with(0.5));
+    }
+
+    /// Checks that welding a compound of a polytope with itself gives back the
+    /// polytope.
+    #[test]
+    fn weld_vertices() {
+        let mut square = Concrete::polygon(4);
+        assert_eq!(square.weld_vertices(Real::EPS), 0);
+
+        let mut double = Concrete::compound(vec![square.clone(), square].into_iter());
+        assert_eq!(double.weld_vertices(Real::EPS), 4);
+        crate::test(&double, [1, 4, 4, 1]);
+
+        let mut double_cube = Concrete::compound(vec![Concrete::hypercube(4); 2].into_iter());
+        assert_eq!(double_cube.weld_vertices(Real::EPS), 8);
+        crate::test(&double_cube, [1, 8, 12, 6, 1]);
+    }
+
+    /// Checks congruence between a few polytopes.
+    #[test]
+    fn is_congruent() {
+        let mut cube = Concrete::cube();
+        cube.element_sort();
+
+        // Rotated and moved around.
+        let mut moved = cube.clone();
+        for v in moved.vertices_mut() {
+            let (x, y) = (v[0], v[1]);
+            v[0] = 0.6 * x - 0.8 * y + 1.0;
+            v[1] = 0.8 * x + 0.6 * y;
+        }
+        assert!(cube.is_congruent(&moved, Real::EPS));
+
+        // Isomorphic, but stretched.
+        let mut cuboid = cube.clone();
+        cuboid.scale(2.0);
+        for v in cuboid.vertices_mut() {
+            v[2] /= 2.0;
+        }
+        assert!(!cube.is_congruent(&cuboid, Real::EPS));
+
+        let mut octahedron = Concrete::orthoplex(4);
+        octahedron.element_sort();
+        assert!(!cube.is_congruent(&octahedron, Real::EPS));
+    }
+
+    /// Merges the coplanar facets of a pentagon with a straight angle and of
+    /// an elongated cube.
+    #[test]
+    fn merge_coplanar_facets() {
+        let mut square = Concrete::new(
+            vec![
+                vec![0.0, 0.0].into(),
+                vec![1.0, 0.0].into(),
+                vec![2.0, 0.0].into(),
+                vec![2.0, 1.0].into(),
+                vec![0.0, 1.0].into(),
+            ],
+            Abstract::polygon(5),
+        );
+        assert_eq!(square.merge_coplanar_facets(Real::EPS), 1);
+        crate::test(&square, [1, 4, 4, 1]);
+
+        let mut cube = Concrete::hypercube(4);
+        assert_eq!(cube.merge_coplanar_facets(Real::EPS), 0);
+
+        // The sides of the prism continue those of the cube.
+        let mut cuboid = cube.augment(0, CapType::Prism, 1.0).unwrap();
+        assert_eq!(cuboid.merge_coplanar_facets(Real::EPS), 4);
+        crate::test(&cuboid, [1, 12, 16, 6, 1]);
+        test_volume(cuboid, Some(2.0));
+    }
+
+    /// Glues a pyramid and a prism onto a facet of a cube.
+    #[test]
+    fn augment() {
+        let cube = Concrete::hypercube(4);
+
+        let augmented = cube.augment(0, CapType::Pyramid, 0.5).unwrap();
+        crate::test(&augmented, [1, 9, 16, 9, 1]);
+        test_volume(augmented, Some(1.0 + 0.5 / 3.0));
+
+        let elongated = cube.augment(0, CapType::Prism, 1.0).unwrap();
+        crate::test(&elongated, [1, 12, 20, 10, 1]);
+        test_volume(elongated, Some(2.0));
+
+        // A cap that doesn't touch the facet can't be glued.
+        let mut far = cube.facet_cap(0, CapType::Pyramid, 0.5).unwrap();
+        far.vertices_mut().iter_mut().for_each(|v| *v *= 3.0);
+        assert!(cube.augment_with(0, &far, Real::EPS).is_none());
+    }
+
+    /// Recovers the Schläfli symbols of some regular polytopes.
+    #[test]
+    fn schlafli_symbol() {
+        let symbol = |mut poly: Concrete| poly.schlafli_symbol().map(|s| s.to_string());
+
+        assert_eq!(symbol(Concrete::polygon(5)), Some("{5}".to_string()));
+        assert_eq!(symbol(Concrete::star_polygon(5, 2)), Some("{5/2}".to_string()));
+        assert_eq!(symbol(Concrete::hypercube(4)), Some("{4,3}".to_string()));
+        assert_eq!(symbol(Concrete::orthoplex(4)), Some("{3,4}".to_string()));
+        assert_eq!(symbol(Concrete::simplex(5)), Some("{3,3,3}".to_string()));
+        assert_eq!(symbol(Concrete::hypercube(5)), Some("{4,3,3}".to_string()));
+
+        // A triangular prism isn't regular.
+        assert_eq!(symbol(Concrete::polygon(3).prism()), None);
+    }
+
+    /// Checks which constructions keep exact coordinates.
+    #[test]
+    fn exact() {
+        assert!(Concrete::hypercube(5).is_exact());
+        assert!(Concrete::orthoplex(4).is_exact());
+        assert!(Concrete::simplex(5).is_exact());
+        assert!(Concrete::simplex(9).is_exact());
+        assert!(!Concrete::simplex(7).is_exact());
+        assert!(Concrete::polygon(12).prism().is_exact());
+        assert!(!Concrete::polygon(5).is_exact());
+
+        // The dual of a cube is an octahedron with vertices at ±2.
+        let octahedron = Concrete::cube().try_dual().unwrap();
+        let two = crate::exact::Exact::int(2);
+        assert!(octahedron
+            .exact_vertices()
+            .unwrap()
+            .iter()
+            .all(|v| v.iter().any(|x| *x == two || x.checked_neg() == Some(two))));
+        assert!(octahedron.try_dual().unwrap().is_exact());
+
+        // Moving the vertices falls back to floating-point.
+        let mut cube = Concrete::cube();
+        cube.scale(1.5);
+        assert!(!cube.is_exact());
+    }
+
+    /// Builds members of the uniform family of a regular polytope.
+    #[test]
+    fn schlafli_diagram() {
+        let symbol = Concrete::hypercube(4).schlafli_symbol().unwrap();
+        assert_eq!(symbol.node_count(), 3);
+        assert_eq!(symbol.diagram(&[true, false, false]), "x4o3o");
+        assert_eq!(symbol.diagram(&[false, true]), "o4x3o");
+        assert_eq!(symbol.diagram(&[true, true, false]), "x4x3o");
+
+        let cuboctahedron = Concrete::from_cd(&symbol.diagram(&[false, true, false])).unwrap();
+        crate::test(&cuboctahedron, [1, 12, 24, 14, 1]);
+
+        let truncated_cube = Concrete::from_cd(&symbol.diagram(&[true, true, false])).unwrap();
+        crate::test(&truncated_cube, [1, 24, 36, 14, 1]);
+
+        let omnitruncate = Concrete::from_cd(&symbol.diagram(&[true, true, true])).unwrap();
+        crate::test(&omnitruncate, [1, 48, 72, 26, 1]);
+    }
+
+    /// Builds the orthoschemes of some regular polytopes, which tile them.
+    #[test]
+    fn orthoscheme() {
+        let mut cube = Concrete::hypercube(4);
+        assert!(cube.is_regular());
+        let flags = cube.flag_count() as Real;
+        test_volume(cube.orthoscheme().unwrap(), Some(1.0 / flags));
+
+        let square = Concrete::hypercube(3);
+        test_volume(square.orthoscheme().unwrap(), Some(1.0 / 8.0));
+
+        assert!(!Concrete::polygon(3).prism().is_regular());
+        assert!(Concrete::nullitope().orthoscheme().is_none());
+    }
+
+    /// Checks that reversing the orientation leaves the vertices alone, keeps
+    /// a polytope orientable, and makes its faces run the other way.
+    #[test]
+    fn reverse_orientation() {
+        let cycles = |p: &Concrete| -> Vec<Vec<usize>> {
+            p.oriented_face_cycles()
+                .unwrap()
+                .into_iter()
+                .map(|cycles| cycles[0].iter().copied().collect())
+                .collect()
+        };
+
+        let mut cube = Concrete::cube();
+        cube.element_sort();
+        let mut reversed = cube.clone();
+        reversed.reverse_orientation();
+
+        assert_eq!(reversed.vertices, cube.vertices);
+        assert_eq!(reversed.abs.orientation(), Orientation::Odd);
+        assert_eq!(reversed.first_oriented_flag().orientation, Orientation::Odd);
+        assert!(reversed.orientable_mut());
+        test_volume(reversed.clone(), Some(1.0));
+
+        // The cycles may start at different vertices.
+        for (mut face, reversed_face) in cycles(&cube).into_iter().zip(cycles(&reversed)) {
+            face.reverse();
+            let len = face.len();
+            assert_eq!(len, reversed_face.len());
+            assert!((0..len).any(|i| face[i..].iter().chain(&face[..i]).eq(&reversed_face)));
+        }
+
+        reversed.reverse_orientation();
+        assert_eq!(reversed.vertices, cube.vertices);
+        assert_eq!(reversed.abs.orientation(), Orientation::Even);
+    }
 }
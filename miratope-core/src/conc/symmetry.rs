@@ -3,9 +3,9 @@
 use std::{collections::{BTreeMap, HashSet}, vec, iter::FromIterator};
 
 use crate::{
-    abs::{Ranked, flag::{FlagIter, Flag}},
+    abs::{Abstract, Ranked, flag::{FlagIter, Flag}},
     conc::Concrete,
-    float::Float,
+    float::{Float, Real},
     group::Group,
     geometry::{Matrix, Point, PointOrd, Subspace},
     Polytope,
@@ -18,9 +18,9 @@ use super::ConcretePolytope;
 impl Flag {
     /// Outputs a sequence of vertices obtained from applying a fixed sequence of flag changes to a flag.
     /// Used for computing the elements of a symmetry group. 
-    fn vertex_sequence(&mut self, p: &Concrete) -> Matrix<f64> {
+    fn vertex_sequence(&mut self, p: &Concrete) -> Matrix<Real> {
         let rank = p.rank();
-        let mut basis = Matrix::<f64>::zeros(rank-1,rank-1);
+        let mut basis = Matrix::<Real>::zeros(rank-1,rank-1);
         let mut columns = basis.column_iter_mut();
         let vertex = &p.vertices[self[1]];
 
@@ -39,7 +39,13 @@ impl Flag {
 
 impl Concrete {
     /// Computes the symmetry group of a polytope, along with a list of vertex mappings.
-    pub fn get_symmetry_group(&mut self) -> Option<(Group<vec::IntoIter<Matrix<f64>>>, Vec<Vec<usize>>)> {
+    /// Returns `None` if the calculation fails, or if the polytope is the
+    /// nullitope.
+    pub fn get_symmetry_group(&mut self) -> Option<(Group<vec::IntoIter<Matrix<Real>>>, Vec<Vec<usize>>)> {
+        if self.is_nullitope() {
+            return None;
+        }
+
         let mut fixed = self.clone(); // We'll relabel the facets if needed so the first facet isn't hemi.
 
         let mut facet_idx = 0;
@@ -48,7 +54,7 @@ impl Concrete {
                 let facet_space = Subspace::from_points(
                     self.abs.element_and_vertices(self.rank()-1, facet_idx).unwrap().0.iter().map(|x| &self.vertices[*x])
                 );
-                if facet_space.distance(&Point::zeros(self.dim().unwrap())) > f64::EPS {
+                if facet_space.distance(&Point::zeros(self.dim().unwrap())) > Real::EPS {
                     break;
                 }
                 facet_idx += 1;
@@ -79,7 +85,7 @@ impl Concrete {
         let flag_iter = FlagIter::new(&fixed.abs);
         let (types, types_map_back) = &fixed.element_types_common();
 
-        let mut vertices_pointord = Vec::<PointOrd<f64>>::new();
+        let mut vertices_pointord = Vec::<PointOrd<Real>>::new();
         for v in &self.vertices {
             vertices_pointord.push(PointOrd::new(v.clone()));
         }
@@ -101,7 +107,7 @@ impl Concrete {
         let base_basis = base_flag.clone().vertex_sequence(&fixed);
         let base_basis_inverse = base_basis.clone().try_inverse().unwrap();
 
-        let mut group = Vec::<Matrix<f64>>::new();
+        let mut group = Vec::<Matrix<Real>>::new();
 
         'a: for flag in flag_iter {
             if flag
@@ -152,7 +158,7 @@ impl Concrete {
     }
 
     /// Computes the rotation subgroup of a polytope, along with a list of vertex mappings.
-    pub fn get_rotation_group(&mut self) -> Option<(Group<vec::IntoIter<Matrix<f64>>>, Vec<Vec<usize>>)> {
+    pub fn get_rotation_group(&mut self) -> Option<(Group<vec::IntoIter<Matrix<Real>>>, Vec<Vec<usize>>)> {
         if let Some((full_group, full_vertex_map)) = self.get_symmetry_group() {
             let mut rotation_group = Vec::new();
             let mut vertex_map = Vec::new();
@@ -175,8 +181,8 @@ impl Concrete {
 
     /// Fills in the vertex map.
     /// A vertex map is an array of (group element, vertex index) with values being the index of the vertex after applying the transformation.
-    pub fn get_vertex_map(&mut self, group: Group<vec::IntoIter<Matrix<f64>>>) -> Vec<Vec<usize>> {
-        let mut vertices = Vec::<PointOrd<f64>>::new();
+    pub fn get_vertex_map(&mut self, group: Group<vec::IntoIter<Matrix<Real>>>) -> Vec<Vec<usize>> {
+        let mut vertices = Vec::<PointOrd<Real>>::new();
         for v in &self.vertices {
             vertices.push(PointOrd::new(v.clone()));
         }
@@ -201,22 +207,147 @@ impl Concrete {
         }
         vertex_map
     }
+
+    /// Returns whether a polytope is regular, that is, whether its symmetries
+    /// act transitively on its flags.
+    pub fn is_regular(&mut self) -> bool {
+        self.element_sort();
+        match self.get_symmetry_group() {
+            Some((group, _)) => group.count() == self.flag_count(),
+            None => false,
+        }
+    }
+
+    /// Builds the characteristic orthoscheme of a polytope, the simplex whose
+    /// vertices are the gravicenters of the elements of a flag, from a vertex
+    /// up to the entire polytope. For a regular polytope, this is a fundamental
+    /// domain of its symmetry group, bounded by its mirrors. Returns `None` in
+    /// the case of the nullitope.
+    pub fn orthoscheme(&self) -> Option<Self> {
+        let rank = self.rank();
+        if rank == 0 {
+            return None;
+        }
+
+        let flag = self.first_flag();
+        let vertices = (1..=rank)
+            .map(|r| {
+                let idxs = self.abs.element_vertices(r, flag[r])?;
+                Some(idxs.iter().map(|&idx| &self.vertices[idx]).sum::<Point<Real>>() / idxs.len() as Real)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Self::new(vertices, Abstract::simplex(rank)))
+    }
+
+    /// Returns the Schläfli symbol of a regular polytope of rank at least 2,
+    /// or `None` if the polytope isn't regular. The entries are read off from
+    /// the faces of the polytope and of its successive vertex figures.
+    pub fn schlafli_symbol(&mut self) -> Option<SchlafliSymbol> {
+        if self.rank() < 3 || !self.is_regular() {
+            return None;
+        }
+
+        let mut symbol = Vec::new();
+        let mut poly = self.clone();
+        loop {
+            symbol.push(polygon_fraction(&poly.element(3, 0)?)?);
+
+            if poly.rank() == 3 {
+                return Some(SchlafliSymbol(symbol));
+            }
+
+            poly = poly.verf(0).ok()??;
+        }
+    }
+}
+
+/// Returns the fraction `n / d` of a regular polygon, read off from the angle
+/// that its first edge subtends at its center.
+fn polygon_fraction(polygon: &Concrete) -> Option<(usize, usize)> {
+    let n = polygon.vertex_count();
+    let center = polygon.gravicenter()?;
+    let edge = &polygon.abs[(2, 0)].subs;
+    let u = &polygon.vertices[edge[0]] - &center;
+    let v = &polygon.vertices[edge[1]] - &center;
+
+    let cos = (u.dot(&v) / (u.norm() * v.norm())).clamp(-1.0, 1.0);
+    let d = (cos.acos() * n as Real / Real::TAU).round() as usize;
+    (d != 0).then(|| (n, d))
+}
+
+/// The [Schläfli symbol](https://polytope.miraheze.org/wiki/Schläfli_symbol)
+/// of a regular polytope, stored as a list of fractions `n / d`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchlafliSymbol(pub Vec<(usize, usize)>);
+
+impl std::fmt::Display for SchlafliSymbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{")?;
+
+        for (i, &(n, d)) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ",")?;
+            }
+
+            if d == 1 {
+                write!(f, "{}", n)?;
+            } else {
+                write!(f, "{}/{}", n, d)?;
+            }
+        }
+
+        write!(f, "}}")
+    }
+}
+
+impl SchlafliSymbol {
+    /// The number of nodes in the linear Coxeter diagram of the symbol.
+    pub fn node_count(&self) -> usize {
+        self.0.len() + 1
+    }
+
+    /// Returns the linear Coxeter diagram of the symmetry group of the symbol,
+    /// with the nodes given by `rings` ringed, like `o4x3o` for the
+    /// cuboctahedron. Any missing entries of `rings` are left unringed.
+    pub fn diagram(&self, rings: &[bool]) -> String {
+        let node = |i: usize| {
+            if rings.get(i).copied().unwrap_or(false) {
+                'x'
+            } else {
+                'o'
+            }
+        };
+
+        let mut diagram = String::new();
+        diagram.push(node(0));
+        for (i, &(n, d)) in self.0.iter().enumerate() {
+            if d == 1 {
+                diagram.push_str(&n.to_string());
+            } else {
+                diagram.push_str(&format!("{}/{}", n, d));
+            }
+            diagram.push(node(i + 1));
+        }
+
+        diagram
+    }
 }
 
 /// A set of vertices.
-pub struct Vertices(pub Vec<Point<f64>>);
+pub struct Vertices(pub Vec<Point<Real>>);
 
 impl Vertices {
     /// Uses the provided symmetry group on the vertices, also outputs the new vertex map.
-    pub fn copy_by_symmetry(&self, group: Group<vec::IntoIter<Matrix<f64>>>) -> (Self, Vec<Vec<usize>>) {
-        let mut vertices = BTreeMap::<PointOrd<f64>, usize>::new();
+    pub fn copy_by_symmetry(&self, group: Group<vec::IntoIter<Matrix<Real>>>) -> (Self, Vec<Vec<usize>>) {
+        let mut vertices = BTreeMap::<PointOrd<Real>, usize>::new();
         let mut vertices_vec = Vec::new();
         let mut c = 0;
 
         for vertex in self.0.clone() {
             if vertices.get(&PointOrd::new(vertex.clone())).is_none() {
                 for isometry in group.clone() {
-                    let new_vertex = PointOrd::<f64>::new(isometry.clone() * vertex.clone());
+                    let new_vertex = PointOrd::<Real>::new(isometry.clone() * vertex.clone());
                     if vertices.get(&new_vertex).is_none() {
                         vertices.insert(new_vertex.clone(), c);
                         vertices_vec.push(new_vertex);
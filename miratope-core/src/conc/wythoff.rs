@@ -0,0 +1,277 @@
+//! Builds polytopes from Coxeter diagrams, using the
+//! [Wythoff construction](https://polytope.miraheze.org/wiki/Wythoffian).
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+};
+
+use super::{hull::from_vertex_sets, Concrete};
+use crate::{
+    cox::cd::{Cd, CdError, Node},
+    float::Real,
+    geometry::{Point, PointOrd},
+};
+
+/// The maximum number of vertices we're willing to generate, so that huge
+/// groups don't freeze the application.
+const MAX_VERTICES: usize = 200_000;
+
+/// An error while building a polytope from a Coxeter diagram.
+#[derive(Clone, Copy, Debug)]
+pub enum WythoffError {
+    /// The diagram couldn't be parsed.
+    Cd(CdError),
+
+    /// The diagram has a snub node, which isn't supported yet.
+    Snub,
+
+    /// Some connected component of the diagram has no ringed nodes.
+    NotMinimal,
+
+    /// The diagram doesn't describe a finite group.
+    NotSpherical,
+
+    /// The polytope has too many vertices.
+    TooLarge,
+}
+
+impl From<CdError> for WythoffError {
+    fn from(err: CdError) -> Self {
+        Self::Cd(err)
+    }
+}
+
+impl Display for WythoffError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cd(err) => write!(f, "{}", err),
+            Self::Snub => f.write_str("snub nodes aren't supported"),
+            Self::NotMinimal => f.write_str("some component of the diagram has no ringed nodes"),
+            Self::NotSpherical => f.write_str("the diagram doesn't describe a finite group"),
+            Self::TooLarge => write!(f, "the polytope has more than {} vertices", MAX_VERTICES),
+        }
+    }
+}
+
+impl std::error::Error for WythoffError {}
+
+/// The result of building a polytope from a Coxeter diagram.
+pub type WythoffResult<T> = Result<T, WythoffError>;
+
+/// Returns whether every connected component of the subdiagram with the nodes
+/// in `mask` has a ringed node.
+fn is_minimal_subdiagram(mask: usize, adjacent: &[Vec<bool>], ringed: &[bool]) -> bool {
+    let mut visited = 0;
+
+    for start in 0..ringed.len() {
+        if mask & (1 << start) == 0 || visited & (1 << start) != 0 {
+            continue;
+        }
+
+        // Walks through the component of the starting node.
+        let mut has_ring = false;
+        let mut stack = vec![start];
+        visited |= 1 << start;
+
+        while let Some(i) = stack.pop() {
+            has_ring |= ringed[i];
+
+            for j in 0..ringed.len() {
+                if adjacent[i][j] && mask & (1 << j) != 0 && visited & (1 << j) == 0 {
+                    visited |= 1 << j;
+                    stack.push(j);
+                }
+            }
+        }
+
+        if !has_ring {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Returns the images of a set of vertices under all of the reflections, as
+/// sorted vectors of indices.
+fn orbit(base: Vec<usize>, perms: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut found = HashSet::new();
+    found.insert(base.clone());
+    let mut orbit = vec![base];
+
+    let mut idx = 0;
+    while idx < orbit.len() {
+        for perm in perms {
+            let mut image: Vec<_> = orbit[idx].iter().map(|&v| perm[v]).collect();
+            image.sort_unstable();
+
+            if found.insert(image.clone()) {
+                orbit.push(image);
+            }
+        }
+
+        idx += 1;
+    }
+
+    orbit
+}
+
+impl Concrete {
+    /// Builds the polytope described by a Coxeter diagram, such as `x4o3o` for
+    /// the cube, via the Wythoff construction. Every element is identified by
+    /// its vertices, so this only works for polytopes whose elements are
+    /// determined by them.
+    pub fn from_cd(input: &str) -> WythoffResult<Self> {
+        Self::wythoff(&Cd::parse(input)?)
+    }
+
+    /// Builds the polytope described by a parsed Coxeter diagram. See
+    /// [`Self::from_cd`].
+    pub fn wythoff(cd: &Cd) -> WythoffResult<Self> {
+        let nodes = cd.nodes();
+        if nodes.iter().any(|node| matches!(node, Node::Snub(_))) {
+            return Err(WythoffError::Snub);
+        }
+        if !cd.minimal() {
+            return Err(WythoffError::NotMinimal);
+        }
+
+        let dim = cd.dim();
+        let cox = cd.cox();
+        let mut normals = cox.normals().ok_or(WythoffError::NotSpherical)?;
+        let adjacent: Vec<Vec<bool>> = (0..dim)
+            .map(|i| (0..dim).map(|j| i != j && cox[(i, j)] != 2.0).collect())
+            .collect();
+
+        // The normals of linked mirrors make acute angles, so they don't all
+        // point into the same chamber. The diagram of a finite group is a
+        // forest, so flipping every other normal along its links fixes this.
+        let mut signs = vec![0.0; dim];
+        for start in 0..dim {
+            if signs[start] != 0.0 {
+                continue;
+            }
+
+            signs[start] = 1.0;
+            let mut stack = vec![start];
+            while let Some(i) = stack.pop() {
+                for j in 0..dim {
+                    if adjacent[i][j] && signs[j] == 0.0 {
+                        signs[j] = -signs[i];
+                        stack.push(j);
+                    }
+                }
+            }
+        }
+        for (mut n, sign) in normals.column_iter_mut().zip(signs) {
+            n *= sign;
+        }
+
+        // The generator lies at half the value of each node from its mirror,
+        // inside the chamber.
+        let generator = normals
+            .transpose()
+            .solve_lower_triangular(&(cd.node_vector() / 2.0))
+            .ok_or(WythoffError::NotSpherical)?;
+
+        // Reflects a point through the ith mirror.
+        let reflect = |p: &Point<Real>, i: usize| {
+            let n = normals.column(i);
+            p - n * (2.0 * p.dot(&n))
+        };
+
+        // Finds the orbit of the generator, along with how every reflection
+        // permutes its points.
+        let mut vertices = vec![generator.clone()];
+        let mut indices = BTreeMap::new();
+        indices.insert(PointOrd::new(generator), 0);
+        let mut perms = vec![Vec::new(); dim];
+
+        let mut v = 0;
+        while v < vertices.len() {
+            for (i, perm) in perms.iter_mut().enumerate() {
+                let image = reflect(&vertices[v], i);
+                let len = vertices.len();
+                let idx = *indices.entry(PointOrd::new(image.clone())).or_insert(len);
+
+                if idx == len {
+                    if len == MAX_VERTICES {
+                        return Err(WythoffError::TooLarge);
+                    }
+                    vertices.push(image);
+                }
+
+                perm.push(idx);
+            }
+
+            v += 1;
+        }
+
+        // Each element of rank k + 1 is the image of a base element, the orbit
+        // of the generator under the mirrors of a subdiagram with k nodes, all
+        // of whose components are ringed.
+        let ringed: Vec<bool> = nodes.iter().map(Node::is_ringed).collect();
+        let mut elements = vec![Vec::new(); dim];
+        elements[0] = (0..vertices.len()).map(|v| vec![v]).collect();
+
+        for k in 1..dim {
+            let mut found = HashSet::new();
+
+            for mask in 0..(1usize << dim) {
+                if mask.count_ones() as usize != k
+                    || !is_minimal_subdiagram(mask, &adjacent, &ringed)
+                {
+                    continue;
+                }
+
+                let gens: Vec<_> = (0..dim)
+                    .filter(|i| mask & (1 << i) != 0)
+                    .map(|i| perms[i].clone())
+                    .collect();
+                let mut base: Vec<_> = orbit(vec![0], &gens).into_iter().flatten().collect();
+                base.sort_unstable();
+
+                for el in orbit(base, &perms) {
+                    if found.insert(el.clone()) {
+                        elements[k].push(el);
+                    }
+                }
+            }
+        }
+
+        // Builds the polytope, using inclusion of vertex sets as incidence.
+        //
+        // Safety: the elements of a Wythoffian are determined by the
+        // subdiagrams of its Coxeter diagram, and their incidences by their
+        // vertices.
+        Ok(unsafe { from_vertex_sets(vertices, &elements) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test;
+
+    /// Checks that a few Wythoffians have the correct amount of elements.
+    #[test]
+    fn wythoffians() {
+        test(&Concrete::from_cd("x").unwrap(), [1, 2, 1]);
+        test(&Concrete::from_cd("x5o").unwrap(), [1, 5, 5, 1]);
+        test(&Concrete::from_cd("x4o3o").unwrap(), [1, 8, 12, 6, 1]);
+        test(&Concrete::from_cd("x3x3x").unwrap(), [1, 24, 36, 14, 1]);
+        test(&Concrete::from_cd("o3x3o3o").unwrap(), [1, 10, 30, 30, 10, 1]);
+        test(&Concrete::from_cd("x x").unwrap(), [1, 4, 4, 1]);
+        test(&Concrete::from_cd("x3o4o3o").unwrap(), [1, 24, 96, 96, 24, 1]);
+        test(&Concrete::from_cd("o5o3o3x").unwrap(), [1, 120, 720, 1200, 600, 1]);
+    }
+
+    /// Checks that invalid diagrams are rejected.
+    #[test]
+    fn errors() {
+        assert!(matches!(Concrete::from_cd("o4o3o"), Err(WythoffError::NotMinimal)));
+        assert!(matches!(Concrete::from_cd("x6o3o"), Err(WythoffError::NotSpherical)));
+        assert!(matches!(Concrete::from_cd("s4o3o"), Err(WythoffError::Snub)));
+    }
+}
@@ -8,7 +8,7 @@ use std::{
     ops::{Index, IndexMut},
 };
 
-use crate::float::Float;
+use crate::float::{Float, Real};
 use crate::group::Group;
 use crate::{geometry::Matrix, group::GenIter};
 
@@ -42,9 +42,9 @@ impl<T: Float> IndexMut<(usize, usize)> for Cox<T> {
     }
 }
 
-impl Cox<f64> {
+impl Cox<Real> {
     /// Initializes a new CD matrix from a vector of nodes and a matrix.
-    pub fn new(matrix: Matrix<f64>) -> Self {
+    pub fn new(matrix: Matrix<Real>) -> Self {
         Self(matrix)
     }
 
@@ -54,7 +54,7 @@ impl Cox<f64> {
     }
 
     /// Links together two nodes with a given edge.
-    pub fn link(&mut self, i: usize, j: usize, edge: f64) {
+    pub fn link(&mut self, i: usize, j: usize, edge: Real) {
         self[(i, j)] = edge;
         self[(j, i)] = edge;
     }
@@ -70,13 +70,13 @@ impl Cox<f64> {
     }
 
     /// Returns a mutable reference to the elements of the matrix.
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f64> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Real> {
         self.0.iter_mut()
     }
 
     /// Creates a Coxeter diagram from an iterator over the entries of its
     /// linear diagram.
-    pub fn from_lin_diagram_iter<I: Iterator<Item = f64>>(iter: I, dim: usize) -> Self {
+    pub fn from_lin_diagram_iter<I: Iterator<Item = Real>>(iter: I, dim: usize) -> Self {
         let mut cox = Self(Matrix::from_fn(dim, dim, |i, j| {
             if i == j {
                 1.0
@@ -94,12 +94,12 @@ impl Cox<f64> {
 
     /// Creates a Coxeter matrix from a linear diagram, whose edges are
     /// described by the vector.
-    pub fn from_lin_diagram(diagram: &[f64]) -> Self {
+    pub fn from_lin_diagram(diagram: &[Real]) -> Self {
         Self::from_lin_diagram_iter(diagram.iter().copied(), diagram.len())
     }
 
     /// Returns the Coxeter matrix for the I2(x) group.
-    pub fn i2(x: f64) -> Self {
+    pub fn i2(x: Real) -> Self {
         Self::from_lin_diagram(&[x])
     }
 
@@ -142,7 +142,7 @@ impl Cox<f64> {
 
     /// Returns an upper triangular matrix whose columns are unit normal vectors
     /// for the hyperplanes described by the Coxeter matrix.
-    pub fn normals(&self) -> Option<Matrix<f64>> {
+    pub fn normals(&self) -> Option<Matrix<Real>> {
         let dim = self.dim();
         let mut mat = Matrix::zeros(dim, dim);
 
@@ -154,12 +154,12 @@ impl Cox<f64> {
             for (j, n_j) in prev_gens.column_iter().enumerate() {
                 // All other entries in the dot product between columns are zero.
                 let dot = n_i.rows_range(0..=j).dot(&n_j.rows_range(0..=j));
-                n_i[j] = ((f64::PI / self[(i, j)]).fcos() - dot) / n_j[j];
+                n_i[j] = ((Real::PI / self[(i, j)]).fcos() - dot) / n_j[j];
             }
 
             // If the vector doesn't fit in spherical space.
-            let norm_sq: f64 = n_i.norm_squared();
-            if norm_sq >= 1.0 - f64::EPS {
+            let norm_sq: Real = n_i.norm_squared();
+            if norm_sq >= 1.0 - Real::EPS {
                 return None;
             } else {
                 n_i[i] = (1.0 - norm_sq).fsqrt();
@@ -170,12 +170,12 @@ impl Cox<f64> {
     }
 
     /// Returns an iterator over the elements of the Coxeter group.
-    pub fn gen_iter(&self) -> Option<GenIter<Matrix<f64>>> {
+    pub fn gen_iter(&self) -> Option<GenIter<Matrix<Real>>> {
         let normals = self.normals()?;
         let dim = normals.nrows();
 
         // Builds a reflection matrix from a vector.
-        let refl_mat = |n: VectorSlice<'_, f64>| {
+        let refl_mat = |n: VectorSlice<'_, Real>| {
             let nn = n.norm_squared();
             let mut mat = Matrix::identity(dim, dim);
 
@@ -194,7 +194,7 @@ impl Cox<f64> {
     }
 
     /// Returns the associated Coxeter [`Group`].
-    pub fn group(&self) -> Option<Group<GenIter<Matrix<f64>>>> {
+    pub fn group(&self) -> Option<Group<GenIter<Matrix<Real>>>> {
         self.gen_iter().map(Into::into)
     }
 }
@@ -5,6 +5,7 @@ use std::{collections::VecDeque, iter, str::FromStr};
 use petgraph::graph::NodeIndex;
 
 use super::cd::{Cd, CdError, CdResult, Edge, EdgeRef, Node, NodeRef};
+use crate::float::Real;
 
 /// Helper struct that parses a [`Cd`] based on a textual notation, adapted from
 /// [Krieger (year)](https://bendwavy.org/klitzing/pdf/Stott_v8.pdf).
@@ -154,7 +155,7 @@ impl<'a> CdBuilder<'a> {
         // We read the number until we find the closing parenthesis.
         while let Some((idx, c)) = self.next() {
             if c == ')' {
-                let val: f64 = self.parse_slice(init_idx, end_idx)?;
+                let val: Real = self.parse_slice(init_idx, end_idx)?;
 
                 // In case the user tries to literally write "NaN" (real funny).
                 return if val.is_nan() {
@@ -355,7 +356,7 @@ mod tests {
 
     /// Tests that a parsed diagram's nodes and Coxeter matrix match expected
     /// values.
-    fn test(diagram: &str, nodes: Vec<Node>, matrix: Matrix<f64>) {
+    fn test(diagram: &str, nodes: Vec<Node>, matrix: Matrix<Real>) {
         let cd = Cd::parse(diagram).unwrap();
         assert_eq!(cd.nodes(), nodes, "Node mismatch!");
         assert_eq!(cd.cox(), Cox::new(matrix), "Coxeter matrix mismatch!");
@@ -365,7 +366,7 @@ mod tests {
     /// Tests some of the I2 symmetry groups.
     fn i2() {
         for n in 2..10 {
-            let nf = n as f64;
+            let nf = n as Real;
 
             test(
                 &format!("x{}x", n),
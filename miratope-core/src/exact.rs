@@ -0,0 +1,524 @@
+//! Exact coordinates for polytopes built from primitives, so that chained
+//! operations don't accumulate floating-point error.
+//!
+//! Coordinates are stored as numbers in the field generated by the rationals
+//! and the square roots of 2, 3 and 5, which is enough for hypercubes,
+//! orthoplexes, many simplices, polygons with a number of sides dividing 24,
+//! and the golden ratio. Every operation is checked, and returns `None` if it
+//! overflows, so that callers can fall back to floating-point coordinates.
+
+use std::fmt::Display;
+
+use crate::{float::Real, geometry::Point};
+
+/// Returns the greatest common divisor of two nonnegative integers.
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+
+    a
+}
+
+/// A rational number, stored in lowest terms with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    /// The numerator.
+    num: i128,
+
+    /// The denominator, which is always positive.
+    den: i128,
+}
+
+impl Rational {
+    /// The rational number 0.
+    pub const ZERO: Self = Self { num: 0, den: 1 };
+
+    /// The rational number 1.
+    pub const ONE: Self = Self { num: 1, den: 1 };
+
+    /// Builds the rational number `num / den`, or returns `None` if the
+    /// denominator is zero.
+    pub fn new(num: i128, den: i128) -> Option<Self> {
+        if den == 0 {
+            return None;
+        }
+
+        let g = gcd(num.checked_abs()?, den.checked_abs()?);
+        let sign = den.signum();
+        Some(Self {
+            num: sign * num / g,
+            den: sign * den / g,
+        })
+    }
+
+    /// Builds a rational number from an integer.
+    pub fn int(n: i64) -> Self {
+        Self {
+            num: n as i128,
+            den: 1,
+        }
+    }
+
+    /// The numerator of the number in lowest terms.
+    pub fn num(&self) -> i128 {
+        self.num
+    }
+
+    /// The denominator of the number in lowest terms.
+    pub fn den(&self) -> i128 {
+        self.den
+    }
+
+    /// Returns whether the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    /// Adds two numbers, or returns `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let g = gcd(self.den, other.den);
+        let den = (self.den / g).checked_mul(other.den)?;
+        let num = self
+            .num
+            .checked_mul(other.den / g)?
+            .checked_add(other.num.checked_mul(self.den / g)?)?;
+        Self::new(num, den)
+    }
+
+    /// Multiplies two numbers, or returns `None` on overflow.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        // Cross-cancelling first keeps the intermediate values small.
+        let g1 = gcd(self.num.checked_abs()?, other.den);
+        let g2 = gcd(other.num.checked_abs()?, self.den);
+        let num = (self.num / g1).checked_mul(other.num / g2)?;
+        let den = (self.den / g2).checked_mul(other.den / g1)?;
+        Self::new(num, den)
+    }
+
+    /// Negates a number, or returns `None` on overflow.
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            num: self.num.checked_neg()?,
+            den: self.den,
+        })
+    }
+
+    /// Inverts a number, or returns `None` if it's zero.
+    pub fn checked_inv(self) -> Option<Self> {
+        Self::new(self.den, self.num)
+    }
+
+    /// Converts the number into a float.
+    pub fn to_real(self) -> Real {
+        self.num as Real / self.den as Real
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+/// The primes whose square roots generate [`Exact`] numbers.
+const PRIMES: [i128; 3] = [2, 3, 5];
+
+/// The number of square roots of products of [`PRIMES`], which make up a
+/// basis of the [`Exact`] numbers over the rationals.
+const BASIS_LEN: usize = 1 << PRIMES.len();
+
+/// The product of the primes in a bitmask over [`PRIMES`].
+fn radicand(mask: usize) -> i128 {
+    PRIMES
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| mask & (1 << i) != 0)
+        .map(|(_, p)| p)
+        .product()
+}
+
+/// An exact number of the form `a + b√2 + c√3 + d√5 + e√6 + …`, with rational
+/// coefficients for the square roots of every product of 2, 3 and 5.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Exact([Rational; BASIS_LEN]);
+
+/// A point with exact coordinates.
+pub type ExactPoint = Vec<Exact>;
+
+impl Exact {
+    /// The number 0.
+    pub const ZERO: Self = Self([Rational::ZERO; BASIS_LEN]);
+
+    /// The number 1.
+    pub const ONE: Self = Self([
+        Rational::ONE,
+        Rational::ZERO,
+        Rational::ZERO,
+        Rational::ZERO,
+        Rational::ZERO,
+        Rational::ZERO,
+        Rational::ZERO,
+        Rational::ZERO,
+    ]);
+
+    /// Builds an exact number from a rational.
+    pub fn rational(r: Rational) -> Self {
+        let mut coeffs = [Rational::ZERO; BASIS_LEN];
+        coeffs[0] = r;
+        Self(coeffs)
+    }
+
+    /// Builds an exact number from an integer.
+    pub fn int(n: i64) -> Self {
+        Self::rational(Rational::int(n))
+    }
+
+    /// Builds the fraction `num / den`, or returns `None` if the denominator
+    /// is zero.
+    pub fn frac(num: i64, den: i64) -> Option<Self> {
+        Rational::new(num as i128, den as i128).map(Self::rational)
+    }
+
+    /// Returns the square root of an integer, or `None` if it isn't an exact
+    /// number, like `√7`.
+    pub fn sqrt(n: u64) -> Option<Self> {
+        let mut rest = n as i128;
+        if rest == 0 {
+            return Some(Self::ZERO);
+        }
+
+        // We pull out every square factor, and keep track of the primes left
+        // under the root.
+        let mut mask = 0;
+        let mut coeff = 1;
+        for (i, &p) in PRIMES.iter().enumerate() {
+            let mut exp = 0;
+            while rest % p == 0 {
+                rest /= p;
+                exp += 1;
+            }
+
+            coeff *= p.pow(exp / 2);
+            if exp % 2 == 1 {
+                mask |= 1 << i;
+            }
+        }
+
+        // Whatever's left must be a perfect square.
+        let root = (rest as f64).sqrt().round() as i128;
+        if root * root != rest {
+            return None;
+        }
+
+        let mut res = Self::ZERO;
+        res.0[mask] = Rational::new(coeff * root, 1)?;
+        Some(res)
+    }
+
+    /// The golden ratio `(1 + √5) / 2`.
+    pub fn phi() -> Self {
+        let half = Rational::new(1, 2).unwrap();
+        let mut phi = Self::rational(half);
+        phi.0[0b100] = half;
+        phi
+    }
+
+    /// Returns the sine and cosine of `m` times 15°, which are always exact.
+    pub fn sin_cos_15(m: usize) -> (Self, Self) {
+        let quarter = Rational::new(1, 4).unwrap();
+        let half = Rational::new(1, 2).unwrap();
+        let neg = |x: Self| x.checked_neg().unwrap();
+
+        // The cosines of 0°, 15°, …, 90°.
+        let cos = |k: usize| {
+            let mut res = Self::ZERO;
+            match k {
+                0 => res.0[0] = Rational::ONE,
+                1 | 5 => {
+                    res.0[0b011] = quarter;
+                    res.0[0b001] = if k == 1 {
+                        quarter
+                    } else {
+                        quarter.checked_neg().unwrap()
+                    };
+                }
+                2 => res.0[0b010] = half,
+                3 => res.0[0b001] = half,
+                4 => res.0[0] = half,
+                _ => {}
+            }
+            res
+        };
+
+        // Reduces any angle to the first quadrant.
+        let cos_full = |m: usize| match m % 24 {
+            k @ 0..=6 => cos(k),
+            k @ 7..=12 => neg(cos(12 - k)),
+            k @ 13..=18 => neg(cos(k - 12)),
+            k => cos(24 - k),
+        };
+
+        (cos_full(m % 24 + 18), cos_full(m))
+    }
+
+    /// Returns whether the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(Rational::is_zero)
+    }
+
+    /// Returns the number as a rational, if it is one.
+    pub fn as_rational(&self) -> Option<Rational> {
+        self.0[1..].iter().all(Rational::is_zero).then(|| self.0[0])
+    }
+
+    /// Negates a number, or returns `None` on overflow.
+    pub fn checked_neg(mut self) -> Option<Self> {
+        for c in &mut self.0 {
+            *c = c.checked_neg()?;
+        }
+
+        Some(self)
+    }
+
+    /// Adds two numbers, or returns `None` on overflow.
+    pub fn checked_add(mut self, other: Self) -> Option<Self> {
+        for (c, d) in self.0.iter_mut().zip(other.0) {
+            *c = c.checked_add(d)?;
+        }
+
+        Some(self)
+    }
+
+    /// Subtracts two numbers, or returns `None` on overflow.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    /// Multiplies two numbers, or returns `None` on overflow.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        let mut res = Self::ZERO;
+
+        for (i, c) in self.0.iter().enumerate().filter(|(_, c)| !c.is_zero()) {
+            for (j, d) in other.0.iter().enumerate().filter(|(_, d)| !d.is_zero()) {
+                // √a · √b = gcd(a, b) · √(ab / gcd(a, b)²).
+                let term = c
+                    .checked_mul(*d)?
+                    .checked_mul(Rational::int(radicand(i & j) as i64))?;
+                res.0[i ^ j] = res.0[i ^ j].checked_add(term)?;
+            }
+        }
+
+        Some(res)
+    }
+
+    /// Applies the field automorphism that flips the sign of the square root
+    /// of the `i`-th prime, or returns `None` on overflow.
+    fn conjugate(mut self, i: usize) -> Option<Self> {
+        for (mask, c) in self.0.iter_mut().enumerate() {
+            if mask & (1 << i) != 0 {
+                *c = c.checked_neg()?;
+            }
+        }
+
+        Some(self)
+    }
+
+    /// Inverts a number, or returns `None` if it's zero or on overflow.
+    pub fn checked_inv(self) -> Option<Self> {
+        // Multiplying by the conjugates removes one square root at a time,
+        // until we're left with a rational.
+        let mut num = Self::ONE;
+        let mut cur = self;
+        for i in 0..PRIMES.len() {
+            let conj = cur.conjugate(i)?;
+            num = num.checked_mul(conj)?;
+            cur = cur.checked_mul(conj)?;
+        }
+
+        let inv = cur.as_rational()?.checked_inv()?;
+        num.checked_mul(Self::rational(inv))
+    }
+
+    /// Divides two numbers, or returns `None` if the divisor is zero or on
+    /// overflow.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        self.checked_mul(other.checked_inv()?)
+    }
+
+    /// Converts the number into a float.
+    pub fn to_real(&self) -> Real {
+        self.0
+            .iter()
+            .enumerate()
+            .map(|(mask, c)| c.to_real() * (radicand(mask) as Real).sqrt())
+            .sum()
+    }
+}
+
+impl Display for Exact {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+
+        for (mask, c) in self.0.iter().enumerate().filter(|(_, c)| !c.is_zero()) {
+            let (num, den) = (c.num(), c.den());
+            if !first {
+                f.write_str(if num < 0 { " - " } else { " + " })?;
+            } else if num < 0 {
+                f.write_str("-")?;
+            }
+            first = false;
+
+            let num = num.abs();
+            match (mask, num) {
+                (0, _) => write!(f, "{}", num)?,
+                (_, 1) => write!(f, "√{}", radicand(mask))?,
+                _ => write!(f, "{}√{}", num, radicand(mask))?,
+            }
+            if den != 1 {
+                write!(f, "/{}", den)?;
+            }
+        }
+
+        if first {
+            f.write_str("0")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts an exact point into a point with float coordinates.
+pub fn to_point(p: &[Exact]) -> Point<Real> {
+    Point::from_iterator(p.len(), p.iter().map(Exact::to_real))
+}
+
+/// Returns the dot product of two exact points, or `None` on overflow.
+pub fn dot(p: &[Exact], q: &[Exact]) -> Option<Exact> {
+    p.iter().zip(q).try_fold(Exact::ZERO, |acc, (x, y)| {
+        acc.checked_add(x.checked_mul(*y)?)
+    })
+}
+
+/// Returns `p + k q`, or `None` on overflow.
+fn add_scaled(p: &[Exact], k: Exact, q: &[Exact]) -> Option<ExactPoint> {
+    p.iter()
+        .zip(q)
+        .map(|(x, y)| x.checked_add(k.checked_mul(*y)?))
+        .collect()
+}
+
+/// Projects a point onto the affine hull of some other points, or returns
+/// `None` if there are no points or on overflow.
+pub fn project(p: &[Exact], points: &[&ExactPoint]) -> Option<ExactPoint> {
+    let (first, rest) = points.split_first()?;
+
+    // An orthogonal basis for the directions of the hull, by Gram–Schmidt.
+    let mut basis: Vec<ExactPoint> = Vec::new();
+    for q in rest {
+        let mut w = add_scaled(q, Exact::int(-1), first)?;
+        for b in &basis {
+            let k = dot(&w, b)?.checked_div(dot(b, b)?)?;
+            w = add_scaled(&w, k.checked_neg()?, b)?;
+        }
+
+        if w.iter().any(|x| !x.is_zero()) {
+            basis.push(w);
+        }
+    }
+
+    // We move the first point along every direction to get closest to p.
+    let diff = add_scaled(p, Exact::int(-1), first)?;
+    let mut res = (*first).clone();
+    for b in &basis {
+        let k = dot(&diff, b)?.checked_div(dot(b, b)?)?;
+        res = add_scaled(&res, k, b)?;
+    }
+
+    Some(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    /// Checks that an exact number has the expected value.
+    fn test(x: Exact, value: f64) {
+        assert_abs_diff_eq!(x.to_real() as f64, value, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn rational() {
+        let r = Rational::new(6, -4).unwrap();
+        assert_eq!((r.num(), r.den()), (-3, 2));
+        assert_eq!(r.to_string(), "-3/2");
+        assert!(Rational::new(1, 0).is_none());
+    }
+
+    #[test]
+    fn sqrt() {
+        test(Exact::sqrt(8).unwrap(), 8f64.sqrt());
+        test(Exact::sqrt(45).unwrap(), 45f64.sqrt());
+        assert!(Exact::sqrt(7).is_none());
+        assert_eq!(Exact::sqrt(12).unwrap().to_string(), "2√3");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let sqrt_2 = Exact::sqrt(2).unwrap();
+        let sqrt_3 = Exact::sqrt(3).unwrap();
+        let sqrt_6 = sqrt_2.checked_mul(sqrt_3).unwrap();
+        assert_eq!(sqrt_6, Exact::sqrt(6).unwrap());
+        assert_eq!(sqrt_2.checked_mul(sqrt_2).unwrap(), Exact::int(2));
+
+        // φ² = φ + 1.
+        let phi = Exact::phi();
+        assert_eq!(
+            phi.checked_mul(phi).unwrap(),
+            phi.checked_add(Exact::ONE).unwrap()
+        );
+
+        // Inverses of sums of several roots.
+        let x = Exact::ONE
+            .checked_add(sqrt_2)
+            .unwrap()
+            .checked_add(Exact::sqrt(5).unwrap())
+            .unwrap();
+        assert_eq!(x.checked_mul(x.checked_inv().unwrap()).unwrap(), Exact::ONE);
+        assert!(Exact::ZERO.checked_inv().is_none());
+    }
+
+    #[test]
+    fn sin_cos() {
+        for m in 0..24 {
+            let angle = m as f64 * std::f64::consts::PI / 12.0;
+            let (sin, cos) = Exact::sin_cos_15(m);
+            test(sin, angle.sin());
+            test(cos, angle.cos());
+        }
+    }
+
+    #[test]
+    fn projection() {
+        let p = vec![Exact::ONE, Exact::ONE];
+        let a = vec![Exact::int(2), Exact::ZERO];
+        let b = vec![Exact::ZERO, Exact::int(2)];
+
+        // The closest point to (1, 1) on the x axis.
+        assert_eq!(
+            project(&p, &[&a, &vec![Exact::ZERO; 2]]).unwrap(),
+            vec![Exact::ONE, Exact::ZERO]
+        );
+
+        // The closest point to the origin on the line x + y = 2.
+        let origin = vec![Exact::ZERO; 2];
+        assert_eq!(project(&origin, &[&a, &b]).unwrap(), p);
+    }
+}
@@ -0,0 +1,389 @@
+//! Evaluates arithmetic expressions like `pi/5`, `sqrt(2)` or `1/3`, so that
+//! exact values can be typed in instead of their decimal expansions.
+//!
+//! # Formal specification
+//!
+//! An expression is made out of numbers, constants, function calls and
+//! parentheses, combined with the usual arithmetic operators:
+//!
+//! * Numbers are written in decimal, like `2`, `0.5` or `1e-3`.
+//! * The constants are `pi` (or `π`), `tau`, `e` and `phi`, the golden ratio.
+//! * The functions are `sqrt`, `cbrt`, `sin`, `cos`, `tan`, `asin`, `acos`,
+//!   `atan`, `exp`, `ln` and `abs`. Their arguments must be parenthesized.
+//! * The operators are `+`, `-`, `*`, `/` and `^`, with their usual precedence.
+//!   Powers are right-associative, and bind tighter than a leading minus sign,
+//!   so that `-2^2` is `-4`.
+//!
+//! A number may be followed by a constant, a function call or a parenthesized
+//! expression to multiply them, like in `2pi` or `3sqrt(2)`. Whitespace
+//! between tokens is ignored.
+//!
+//! Expressions whose value isn't a finite number, like `1/0` or `sqrt(-1)`,
+//! are rejected.
+
+use std::{fmt::Display, iter::Peekable, str::CharIndices};
+
+use crate::float::Real;
+
+/// Represents an error while evaluating an expression.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExprError {
+    /// The expression ended before it was complete.
+    UnexpectedEnding {
+        /// The position at which the expression ended.
+        pos: usize,
+    },
+
+    /// A character that doesn't belong where it was found.
+    UnexpectedChar {
+        /// The position of the character.
+        pos: usize,
+
+        /// The character itself.
+        c: char,
+    },
+
+    /// A name that isn't a constant or a function.
+    UnknownName {
+        /// The position at which the name starts.
+        pos: usize,
+
+        /// The name itself.
+        name: String,
+    },
+
+    /// A number that couldn't be parsed.
+    ParseError {
+        /// The position at which the number starts.
+        pos: usize,
+    },
+
+    /// The expression evaluated to infinity or NaN, as in `1/0` or `sqrt(-1)`.
+    NotFinite,
+}
+
+impl Display for ExprError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedEnding { pos } => write!(f, "expression ended abruptly at index {}", pos),
+            Self::UnexpectedChar { pos, c } => {
+                write!(f, "unexpected character {:?} at index {}", c, pos)
+            }
+            Self::UnknownName { pos, name } => write!(f, "unknown name {:?} at index {}", name, pos),
+            Self::ParseError { pos } => write!(f, "invalid number at index {}", pos),
+            Self::NotFinite => write!(f, "the result isn't a finite number"),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+/// The result of evaluating an expression.
+pub type ExprResult<T> = Result<T, ExprError>;
+
+/// Returns the value of a constant with a given name.
+fn constant(name: &str) -> Option<Real> {
+    use std::f64::consts;
+
+    Some(match name {
+        "pi" | "π" => consts::PI,
+        "tau" => consts::TAU,
+        "e" => consts::E,
+        "phi" => (1.0 + 5f64.sqrt()) / 2.0,
+        _ => return None,
+    } as Real)
+}
+
+/// Returns the function with a given name.
+fn function(name: &str) -> Option<fn(Real) -> Real> {
+    Some(match name {
+        "sqrt" => Real::sqrt,
+        "cbrt" => Real::cbrt,
+        "sin" => Real::sin,
+        "cos" => Real::cos,
+        "tan" => Real::tan,
+        "asin" => Real::asin,
+        "acos" => Real::acos,
+        "atan" => Real::atan,
+        "exp" => Real::exp,
+        "ln" => Real::ln,
+        "abs" => Real::abs,
+        _ => return None,
+    })
+}
+
+/// Helper struct that evaluates an expression by recursive descent.
+struct Evaluator<'a> {
+    /// The expression being evaluated.
+    expr: &'a str,
+
+    /// A peekable iterator over the characters of the expression and their
+    /// indices. Used to keep track of where we're reading.
+    iter: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Evaluator<'a> {
+    /// Initializes a new evaluator from a string.
+    fn new(expr: &'a str) -> Self {
+        Self {
+            expr,
+            iter: expr.char_indices().peekable(),
+        }
+    }
+
+    /// Peeks at the next non-whitespace index-character pair, or returns
+    /// `None` if we've run out of them.
+    fn peek(&mut self) -> Option<(usize, char)> {
+        while let Some(&(_, c)) = self.iter.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.iter.next();
+        }
+
+        self.iter.peek().copied()
+    }
+
+    /// Skips the next character if it's the given one, and returns whether
+    /// it was.
+    fn eat(&mut self, c: char) -> bool {
+        if matches!(self.peek(), Some((_, d)) if d == c) {
+            self.iter.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns an error for the next character, or for the end of the
+    /// expression if there's none.
+    fn unexpected(&mut self) -> ExprError {
+        match self.peek() {
+            Some((pos, c)) => ExprError::UnexpectedChar { pos, c },
+            None => ExprError::UnexpectedEnding {
+                pos: self.expr.len(),
+            },
+        }
+    }
+
+    /// Reads characters for as long as they satisfy a predicate, and returns
+    /// the position where they start along with the slice they make up.
+    fn take_while<F: Fn(char) -> bool>(&mut self, pred: F) -> (usize, &'a str) {
+        let start = self.peek().map_or(self.expr.len(), |(pos, _)| pos);
+        let mut end = start;
+
+        while let Some(&(pos, c)) = self.iter.peek() {
+            if !pred(c) {
+                break;
+            }
+            end = pos + c.len_utf8();
+            self.iter.next();
+        }
+
+        (start, &self.expr[start..end])
+    }
+
+    /// Evaluates a sum or difference of terms.
+    fn sum(&mut self) -> ExprResult<Real> {
+        let mut value = self.product()?;
+
+        loop {
+            if self.eat('+') {
+                value += self.product()?;
+            } else if self.eat('-') {
+                value -= self.product()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Evaluates a product or quotient of factors. Factors that start with a
+    /// letter or a parenthesis are implicitly multiplied.
+    fn product(&mut self) -> ExprResult<Real> {
+        let mut value = self.factor()?;
+
+        loop {
+            if self.eat('*') {
+                value *= self.factor()?;
+            } else if self.eat('/') {
+                value /= self.factor()?;
+            } else if matches!(self.peek(), Some((_, c)) if c.is_alphabetic() || c == '(') {
+                value *= self.power()?;
+            } else {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Evaluates a power, possibly preceded by signs.
+    fn factor(&mut self) -> ExprResult<Real> {
+        if self.eat('-') {
+            Ok(-self.factor()?)
+        } else if self.eat('+') {
+            self.factor()
+        } else {
+            self.power()
+        }
+    }
+
+    /// Evaluates an atom, possibly raised to some exponent.
+    fn power(&mut self) -> ExprResult<Real> {
+        let base = self.atom()?;
+
+        if self.eat('^') {
+            Ok(base.powf(self.factor()?))
+        } else {
+            Ok(base)
+        }
+    }
+
+    /// Evaluates a number, a constant, a function call, or a parenthesized
+    /// expression.
+    fn atom(&mut self) -> ExprResult<Real> {
+        match self.peek() {
+            Some((_, '(')) => {
+                self.iter.next();
+                let value = self.sum()?;
+
+                if self.eat(')') {
+                    Ok(value)
+                } else {
+                    Err(self.unexpected())
+                }
+            }
+            Some((_, c)) if c.is_ascii_digit() || c == '.' => self.number(),
+            Some((_, c)) if c.is_alphabetic() => {
+                let (pos, name) = self.take_while(char::is_alphanumeric);
+
+                if let Some(value) = constant(name) {
+                    Ok(value)
+                } else if let Some(f) = function(name) {
+                    if self.eat('(') {
+                        let value = self.sum()?;
+
+                        if self.eat(')') {
+                            Ok(f(value))
+                        } else {
+                            Err(self.unexpected())
+                        }
+                    } else {
+                        Err(self.unexpected())
+                    }
+                } else {
+                    Err(ExprError::UnknownName {
+                        pos,
+                        name: name.to_string(),
+                    })
+                }
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// Evaluates a decimal number, possibly in scientific notation.
+    fn number(&mut self) -> ExprResult<Real> {
+        let (pos, mantissa) = self.take_while(|c| c.is_ascii_digit() || c == '.');
+        let mut end = pos + mantissa.len();
+
+        // An exponent is only read if it's followed by digits, so that `2e`
+        // is still read as twice Euler's number.
+        let mut rest = self.expr[end..].chars();
+        if let Some('e') | Some('E') = rest.next() {
+            let mut len = 1;
+            let mut next = rest.next();
+            if let Some('+') | Some('-') = next {
+                len += 1;
+                next = rest.next();
+            }
+
+            if matches!(next, Some(c) if c.is_ascii_digit()) {
+                for _ in 0..len {
+                    self.iter.next();
+                }
+                let (_, exponent) = self.take_while(|c| c.is_ascii_digit());
+                end += len + exponent.len();
+            }
+        }
+
+        self.expr[pos..end]
+            .parse()
+            .map_err(|_| ExprError::ParseError { pos })
+    }
+}
+
+/// Evaluates an arithmetic expression, as described in the
+/// [module-level documentation](self).
+pub fn eval(expr: &str) -> ExprResult<Real> {
+    let mut evaluator = Evaluator::new(expr);
+    let value = evaluator.sum()?;
+
+    if evaluator.peek().is_some() {
+        Err(evaluator.unexpected())
+    } else if !value.is_finite() {
+        Err(ExprError::NotFinite)
+    } else {
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    /// Evaluates an expression and compares it to a value.
+    fn test(expr: &str, value: f64) {
+        assert_abs_diff_eq!(eval(expr).unwrap() as f64, value, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn numbers() {
+        test("2", 2.0);
+        test("0.5", 0.5);
+        test(".25", 0.25);
+        test("1e-3", 0.001);
+        test("2.5E2", 250.0);
+    }
+
+    #[test]
+    fn operators() {
+        test("1/3", 1.0 / 3.0);
+        test("1 + 2 * 3", 7.0);
+        test("(1 + 2) * 3", 9.0);
+        test("2^3^2", 512.0);
+        test("-2^2", -4.0);
+        test("2 - -1", 3.0);
+    }
+
+    #[test]
+    fn names() {
+        use std::f64::consts::PI;
+
+        test("pi/5", PI / 5.0);
+        test("sqrt(2)", 2f64.sqrt());
+        test("phi", (1.0 + 5f64.sqrt()) / 2.0);
+        test("2pi", 2.0 * PI);
+        test("2e", 2.0 * std::f64::consts::E);
+        test("3 sqrt(2)", 3.0 * 2f64.sqrt());
+        test("cos(pi/3)", 0.5);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(eval("1 +"), Err(ExprError::UnexpectedEnding { pos: 3 }));
+        assert_eq!(eval("(1"), Err(ExprError::UnexpectedEnding { pos: 2 }));
+        assert_eq!(eval("1 )"), Err(ExprError::UnexpectedChar { pos: 2, c: ')' }));
+        assert_eq!(
+            eval("foo(2)"),
+            Err(ExprError::UnknownName {
+                pos: 0,
+                name: "foo".to_string()
+            })
+        );
+        assert_eq!(eval("sqrt 2"), Err(ExprError::UnexpectedChar { pos: 5, c: '2' }));
+        assert_eq!(eval("1.2.3"), Err(ExprError::ParseError { pos: 0 }));
+        assert_eq!(eval("sqrt(-1)"), Err(ExprError::NotFinite));
+        assert_eq!(eval("1/0"), Err(ExprError::NotFinite));
+    }
+}
@@ -1,19 +1,32 @@
-//! Contains the code that opens a GGB file and parses it into a polytope.
+//! Contains the code that opens a GGB file and parses it into a polytope, and
+//! the code that writes a polytope as a GGB file.
 
 // This code is unfinished.
 #![allow(dead_code)]
 #![allow(clippy::collapsible_match)]
 
-use std::io::Result as IoResult;
+use std::{
+    fmt::Write as FmtWrite,
+    io::{Cursor, Result as IoResult, Write},
+};
 
-use crate::{conc::Concrete, geometry::Point};
+use crate::{
+    abs::Ranked,
+    conc::{cycle::CycleList, Concrete, ConcretePolytope},
+    float::Real,
+    geometry::Point,
+};
 
 use nalgebra::dvector;
+use vec_like::VecLike;
 use xml::{
     attribute::OwnedAttribute,
     reader::{EventReader, XmlEvent},
 };
-use zip::result::ZipError;
+use zip::{result::ZipError, write::FileOptions, ZipWriter};
+
+/// The extension of GGB files.
+pub const GGB_EXT: &str = "ggb";
 
 type Events<'a> = xml::reader::Events<&'a [u8]>;
 
@@ -82,7 +95,7 @@ impl<'a> XmlReader<'a> {
     /// ```xml
     /// <element type="point3d" label="A">
     /// ```
-    fn read_point(&mut self, attributes: &[OwnedAttribute]) -> GgbResult<Vertex<f64>> {
+    fn read_point(&mut self, attributes: &[OwnedAttribute]) -> GgbResult<Vertex<Real>> {
         let label = attribute(attributes, "label").unwrap_or_default();
         let coord_attributes = self.read_until("coords")?;
 
@@ -90,7 +103,7 @@ impl<'a> XmlReader<'a> {
         /// the same name.
         macro_rules! read_coord {
             ($x:ident) => {
-                let $x: f64;
+                let $x: Real;
 
                 if let Some(c) = attribute(&coord_attributes, stringify!($x)) {
                     if let Ok(c) = c.parse() {
@@ -210,7 +223,7 @@ fn read_face() -> Face {
 
 /// Parses the `geogebra.xml` file to produce a polytope.
 pub(super) fn parse_xml(xml: &str) -> GgbResult<Concrete> {
-    let mut vertices: Vec<Vertex<f64>> = Vec::new();
+    let mut vertices: Vec<Vertex<Real>> = Vec::new();
     let mut edges = Vec::new();
     let mut xml = XmlReader::new(xml);
 
@@ -255,3 +268,138 @@ pub(super) fn parse_xml(xml: &str) -> GgbResult<Concrete> {
         }
     }
 }
+
+impl Concrete {
+    /// Writes the polytope as a GGB file, with a point for every vertex, a
+    /// segment for every edge, and a polygon for every face. Polytopes in more
+    /// than 3D are projected orthogonally onto their first three coordinates.
+    pub fn to_ggb(&self) -> Vec<u8> {
+        self.to_ggb_with(&self.vertices)
+    }
+
+    /// Writes the polytope as a GGB file, placing its vertices at the given
+    /// points instead. This is useful to export some projection of the
+    /// polytope into 3D. Only the first three coordinates of each point are
+    /// kept.
+    pub fn to_ggb_with(&self, vertices: &[Point<Real>]) -> Vec<u8> {
+        let point_label = |idx: usize| format!("V_{{{}}}", idx + 1);
+        let mut xml = String::new();
+
+        // Writing to a string never fails.
+        writeln!(xml, r#"<?xml version="1.0" encoding="utf-8"?>"#).unwrap();
+        writeln!(xml, r#"<geogebra format="5.0" app="classic" subApp="3D">"#).unwrap();
+        if self.dim_or() > 3 {
+            writeln!(
+                xml,
+                "<!-- Projected from {}D into 3D, so some information is lost. -->",
+                self.dim_or()
+            )
+            .unwrap();
+        }
+        writeln!(xml, r#"<construction title="" author="" date="">"#).unwrap();
+
+        // Writes the vertices.
+        for (idx, v) in vertices.iter().enumerate() {
+            let coord = |i: usize| v.get(i).copied().unwrap_or_default();
+            writeln!(
+                xml,
+                r#"<element type="point3d" label="{}"><coords x="{}" y="{}" z="{}" w="1"/></element>"#,
+                point_label(idx),
+                coord(0),
+                coord(1),
+                coord(2)
+            )
+            .unwrap();
+        }
+
+        // Writes the edges.
+        if self.rank() >= 2 {
+            for (idx, edge) in self[2].iter().enumerate() {
+                if let [v0, v1] = edge.subs.as_slice() {
+                    writeln!(
+                        xml,
+                        r#"<command name="Segment"><input a0="{}" a1="{}"/><output a0="e_{{{}}}"/></command>"#,
+                        point_label(*v0),
+                        point_label(*v1),
+                        idx + 1
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        // Writes the faces, or the polytope itself in the polygonal case.
+        let cycles: Vec<CycleList> = match self.rank() {
+            3 => vec![CycleList::from_edges(self[2].iter().map(|edge| &edge.subs))],
+            rank if rank > 3 => self[3]
+                .iter()
+                .map(|face| CycleList::from_edges(face.subs.iter().map(|&i| &self[(2, i)].subs)))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        for (idx, cycle) in cycles.iter().flat_map(|cycles| cycles.iter()).enumerate() {
+            let input: Vec<_> = cycle
+                .iter()
+                .enumerate()
+                .map(|(i, &v)| format!(r#"a{}="{}""#, i, point_label(v)))
+                .collect();
+            writeln!(
+                xml,
+                r#"<command name="Polygon"><input {}/><output a0="f_{{{}}}"/></command>"#,
+                input.join(" "),
+                idx + 1
+            )
+            .unwrap();
+        }
+
+        writeln!(xml, "</construction>").unwrap();
+        writeln!(xml, "</geogebra>").unwrap();
+
+        // Writing to memory never fails.
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("geogebra.xml", FileOptions::default())
+            .unwrap();
+        zip.write_all(xml.as_bytes()).unwrap();
+        zip.finish().unwrap().into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    use std::io::Read;
+
+    /// Reads back the `geogebra.xml` file from a GGB file.
+    fn read_xml(ggb: Vec<u8>) -> String {
+        let mut xml = String::new();
+        zip::ZipArchive::new(Cursor::new(ggb))
+            .unwrap()
+            .by_name("geogebra.xml")
+            .unwrap()
+            .read_to_string(&mut xml)
+            .unwrap();
+        xml
+    }
+
+    /// Writes a pentagon, a cube, and a tesseract.
+    #[test]
+    fn to_ggb() {
+        let pentagon = read_xml(Concrete::polygon(5).to_ggb());
+        assert_eq!(pentagon.matches("point3d").count(), 5);
+        assert_eq!(pentagon.matches(r#"name="Segment""#).count(), 5);
+        assert_eq!(pentagon.matches(r#"name="Polygon""#).count(), 1);
+
+        let cube = read_xml(Concrete::cube().to_ggb());
+        assert_eq!(cube.matches("point3d").count(), 8);
+        assert_eq!(cube.matches(r#"name="Segment""#).count(), 12);
+        assert_eq!(cube.matches(r#"name="Polygon""#).count(), 6);
+        assert!(!cube.contains("<!--"));
+
+        let tesseract = read_xml(Concrete::hypercube(5).to_ggb());
+        assert_eq!(tesseract.matches(r#"name="Polygon""#).count(), 24);
+        assert!(tesseract.contains("<!--"));
+    }
+}
@@ -0,0 +1,66 @@
+//! Writing the incidence matrix between the elements of two ranks of a
+//! polytope as a CSV file.
+//!
+//! # Format
+//! Every row of the file corresponds to an element of the first rank, and every
+//! column to an element of the second, both in the order they're stored in.
+//! Entries are `1` if the elements are incident and `0` otherwise, separated by
+//! commas. There's no header, so that the file can be read directly as a
+//! matrix, like with `numpy.loadtxt(path, delimiter=",")`.
+
+use std::fmt::Write;
+
+use crate::{abs::Abstract, conc::Concrete};
+
+/// The extension of CSV files.
+pub const CSV_EXT: &str = "csv";
+
+impl Abstract {
+    /// Writes the incidence matrix between the elements of ranks `r0` and `r1`
+    /// in the format described in the [module documentation](self). Returns
+    /// `None` if either rank is out of bounds.
+    pub fn to_incidence_csv(&self, r0: usize, r1: usize) -> Option<String> {
+        let matrix = self.incidence_matrix(r0, r1)?;
+        let mut csv = String::new();
+
+        // Writing to a string never fails.
+        for row in matrix {
+            let row: Vec<_> = row.iter().map(|&inc| if inc { "1" } else { "0" }).collect();
+            writeln!(csv, "{}", row.join(",")).unwrap();
+        }
+
+        Some(csv)
+    }
+}
+
+impl Concrete {
+    /// Writes the incidence matrix between the elements of two ranks. See
+    /// [`Abstract::to_incidence_csv`].
+    pub fn to_incidence_csv(&self, r0: usize, r1: usize) -> Option<String> {
+        self.abs.to_incidence_csv(r0, r1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{abs::Abstract, Polytope};
+
+    /// Writes the vertex-facet incidence matrix of a tetrahedron.
+    #[test]
+    fn tetrahedron() {
+        let csv = Abstract::simplex(4).to_incidence_csv(1, 3).unwrap();
+        let rows: Vec<_> = csv.lines().collect();
+
+        assert_eq!(rows.len(), 4);
+        for row in rows {
+            assert_eq!(row.split(',').count(), 4);
+            assert_eq!(row.matches('1').count(), 3);
+        }
+    }
+
+    /// Ranks out of bounds give no matrix.
+    #[test]
+    fn rank() {
+        assert!(Abstract::polygon(3).to_incidence_csv(1, 5).is_none());
+    }
+}
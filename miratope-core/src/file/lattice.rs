@@ -0,0 +1,300 @@
+//! Reading and writing the incidence structure of a polytope as plain text,
+//! without any coordinates.
+//!
+//! # Format
+//! A lattice file starts with the word `ABS`, followed by the rank of the
+//! polytope. Then, for every rank from the vertices up to the facets, it lists
+//! the number of elements of that rank, followed by one line per element with
+//! the indices of its subelements, separated by spaces. Vertices have no lines
+//! of their own, since their only subelement is the minimal element. The
+//! minimal and maximal elements are implicit. Anything after a `#` is a
+//! comment.
+//!
+//! A square, for instance, is written as follows.
+//!
+//! ```txt
+//! ABS 2
+//! # Vertices
+//! 4
+//! # Edges
+//! 4
+//! 0 1
+//! 1 2
+//! 2 3
+//! 0 3
+//! ```
+
+use std::fmt::{Display, Write};
+
+use crate::{
+    abs::{Abstract, AbstractBuilder, AbstractError, Ranked, SubelementList, Subelements},
+    conc::Concrete,
+    float::{Float, Real},
+    geometry::Point,
+    ELEMENT_NAMES,
+};
+
+use vec_like::VecLike;
+
+/// The extension of lattice files.
+pub const LATTICE_EXT: &str = "abs";
+
+/// Any error encountered while parsing a lattice file.
+#[derive(Clone, Copy, Debug)]
+pub enum LatticeError {
+    /// Empty file.
+    Empty,
+
+    /// Didn't find the `ABS` magic word.
+    MagicWord,
+
+    /// Could not parse the rank.
+    Rank,
+
+    /// The file ended unexpectedly.
+    UnexpectedEnding,
+
+    /// Could not parse a number on a given line.
+    Parsing(usize),
+
+    /// An index on a given line doesn't refer to any element.
+    Index(usize),
+
+    /// The parsed lattice isn't a valid polytope.
+    Invalid(AbstractError),
+}
+
+impl Display for LatticeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "file is empty."),
+            Self::MagicWord => write!(f, "no \"ABS\" detected"),
+            Self::Rank => write!(f, "could not read rank"),
+            Self::UnexpectedEnding => write!(f, "file ended unexpectedly"),
+            Self::Parsing(line) => write!(f, "could not parse number at line {}", line),
+            Self::Index(line) => write!(f, "invalid index at line {}", line),
+            Self::Invalid(err) => write!(f, "invalid polytope: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for LatticeError {}
+
+/// The result of parsing a lattice file.
+pub type LatticeResult<T> = Result<T, LatticeError>;
+
+/// Iterates over the non-empty lines of a lattice file, along with their line
+/// numbers, with comments stripped.
+fn lines(src: &str) -> impl Iterator<Item = (usize, &str)> {
+    src.lines().enumerate().filter_map(|(idx, line)| {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        (!line.is_empty()).then(|| (idx + 1, line))
+    })
+}
+
+/// Parses a line of space-separated numbers.
+fn parse_numbers(line_no: usize, line: &str) -> LatticeResult<Vec<usize>> {
+    line.split_whitespace()
+        .map(|num| num.parse().map_err(|_| LatticeError::Parsing(line_no)))
+        .collect()
+}
+
+/// Parses a line with a single number.
+fn parse_count(line: Option<(usize, &str)>) -> LatticeResult<usize> {
+    let (line_no, line) = line.ok_or(LatticeError::UnexpectedEnding)?;
+    match parse_numbers(line_no, line)?.as_slice() {
+        &[count] => Ok(count),
+        _ => Err(LatticeError::Parsing(line_no)),
+    }
+}
+
+impl Abstract {
+    /// Writes the incidence structure of the polytope in the format described
+    /// in the [module documentation](self).
+    pub fn to_abstract_text(&self) -> String {
+        let rank = self.rank();
+        let mut text = String::new();
+
+        // Writing to a string never fails.
+        writeln!(text, "ABS {}", rank as isize - 1).unwrap();
+
+        for r in 1..rank {
+            match ELEMENT_NAMES.get(r) {
+                Some(name) => writeln!(text, "# {}", name).unwrap(),
+                None => writeln!(text, "# {}-elements", r - 1).unwrap(),
+            }
+            writeln!(text, "{}", self.el_count(r)).unwrap();
+
+            if r >= 2 {
+                for el in self[r].iter() {
+                    let subs: Vec<_> = el.subs.iter().map(usize::to_string).collect();
+                    writeln!(text, "{}", subs.join(" ")).unwrap();
+                }
+            }
+        }
+
+        text
+    }
+
+    /// Reads a polytope from the format described in the
+    /// [module documentation](self), and checks that it's valid.
+    pub fn from_abstract_text(src: &str) -> LatticeResult<Self> {
+        let mut lines = lines(src);
+
+        // Reads the header.
+        let (_, header) = lines.next().ok_or(LatticeError::Empty)?;
+        let mut header = header.split_whitespace();
+        if header.next() != Some("ABS") {
+            return Err(LatticeError::MagicWord);
+        }
+        let rank = header
+            .next()
+            .and_then(|rank| rank.parse::<isize>().ok())
+            .filter(|&rank| rank >= -1)
+            .ok_or(LatticeError::Rank)?;
+        let rank = (rank + 1) as usize;
+
+        let mut builder = AbstractBuilder::with_rank_capacity(rank);
+        builder.push_min();
+
+        if rank >= 1 {
+            let mut prev_count = 1;
+
+            for r in 1..rank {
+                let count = parse_count(lines.next())?;
+
+                if r == 1 {
+                    builder.push_vertices(count);
+                } else {
+                    let mut subelements = SubelementList::with_capacity(count);
+
+                    for _ in 0..count {
+                        let (line_no, line) = lines.next().ok_or(LatticeError::UnexpectedEnding)?;
+                        let subs = parse_numbers(line_no, line)?;
+
+                        if subs.iter().any(|&sub| sub >= prev_count) {
+                            return Err(LatticeError::Index(line_no));
+                        }
+                        subelements.push(Subelements::from(subs));
+                    }
+
+                    builder.push(subelements);
+                }
+
+                prev_count = count;
+            }
+
+            builder.push_max();
+        }
+
+        // Safety: we check that the polytope is valid right after.
+        let poly = unsafe { builder.build() };
+        poly.ranks().is_valid().map_err(LatticeError::Invalid)?;
+        Ok(poly)
+    }
+}
+
+impl Concrete {
+    /// Writes the incidence structure of the polytope, ignoring its
+    /// coordinates. See [`Abstract::to_abstract_text`].
+    pub fn to_abstract_text(&self) -> String {
+        self.abs.to_abstract_text()
+    }
+
+    /// Reads a polytope from a lattice file. Since these don't store any
+    /// coordinates, the vertices are placed evenly around a circle.
+    pub fn from_abstract_text(src: &str) -> LatticeResult<Self> {
+        let abs = Abstract::from_abstract_text(src)?;
+        let dim = abs.rank().saturating_sub(1);
+        let vertex_count = abs.vertex_count();
+
+        let vertices = (0..vertex_count)
+            .map(|idx| {
+                let mut vertex = Point::zeros(dim);
+
+                if dim >= 2 {
+                    let angle = Real::TAU * idx as Real / vertex_count as Real;
+                    vertex[0] = angle.cos();
+                    vertex[1] = angle.sin();
+                } else if dim == 1 {
+                    vertex[0] = idx as Real;
+                }
+
+                vertex
+            })
+            .collect();
+
+        Ok(Self::new(vertices, abs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conc::ConcretePolytope, test, Polytope};
+
+    /// Writes a polytope as a lattice file and reads it back.
+    fn test_round_trip<I: IntoIterator<Item = usize> + Clone>(poly: &Abstract, element_counts: I) {
+        const ERR: &str = "lattice file could not be reloaded.";
+        let text = poly.to_abstract_text();
+        let reloaded = Abstract::from_abstract_text(&text).expect(ERR);
+
+        test(&reloaded, element_counts);
+        assert_eq!(reloaded.to_abstract_text(), text);
+    }
+
+    /// Round-trips the nullitope.
+    #[test]
+    fn nullitope() {
+        test_round_trip(&Abstract::nullitope(), [1]);
+    }
+
+    /// Round-trips a point.
+    #[test]
+    fn point() {
+        test_round_trip(&Abstract::point(), [1, 1]);
+    }
+
+    /// Round-trips a pentagon.
+    #[test]
+    fn pentagon() {
+        test_round_trip(&Abstract::polygon(5), [1, 5, 5, 1]);
+    }
+
+    /// Round-trips a cube.
+    #[test]
+    fn cube() {
+        test_round_trip(&Abstract::cube(), [1, 8, 12, 6, 1]);
+    }
+
+    /// Reads a square with comments, and gives it placeholder coordinates.
+    #[test]
+    fn square() {
+        let src = "ABS 2 # A square.\n4\n\n4\n0 1\n1 2\n2 3 # An edge.\n0 3\n";
+        let square = Concrete::from_abstract_text(src).unwrap();
+
+        test(&square, [1, 4, 4, 1]);
+        assert_eq!(square.dim(), Some(2));
+    }
+
+    /// Checks that invalid lattices are rejected.
+    #[test]
+    fn invalid() {
+        assert!(matches!(
+            Abstract::from_abstract_text("OFF 2"),
+            Err(LatticeError::MagicWord)
+        ));
+        assert!(matches!(
+            Abstract::from_abstract_text("ABS 2\n3\n3\n0 1\n1 2\n0 3"),
+            Err(LatticeError::Index(6))
+        ));
+        assert!(matches!(
+            Abstract::from_abstract_text("ABS 2\n3\n3\n0 1 2\n1 2\n0 2"),
+            Err(LatticeError::Invalid(_))
+        ));
+        assert!(matches!(
+            Abstract::from_abstract_text("ABS 2\n3\n3\n0 1"),
+            Err(LatticeError::UnexpectedEnding)
+        ));
+    }
+}
@@ -1,11 +1,18 @@
 //! Reading from and writing to files in various different formats.
 
 pub mod ggb;
+pub mod incidence;
+pub mod lattice;
+pub mod numpy;
 pub mod off;
+pub mod ply;
+pub mod stella;
+pub mod svg;
 
 use self::{
     ggb::{GgbError, GgbResult},
-    off::{OffParseResult, OffReader},
+    lattice::{LatticeError, LatticeResult},
+    off::{OffParseResult, OffReader, GZ_EXT},
 };
 use crate::conc::Concrete;
 
@@ -13,7 +20,7 @@ use off::OffParseError;
 use zip::result::ZipError;
 
 pub use std::io::Error as IoError;
-use std::{fs::File, string::FromUtf8Error, fmt::Display};
+use std::{ffi::OsStr, fmt::Display, fs::File, path::Path, string::FromUtf8Error};
 
 /// Any error encountered while trying to load a polytope.
 #[derive(Debug)]
@@ -24,6 +31,9 @@ pub enum FileError<'a> {
     /// An error while reading a GGB file.
     GgbError(GgbError),
 
+    /// An error while reading a lattice file.
+    LatticeError(LatticeError),
+
     /// Some generic I/O error occured.
     IoError(IoError),
 
@@ -43,6 +53,7 @@ impl<'a> Display for FileError<'a> {
         match self {
             Self::OffError(err) => write!(f, "OFF error: {}", err),
             Self::GgbError(err) => write!(f, "GGB error: {}", err),
+            Self::LatticeError(err) => write!(f, "lattice error: {}", err),
             Self::IoError(err) => write!(f, "IO error: {}", err),
             Self::ZipError(err) => write!(f, "ZIP error while opening GGB: {}", err),
             Self::InvalidFile(err) => write!(f, "invalid file: {}", err),
@@ -67,6 +78,13 @@ impl<'a> From<GgbError> for FileError<'a> {
     }
 }
 
+/// [`LatticeError`] is a type of [`FileError`].
+impl<'a> From<LatticeError> for FileError<'a> {
+    fn from(err: LatticeError) -> Self {
+        Self::LatticeError(err)
+    }
+}
+
 /// [`FromUtf8Error`] is a type of [`FileError`].
 impl<'a> From<FromUtf8Error> for FileError<'a> {
     fn from(err: FromUtf8Error) -> Self {
@@ -103,9 +121,13 @@ pub trait FromFile: Sized {
     /// 3D.
     fn from_ggb(file: File) -> GgbResult<Self>;
 
-    /// Loads a polytope from a file path.
-    fn from_path<U: AsRef<std::path::Path>>(fp: &U) -> FileResult<'_, Self> {
-        use std::{ffi::OsStr, fs};
+    /// Reads a lattice file, which only stores the incidences of the polytope.
+    fn from_lattice(src: &str) -> LatticeResult<Self>;
+
+    /// Loads a polytope from a file path. Files ending in `.gz` are read as
+    /// gzipped OFF files.
+    fn from_path<U: AsRef<Path>>(fp: &U) -> FileResult<'_, Self> {
+        use std::{fs, io::Read};
 
         let ext = fp
             .as_ref()
@@ -114,14 +136,22 @@ pub trait FromFile: Sized {
             .unwrap_or_default();
 
         match ext {
-            // Reads the file as an OFF file.
-            "off" => match String::from_utf8(fs::read(fp)?) {
-                Ok(src) => Ok(Self::from_off(&src)?),
-                Err(err) => Err(err.into()),
-            },
+            // Reads the file as an OFF file. Invalid UTF-8 can only really
+            // show up within comments, so we don't fail on it.
+            "off" => Ok(Self::from_off(&String::from_utf8_lossy(&fs::read(fp)?))?),
+
+            // Decompresses the file and reads it as an OFF file.
+            GZ_EXT => {
+                let mut src = Vec::new();
+                flate2::read::GzDecoder::new(File::open(fp)?).read_to_end(&mut src)?;
+                Ok(Self::from_off(&String::from_utf8_lossy(&src))?)
+            }
 
             // Reads the file as a GGB file.
-            "ggb" => Ok(Self::from_ggb(fs::File::open(fp)?)?),
+            ggb::GGB_EXT => Ok(Self::from_ggb(File::open(fp)?)?),
+
+            // Reads the file as a lattice file.
+            lattice::LATTICE_EXT => Ok(Self::from_lattice(&fs::read_to_string(fp)?)?),
 
             // Could not recognize the file extension.
             ext => Err(FileError::InvalidExtension(ext)),
@@ -151,6 +181,23 @@ impl FromFile for Concrete {
             Err(GgbError::InvalidGgb)
         }
     }
+
+    fn from_lattice(src: &str) -> LatticeResult<Self> {
+        Self::from_abstract_text(src)
+    }
+}
+
+/// Returns the name of a file without its extension. Gzipped files lose both
+/// the `.gz` and the extension before it, so that `cube.off.gz` becomes `cube`.
+pub fn file_stem<P: AsRef<Path> + ?Sized>(fp: &P) -> Option<&str> {
+    let fp = fp.as_ref();
+    let stem = Path::new(fp.file_stem()?);
+
+    if fp.extension() == Some(OsStr::new(GZ_EXT)) {
+        stem.file_stem()?.to_str()
+    } else {
+        stem.to_str()
+    }
 }
 
 /// A position in a file.
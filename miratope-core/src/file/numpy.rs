@@ -0,0 +1,72 @@
+//! Writing the coordinates of polytopes as Python literals, so that they can be
+//! pasted into a script that uses [NumPy](https://numpy.org/).
+
+use std::fmt::Write;
+
+use crate::{abs::Ranked, conc::Concrete};
+
+use vec_like::VecLike;
+
+/// The indentation used for the rows of the arrays.
+const INDENT: &str = "    ";
+
+/// Writes a list of rows as a NumPy array, one row per line.
+fn write_array<T: std::fmt::Debug, I: IntoIterator<Item = Vec<T>>>(out: &mut String, rows: I) {
+    out.push_str("np.array([\n");
+
+    // Writing to a string never fails.
+    for row in rows {
+        let row: Vec<_> = row.iter().map(|x| format!("{:?}", x)).collect();
+        writeln!(out, "{}[{}],", INDENT, row.join(", ")).unwrap();
+    }
+
+    out.push_str("])");
+}
+
+impl Concrete {
+    /// Writes the vertex coordinates of the polytope as a NumPy array, with
+    /// one row per vertex. If `edges` is set, the edges are also written as an
+    /// array of pairs of vertex indices, and both arrays are assigned to the
+    /// variables `vertices` and `edges`.
+    pub fn to_numpy(&self, edges: bool) -> String {
+        let mut out = String::new();
+        let vertices = self.vertices.iter().map(|v| v.iter().copied().collect());
+
+        if !edges {
+            write_array(&mut out, vertices);
+            return out;
+        }
+
+        out.push_str("vertices = ");
+        write_array(&mut out, vertices);
+        out.push_str("\nedges = ");
+
+        if self.rank() >= 2 {
+            write_array(&mut out, self[2].iter().map(|edge| edge.subs.clone().into_inner()));
+        } else {
+            write_array(&mut out, std::iter::empty::<Vec<usize>>());
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    /// Writes a square and the edges of a cube.
+    #[test]
+    fn numpy() {
+        let square = Concrete::polygon(4).to_numpy(false);
+        assert!(square.starts_with("np.array([\n"));
+        assert!(square.ends_with("])"));
+        assert_eq!(square.matches("    [").count(), 4);
+
+        let cube = Concrete::cube().to_numpy(true);
+        assert!(cube.starts_with("vertices = np.array(["));
+        assert!(cube.contains("\nedges = np.array(["));
+        assert_eq!(cube.matches("    [").count(), 8 + 12);
+    }
+}
@@ -1,19 +1,24 @@
 //! The code that opens an OFF file and parses it into a polytope.
 //! Also the code that writes an OFF file of a polytope.
 
-use std::{collections::{HashMap, HashSet}, fmt::Display, io::Error as IoError, path::Path, str::FromStr};
+use std::{collections::{HashMap, HashSet}, fmt::Display, fs::File, io::{Error as IoError, Write}, path::Path, str::FromStr};
 
 use super::Position;
 
 use crate::{
     abs::{AbstractBuilder, Ranked, SubelementList, Subelements},
-    conc::{cycle::CycleList, Concrete},
-    geometry::Point,
+    conc::{cycle::CycleList, Concrete, ConcretePolytope, Rgba},
+    float::Real,
+    geometry::{Hypersphere, Point},
     Polytope, COMPONENTS, ELEMENT_NAMES,
 };
 
+use flate2::{write::GzEncoder, Compression};
 use vec_like::VecLike;
 
+/// The extension of gzipped OFF files, which usually end in `.off.gz`.
+pub const GZ_EXT: &str = "gz";
+
 /// The header for OFF files created with Miratope.
 const HEADER: &str = concat!(
     "Generated using Miratope v",
@@ -131,8 +136,11 @@ struct TokenIter<'a> {
 
 impl<'a> TokenIter<'a> {
     /// Returns an iterator over the OFF file, with all whitespace and comments
-    /// removed.
+    /// removed. A leading byte order mark, as written by some Windows editors,
+    /// is skipped.
     fn new(src: &'a str) -> Self {
+        let src = src.strip_prefix('\u{feff}').unwrap_or(src);
+
         Self {
             src,
             iter: src.char_indices(),
@@ -147,6 +155,7 @@ impl<'a> TokenIter<'a> {
         let (mut idx, mut c) = self.iter.next()?;
         let init_idx = idx;
         let mut end_idx = init_idx;
+        let mut read_any = false;
 
         loop {
             match c {
@@ -172,27 +181,45 @@ impl<'a> TokenIter<'a> {
                 break;
             }
 
-            // Advances the iterator.
-            end_idx = idx;
+            // Advances the iterator. We store the end of the character, so
+            // that tokens ending in a multi-byte character are sliced right.
+            end_idx = idx + c.len_utf8();
+            read_any = true;
             if let Some((new_idx, new_c)) = self.iter.next() {
                 idx = new_idx;
                 c = new_c;
             } else {
-                // We do this so that it seems like we read something at the end.
-                idx += 1;
                 break;
             }
         }
 
         // If we immediately broke out of the loop, this means we just read a
         // single character in a comment or a whitespace. That is, garbage.
-        Some(if init_idx == idx {
-            OffNext::Garbage
+        Some(if read_any {
+            OffNext::Token(&self.src[init_idx..end_idx])
         } else {
-            OffNext::Token(&self.src[init_idx..=end_idx])
+            OffNext::Garbage
         })
     }
 
+    /// Reads all of the remaining tokens in the current line of the OFF file.
+    fn rest_of_line(&mut self) -> Vec<Token<'a>> {
+        let mut tokens = Vec::new();
+
+        // We've just read an end line, so there's nothing else in this line.
+        while self.position.column != 0 {
+            let pos = self.position;
+
+            match self.try_next() {
+                Some(OffNext::Token(slice)) => tokens.push(Token { slice, pos }),
+                Some(OffNext::Garbage) => {}
+                None => break,
+            }
+        }
+
+        tokens
+    }
+
     /// Reads and parses the next token from the OFF file.
     pub fn parse_next<U: FromStr>(&mut self) -> OffParseResult<U> {
         self.next()
@@ -214,6 +241,29 @@ impl<'a> Iterator for TokenIter<'a> {
     }
 }
 
+/// Parses a color from the tokens after an element in an OFF file. Colors
+/// are given either as three or four integers from 0 to 255, or as three or
+/// four floats from 0 to 1. Anything else, like colormap indices or tokens
+/// that aren't numbers at all, gets ignored.
+fn parse_color(tokens: &[Token<'_>]) -> Option<Rgba> {
+    if tokens.len() != 3 && tokens.len() != 4 {
+        return None;
+    }
+
+    let is_float = tokens.iter().any(|t| t.slice.contains('.'));
+    let mut color = [1.0; 4];
+
+    for (c, token) in color.iter_mut().zip(tokens) {
+        *c = if is_float {
+            token.slice.parse().ok()?
+        } else {
+            token.slice.parse::<u8>().ok()? as f32 / 255.0
+        };
+    }
+
+    Some(color)
+}
+
 /// An auxiliary struct that reads through an OFF file and builds a concrete
 /// polytope out of it.
 pub struct OffReader<'a> {
@@ -244,8 +294,16 @@ impl<'a> OffReader<'a> {
     }
 
     /// Reads the first token from the OFF file, returns the polytope's rank.
+    /// Besides the `4OFF` style headers, this also supports the `nOFF` header,
+    /// in which the dimension is given by the next token.
     fn rank(&mut self) -> OffParseResult<usize> {
-        self.next().ok_or(OffParseError::Empty)?.rank()
+        let token = self.next().ok_or(OffParseError::Empty)?;
+
+        if token.slice == "nOFF" {
+            Ok(self.iter.parse_next::<usize>()? + 1)
+        } else {
+            token.rank()
+        }
     }
 
     /// Gets the number of elements from the OFF file from rank 1 up to rank
@@ -278,7 +336,7 @@ impl<'a> OffReader<'a> {
         &mut self,
         count: usize,
         dim: usize,
-    ) -> OffParseResult<Vec<Point<f64>>> {
+    ) -> OffParseResult<Vec<Point<Real>>> {
         // Reads all vertices.
         let mut vertices = Vec::with_capacity(count);
 
@@ -299,14 +357,18 @@ impl<'a> OffReader<'a> {
     /// Reads the faces from the OFF file and gets the edges and faces from
     /// them. Since the OFF file doesn't store edges explicitly, this is harder
     /// than reading general elements.
+    ///
+    /// Also returns the face colors, if any face had one.
     fn parse_edges_and_faces(
         &mut self,
         rank: usize,
         num_edges: usize,
         num_faces: usize,
-    ) -> OffParseResult<(SubelementList, SubelementList)> {
+    ) -> OffParseResult<(SubelementList, SubelementList, Option<Vec<Rgba>>)> {
         let mut edges = SubelementList::with_capacity(num_edges);
         let mut faces = SubelementList::with_capacity(num_faces);
+        let mut colors = Vec::with_capacity(num_faces);
+        let mut colored = false;
         let mut hash_edges = HashMap::new();
 
         // Add each face to the element list.
@@ -349,10 +411,10 @@ impl<'a> OffReader<'a> {
                 faces.push(face);
             }
 
-            // Goes to the end of the line in order to ignore things like colour info.
-            if self.iter.position.column != 0 {
-                self.iter.comment = true;
-            }
+            // Reads the color info at the end of the line, if any.
+            let color = parse_color(&self.iter.rest_of_line());
+            colored |= color.is_some();
+            colors.push(color.unwrap_or([1.0; 4]));
         }
 
         // If this is a polygon, we add a single maximal element as a face.
@@ -366,7 +428,14 @@ impl<'a> OffReader<'a> {
             println!("WARNING: Edge count doesn't match expected edge count!");
         }
 
-        Ok((edges, faces))
+        // Components don't get colors.
+        let colors = if colored && rank != 3 {
+            Some(colors)
+        } else {
+            None
+        };
+
+        Ok((edges, faces, colors))
     }
 
     /// Parses the next set of d-elements from the OFF file.
@@ -427,10 +496,13 @@ impl<'a> OffReader<'a> {
         self.abs.push_vertices(vertices.len());
 
         // Reads edges and faces.
+        let mut face_colors = None;
         if rank >= 3 {
-            let (edges, faces) = self.parse_edges_and_faces(rank, num_elems[1], num_elems[2])?;
+            let (edges, faces, colors) =
+                self.parse_edges_and_faces(rank, num_elems[1], num_elems[2])?;
             self.abs.push(edges);
             self.abs.push(faces);
+            face_colors = colors;
         }
 
         // Adds all higher elements.
@@ -447,7 +519,9 @@ impl<'a> OffReader<'a> {
         // Builds the concrete polytope.
 
         // Safety: TODO this isn't actually safe. We need to do some checking.
-        Ok(Concrete::new(vertices, unsafe { self.abs.build() }))
+        let mut poly = Concrete::new(vertices, unsafe { self.abs.build() });
+        poly.face_colors = face_colors;
+        Ok(poly)
     }
 }
 
@@ -484,11 +558,30 @@ impl Concrete {
 pub struct OffOptions {
     /// Whether the OFF file should have comments specifying each face type.
     pub comments: bool,
+
+    /// Whether the OFF file should store the face colors of the polytope,
+    /// whenever it has them.
+    pub colors: bool,
+
+    /// Whether colors are written as three floats between 0 and 1, rather
+    /// than as four integers between 0 and 255.
+    pub float_colors: bool,
+
+    /// Whether the comments of the OFF file should include the radii and
+    /// centers of the hyperspheres around and within the polytope, so that
+    /// other programs can tell its scale. These can be slow to calculate, so
+    /// they're off by default.
+    pub metrics: bool,
 }
 
 impl Default for OffOptions {
     fn default() -> Self {
-        OffOptions { comments: true }
+        OffOptions {
+            comments: true,
+            colors: true,
+            float_colors: false,
+            metrics: false,
+        }
     }
 }
 
@@ -511,6 +604,16 @@ pub enum OffWriteError {
         /// The index of the second edge.
         idx1: usize,
     },
+
+    /// The polytope's vertices have more coordinates than the OFF header
+    /// allows for its rank.
+    Dimension {
+        /// The dimension of the polytope's vertices.
+        dim: usize,
+
+        /// The rank of the polytope.
+        rank: usize,
+    },
 }
 
 impl Display for OffWriteError {
@@ -524,6 +627,11 @@ impl Display for OffWriteError {
                 "cannot write coincident edges with indices {} and {}",
                 idx0, idx1
             ),
+            Self::Dimension { dim, rank } => write!(
+                f,
+                "cannot write {}-dimensional vertices of a polytope of rank {}",
+                dim, rank
+            ),
         }
     }
 }
@@ -571,6 +679,29 @@ impl<'a> OffWriter<'a> {
         self.options.comments
     }
 
+    /// Returns the face colors to write into the OFF file, if any.
+    fn face_colors(&self) -> Option<&'a [Rgba]> {
+        if self.options.colors {
+            self.poly.face_colors()
+        } else {
+            None
+        }
+    }
+
+    /// Writes a color at the end of an element's line.
+    fn write_color(&mut self, color: Rgba) {
+        if self.options.float_colors {
+            for c in &color[..3] {
+                self.push_str(format!(" {:.3}", c.clamp(0.0, 1.0)));
+            }
+        } else {
+            for c in color {
+                self.push(' ');
+                self.push_to_str((c.clamp(0.0, 1.0) * 255.0).round() as u8);
+            }
+        }
+    }
+
     /// Appends a given character to the OFF file.
     fn push(&mut self, ch: char) {
         self.off.push(ch)
@@ -586,11 +717,43 @@ impl<'a> OffWriter<'a> {
         self.push_str(data.to_string())
     }
 
+    /// The number of coordinates of each vertex in the OFF file. This is
+    /// also the number in the `nOFF` header.
+    fn dim(&self) -> isize {
+        self.rank() as isize - 1
+    }
+
+    /// Writes the radius and center of a hypersphere as comments, if it
+    /// exists.
+    fn write_sphere(&mut self, name: &str, sphere: Option<&Hypersphere<Real>>) {
+        if let Some(sphere) = sphere {
+            self.push_str(format!("# {} radius: {}\n# {} center:", name, sphere.radius(), name));
+
+            let dim = self.dim() as usize;
+            for c in sphere.center.iter().chain(std::iter::repeat(&0.0)).take(dim) {
+                self.push(' ');
+                self.push_to_str(c);
+            }
+            self.push('\n');
+        }
+    }
+
+    /// Writes the hyperspheres around and within the polytope as comments,
+    /// whenever they've been calculated.
+    fn write_metrics(&mut self) {
+        let poly = self.poly;
+        if let Some(metrics) = poly.metrics() {
+            self.write_sphere("Circumsphere", metrics.circumsphere.as_ref());
+            self.write_sphere("Bounding sphere", metrics.bounding_sphere.as_ref());
+            self.write_sphere("Insphere", metrics.insphere.as_ref());
+        }
+    }
+
     /// Writes the OFF format header.
     fn write_rank(&mut self) {
-        let rank = self.rank();
-        if rank != 4 {
-            self.push_to_str(rank as isize - 1);
+        let dim = self.dim();
+        if dim != 3 {
+            self.push_to_str(dim);
         }
         self.push_str("OFF\n");
     }
@@ -688,9 +851,11 @@ impl<'a> OffWriter<'a> {
             self.push('\n');
         }
 
-        // Adds the coordinates.
+        // Adds the coordinates, padding them with zeros if the polytope lives
+        // in a lower dimension than its rank indicates.
+        let dim = self.dim() as usize;
         for v in &self.poly.vertices {
-            for c in v {
+            for c in v.iter().chain(std::iter::repeat(&0.0)).take(dim) {
                 self.push_to_str(c);
                 self.push(' ');
             }
@@ -724,10 +889,21 @@ impl<'a> OffWriter<'a> {
                 self.push('\n');
             }
         } else {
-            for (idx, face) in self.poly[3].iter().enumerate() {
-                self.push_to_str(face.subs.len());
-                let mut cycles =
-                    CycleList::from_edges(face.subs.iter().map(|&i| &self.poly[(2, i)].subs));
+            let colors = self.face_colors();
+            let poly = self.poly;
+
+            // Orients the faces consistently whenever possible.
+            let faces = poly.oriented_face_cycles().unwrap_or_else(|| {
+                poly[3]
+                    .iter()
+                    .map(|face| {
+                        CycleList::from_edges(face.subs.iter().map(|&i| &poly[(2, i)].subs))
+                    })
+                    .collect()
+            });
+
+            for (idx, mut cycles) in faces.into_iter().enumerate() {
+                self.push_to_str(poly[(3, idx)].subs.len());
 
                 if cycles.len() > 1 {
                     return Err(OffWriteError::CompoundFace { idx });
@@ -737,6 +913,10 @@ impl<'a> OffWriter<'a> {
                     self.push(' ');
                     self.push_to_str(v);
                 }
+
+                if let Some(colors) = colors {
+                    self.write_color(colors[idx]);
+                }
                 self.push('\n');
             }
         }
@@ -782,6 +962,16 @@ impl<'a> OffWriter<'a> {
             self.push_str("# ");
             self.push_str(HEADER);
             self.push('\n');
+
+            if self.options.metrics {
+                self.write_metrics();
+            }
+        }
+
+        // The OFF format can't store more coordinates than the header says.
+        let dim = self.poly.dim_or();
+        if dim as isize > self.dim() {
+            return Err(OffWriteError::Dimension { dim, rank });
         }
 
         // Writes header.
@@ -856,12 +1046,32 @@ impl Concrete {
         fixed.untangle_faces();
         fixed.element_sort();
 
+        // Polytopes in a higher dimension than their rank are projected into
+        // the subspace they span.
+        if fixed.dim_or() + 1 > fixed.rank() {
+            fixed.flatten();
+        }
+
+        if options.comments && options.metrics {
+            fixed.metrics_mut();
+        }
+
         OffWriter::new(&fixed, options).build()
     }
 
-    /// Writes a polytope's OFF file in a specified file path.
+    /// Writes a polytope's OFF file in a specified file path. The file is
+    /// gzipped if the path ends in `.gz`.
     pub fn to_path<P: AsRef<Path>>(&self, fp: P, opt: OffOptions) -> OffSaveResult<()> {
-        std::fs::write(fp, self.to_off(opt)?)?;
+        let off = self.to_off(opt)?;
+
+        if fp.as_ref().extension().and_then(|ext| ext.to_str()) == Some(GZ_EXT) {
+            let mut encoder = GzEncoder::new(File::create(fp)?, Compression::default());
+            encoder.write_all(off.as_bytes())?;
+            encoder.finish()?;
+        } else {
+            std::fs::write(fp, off)?;
+        }
+
         Ok(())
     }
 }
@@ -870,6 +1080,7 @@ impl Concrete {
 mod tests {
     use super::*;
     use crate::file::FromFile;
+    use crate::float::Float;
     use crate::test;
 
     /// Tests a particular OFF file.
@@ -935,12 +1146,137 @@ mod tests {
         test_off!("pen", [1, 5, 10, 10, 5, 1])
     }
 
+    /// Checks that a tesseract has the correct amount of elements.
+    #[test]
+    fn tes_nums() {
+        test_off!("tes", [1, 16, 32, 24, 8, 1])
+    }
+
+    /// Checks that the `nOFF` header is correctly parsed.
+    #[test]
+    fn noff() {
+        let src = include_str!("tes.off").replacen("4OFF", "nOFF\n4", 1);
+        test_off_file(&src, [1, 16, 32, 24, 8, 1])
+    }
+
+    /// Checks that a tesseract is exported with the correct header and the
+    /// correct number of coordinates.
+    #[test]
+    fn tes_export() {
+        let tes = Concrete::from_off(include_str!("tes.off")).unwrap();
+        let off = tes.to_off(OffOptions {
+            comments: false,
+            ..Default::default()
+        });
+        let off = off.unwrap();
+
+        let mut lines = off.lines().filter(|line| !line.is_empty());
+        assert_eq!(lines.next(), Some("4OFF"));
+        assert_eq!(lines.next(), Some("16 24 32 8"));
+        for _ in 0..16 {
+            assert_eq!(lines.next().unwrap().split_whitespace().count(), 4);
+        }
+    }
+
+    /// Checks that a square in 4D is exported as a 2D polygon.
+    #[test]
+    fn flatten_export() {
+        let square = Concrete::from_off(include_str!("tes.off"))
+            .unwrap()
+            .facet(0)
+            .unwrap()
+            .facet(0)
+            .unwrap();
+
+        let off = square.to_off(Default::default()).unwrap();
+        test(&Concrete::from_off(&off).unwrap(), [1, 4, 4, 1]);
+    }
+
+    /// Checks that the hyperspheres of a tesseract are written as comments.
+    #[test]
+    fn metrics_export() {
+        let tes = Concrete::from_off(include_str!("tes.off")).unwrap();
+        assert!(!tes.to_off(Default::default()).unwrap().contains("radius"));
+
+        let off = tes
+            .to_off(OffOptions {
+                metrics: true,
+                ..Default::default()
+            })
+            .unwrap();
+
+        let radius = |name: &str| -> Real {
+            let prefix = format!("# {} radius: ", name);
+            off.lines()
+                .find_map(|line| line.strip_prefix(&prefix))
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        assert!((radius("Circumsphere") - 2.0).abs() < Real::EPS);
+        assert!((radius("Bounding sphere") - 2.0).abs() < Real::EPS);
+        assert!((radius("Insphere") - 1.0).abs() < Real::EPS);
+    }
+
     /// Checks that comments are correctly parsed.
     #[test]
     fn comments() {
         test_off!("comments", [1, 4, 6, 4, 1])
     }
 
+    /// Checks that blank lines, comments and extra whitespace are skipped
+    /// anywhere in the file.
+    #[test]
+    fn messy() {
+        test_off!("messy", [1, 4, 6, 4, 1])
+    }
+
+    /// Checks that Windows line endings and a byte order mark are tolerated.
+    #[test]
+    fn windows() {
+        let src = format!("\u{feff}{}", include_str!("messy.off").replace('\n', "\r\n"));
+        test_off_file(&src, [1, 4, 6, 4, 1])
+    }
+
+    /// Checks that a gzipped OFF file can be written and read back.
+    #[test]
+    fn gzip() {
+        let path = std::env::temp_dir().join("miratope_gzip_test.off.gz");
+        let tes = Concrete::from_off(include_str!("tes.off")).unwrap();
+        tes.to_path(&path, Default::default()).unwrap();
+
+        let reloaded = Concrete::from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+        test(&reloaded.unwrap(), [1, 16, 32, 24, 8, 1]);
+        assert_eq!(crate::file::file_stem(&path), Some("miratope_gzip_test"));
+    }
+
+    /// Checks that face colors are read and written back.
+    #[test]
+    fn colors() {
+        let src = "OFF\n4 4 6\n1 1 1\n1 -1 -1\n-1 1 -1\n-1 -1 1\n\
+            3 0 1 2 255 0 0\n3 3 1 0 0 255 0 255\n3 2 3 0 0 0 1.0\n3 1 3 2 # no color";
+        let poly = Concrete::from_off(src).unwrap();
+
+        let colors = poly.face_colors().expect("face colors could not be read");
+        assert_eq!(colors[0], [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(colors[1], [0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(colors[2], [0.0, 0.0, 1.0, 1.0]);
+        assert_eq!(colors[3], [1.0; 4]);
+
+        let reloaded = Concrete::from_off(&poly.to_off(Default::default()).unwrap()).unwrap();
+        assert_eq!(reloaded.face_colors(), Some(colors));
+    }
+
+    /// Checks that trailing tokens that aren't a color are ignored.
+    #[test]
+    fn invalid_colors() {
+        let src = "OFF\n4 4 6\n1 1 1\n1 -1 -1\n-1 1 -1\n-1 -1 1\n\
+            3 0 1 2 foo bar baz\n3 3 1 0 256 0 0\n3 2 3 0 1.0 x 0\n3 1 3 2";
+        let poly = Concrete::from_off(src).unwrap();
+        assert_eq!(poly.face_colors(), None);
+    }
+
     /// Attempts to parse an OFF file, unwraps it.
     fn unwrap_off(src: &str) {
         Concrete::from_off(src).unwrap();
@@ -973,4 +1309,11 @@ mod tests {
     fn parse() {
         unwrap_off("OFF\n10 foo bar")
     }
+
+    /// A token with non-ASCII characters should fail, but not panic.
+    #[test]
+    #[should_panic(expected = "Parsing(Position { row: 2, column: 4 })")]
+    fn non_ascii() {
+        unwrap_off("OFF\n4 4 6\n1 1 1π")
+    }
 }
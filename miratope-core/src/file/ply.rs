@@ -0,0 +1,152 @@
+//! Writing polytopes as ASCII PLY files, which keep the colors of the faces and
+//! can be opened by most mesh viewers, such as [MeshLab](https://www.meshlab.net/).
+
+use std::fmt::Write;
+
+use crate::{
+    abs::Ranked,
+    conc::{cycle::CycleList, Concrete, ConcretePolytope, Rgba},
+    float::Real,
+    geometry::Point,
+};
+
+use vec_like::VecLike;
+
+/// The extension of PLY files.
+pub const PLY_EXT: &str = "ply";
+
+/// Converts a color with components between 0 and 1 into the bytes written
+/// into a PLY file.
+fn color_bytes(color: Rgba) -> [u8; 4] {
+    color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+impl Concrete {
+    /// Writes the polytope as an ASCII PLY file, with a face for every polygon
+    /// and the colors of the faces, if any. Polytopes in more than 3D are
+    /// projected orthogonally onto their first three coordinates.
+    pub fn to_ply(&self) -> Vec<u8> {
+        self.to_ply_with(&self.vertices)
+    }
+
+    /// Writes the polytope as an ASCII PLY file, placing its vertices at the
+    /// given points instead. This is useful to export some projection of the
+    /// polytope into 3D. Only the first three coordinates of each point are
+    /// kept.
+    pub fn to_ply_with(&self, vertices: &[Point<Real>]) -> Vec<u8> {
+        // The faces, as cycles of vertices, together with their indices. The
+        // faces of polyhedra are oriented consistently whenever possible.
+        let cycles: Vec<(usize, CycleList)> = match self.oriented_face_cycles() {
+            Some(faces) => faces.into_iter().enumerate().collect(),
+            None => match self.rank() {
+                3 => vec![(0, CycleList::from_edges(self[2].iter().map(|edge| &edge.subs)))],
+                rank if rank > 3 => self[3]
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, face)| {
+                        let edges = face.subs.iter().map(|&i| &self[(2, i)].subs);
+                        (idx, CycleList::from_edges(edges))
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            },
+        };
+        let faces: Vec<_> = cycles
+            .iter()
+            .flat_map(|(idx, cycles)| cycles.iter().map(move |cycle| (*idx, cycle)))
+            .collect();
+        let colors = self.face_colors();
+
+        let mut ply = String::new();
+
+        // Writing to a string never fails.
+        writeln!(ply, "ply").unwrap();
+        writeln!(ply, "format ascii 1.0").unwrap();
+        writeln!(ply, "comment Generated by Miratope").unwrap();
+        if self.dim_or() > 3 {
+            writeln!(
+                ply,
+                "comment Projected from {}D into 3D, so some information is lost.",
+                self.dim_or()
+            )
+            .unwrap();
+        }
+        writeln!(ply, "element vertex {}", vertices.len()).unwrap();
+        for coord in ["x", "y", "z"] {
+            writeln!(ply, "property float {}", coord).unwrap();
+        }
+        writeln!(ply, "element face {}", faces.len()).unwrap();
+        writeln!(ply, "property list uchar int vertex_indices").unwrap();
+        if colors.is_some() {
+            for channel in ["red", "green", "blue", "alpha"] {
+                writeln!(ply, "property uchar {}", channel).unwrap();
+            }
+        }
+        writeln!(ply, "end_header").unwrap();
+
+        // Writes the vertices.
+        for v in vertices {
+            let coord = |i: usize| v.get(i).copied().unwrap_or_default();
+            writeln!(ply, "{} {} {}", coord(0), coord(1), coord(2)).unwrap();
+        }
+
+        // Writes the faces.
+        for (idx, cycle) in faces {
+            write!(ply, "{}", cycle.len()).unwrap();
+            for v in cycle.iter() {
+                write!(ply, " {}", v).unwrap();
+            }
+
+            if let Some(colors) = colors {
+                for c in color_bytes(colors[idx]) {
+                    write!(ply, " {}", c).unwrap();
+                }
+            }
+            writeln!(ply).unwrap();
+        }
+
+        ply.into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    /// Reads the header and the data lines from a PLY file.
+    fn parse(ply: Vec<u8>) -> (Vec<String>, Vec<Vec<String>>) {
+        let ply = String::from_utf8(ply).unwrap();
+        let (header, body) = ply.split_once("end_header\n").unwrap();
+
+        (
+            header.lines().map(String::from).collect(),
+            body.lines()
+                .map(|line| line.split_whitespace().map(String::from).collect())
+                .collect(),
+        )
+    }
+
+    /// Writes a cube, a colored cube, and a tesseract.
+    #[test]
+    fn to_ply() {
+        let (header, body) = parse(Concrete::cube().to_ply());
+        assert_eq!(header[0], "ply");
+        assert!(header.contains(&"element vertex 8".to_string()));
+        assert!(header.contains(&"element face 6".to_string()));
+        assert!(!header.iter().any(|line| line.contains("red")));
+        assert_eq!(body.len(), 8 + 6);
+        assert!(body[8..].iter().all(|face| face.len() == 5 && face[0] == "4"));
+
+        let mut cube = Concrete::cube();
+        cube.face_colors = Some(vec![[1.0, 0.0, 0.0, 1.0]; 6]);
+        let (header, body) = parse(cube.to_ply());
+        assert!(header.contains(&"property uchar red".to_string()));
+        assert_eq!(body[8][5..], ["255", "0", "0", "255"]);
+
+        let (header, body) = parse(Concrete::hypercube(5).to_ply());
+        assert!(header.contains(&"element face 24".to_string()));
+        assert!(header.iter().any(|line| line.starts_with("comment Projected")));
+        assert_eq!(body.len(), 16 + 24);
+    }
+}
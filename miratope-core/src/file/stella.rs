@@ -0,0 +1,99 @@
+//! Writing polytopes as OFF files in the dialect that
+//! [Stella](https://www.software3d.com/Stella.php) imports. Stella reads
+//! polyhedra from OFF files, and Stella4D also reads polychora from 4OFF
+//! files. These differ from the OFF files Miratope usually writes in that:
+//!
+//! - only polyhedra and polychora can be written,
+//! - there are no comments,
+//! - face colors are written as three floats between 0 and 1, without an
+//!   alpha component.
+//!
+//! As in any other OFF file, the faces of polyhedra are oriented consistently
+//! whenever possible, so that Stella can tell their inside from their outside.
+
+use std::fmt::Display;
+
+use super::off::{OffOptions, OffWriteError};
+use crate::{abs::Ranked, conc::Concrete};
+
+/// An error while writing a Stella-compatible OFF file.
+#[derive(Clone, Copy, Debug)]
+pub enum StellaError {
+    /// Stella can only open polyhedra and polychora.
+    Rank(usize),
+
+    /// The OFF file couldn't be written.
+    OffWriteError(OffWriteError),
+}
+
+impl From<OffWriteError> for StellaError {
+    fn from(err: OffWriteError) -> Self {
+        Self::OffWriteError(err)
+    }
+}
+
+impl Display for StellaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rank(rank) => write!(
+                f,
+                "Stella can only open polyhedra and polychora, not polytopes of rank {}",
+                *rank as isize - 1
+            ),
+            Self::OffWriteError(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for StellaError {}
+
+/// The result of trying to write a Stella-compatible OFF file.
+pub type StellaResult<T> = Result<T, StellaError>;
+
+impl Concrete {
+    /// Writes a polyhedron or a polychoron as an OFF file that Stella can
+    /// import.
+    pub fn to_stella_off(&self) -> StellaResult<String> {
+        let rank = self.rank();
+        if rank != 4 && rank != 5 {
+            return Err(StellaError::Rank(rank));
+        }
+
+        Ok(self.to_off(OffOptions {
+            comments: false,
+            colors: true,
+            float_colors: true,
+            metrics: false,
+        })?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{conc::Concrete, file::FromFile, Polytope};
+
+    /// Writes a colored cube, and reads it back.
+    #[test]
+    fn cube() {
+        let mut cube = Concrete::hypercube(4);
+        cube.face_colors = Some(vec![[1.0, 0.5, 0.0, 1.0]; 6]);
+        let off = cube.to_stella_off().unwrap();
+
+        let mut lines = off.lines().filter(|line| !line.is_empty());
+        assert_eq!(lines.next(), Some("OFF"));
+        assert!(!off.contains('#'));
+        assert!(off.lines().any(|line| line.ends_with(" 1.000 0.500 0.000")));
+
+        let read = Concrete::from_off(&off).unwrap();
+        crate::test(&read, [1, 8, 12, 6, 1]);
+        assert_eq!(read.face_colors().unwrap()[0], [1.0, 0.5, 0.0, 1.0]);
+    }
+
+    /// Stella can't open polygons or polytera.
+    #[test]
+    fn rank() {
+        assert!(Concrete::polygon(5).to_stella_off().is_err());
+        assert!(Concrete::hypercube(6).to_stella_off().is_err());
+        assert!(Concrete::hypercube(5).to_stella_off().unwrap().starts_with("4OFF"));
+    }
+}
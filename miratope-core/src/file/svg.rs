@@ -0,0 +1,134 @@
+//! Writing orthogonal projections of polytopes onto a plane as SVG images.
+
+use std::fmt::Write;
+
+use crate::{
+    abs::Ranked,
+    conc::{Concrete, ConcretePolytope},
+    float::{Float, Real},
+    geometry::Vector,
+};
+
+use vec_like::VecLike;
+
+/// The extension of SVG files.
+pub const SVG_EXT: &str = "svg";
+
+/// The width and height of the image.
+const SIZE: Real = 500.0;
+
+/// The space between the picture and the border of the image.
+const MARGIN: Real = 20.0;
+
+/// The radius of the dots drawn at the vertices.
+const VERTEX_RADIUS: Real = 2.5;
+
+/// The width of the lines drawn at the edges.
+const EDGE_WIDTH: Real = 1.0;
+
+/// Projects a point onto a plane spanned by two orthonormal vectors. Any
+/// missing coordinates are treated as zero.
+fn project(plane: &[Vector<Real>; 2], v: &Vector<Real>) -> (Real, Real) {
+    let dot = |u: &Vector<Real>| u.iter().zip(v.iter()).map(|(a, b)| a * b).sum();
+    (dot(&plane[0]), dot(&plane[1]))
+}
+
+impl Concrete {
+    /// Projects the polytope orthogonally onto a plane spanned by two
+    /// orthonormal vectors, and draws its vertices and edges as an SVG image.
+    /// The first vector points right, and the second one points up.
+    pub fn to_svg(&self, plane: &[Vector<Real>; 2]) -> String {
+        let points: Vec<_> = self.vertices.iter().map(|v| project(plane, v)).collect();
+
+        // Fits the projection into the image, keeping it centered.
+        let radius = points
+            .iter()
+            .map(|&(x, y)| x.abs().max(y.abs()))
+            .fold(0.0, Real::max);
+        let scale = if radius > Real::EPS {
+            (SIZE / 2.0 - MARGIN) / radius
+        } else {
+            1.0
+        };
+        let to_image = |(x, y): (Real, Real)| (SIZE / 2.0 + x * scale, SIZE / 2.0 - y * scale);
+
+        let mut svg = String::new();
+
+        // Writing to a string never fails.
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{0}" viewBox="0 0 {0} {0}">"#,
+            SIZE
+        )
+        .unwrap();
+
+        // Draws the edges.
+        if self.rank() >= 2 {
+            writeln!(
+                svg,
+                r#"<g stroke="black" stroke-width="{}" stroke-linecap="round">"#,
+                EDGE_WIDTH
+            )
+            .unwrap();
+
+            for edge in self[2].iter().filter(|edge| edge.subs.len() == 2) {
+                let (x1, y1) = to_image(points[edge.subs[0]]);
+                let (x2, y2) = to_image(points[edge.subs[1]]);
+                writeln!(
+                    svg,
+                    r#"<line x1="{:.3}" y1="{:.3}" x2="{:.3}" y2="{:.3}"/>"#,
+                    x1, y1, x2, y2
+                )
+                .unwrap();
+            }
+
+            writeln!(svg, "</g>").unwrap();
+        }
+
+        // Draws the vertices.
+        writeln!(svg, r#"<g fill="black">"#).unwrap();
+        for &point in &points {
+            let (x, y) = to_image(point);
+            writeln!(
+                svg,
+                r#"<circle cx="{:.3}" cy="{:.3}" r="{}"/>"#,
+                x, y, VERTEX_RADIUS
+            )
+            .unwrap();
+        }
+        writeln!(svg, "</g>").unwrap();
+
+        writeln!(svg, "</svg>").unwrap();
+        svg
+    }
+
+    /// Draws the projection of the polytope onto the plane of its first two
+    /// coordinates as an SVG image. See [`Self::to_svg`].
+    pub fn to_svg_xy(&self) -> String {
+        let dim = self.dim_or().max(2);
+        let mut x = Vector::zeros(dim);
+        let mut y = Vector::zeros(dim);
+        x[0] = 1.0;
+        y[1] = 1.0;
+
+        self.to_svg(&[x, y])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Polytope;
+
+    /// Draws a square and a cube.
+    #[test]
+    fn svg() {
+        let square = Concrete::polygon(4).to_svg_xy();
+        assert_eq!(square.matches("<line").count(), 4);
+        assert_eq!(square.matches("<circle").count(), 4);
+
+        let cube = Concrete::cube().to_svg_xy();
+        assert_eq!(cube.matches("<line").count(), 12);
+        assert_eq!(cube.matches("<circle").count(), 8);
+    }
+}
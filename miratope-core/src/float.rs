@@ -1,5 +1,16 @@
 //! Defines a [`Float`] trait, which allows Miratope to be generic over `f32` or
 //! `f64`.
+//!
+//! The [`Real`] alias picks the type used by concrete polytopes. It's `f64` by
+//! default, and `f32` when the `f32` feature is enabled.
+
+/// The floating point type used for the coordinates of concrete polytopes.
+#[cfg(not(feature = "f32"))]
+pub type Real = f64;
+
+/// The floating point type used for the coordinates of concrete polytopes.
+#[cfg(feature = "f32")]
+pub type Real = f32;
 
 /// A trait containing the constants associated to each floating point type.
 ///
@@ -151,3 +162,35 @@ impl Float for f64 {
         u as Self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Float, Real};
+    use approx::abs_diff_eq;
+
+    /// Checks that the hardcoded constants agree with the computed ones.
+    #[test]
+    fn constants() {
+        assert!(abs_diff_eq!(Real::SQRT_2, Real::TWO.fsqrt(), epsilon = Real::EPS));
+        assert!(abs_diff_eq!(Real::SQRT_3, Real::THREE.fsqrt(), epsilon = Real::EPS));
+        assert!(abs_diff_eq!(Real::SQRT_5, Real::FIVE.fsqrt(), epsilon = Real::EPS));
+        assert!(abs_diff_eq!(Real::HALF_SQRT_2, Real::SQRT_2 / Real::TWO, epsilon = Real::EPS));
+        assert!(abs_diff_eq!(Real::TAU, Real::TWO * Real::PI, epsilon = Real::EPS));
+    }
+
+    /// Checks that the epsilon is comfortably above the machine epsilon, so
+    /// that rounding errors don't trip up tolerance checks.
+    #[test]
+    fn eps() {
+        assert!(Real::EPS > <Real as ordered_float::Float>::epsilon() * Real::f64(100.0));
+        assert!(Real::EPS < Real::f64(1e-3));
+    }
+
+    /// Checks the conversions from other numeric types.
+    #[test]
+    fn conversions() {
+        assert_eq!(Real::usize(3), Real::THREE);
+        assert_eq!(Real::u32(5), Real::FIVE);
+        assert_eq!(Real::f64(0.5) * Real::TWO, Real::ONE);
+    }
+}
@@ -18,7 +18,7 @@ use std::{
 };
 
 use crate::{
-    float::Float,
+    float::{Float, Real},
     ElementMap, conc::Concrete, abs::Ranked, Polytope,
 };
 
@@ -32,6 +32,7 @@ use vec_like::VecLike;
 /// where the hypersphere is used to reciprocate polytopes. For convenience, we
 /// allow the hypersphere to have a negative squared radius, which results in
 /// the dualized polytope being reflected about its center.
+#[derive(Clone, Debug)]
 pub struct Hypersphere<T: Float> {
     /// The center of the hypersphere.
     pub center: Point<T>,
@@ -210,6 +211,48 @@ impl<T: Float> Subspace<T> {
         Some(subspace)
     }
 
+    /// Creates the subspace of at most a given rank that best fits a set of
+    /// points in the least squares sense. It passes through their centroid,
+    /// and its basis is made out of their principal directions.
+    ///
+    /// Unlike [`Self::from_points_with`], this also works for points that
+    /// only approximately lie on a subspace of the given rank, such as the
+    /// vertices of faces that come from slicing a polytope. Use
+    /// [`Self::distance`] to check how far off the points actually are.
+    pub fn best_fit<'a, U: Iterator<Item = &'a Point<T>>>(points: U, rank: usize) -> Self {
+        let points: Vec<_> = points.collect();
+        let mut offset = Point::zeros(
+            points
+                .first()
+                .expect("A hyperplane can't be created from an empty point array!")
+                .len(),
+        );
+        for &p in &points {
+            offset += p;
+        }
+        offset /= T::usize(points.len());
+
+        let centered: Vec<_> = points.iter().map(|&p| p - &offset).collect();
+        let svd = Matrix::from_columns(&centered).svd(true, false);
+        let (u, singular_values) = (svd.u.unwrap(), svd.singular_values);
+
+        // The principal directions, from the most to the least significant.
+        let mut directions: Vec<_> = (0..singular_values.len()).collect();
+        directions.sort_unstable_by(|&i, &j| {
+            singular_values[j].partial_cmp(&singular_values[i]).unwrap()
+        });
+
+        Self {
+            basis: directions
+                .into_iter()
+                .take(rank)
+                .filter(|&i| singular_values[i] > T::EPS)
+                .map(|i| u.column(i).into_owned())
+                .collect(),
+            offset,
+        }
+    }
+
     /// Projects a point onto the subspace.
     pub fn project(&self, p: &Point<T>) -> Point<T> {
         let p = p - &self.offset;
@@ -249,7 +292,12 @@ impl<T: Float> Subspace<T> {
 	
     /// Returns whether a point is contained on the subspace.
     pub fn is_outer(&self, p: &Point<T>) -> bool {
-        abs_diff_eq!(self.distance(p), T::ZERO, epsilon = T::EPS)
+        self.is_outer_with(p, T::EPS)
+    }
+
+    /// Returns whether a point lies within a given distance of the subspace.
+    pub fn is_outer_with(&self, p: &Point<T>, tolerance: T) -> bool {
+        abs_diff_eq!(self.distance(p), T::ZERO, epsilon = tolerance)
     }
 
     /// Computes a normal vector to the subspace, so that the specified point is
@@ -267,14 +315,14 @@ impl<T: Float> Subspace<T> {
 
 impl Concrete {
     /// Computes the affine hull of an element.
-    pub fn affine_hull(&self, rank: usize, idx: usize) -> Subspace<f64> {
+    pub fn affine_hull(&self, rank: usize, idx: usize) -> Subspace<Real> {
         Subspace::from_points(
             &mut self.element(rank, idx).unwrap().vertices.iter(),
         )
     }
 
     /// Computes the affine hulls of all elements and puts them in an `ElementMap`.
-    pub fn element_map_affine_hulls(&self) -> ElementMap<Subspace<f64>> {
+    pub fn element_map_affine_hulls(&self) -> ElementMap<Subspace<Real>> {
         let mut element_map = ElementMap::new();
         for r in 1..self.rank() {
             element_map.push(Vec::new());
@@ -518,4 +566,37 @@ mod tests {
             dvector![4.0 / 3.0, 4.0 / 3.0, 4.0 / 3.0, 4.0 / 3.0],
         );
     }
+
+    /// Fits a plane to a concave quadrilateral that's slightly bent out of its
+    /// plane. Fanning it from its first vertex would cover up its notch, so
+    /// the reflex vertex must remain reflex once flattened.
+    #[test]
+    fn best_fit() {
+        let dart: Vec<Point<f64>> = vec![
+            dvector![0.0, 0.0, 1.0, 1e-6],
+            dvector![4.0, 2.0, 1.0, -1e-6],
+            dvector![0.0, 4.0, 1.0, 1e-6],
+            dvector![1.0, 2.0, 1.0, -1e-6],
+        ];
+
+        assert!(Subspace::from_points_with(dart.iter(), 2).is_none());
+        let plane = Subspace::best_fit(dart.iter(), 2);
+        assert_eq!(plane.rank(), 2);
+        for p in &dart {
+            assert!(plane.distance(p) < 1e-5);
+        }
+
+        // The orientation of the corners of the flattened quadrilateral.
+        let flat: Vec<_> = dart.iter().map(|p| plane.flatten(p)).collect();
+        let turn = |i: usize| {
+            let (p, q, r) = (&flat[(i + 3) % 4], &flat[i], &flat[(i + 1) % 4]);
+            let (u, v) = (q - p, r - q);
+            u[0] * v[1] - u[1] * v[0]
+        };
+
+        assert!(turn(0) * turn(3) < 0.0);
+        for i in 1..3 {
+            assert!(turn(0) * turn(i) > 0.0);
+        }
+    }
 }
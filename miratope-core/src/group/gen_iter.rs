@@ -5,6 +5,7 @@ use std::collections::{BTreeMap, VecDeque};
 
 use crate::cox::cd::CdResult;
 use crate::cox::Cox;
+use crate::float::Real;
 use crate::geometry::Matrix;
 use crate::group::Group;
 
@@ -147,7 +148,7 @@ impl<T: GroupItem + Clone> GenIter<T> {
     }
 }
 
-impl GenIter<Matrix<f64>> {
+impl GenIter<Matrix<Real>> {
     /// Parses a diagram and turns it into a GenIter.
     pub fn parse(input: &str) -> CdResult<Option<Self>> {
         Cox::parse(input).map(|cox| cox.gen_iter())
@@ -16,7 +16,7 @@ use std::{
 
 use crate::{
     cox::{cd::CdResult, Cox},
-    float::Float,
+    float::{Float, Real},
     geometry::Matrix,
 };
 
@@ -147,7 +147,7 @@ impl<T: Float> Group<Cyclic<Matrix<T>>> {
     }
 }
 
-impl Group<GenIter<Matrix<f64>>> {
+impl Group<GenIter<Matrix<Real>>> {
     /// Parses a diagram and turns it into a Coxeter group.
     pub fn parse(input: &str) -> CdResult<Option<Self>> {
         GenIter::parse(input).map(|gens| gens.map(Into::into))
@@ -534,7 +534,7 @@ mod tests {
     use gcd::Gcd;
 
     /// Tests a given symmetry group.
-    fn test<I: Iterator<Item = Matrix<f64>>>(
+    fn test<I: Iterator<Item = Matrix<Real>>>(
         group: Group<I>,
         order: usize,
         rot_order: usize,
@@ -561,7 +561,7 @@ mod tests {
     }
 
     /// Parses a CD and unwraps it.
-    fn parse_unwrap(input: &str) -> Group<GenIter<Matrix<f64>>> {
+    fn parse_unwrap(input: &str) -> Group<GenIter<Matrix<Real>>> {
         Group::parse(input).unwrap().unwrap()
     }
 
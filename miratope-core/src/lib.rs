@@ -26,6 +26,8 @@
 pub mod abs;
 pub mod conc;
 pub mod cox;
+pub mod exact;
+pub mod expr;
 pub mod file;
 pub mod float;
 pub mod geometry;
@@ -373,6 +375,25 @@ pub trait Polytope:
     /// `None` if this Petrie polygon is invalid.
     fn petrie_polygon_with(&mut self, flag: Flag) -> Option<Self>;
 
+    /// Returns the indices of the vertices of the Petrie polygon through the
+    /// [first flag](Self::first_flag), in cyclic order. Sorts a copy of the
+    /// polytope if it isn't already sorted. Returns `None` if the polytope has
+    /// rank less than 3, so that it isn't at least a polygon, or if the Petrie
+    /// polygon self-intersects.
+    fn first_petrie_polygon(&self) -> Option<Vec<usize>> {
+        if self.rank() < 3 {
+            return None;
+        }
+
+        if self.abs().sorted() {
+            self.petrie_polygon_vertices(self.first_flag())
+        } else {
+            let mut abs = self.abs().clone();
+            abs.element_sort();
+            abs.petrie_polygon_vertices(abs.first_flag())
+        }
+    }
+
     /// Returns the first [`Flag`] of a polytope. This is the flag built when we
     /// start at the maximal element and repeatedly take the first subelement.
     fn first_flag(&self) -> Flag {
@@ -395,7 +416,10 @@ pub trait Polytope:
     /// when we start at the maximal element and repeatedly take the first
     /// subelement.
     fn first_oriented_flag(&self) -> OrientedFlag {
-        self.first_flag().into()
+        OrientedFlag {
+            flag: self.first_flag(),
+            orientation: self.abs().orientation(),
+        }
     }
 
     /// Returns an iterator over all [`Flag`]s of a polytope.
@@ -403,6 +427,22 @@ pub trait Polytope:
         FlagIter::new(self.abs())
     }
 
+    /// Returns the number of [`Flag`]s of a polytope. Rather than going through
+    /// every flag, this counts the chains from the minimal element up to every
+    /// other element, rank by rank.
+    fn flag_count(&self) -> usize {
+        let mut counts = vec![1];
+
+        for r in 1..=self.rank() {
+            counts = self.abs()[r]
+                .iter()
+                .map(|el| el.subs.iter().map(|&sub| counts[sub]).sum())
+                .collect();
+        }
+
+        counts[0]
+    }
+
     /// Returns an iterator over all [`OrientedFlag`]s of a polytope.
     ///
     /// # Panics
@@ -446,6 +486,20 @@ pub trait Polytope:
     /// Splits compound faces into their components.
     fn untangle_faces(&mut self);
 
+    /// Removes a facet from the polytope in place, leaving an open polytope.
+    /// Returns `false` and leaves the polytope untouched if this isn't
+    /// possible. See [`Abstract::remove_facet`] for details.
+    fn remove_facet(&mut self, idx: usize) -> bool {
+        self.abs_mut().remove_facet(idx)
+    }
+
+    /// Removes facets until no ridge is shared by more than two of them, and
+    /// returns how many were removed. See
+    /// [`Abstract::remove_non_manifold_facets`] for details.
+    fn remove_non_manifold_facets(&mut self) -> usize {
+        self.abs_mut().remove_non_manifold_facets()
+    }
+
     /// Determines whether a given polytope is
     /// [orientable](https://polytope.miraheze.org/wiki/Orientability).
     ///
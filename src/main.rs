@@ -20,10 +20,12 @@ use no_cull_pipeline::PbrNoBackfaceBundle;
 
 use ui::{
     camera::{CameraInputEvent, ProjectionType},
+    config::Config,
+    main_window::Overlay,
     MiratopePlugins,
 };
 
-use crate::mesh::Renderable;
+use crate::mesh::{HiddenFacets, PolytopeColors, Renderable};
 
 mod mesh;
 mod no_cull_pipeline;
@@ -32,9 +34,9 @@ mod ui;
 /// The link to the [Polytope Wiki](https://polytope.miraheze.org/wiki/).
 pub const WIKI_LINK: &str = "https://polytope.miraheze.org/wiki/";
 
-/// The floating-point type for the entire application. Can be either `f32` or
-/// `f64`, and it should compile the same.
-type Float = f64;
+/// The floating-point type for the entire application. This is `f64` by
+/// default, or `f32` when built with the `f32` feature.
+type Float = miratope_core::float::Real;
 
 /// A [`Concrete`](miratope_core::conc::Concrete) polytope with the floating
 /// type for the application.
@@ -42,19 +44,19 @@ type Concrete = miratope_core::conc::Concrete;
 
 /// A [`Point`](miratope_core::geometry::Point) with the floating type
 /// for the application.
-type Point = miratope_core::geometry::Point<f64>;
+type Point = miratope_core::geometry::Point<Float>;
 
 /// A [`Vector`](miratope_core::geometry::Vector) with the floating
 /// type for the application.
-type Vector = miratope_core::geometry::Vector<f64>;
+type Vector = miratope_core::geometry::Vector<Float>;
 
 /// A [`Hypersphere`](miratope_core::geometry::Hypersphere) with the
 /// floating type for the application.
-type Hypersphere = miratope_core::geometry::Hypersphere<f64>;
+type Hypersphere = miratope_core::geometry::Hypersphere<Float>;
 
 /// A [`Hyperplane`](miratope_core::geometry::Hyperplane) with the
 /// floating type for the application.
-type Hyperplane = miratope_core::geometry::Hyperplane<f64>;
+type Hyperplane = miratope_core::geometry::Hyperplane<Float>;
 
 /// The default epsilon value throughout the application.
 const EPS: Float = <Float as miratope_core::float::Float>::EPS;
@@ -67,7 +69,8 @@ fn main() {
             title: concat!("miratope v", env!("CARGO_PKG_VERSION")).to_string(),
             ..Default::default()
         })
-        .insert_resource(Msaa { samples: 4 })
+        // The sample count must be known before the render pipeline is built.
+        .insert_resource(Config::load().antialiasing.msaa())
         .add_plugins(DefaultPlugins)
         .add_plugin(EguiPlugin)
         .add_plugins(MiratopePlugins)
@@ -108,29 +111,95 @@ fn setup(
         ..Default::default()
     });
 
+    // Overlay material, which only shows the colors of the lines.
+    let overlay_material = materials.add(StandardMaterial {
+        base_color: Color::WHITE,
+        unlit: true,
+        ..Default::default()
+    });
+
+    // Ghost material, which is faint and see-through.
+    let ghost_material = materials.add(StandardMaterial {
+        base_color: Color::rgba(1., 1., 1., 0.15),
+        ..Default::default()
+    });
+
     // Camera configuration.
     let mut cam_anchor = Default::default();
     let mut cam = Default::default();
     CameraInputEvent::reset(&mut cam_anchor, &mut cam);
 
+    // The colors are kept, so that the meshes can be faded by depth later on.
+    let (mesh, mesh_colors) = poly.mesh_with_colors(ProjectionType::Perspective, &Default::default());
+    let (wireframe, wireframe_colors) = poly.wireframe_with_colors(
+        ProjectionType::Perspective,
+        &Default::default(),
+        1,
+        &Default::default(),
+        &Default::default(),
+    );
+
     commands
         .spawn()
         // Mesh
         .insert_bundle(PbrNoBackfaceBundle {
-            mesh: meshes.add(poly.mesh(ProjectionType::Perspective)),
+            mesh: meshes.add(mesh),
             material: mesh_material,
             ..Default::default()
         })
         // Wireframe
         .with_children(|cb| {
             cb.spawn().insert_bundle(PbrNoBackfaceBundle {
-                mesh: meshes.add(poly.wireframe(ProjectionType::Perspective)),
+                mesh: meshes.add(wireframe),
                 material: wf_material,
                 ..Default::default()
             });
         })
         // Polytope
-        .insert(poly);
+        .insert(poly)
+        .insert(HiddenFacets::default())
+        .insert(PolytopeColors {
+            mesh: mesh_colors,
+            wireframe: wireframe_colors,
+        });
+
+    // Axes, ground grid, Petrie polygon, vertex marker, highlighted element
+    // and slicing hyperplanes, hidden until they're turned on.
+    for overlay in [
+        Overlay::Axes,
+        Overlay::Grid,
+        Overlay::Petrie,
+        Overlay::Vertex,
+        Overlay::Element,
+        Overlay::SectionPlane,
+    ] {
+        commands
+            .spawn()
+            .insert_bundle(PbrNoBackfaceBundle {
+                mesh: meshes.add(mesh::empty_mesh()),
+                material: overlay_material.clone(),
+                visible: Visible {
+                    is_visible: false,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .insert(overlay);
+    }
+
+    // The polytope before the last operation, hidden until it's turned on.
+    commands
+        .spawn()
+        .insert_bundle(PbrNoBackfaceBundle {
+            mesh: meshes.add(mesh::empty_mesh()),
+            material: ghost_material,
+            visible: Visible {
+                is_visible: false,
+                is_transparent: true,
+            },
+            ..Default::default()
+        })
+        .insert(Overlay::Ghost);
 
     // Camera anchor
     commands
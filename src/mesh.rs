@@ -2,8 +2,8 @@
 
 use std::collections::HashMap;
 
-use crate::ui::camera::ProjectionType;
-use crate::{Concrete, Float, Point, EPS};
+use crate::ui::camera::{Orientation4D, ProjectionType};
+use crate::{Concrete, Float, Hyperplane, Point, EPS};
 
 use bevy::{
     prelude::Mesh,
@@ -13,66 +13,56 @@ use lyon::{math::point, path::Path, tessellation::*};
 use miratope_core::conc::cycle::CycleList;
 use miratope_core::{
     abs::{ElementList, Ranked},
-    conc::ConcretePolytope,
+    conc::{ConcretePolytope, Rgba},
     geometry::{Subspace, Vector},
+    Polytope,
 };
 
 use vec_like::*;
 
-/// Attempts to turn the cycle into a 2D path, which can then be given to
-/// the tessellator. Uses the specified vertex list to grab the coordinates
-/// of the vertices on the path.
+/// How far the vertices of a face may be from its best-fitting plane, relative
+/// to the size of the face, for it to still be rendered. Faces that come from
+/// slicing a polytope are usually slightly off due to rounding errors.
+const PLANARITY_TOLERANCE: Float = 1e-4;
+
+/// Attempts to turn the cycles of a face into a 2D path, which can then be
+/// given to the tessellator. Uses the specified vertex list to grab the
+/// coordinates of the vertices on the path.
 ///
-/// If the cycle isn't 2D, we return `None`.
+/// The vertices are projected onto the plane that best fits them, so that
+/// slightly skewed faces still render properly. The tessellator then takes
+/// care of any concave faces. If the face isn't even approximately 2D, we
+/// return `None`.
 pub fn path(cycles: &CycleList, vertices: &[Point]) -> Option<Path> {
-    let dim = vertices[0].len();
-    let mut builder = Path::builder();
+    let face_vertices = || {
+        cycles
+            .iter()
+            .flat_map(|cycle| cycle.iter().map(|&idx| &vertices[idx]))
+    };
 
-    for (idx, cycle) in cycles.iter().enumerate() {
-        let mut cycle_iter = cycle.iter().map(|&idx| &vertices[idx]);
+    // We don't bother with any polygons that aren't in 2D space.
+    let s = Subspace::best_fit(face_vertices(), 2);
+    if s.rank() != 2 {
+        return None;
+    }
 
-        // We don't bother with any polygons that aren't in 2D space.
-        let s = Subspace::from_points_with(cycle_iter.clone(), 2)?;
-
-        // We find the two axis directions most convenient for projecting down.
-        // Convenience is measured as the length of an axis vector projected
-        // down onto the plane our cycle lies in.
-
-        // The index of the axis vector that gives the largest length when
-        // projected, and that such length.
-        let mut idx0 = 0;
-        let mut len0 = 0.0;
-
-        // The index of the axis vector that gives the second largest length
-        // when projected, and that such length.
-        let mut idx1 = 0;
-        let mut len1 = 0.0;
-
-        // We compute idx0 and idx1 real quick.
-        let mut e = Point::zeros(dim);
-        for i in 0..dim {
-            e[i] = 1.0;
-
-            let len = s.project(&e).norm();
-            // This is the largest length we've found so far.
-            if len > len0 {
-                len1 = len0;
-                idx1 = idx0;
-                len0 = len;
-                idx0 = i;
-            }
-            // This is the second largest length we've found so far.
-            else if len > len1 {
-                len1 = len;
-                idx1 = i;
-            }
+    let size = face_vertices()
+        .map(|v| (v - &s.offset).norm())
+        .fold(0.0, Float::max);
+    if face_vertices().any(|v| s.distance(v) > PLANARITY_TOLERANCE * size) {
+        return None;
+    }
 
-            e[i] = 0.0;
-        }
+    // Converts a point in the polytope to a point in the path via
+    // orthogonal projection onto the plane.
+    let path_point = |v: &Point| {
+        let p = s.flatten(v);
+        point(p[0] as f32, p[1] as f32)
+    };
 
-        // Converts a point in the polytope to a point in the path via
-        // orthogonal projection at our convenient axes.
-        let path_point = |v: &Point| point(v[idx0] as f32, v[idx1] as f32);
+    let mut builder = Path::builder();
+    for (idx, cycle) in cycles.iter().enumerate() {
+        let mut cycle_iter = cycle.iter().map(|&idx| &vertices[idx]);
 
         // We build a path from the polygon.
         let v = cycle_iter.next().unwrap();
@@ -97,6 +87,9 @@ struct Triangulation {
 
     /// Indices of the vertices that make up the triangles.
     triangles: Vec<u32>,
+
+    /// The index of the face each triangle belongs to.
+    faces: Vec<usize>,
 }
 
 impl Triangulation {
@@ -104,6 +97,7 @@ impl Triangulation {
     fn new(polytope: &Concrete) -> Self {
         let mut extra_vertices = Vec::new();
         let mut triangles = Vec::new();
+        let mut face_idxs = Vec::new();
         let empty_els = ElementList::new();
 
         // Either returns a reference to the element list of a given rank, or
@@ -116,7 +110,7 @@ impl Triangulation {
         let concrete_vertex_len = polytope.vertices.len() as u32;
 
         // We render each face separately.
-        for face in faces {
+        for (face_idx, face) in faces.iter().enumerate() {
             // We tesselate this path.
             let cycles = CycleList::from_edges(face.subs.iter().map(|&i| &edges[i].subs));
             if let Some(path) = path(&cycles, &polytope.vertices) {
@@ -181,12 +175,15 @@ impl Triangulation {
                 {
                     triangles.push(new_idx);
                 }
+
+                face_idxs.resize(triangles.len() / 3, face_idx);
             }
         }
 
         Self {
             extra_vertices,
             triangles,
+            faces: face_idxs,
         }
     }
 }
@@ -208,17 +205,615 @@ fn normals(vertices: &[[f32; 3]]) -> Vec<[f32; 3]> {
         .collect()
 }
 
+/// The vertex color used when a polytope doesn't specify any.
+const WHITE: Rgba = [1.0; 4];
+
+/// The settings for depth cueing, which fades the far away parts of a
+/// polytope toward the background color, so that dense projections are easier
+/// to read.
+#[derive(Clone, PartialEq)]
+pub struct DepthCue {
+    /// Whether depth cueing is enabled.
+    pub enabled: bool,
+
+    /// How much the farthest vertices get faded, between 0 and 1.
+    pub intensity: f32,
+
+    /// Whether to measure depth by the fourth coordinate of the polytope,
+    /// rather than by the distance to the camera.
+    pub use_w: bool,
+
+    /// The position of the camera.
+    pub camera: [f32; 3],
+
+    /// The background color.
+    pub background: [f32; 3],
+}
+
+impl Default for DepthCue {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            intensity: 0.7,
+            use_w: false,
+            camera: [0.0; 3],
+            background: [0.0; 3],
+        }
+    }
+}
+
+impl DepthCue {
+    /// Returns how much each vertex of a mesh should be faded toward the
+    /// background. Returns `None` if depth cueing is disabled.
+    fn fades(&self, mesh: &MeshColors) -> Option<Vec<f32>> {
+        if !self.enabled {
+            return None;
+        }
+
+        let depths: Vec<f32> = match mesh.w.as_ref().filter(|_| self.use_w) {
+            Some(w) => w.clone(),
+            None => mesh
+                .positions
+                .iter()
+                .map(|v| {
+                    let [x, y, z] = [0, 1, 2].map(|i| v[i] - self.camera[i]);
+                    (x * x + y * y + z * z).sqrt()
+                })
+                .collect(),
+        };
+
+        let (min, max) = depths
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &d| {
+                (min.min(d), max.max(d))
+            });
+        let range = max - min;
+
+        Some(
+            depths
+                .into_iter()
+                .map(|d| {
+                    if range > EPS as f32 {
+                        self.intensity * (d - min) / range
+                    } else {
+                        0.0
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Fades a color toward the background by a given amount.
+    fn fade(&self, mut color: Rgba, t: f32) -> Rgba {
+        for i in 0..3 {
+            color[i] += (self.background[i] - color[i]) * t;
+        }
+
+        color
+    }
+
+    /// Returns the colors of the vertices of a mesh, faded by their depth.
+    pub fn colors(&self, mesh: &MeshColors) -> Vec<Rgba> {
+        match self.fades(mesh) {
+            Some(fades) => mesh
+                .colors
+                .iter()
+                .zip(fades)
+                .map(|(&color, t)| self.fade(color, t))
+                .collect(),
+            None => mesh.colors.clone(),
+        }
+    }
+
+    /// Fades the colors of a mesh again, without rebuilding it.
+    pub fn recolor(&self, mesh: &mut Mesh, colors: &MeshColors) {
+        // Empty meshes keep their placeholder vertex.
+        if !colors.colors.is_empty() {
+            mesh.set_attribute(Mesh::ATTRIBUTE_COLOR, self.colors(colors));
+        }
+    }
+}
+
+/// Everything needed to fade the vertices of a mesh by their depth, so that
+/// the mesh can be recolored whenever only the depth cueing changes.
+#[derive(Clone, Default)]
+pub struct MeshColors {
+    /// The positions of the vertices of the mesh.
+    positions: Vec<[f32; 3]>,
+
+    /// The fourth coordinates of the vertices before projecting, if they have
+    /// any.
+    w: Option<Vec<f32>>,
+
+    /// The colors of the vertices before fading.
+    colors: Vec<Rgba>,
+}
+
+impl MeshColors {
+    /// Stores the colors of the vertices of a mesh, given the original
+    /// vertices and their projections into 3D.
+    fn new<'a, I: Iterator<Item = &'a Point>>(
+        vertices: I,
+        positions: Vec<[f32; 3]>,
+        colors: Vec<Rgba>,
+    ) -> Self {
+        Self {
+            w: vertices.map(|p| p.get(3).map(|&x| x as f32)).collect(),
+            positions,
+            colors,
+        }
+    }
+
+    /// Returns the positions of the vertices of the mesh.
+    pub fn positions(&self) -> &[[f32; 3]] {
+        &self.positions
+    }
+}
+
+/// The colors of the mesh and the wireframe of a polytope, stored alongside
+/// it.
+#[derive(Clone, Default)]
+pub struct PolytopeColors {
+    /// The colors of the mesh.
+    pub mesh: MeshColors,
+
+    /// The colors of the wireframe.
+    pub wireframe: MeshColors,
+}
+
+/// The number of segments each edge of the wireframe is split into. Since each
+/// endpoint is projected separately, more segments make for smoother curves.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct EdgeSubdivisions(pub usize);
+
+impl Default for EdgeSubdivisions {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+/// Simplifies the wireframe of polytopes with many edges, so that they can
+/// still be rotated smoothly. Above a threshold, only the edges that look the
+/// longest after projecting are drawn, since the shortest ones are the hardest
+/// to make out anyways. The polytope itself is left untouched.
+#[derive(Clone, Copy, PartialEq)]
+pub struct WireframeDetail {
+    /// The number of edges that are always drawn.
+    pub threshold: usize,
+
+    /// The fraction of the edges drawn above the threshold, between 0 and 1.
+    pub quality: f32,
+}
+
+impl Default for WireframeDetail {
+    fn default() -> Self {
+        Self {
+            threshold: 500,
+            quality: 0.5,
+        }
+    }
+}
+
+impl WireframeDetail {
+    /// Returns the number of edges that get drawn out of a given total.
+    pub fn edge_budget(&self, edge_count: usize) -> usize {
+        if edge_count <= self.threshold {
+            edge_count
+        } else {
+            ((edge_count as f32 * self.quality.clamp(0.0, 1.0)) as usize)
+                .max(self.threshold)
+                .min(edge_count)
+        }
+    }
+}
+
+/// How far the facets of a polytope are pushed away from its gravicenter when
+/// it's drawn, as a multiple of the distance from the gravicenter to each
+/// facet's gravicenter. The polytope itself is left untouched.
+#[derive(Clone, Copy, PartialEq, Default)]
+pub struct ExplodeFacets(pub Float);
+
+impl ExplodeFacets {
+    /// Returns a compound of the facets of a polytope that aren't hidden,
+    /// pushed apart, or `None` if the polytope should be drawn as usual. Only
+    /// polyhedra and higher get exploded or have facets hidden.
+    pub fn apply(&self, poly: &Concrete, hidden: &HiddenFacets) -> Option<Concrete> {
+        let hiding = hidden.applies_to(poly);
+        if (self.0 <= 0.0 && !hiding) || poly.rank() < 4 {
+            return None;
+        }
+
+        let center = poly.gravicenter()?;
+        let shown = (0..poly.facet_count()).filter(|&idx| !hiding || !hidden.is_hidden(idx));
+        Some(Concrete::compound(shown.filter_map(|idx| {
+            let mut facet = poly.facet(idx)?;
+
+            // Polygons need to be drawn as ditopes to get any faces.
+            if facet.rank() == 3 {
+                facet.ditope_mut();
+            }
+
+            let offset = (facet.gravicenter()? - &center) * self.0;
+            for v in facet.vertices_mut() {
+                *v += &offset;
+            }
+
+            Some(facet)
+        })))
+    }
+}
+
+/// Which facets of a polytope aren't drawn, so that the ones behind them can
+/// be seen. This is stored alongside the polytope, and only applies while the
+/// polytope has exactly as many facets as there are entries.
+#[derive(Clone, Default)]
+pub struct HiddenFacets(pub Vec<bool>);
+
+impl HiddenFacets {
+    /// Returns whether any facets of a polytope are hidden.
+    pub fn applies_to(&self, poly: &Concrete) -> bool {
+        self.0.len() == poly.facet_count() && self.0.contains(&true)
+    }
+
+    /// Returns whether a given facet is hidden.
+    pub fn is_hidden(&self, idx: usize) -> bool {
+        self.0.get(idx).copied().unwrap_or(false)
+    }
+
+    /// Hides or shows a facet of a polytope with a given number of facets. If
+    /// the entries were for some other polytope, every other facet is shown.
+    pub fn set(&mut self, facet_count: usize, idx: usize, hidden: bool) {
+        if self.0.len() != facet_count {
+            self.0 = vec![false; facet_count];
+        }
+        self.0[idx] = hidden;
+    }
+}
+
+/// How much the cells are shrunk toward their gravicenters when they're drawn
+/// on their own, so that they can be told apart.
+const CELL_SHRINK: Float = 0.8;
+
+/// The size of the crosses marking the vertices, relative to the polytope.
+const VERTEX_MARK_SIZE: Float = 0.03;
+
+/// Which ranks of elements are drawn. Vertices and edges make up the
+/// wireframe, while faces and cells make up the solid mesh.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ShownRanks {
+    /// Whether the vertices are marked with small crosses.
+    pub vertices: bool,
+
+    /// Whether the edges are drawn.
+    pub edges: bool,
+
+    /// Whether the faces are drawn.
+    pub faces: bool,
+
+    /// Whether the cells are drawn, each shrunk toward its gravicenter. Since
+    /// the faces would hide them, they're only drawn when the faces aren't.
+    pub cells: bool,
+}
+
+impl Default for ShownRanks {
+    fn default() -> Self {
+        Self {
+            vertices: false,
+            edges: true,
+            faces: true,
+            cells: false,
+        }
+    }
+}
+
+impl ShownRanks {
+    /// Builds the solid mesh of a polytope, made out of either its faces or its
+    /// cells, along with its colors.
+    pub fn mesh(
+        &self,
+        poly: &Concrete,
+        projection_type: ProjectionType,
+        depth_cue: &DepthCue,
+    ) -> (Mesh, MeshColors) {
+        if self.faces {
+            poly.mesh_with_colors(projection_type, depth_cue)
+        } else if let Some(cells) = self.cells.then(|| shrunk_cells(poly)).flatten() {
+            cells.mesh_with_colors(projection_type, depth_cue)
+        } else {
+            (empty_mesh(), MeshColors::default())
+        }
+    }
+}
+
+/// Returns a compound of the cells of a polytope, each shrunk toward its
+/// gravicenter, or `None` if it has no cells.
+fn shrunk_cells(poly: &Concrete) -> Option<Concrete> {
+    if poly.rank() < 4 {
+        return None;
+    }
+
+    Some(Concrete::compound((0..poly.el_count(4)).filter_map(|idx| {
+        let mut cell = poly.element(4, idx)?;
+        let center = cell.gravicenter()?;
+
+        for v in cell.vertices_mut() {
+            *v = &center + (&*v - &center) * CELL_SHRINK;
+        }
+
+        Some(cell)
+    })))
+}
+
 /// Returns an empty mesh.
-fn empty_mesh() -> Mesh {
+pub fn empty_mesh() -> Mesh {
     let mut mesh = Mesh::new(PrimitiveTopology::LineList);
     mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0; 3]]);
     mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0; 3]]);
     mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]]);
+    mesh.set_attribute(Mesh::ATTRIBUTE_COLOR, vec![WHITE]);
     mesh.set_indices(Some(Indices::U16(Vec::new())));
 
     mesh
 }
 
+/// The colors of the x, y, and z axes.
+const AXIS_COLORS: [Rgba; 3] = [
+    [1.0, 0.25, 0.25, 1.0],
+    [0.25, 1.0, 0.25, 1.0],
+    [0.3, 0.45, 1.0, 1.0],
+];
+
+/// The color of the ground grid.
+const GRID_COLOR: Rgba = [0.5, 0.5, 0.5, 1.0];
+
+/// The color of the highlighted Petrie polygon.
+const PETRIE_COLOR: Rgba = [1.0, 0.75, 0.1, 1.0];
+
+/// The color of the marker on the highlighted vertex.
+const VERTEX_COLOR: Rgba = [0.1, 0.9, 1.0, 1.0];
+
+/// The color of the edges of the highlighted element.
+const ELEMENT_COLOR: Rgba = [1.0, 0.3, 0.6, 1.0];
+
+/// The color of the edges of the polytope being sliced.
+const SECTION_WHOLE_COLOR: Rgba = [0.6, 0.6, 0.6, 1.0];
+
+/// The color of the outline of the slicing hyperplanes.
+const SECTION_PLANE_COLOR: Rgba = [0.2, 0.7, 1.0, 1.0];
+
+/// The color of the edges of the cross-sections, where the hyperplanes cut the
+/// polytope.
+const SECTION_CUT_COLOR: Rgba = [1.0, 0.5, 0.1, 1.0];
+
+/// How much bigger than the bounding box of the polytope the outline of the
+/// slicing hyperplanes is drawn.
+const SECTION_PLANE_MARGIN: Float = 1.1;
+
+/// The number of grid lines on each side of the origin.
+const GRID_LINES: i32 = 5;
+
+/// Builds a mesh out of line segments, each with its own color. The endpoints
+/// are projected along with the polytope, so that the lines stay consistent
+/// with it.
+fn line_mesh(poly: &Concrete, lines: &[(Point, Point, Rgba)], projection_type: ProjectionType) -> Mesh {
+    if lines.is_empty() {
+        return empty_mesh();
+    }
+
+    let points = lines.iter().flat_map(|(p, q, _)| [p, q]);
+    let vertices = vertex_coords(poly, points, projection_type);
+    let colors: Vec<_> = lines.iter().flat_map(|&(_, _, c)| [c, c]).collect();
+    let indices = (0..vertices.len() as u32).collect();
+
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+    mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals(&vertices));
+    mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; vertices.len()]);
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
+    mesh.set_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+    mesh.set_indices(Some(Indices::U32(indices)));
+
+    mesh
+}
+
+/// Returns a point in the dimension of a polytope (or 3D, if it's lower) with
+/// the given first three coordinates.
+fn overlay_point(poly: &Concrete, coords: [Float; 3]) -> Point {
+    let mut p = Point::zeros(poly.dim_or().max(3));
+    for (i, c) in coords.iter().enumerate() {
+        p[i] = *c;
+    }
+    p
+}
+
+/// The length of the axes and the spacing of the grid for a polytope, so that
+/// they're comparable to its size.
+fn overlay_scale(poly: &Concrete) -> Float {
+    poly.vertices()
+        .iter()
+        .map(|v| v.norm())
+        .fold(1.0, Float::max)
+}
+
+/// Builds the mesh of the x, y, and z axes, with the same projection as the
+/// polytope.
+pub fn axes_mesh(poly: &Concrete, projection_type: ProjectionType) -> Mesh {
+    let len = 1.5 * overlay_scale(poly);
+    let origin = overlay_point(poly, [0.0; 3]);
+
+    let lines: Vec<_> = AXIS_COLORS
+        .iter()
+        .enumerate()
+        .map(|(i, &color)| {
+            let mut end = [0.0; 3];
+            end[i] = len;
+            (origin.clone(), overlay_point(poly, end), color)
+        })
+        .collect();
+
+    line_mesh(poly, &lines, projection_type)
+}
+
+/// Builds the mesh of a grid on the xz-plane, with the same projection as the
+/// polytope.
+pub fn grid_mesh(poly: &Concrete, projection_type: ProjectionType) -> Mesh {
+    let step = overlay_scale(poly) / GRID_LINES as Float;
+    let edge = step * GRID_LINES as Float;
+    let mut lines = Vec::new();
+
+    for i in -GRID_LINES..=GRID_LINES {
+        let t = step * i as Float;
+        lines.push((
+            overlay_point(poly, [t, 0.0, -edge]),
+            overlay_point(poly, [t, 0.0, edge]),
+            GRID_COLOR,
+        ));
+        lines.push((
+            overlay_point(poly, [-edge, 0.0, t]),
+            overlay_point(poly, [edge, 0.0, t]),
+            GRID_COLOR,
+        ));
+    }
+
+    line_mesh(poly, &lines, projection_type)
+}
+
+/// Builds the mesh of the Petrie polygon through the first flag of the
+/// polytope, or an empty mesh if it has none.
+pub fn petrie_mesh(poly: &Concrete, projection_type: ProjectionType) -> Mesh {
+    let petrie = poly.first_petrie_polygon().unwrap_or_default();
+    let vertices = poly.vertices();
+
+    let lines: Vec<_> = petrie
+        .iter()
+        .zip(petrie.iter().cycle().skip(1))
+        .map(|(&i, &j)| (vertices[i].clone(), vertices[j].clone(), PETRIE_COLOR))
+        .collect();
+
+    line_mesh(poly, &lines, projection_type)
+}
+
+/// Builds the mesh of a small cross on the vertex with a given index, or an
+/// empty mesh if there's no such vertex.
+pub fn vertex_mesh(poly: &Concrete, idx: usize, projection_type: ProjectionType) -> Mesh {
+    let vertex = match poly.vertices().get(idx) {
+        Some(vertex) => vertex,
+        None => return empty_mesh(),
+    };
+    let len = 0.1 * overlay_scale(poly);
+
+    let lines: Vec<_> = (0..vertex.len().min(3))
+        .map(|i| {
+            let mut offset = Point::zeros(vertex.len());
+            offset[i] = len;
+            (vertex - &offset, vertex + &offset, VERTEX_COLOR)
+        })
+        .collect();
+
+    line_mesh(poly, &lines, projection_type)
+}
+
+/// Builds the mesh of the edges of the element with a given rank and index, or
+/// an empty mesh if there's no such element. Vertices are marked as in
+/// [`vertex_mesh`].
+pub fn element_mesh(
+    poly: &Concrete,
+    rank: usize,
+    idx: usize,
+    projection_type: ProjectionType,
+) -> Mesh {
+    if rank == 1 {
+        return vertex_mesh(poly, idx, projection_type);
+    }
+
+    let element = match poly.element(rank, idx) {
+        Some(element) if element.rank() >= 2 => element,
+        _ => return empty_mesh(),
+    };
+    let vertices = element.vertices();
+
+    let lines: Vec<_> = element[2]
+        .iter()
+        .map(|edge| {
+            (
+                vertices[edge.subs[0]].clone(),
+                vertices[edge.subs[1]].clone(),
+                ELEMENT_COLOR,
+            )
+        })
+        .collect();
+
+    line_mesh(poly, &lines, projection_type)
+}
+
+/// Returns the edges of a polytope as line segments of a given color.
+fn edge_lines(poly: &Concrete, color: Rgba) -> Vec<(Point, Point, Rgba)> {
+    let vertices = poly.vertices();
+
+    poly.get_element_list(2)
+        .map(|edges| {
+            edges
+                .iter()
+                .map(|edge| {
+                    (
+                        vertices[edge.subs[0]].clone(),
+                        vertices[edge.subs[1]].clone(),
+                        color,
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Builds the mesh of a polytope being sliced in the cross-section view: its
+/// edges, the outlines of the slicing hyperplanes within its bounding box, and
+/// the edges of the cross-sections in place. Everything is rotated in 4D and
+/// projected together with the polytope.
+pub fn section_plane_mesh(
+    poly: &Concrete,
+    planes: &[Hyperplane],
+    orientation: &Orientation4D,
+    projection_type: ProjectionType,
+) -> Mesh {
+    let dim = match poly.dim() {
+        Some(dim) if dim > 0 => dim,
+        _ => return empty_mesh(),
+    };
+
+    // The hyperplanes are drawn as their intersection with a box somewhat
+    // larger than the bounding box of the polytope.
+    let mut lo = poly.vertices[0].clone();
+    let mut hi = poly.vertices[0].clone();
+    for v in poly.vertices() {
+        lo = lo.inf(v);
+        hi = hi.sup(v);
+    }
+    let center = (&lo + &hi) / 2.0;
+    let size = (hi - lo).map(|x| SECTION_PLANE_MARGIN * x.max(EPS));
+
+    let mut bounding_box = Concrete::hypercube(dim + 1);
+    for v in bounding_box.vertices_mut() {
+        *v = &center + v.component_mul(&size);
+    }
+
+    let mut parts = vec![(poly.clone(), SECTION_WHOLE_COLOR)];
+    for plane in planes {
+        parts.push((bounding_box.cross_section(plane), SECTION_PLANE_COLOR));
+        parts.push((poly.cross_section(plane), SECTION_CUT_COLOR));
+    }
+
+    let mut lines = Vec::new();
+    for (part, color) in &parts {
+        lines.append(&mut edge_lines(
+            orientation.apply(part).as_ref().unwrap_or(part),
+            *color,
+        ));
+    }
+
+    let rotated = orientation.apply(poly);
+    line_mesh(rotated.as_ref().unwrap_or(poly), &lines, projection_type)
+}
+
 /// Gets the coordinates of the vertices, after projecting down into 3D.
 fn vertex_coords<'a, I: Iterator<Item = &'a Point>>(
     poly: &Concrete,
@@ -253,13 +848,31 @@ fn vertex_coords<'a, I: Iterator<Item = &'a Point>>(
     }
 }
 
+/// Gets the vertices of the polytope after projecting them down into 3D, in
+/// the same way as they're shown on screen.
+pub fn projected_vertices(poly: &Concrete, projection_type: ProjectionType) -> Vec<Point> {
+    vertex_coords(poly, poly.vertices.iter(), projection_type)
+        .into_iter()
+        .map(|v| Point::from_iterator(3, v.iter().map(|&x| x as Float)))
+        .collect()
+}
+
 /// A trait for a polytope for which we can build a mesh.
 pub trait Renderable: ConcretePolytope {
     /// Builds the mesh of a polytope.
-    fn mesh(&self, projection_type: ProjectionType) -> Mesh {
+    fn mesh(&self, projection_type: ProjectionType, depth_cue: &DepthCue) -> Mesh {
+        self.mesh_with_colors(projection_type, depth_cue).0
+    }
+
+    /// Builds the mesh of a polytope, along with its colors before fading.
+    fn mesh_with_colors(
+        &self,
+        projection_type: ProjectionType,
+        depth_cue: &DepthCue,
+    ) -> (Mesh, MeshColors) {
         // If there's no vertices, returns an empty mesh.
         if self.vertex_count() == 0 {
-            return empty_mesh();
+            return (empty_mesh(), MeshColors::default());
         }
 
         let mut poly = self.clone();
@@ -272,42 +885,133 @@ pub trait Renderable: ConcretePolytope {
         // Triangulates the polytope's faces, projects the vertices of both the
         // polytope and the triangulation.
         let triangulation = Triangulation::new(poly.con());
-        let vertices = vertex_coords(
-            self.con(),
+        let all_vertices = || {
             self.vertices()
                 .iter()
-                .chain(triangulation.extra_vertices.iter()),
-            projection_type,
-        );
+                .chain(triangulation.extra_vertices.iter())
+        };
+        let vertices = vertex_coords(self.con(), all_vertices(), projection_type);
+
+        // If the faces have their own colors, every triangle needs its own
+        // vertices, so that the colors don't bleed into each other.
+        let (colors, triangles) = if let Some(face_colors) = self.con().face_colors() {
+            let originals: Vec<_> = all_vertices().collect();
+            let vertices: Vec<_> = triangulation
+                .triangles
+                .iter()
+                .map(|&idx| vertices[idx as usize])
+                .collect();
+            let colors: Vec<Rgba> = (0..vertices.len())
+                .map(|idx| face_colors[triangulation.faces[idx / 3]])
+                .collect();
+            let triangles: Vec<u32> = (0..vertices.len() as u32).collect();
+
+            let originals = triangulation.triangles.iter().map(|&idx| originals[idx as usize]);
+            (MeshColors::new(originals, vertices, colors), triangles)
+        } else {
+            let colors = vec![WHITE; vertices.len()];
+            (MeshColors::new(all_vertices(), vertices, colors), triangulation.triangles)
+        };
+        let vertices = colors.positions.clone();
 
         // Builds the actual mesh.
         let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
         mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 1.0]; vertices.len()]);
         mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals(&vertices));
         mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
-        mesh.set_indices(Some(Indices::U32(triangulation.triangles)));
+        mesh.set_attribute(Mesh::ATTRIBUTE_COLOR, depth_cue.colors(&colors));
+        mesh.set_indices(Some(Indices::U32(triangles)));
+
+        (mesh, colors)
+    }
 
-        mesh
+    /// Builds the wireframe of a polytope, splitting every edge into a number
+    /// of segments, so that edges can curve under projection. The vertices and
+    /// edges are only drawn if the given [`ShownRanks`] say so, and the edges
+    /// are simplified according to the [`WireframeDetail`].
+    fn wireframe(
+        &self,
+        projection_type: ProjectionType,
+        depth_cue: &DepthCue,
+        subdivisions: usize,
+        ranks: &ShownRanks,
+        detail: &WireframeDetail,
+    ) -> Mesh {
+        self.wireframe_with_colors(projection_type, depth_cue, subdivisions, ranks, detail)
+            .0
     }
 
-    /// Builds the wireframe of a polytope.
-    fn wireframe(&self, projection_type: ProjectionType) -> Mesh {
+    /// Builds the wireframe of a polytope, along with its colors before
+    /// fading.
+    fn wireframe_with_colors(
+        &self,
+        projection_type: ProjectionType,
+        depth_cue: &DepthCue,
+        subdivisions: usize,
+        ranks: &ShownRanks,
+        detail: &WireframeDetail,
+    ) -> (Mesh, MeshColors) {
         let vertex_count = self.vertex_count();
 
         // If there's no vertices, returns an empty mesh.
         if vertex_count == 0 {
-            return empty_mesh();
+            return (empty_mesh(), MeshColors::default());
         }
 
         let edge_count = self.edge_count();
+        let subdivisions = subdivisions.max(1);
+
+        // The points on the wireframe: the vertices, followed by the points
+        // that split each edge into segments, which get projected separately.
+        let mut points = self.vertices().clone();
+        let mut indices = Vec::with_capacity(edge_count * subdivisions * 2);
 
-        // We add a single vertex so that Miratope doesn't crash.
-        let vertices = vertex_coords(self.con(), self.vertices().iter(), projection_type);
-        let mut indices = Vec::with_capacity(edge_count * 2);
+        // Marks every vertex with a small cross.
+        if ranks.vertices {
+            let len = VERTEX_MARK_SIZE * overlay_scale(self.con());
+
+            for idx in 0..vertex_count {
+                let v = &self.vertices()[idx];
+                for i in 0..v.len().min(3) {
+                    let mut offset = Point::zeros(v.len());
+                    offset[i] = len;
+
+                    indices.push(points.len() as u32);
+                    points.push(v - &offset);
+                    indices.push(points.len() as u32);
+                    points.push(v + &offset);
+                }
+            }
+        }
 
         // Adds the edges to the wireframe.
-        if let Some(edges) = self.get_element_list(2) {
-            for edge in edges {
+        if let Some(edges) = self.get_element_list(2).filter(|_| ranks.edges) {
+            // Picks out the edges that look the longest, if there's too many.
+            let budget = detail.edge_budget(edge_count);
+            let mut shown = vec![true; edge_count];
+
+            if budget < edge_count {
+                let projected = vertex_coords(self.con(), self.vertices().iter(), projection_type);
+                let lengths: Vec<f32> = edges
+                    .iter()
+                    .map(|edge| {
+                        let (p, q) = (projected[edge.subs[0]], projected[edge.subs[1]]);
+                        (0..3).map(|i| (p[i] - q[i]).powi(2)).sum()
+                    })
+                    .collect();
+
+                let mut order: Vec<_> = (0..edge_count).collect();
+                order.sort_unstable_by(|&i, &j| {
+                    lengths[j]
+                        .partial_cmp(&lengths[i])
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+                for &idx in &order[budget..] {
+                    shown[idx] = false;
+                }
+            }
+
+            for edge in edges.iter().zip(shown).filter_map(|(edge, shown)| shown.then(|| edge)) {
                 debug_assert_eq!(
                     edge.subs.len(),
                     2,
@@ -315,19 +1019,36 @@ pub trait Renderable: ConcretePolytope {
                     edge.subs.len()
                 );
 
-                indices.push(edge.subs[0] as u16);
-                indices.push(edge.subs[1] as u16);
+                let (v0, v1) = (edge.subs[0], edge.subs[1]);
+                let mut prev = v0;
+
+                for k in 1..subdivisions {
+                    let t = k as Float / subdivisions as Float;
+                    let p = &self.vertices()[v0] * (1.0 - t) + &self.vertices()[v1] * t;
+                    points.push(p);
+
+                    indices.push(prev as u32);
+                    prev = points.len() - 1;
+                    indices.push(prev as u32);
+                }
+
+                indices.push(prev as u32);
+                indices.push(v1 as u32);
             }
         }
 
-        // Sets the mesh attributes.
+        let vertices = vertex_coords(self.con(), points.iter(), projection_type);
+        let colors = MeshColors::new(points.iter(), vertices.clone(), vec![WHITE; points.len()]);
+
+        // Sets the mesh attributes, fading the far away vertices.
         let mut mesh = Mesh::new(PrimitiveTopology::LineList);
         mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals(&vertices));
         mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, vertices);
-        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; vertex_count]);
-        mesh.set_indices(Some(Indices::U16(indices)));
+        mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0; 2]; points.len()]);
+        mesh.set_attribute(Mesh::ATTRIBUTE_COLOR, depth_cue.colors(&colors));
+        mesh.set_indices(Some(Indices::U32(indices)));
 
-        mesh
+        (mesh, colors)
     }
 }
 
@@ -45,6 +45,7 @@ struct PointLight {
 layout(location = 0) in vec3 v_WorldPosition;
 layout(location = 1) in vec3 v_WorldNormal;
 layout(location = 2) in vec2 v_Uv;
+layout(location = 4) in vec4 v_Color;
 
 #ifdef STANDARDMATERIAL_NORMAL_MAP
 layout(location = 3) in vec4 v_WorldTangent;
@@ -281,6 +282,8 @@ void main() {
     // https://github.com/glslify/glsl-face-normal/blob/master/index.glsl
     vec3 fdx = dFdx(v_WorldPosition);
     vec3 fdy = dFdy(v_WorldPosition);
-    vec4 output_color = vec4(abs(dot(normalize(cross(fdx, fdy)),normalize(PointLights[0].pos.xyz))) * base_color.xyz, 1.0);
+    float diffuse = abs(dot(normalize(cross(fdx, fdy)),normalize(PointLights[0].pos.xyz)));
+    vec3 light = clamp(AmbientColor.xyz + vec3(diffuse), 0.0, 1.0);
+    vec4 output_color = vec4(light * base_color.xyz * v_Color.xyz, 1.0);
     o_Target = output_color;
 }
\ No newline at end of file
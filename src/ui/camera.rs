@@ -9,6 +9,29 @@ use bevy::{
     render::camera::Camera,
 };
 use bevy_egui::{egui::CtxRef, EguiContext};
+use miratope_core::{conc::ConcretePolytope, geometry::Matrix};
+use serde::{Deserialize, Serialize};
+
+use super::{config::FitSection, top_panel::SectionState};
+use crate::{mesh::projected_vertices, Concrete, Float};
+
+/// The closest the camera can get to its anchor.
+const MIN_DISTANCE: f32 = 0.05;
+
+/// The furthest the camera can get from its anchor, and the furthest the
+/// anchor can get from the origin.
+const MAX_DISTANCE: f32 = 400.;
+
+/// The vertical field of view of the camera, which is Bevy's default.
+const FOV: f32 = std::f32::consts::PI / 4.;
+
+/// How much bigger than the polytope the view is when fitting the camera to
+/// it.
+const FIT_MARGIN: f32 = 1.2;
+
+/// The factor by which the size of the polytope must change for the camera to
+/// be automatically fit to it again.
+const FIT_THRESHOLD: f32 = 4.;
 
 /// The plugin handling all camera input.
 pub struct InputPlugin;
@@ -20,11 +43,15 @@ impl Plugin for InputPlugin {
             // We register inputs after the library has been shown, so that we
             // know whether mouse input should register.
             .add_system(add_cam_input_events.system().after("show_library"))
-            .add_system(update_cameras_and_anchors.system());
+            .add_system(auto_fit.system())
+            .add_system(rotate_4d.system().after("show_library"))
+            .add_system(update_cameras_and_anchors.system())
+            .init_resource::<FitRadius>()
+            .init_resource::<Orientation4D>();
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Serialize, Deserialize)]
 pub enum ProjectionType {
     /// We're projecting orthogonally.
     Orthogonal,
@@ -54,6 +81,63 @@ impl ProjectionType {
     }
 }
 
+/// The orientation of the polytope in four or more dimensions, applied to its
+/// vertices before they're projected down into 3D. The camera can only rotate
+/// the first three coordinates, so this is what brings the fourth one into
+/// view. It's changed by right-dragging, which rotates in the xw and yw planes.
+#[derive(Default)]
+pub struct Orientation4D(Option<Matrix<Float>>);
+
+impl Orientation4D {
+    /// Returns the orientation as a 4 × 4 rotation matrix.
+    pub fn matrix(&self) -> Matrix<Float> {
+        self.0.clone().unwrap_or_else(|| Matrix::identity(4, 4))
+    }
+
+    /// Returns whether the polytope is shown in its original orientation.
+    pub fn is_identity(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Resets the orientation, so that the polytope is shown as is.
+    pub fn reset(&mut self) {
+        self.0 = None;
+    }
+
+    /// Rotates by a given angle in the plane spanned by a given axis and the
+    /// w-axis.
+    pub fn rotate(&mut self, axis: usize, angle: Float) {
+        let (sin, cos) = angle.sin_cos();
+        let mut rotation = Matrix::identity(4, 4);
+        rotation[(axis, axis)] = cos;
+        rotation[(axis, 3)] = -sin;
+        rotation[(3, axis)] = sin;
+        rotation[(3, 3)] = cos;
+
+        self.0 = Some(rotation * self.matrix());
+    }
+
+    /// Returns the polytope rotated into this orientation, or `None` if it
+    /// should be drawn as is.
+    pub fn apply(&self, poly: &Concrete) -> Option<Concrete> {
+        let dim = poly.dim_or();
+        let rotation = self.0.as_ref()?;
+        if dim < 4 {
+            return None;
+        }
+
+        // The rotation leaves any coordinates past the fourth untouched.
+        let mut matrix = Matrix::identity(dim, dim);
+        for i in 0..4 {
+            for j in 0..4 {
+                matrix[(i, j)] = rotation[(i, j)];
+            }
+        }
+
+        Some(poly.clone().apply(&matrix))
+    }
+}
+
 /// An input event for the camera.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum CameraInputEvent {
@@ -77,6 +161,19 @@ pub enum CameraInputEvent {
 
     /// Resets the camera to its default state.
     Reset,
+
+    /// Points the camera straight at the xy-plane, keeping its distance.
+    FaceXy,
+
+    /// Moves the anchor to the center of a sphere and the camera so that the
+    /// sphere fills the view.
+    Fit {
+        /// The center of the sphere.
+        center: Vec3,
+
+        /// The radius of the sphere.
+        radius: f32,
+    },
 }
 
 impl Mul<f32> for CameraInputEvent {
@@ -111,6 +208,7 @@ impl CameraInputEvent {
 
     fn translate(vec: Vec3, anchor_tf: &mut Transform, cam_gtf: &GlobalTransform) {
         anchor_tf.translation += cam_gtf.rotation * vec;
+        anchor_tf.translation = anchor_tf.translation.clamp_length_max(MAX_DISTANCE);
     }
 
     fn roll(roll: f32, anchor_tf: &mut Transform) {
@@ -120,7 +218,7 @@ impl CameraInputEvent {
     /// Zooms into the camera.
     fn zoom(zoom: f32, cam_tf: &mut Transform) {
         cam_tf.translation.z += zoom * cam_tf.translation.length();
-        cam_tf.translation.z = cam_tf.translation.z.max(0.05).min(400.);
+        cam_tf.translation.z = cam_tf.translation.z.max(MIN_DISTANCE).min(MAX_DISTANCE);
     }
 
     /// Resets the camera to the default position.
@@ -131,6 +229,47 @@ impl CameraInputEvent {
                 .looking_at(Vec3::default(), Vec3::Y);
     }
 
+    /// Points the camera straight at the xy-plane, with the y axis pointing
+    /// up, while keeping its distance to the anchor.
+    pub fn face_xy(anchor_tf: &mut Transform, cam_tf: &mut Transform) {
+        *cam_tf = Transform::from_translation(Vec3::new(0., 0., cam_tf.translation.length()));
+        *anchor_tf = Transform::identity();
+    }
+
+    /// Moves the anchor to the center of a sphere, and the camera so that the
+    /// sphere fills the view, while keeping the camera's orientation.
+    pub fn fit(center: Vec3, radius: f32, anchor_tf: &mut Transform, cam_tf: &mut Transform) {
+        let distance = FIT_MARGIN * radius / (FOV / 2.).sin();
+        anchor_tf.translation = center.clamp_length_max(MAX_DISTANCE);
+        cam_tf.translation = Vec3::new(0., 0., distance.max(MIN_DISTANCE).min(MAX_DISTANCE));
+    }
+
+    /// Returns the event that fits the camera to the projection of a polytope,
+    /// or `None` if it has no vertices.
+    pub fn fit_to(poly: &Concrete, projection_type: ProjectionType) -> Option<Self> {
+        let vertices = projected_vertices(poly, projection_type);
+        let first = vertices.first()?;
+
+        // The center of the bounding box.
+        let mut lo = first.clone();
+        let mut hi = first.clone();
+        for v in &vertices {
+            lo = lo.inf(v);
+            hi = hi.sup(v);
+        }
+        let center = (lo + hi) / 2.;
+
+        let radius = vertices
+            .iter()
+            .map(|v| (v - &center).norm())
+            .fold(0., Float::max);
+
+        Some(Self::Fit {
+            center: Vec3::new(center[0] as f32, center[1] as f32, center[2] as f32),
+            radius: radius as f32,
+        })
+    }
+
     fn update_camera_and_anchor(
         &self,
         anchor_tf: &mut Transform,
@@ -143,6 +282,8 @@ impl CameraInputEvent {
             Self::Roll(roll) => Self::roll(roll, anchor_tf),
             Self::Zoom(zoom) => Self::zoom(zoom, cam_tf),
             Self::Reset => Self::reset(anchor_tf, cam_tf),
+            Self::FaceXy => Self::face_xy(anchor_tf, cam_tf),
+            Self::Fit { center, radius } => Self::fit(center, radius, anchor_tf, cam_tf),
         }
     }
 
@@ -196,9 +337,14 @@ impl CameraInputEvent {
         mut mouse_move: EventReader<'_, '_, MouseMotion>,
         height: f32,
         real_scale: f32,
+        rotates_4d: bool,
         cam_inputs: &mut EventWriter<'_, '_, Self>,
     ) {
-        if mouse_button.pressed(MouseButton::Left) || mouse_button.pressed(MouseButton::Right) {
+        // Right-dragging rotates in 4D instead whenever there's a fourth
+        // dimension to rotate into, see `rotate_4d`.
+        if mouse_button.pressed(MouseButton::Left)
+            || (mouse_button.pressed(MouseButton::Right) && !rotates_4d)
+        {
             for MouseMotion { mut delta } in mouse_move.iter() {
                 delta.x /= height;
                 delta.y /= height;
@@ -235,6 +381,7 @@ fn add_cam_input_events(
     windows: Res<'_, Windows>,
     mut cam_inputs: EventWriter<'_, '_, CameraInputEvent>,
     egui_ctx: Res<'_, EguiContext>,
+    polies: Query<'_, '_, &Concrete>,
 ) {
     let height = {
         let primary_win = windows.get_primary().expect("There is no primary window");
@@ -253,12 +400,117 @@ fn add_cam_input_events(
             mouse_move,
             height,
             real_scale,
+            rotates_4d(&polies),
             cam_inputs,
         );
         CameraInputEvent::cam_events_from_wheel(mouse_wheel, scale, cam_inputs);
     }
 }
 
+/// The radius of the polytope the last time the camera was fit to it, or
+/// `None` if it hasn't been measured yet.
+#[derive(Default)]
+pub struct FitRadius(pub Option<f32>);
+
+/// The system that fits the camera to the polytope when Z is pressed, or when
+/// the size of the polytope changes dramatically, such as when a file of a
+/// different scale is loaded. If [`FitSection`] is on, the camera is also fit
+/// to the first slice of the cross-section view.
+#[allow(clippy::too_many_arguments)]
+fn auto_fit(
+    keyboard: Res<'_, Input<KeyCode>>,
+    egui_ctx: Res<'_, EguiContext>,
+    projection_type: Res<'_, ProjectionType>,
+    section_state: Res<'_, SectionState>,
+    fit_section: Res<'_, FitSection>,
+    mut fit_radius: ResMut<'_, FitRadius>,
+    polies: Query<'_, '_, &Concrete>,
+    changed_polies: Query<'_, '_, (), Changed<Concrete>>,
+    mut cam_inputs: EventWriter<'_, '_, CameraInputEvent>,
+    (mut was_section, mut pending_section): (Local<'_, bool>, Local<'_, bool>),
+) {
+    let hotkey = !egui_ctx.ctx().wants_keyboard_input() && keyboard.just_pressed(KeyCode::Z);
+
+    // The first slice is shown right after the view is opened.
+    let section = matches!(*section_state, SectionState::Active { .. });
+    if section && !*was_section {
+        *pending_section = fit_section.0;
+    }
+    *was_section = section;
+
+    // Cross-sections change size continuously as they're scrubbed through, so
+    // we leave the camera alone unless told otherwise.
+    let changed = changed_polies.iter().next().is_some();
+    let first_slice = changed && section && *pending_section;
+    if first_slice {
+        *pending_section = false;
+    }
+    let changed = changed && (!section || fit_section.0);
+    let force = hotkey || first_slice;
+
+    if !force && !changed {
+        return;
+    }
+
+    let event = match polies.iter().next() {
+        Some(poly) => CameraInputEvent::fit_to(poly, *projection_type),
+        None => None,
+    };
+
+    if let Some(CameraInputEvent::Fit { center, radius }) = event {
+        // The first polytope is shown from the default view.
+        let dramatic = fit_radius
+            .0
+            .map_or(false, |old| radius > old * FIT_THRESHOLD || radius * FIT_THRESHOLD < old);
+
+        if force || dramatic {
+            cam_inputs.send(CameraInputEvent::Fit { center, radius });
+        }
+
+        if force || dramatic || fit_radius.0.is_none() {
+            fit_radius.0 = Some(radius);
+        }
+    }
+}
+
+/// Returns whether right-dragging rotates the polytope in 4D, which requires
+/// it to have a fourth dimension to rotate into. Otherwise, it rotates the
+/// camera like left-dragging does.
+fn rotates_4d(polies: &Query<'_, '_, &Concrete>) -> bool {
+    polies.iter().next().map_or(false, |p| p.dim_or() >= 4)
+}
+
+/// The system that rotates the polytope in the xw and yw planes when it's
+/// right-dragged. The polytope is redrawn once the orientation changes.
+fn rotate_4d(
+    mouse_button: Res<'_, Input<MouseButton>>,
+    mut mouse_move: EventReader<'_, '_, MouseMotion>,
+    windows: Res<'_, Windows>,
+    egui_ctx: Res<'_, EguiContext>,
+    mut orientation: ResMut<'_, Orientation4D>,
+    polies: Query<'_, '_, &Concrete>,
+) {
+    // Dragging across the entire window rotates by half a turn.
+    const SPEED: Float = std::f64::consts::PI as Float;
+
+    if !mouse_button.pressed(MouseButton::Right)
+        || egui_ctx.ctx().wants_pointer_input()
+        || !rotates_4d(&polies)
+    {
+        return;
+    }
+
+    let height = match windows.get_primary() {
+        Some(window) => window.physical_height() as Float,
+        None => return,
+    };
+
+    for MouseMotion { delta } in mouse_move.iter() {
+        orientation.rotate(0, SPEED * delta.x as Float / height);
+        orientation.rotate(1, -SPEED * delta.y as Float / height);
+    }
+}
+
 fn update_cameras_and_anchors(
     mut events: EventReader<'_, '_, CameraInputEvent>,
     q: Query<
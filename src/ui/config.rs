@@ -10,7 +10,10 @@ use std::{
 use bevy::{app::AppExit, prelude::*};
 use bevy_egui::{egui, EguiContext};
 use directories::ProjectDirs;
-use serde::{Deserialize, Serialize};
+use miratope_core::conc::ConcretePolytope;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::Concrete;
 
 /// The default path in which we look for the Miratope library.
 const DEFAULT_PATH: &str = "./lib";
@@ -23,23 +26,23 @@ pub struct ConfigPlugin;
 
 impl Plugin for ConfigPlugin {
     fn build(&self, app: &mut App) {
-        // The configuration directory.
-        let config_dir = Config::config_dir();
-
-        // The configuration path.
-        let mut config_path = ConfigPath(config_dir.clone());
-        config_path.0.push(CONF_FILE);
-
         // Reads the entire configuration from file.
-        let config = Config::read(&config_dir, &config_path);
+        let config = Config::load();
 
         // Makes resources from the configuration, which may or may not
         // correspond to the actual stored values themselves.
-        app.insert_resource(config_path)
+        app.insert_resource(Config::config_path())
             .insert_resource(LibPath::default())
-            .insert_resource(config.background_color.clear_color())
+            .insert_resource(config.scene.background_color.clear_color())
             .insert_resource(config.light_mode.visuals())
+            .insert_resource(config.scene)
+            .insert_resource(config.antialiasing)
+            .insert_resource(config.recenter_on_load)
+            .insert_resource(config.metrics_on_save)
+            .insert_resource(config.fit_section)
+            .insert_resource(config.element_cap)
             .add_system(update_visuals.system())
+            .add_system(update_scene.system())
             .add_system_to_stage(CoreStage::Last, save_config.system());
     }
 }
@@ -79,21 +82,176 @@ impl AsRef<OsStr> for LibPath {
 pub struct BgColor(f32, f32, f32);
 
 impl BgColor {
-    /// Makes a new `BgColor` from the given `ClearColor`.
-    pub fn new(clear_color: &ClearColor) -> Self {
-        let color = clear_color.0;
-        Self(color.r(), color.g(), color.b())
-    }
-
     /// Makes a new `ClearColor` from the given `BgColor`.
     pub fn clear_color(&self) -> ClearColor {
         ClearColor(Color::rgb(self.0, self.1, self.2))
     }
+
+    /// Returns the RGB components of the color.
+    pub fn rgb(&self) -> [f32; 3] {
+        [self.0, self.1, self.2]
+    }
+
+    /// Shows a color picker for the background color. Returns whether the
+    /// color was changed.
+    pub fn color_edit_button(&mut self, ui: &mut egui::Ui) -> bool {
+        // The current background color.
+        let [r, g, b] = [self.0, self.1, self.2].map(|c| (c * 255.0) as u8);
+        let color = egui::Color32::from_rgb(r, g, b);
+
+        // The new background color.
+        let mut new_color = color;
+        egui::color_picker::color_edit_button_srgba(
+            ui,
+            &mut new_color,
+            egui::color_picker::Alpha::Opaque,
+        );
+
+        // Updates the background color if necessary.
+        if color != new_color {
+            self.0 = new_color.r() as f32 / 255.0;
+            self.1 = new_color.g() as f32 / 255.0;
+            self.2 = new_color.b() as f32 / 255.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// The distance from the camera to the key light.
+const LIGHT_DISTANCE: f32 = 50.5;
+
+/// The background and lighting of the scene.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SceneSettings {
+    /// The background color of the application.
+    pub background_color: BgColor,
+
+    /// The brightness of the ambient light, between 0 and 1.
+    pub ambient: f32,
+
+    /// The direction of the key light, relative to the camera.
+    pub light_direction: [f32; 3],
+}
+
+impl Default for SceneSettings {
+    fn default() -> Self {
+        Self {
+            background_color: Default::default(),
+            ambient: 0.0,
+            light_direction: Vec3::new(-5.0, 5.0, 50.0).normalize().into(),
+        }
+    }
+}
+
+impl SceneSettings {
+    /// The position of the key light, relative to the camera.
+    pub fn light_translation(&self) -> Vec3 {
+        Vec3::from(self.light_direction).normalize_or_zero() * LIGHT_DISTANCE
+    }
+}
+
+/// Applies the scene settings to the clear color and the light entities
+/// whenever they change.
+fn update_scene(
+    scene: Res<'_, SceneSettings>,
+    mut clear_color: ResMut<'_, ClearColor>,
+    mut ambient_light: ResMut<'_, AmbientLight>,
+    mut lights: Query<'_, '_, &mut Transform, With<PointLight>>,
+) {
+    if scene.is_changed() {
+        *clear_color = scene.background_color.clear_color();
+        ambient_light.color = Color::WHITE;
+        ambient_light.brightness = scene.ambient;
+
+        for mut transform in lights.iter_mut() {
+            transform.translation = scene.light_translation();
+        }
+    }
+}
+
+/// The number of samples used for multisample antialiasing. Since the render
+/// pipeline is built at startup, changes only apply after a restart.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Antialiasing(pub u32);
+
+impl Default for Antialiasing {
+    fn default() -> Self {
+        Self(4)
+    }
+}
+
+impl Antialiasing {
+    /// The sample counts that can be chosen.
+    pub const SAMPLES: [u32; 4] = [1, 2, 4, 8];
+
+    /// Returns the corresponding MSAA resource.
+    pub fn msaa(&self) -> Msaa {
+        Msaa { samples: self.0 }
+    }
+}
+
+/// Whether polytopes are recentered when they're loaded from a file. When off,
+/// they keep the coordinates stored in the file. On by default, as polytopes
+/// were always recentered before this could be changed.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct RecenterOnLoad(pub bool);
+
+impl Default for RecenterOnLoad {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+impl RecenterOnLoad {
+    /// Recenters a freshly loaded polytope, if the setting is on.
+    pub fn apply(&self, p: &mut Concrete) {
+        if self.0 {
+            p.recenter();
+        }
+    }
+}
+
+/// Whether saved OFF files note the hyperspheres around and within the polytope
+/// in their comments. Off by default, since these can be slow to calculate.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct MetricsOnSave(pub bool);
+
+/// The number of elements above which convex hulls and facetings give up, or
+/// 0 if they never do.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct ElementCap(pub usize);
+
+impl Default for ElementCap {
+    fn default() -> Self {
+        Self(1_000_000)
+    }
+}
+
+impl ElementCap {
+    /// Returns the cap, or `None` if there's none.
+    pub fn get(&self) -> Option<usize> {
+        (self.0 != 0).then(|| self.0)
+    }
+}
+
+/// Whether the camera is fit to the cross-section when the view is opened, and
+/// whenever the size of the slice changes dramatically. When off, the camera
+/// stays put while slicing.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct FitSection(pub bool);
+
+impl Default for FitSection {
+    fn default() -> Self {
+        Self(true)
+    }
 }
 
 /// Whether light mode is turned on or off.
 #[derive(Default, Serialize, Deserialize)]
-pub struct LightMode(bool);
+pub struct LightMode(pub bool);
 
 impl LightMode {
     /// Returns the corresponding egui visuals.
@@ -118,12 +276,41 @@ fn update_visuals(egui_ctx: Res<'_, EguiContext>, visuals: Res<'_, egui::Visuals
 /// Miratope. This is used only to read and write to disk – throughout the rest
 /// of the application, each of its attributes represents a separate resource.
 #[derive(Default, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Config {
-    /// The background color of the application.
-    pub background_color: BgColor,
+    /// The background and lighting of the scene.
+    pub scene: SceneSettings,
 
     /// Whether light mode is enabled.
     pub light_mode: LightMode,
+
+    /// The number of samples used for antialiasing.
+    pub antialiasing: Antialiasing,
+
+    /// Whether polytopes are recentered when they're loaded.
+    pub recenter_on_load: RecenterOnLoad,
+
+    /// Whether saved OFF files note the hyperspheres of the polytope.
+    pub metrics_on_save: MetricsOnSave,
+
+    /// Whether the camera is fit to cross-sections.
+    pub fit_section: FitSection,
+
+    /// The number of elements above which hulls and facetings give up.
+    pub element_cap: ElementCap,
+
+    /// The background color, as saved before it became part of the scene
+    /// settings. It's moved into them when the configuration is read.
+    #[serde(skip_serializing, deserialize_with = "deserialize_some")]
+    background_color: Option<BgColor>,
+}
+
+/// Deserializes a value that, if present, is stored without being wrapped in
+/// `Some`.
+fn deserialize_some<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error> {
+    T::deserialize(deserializer).map(Some)
 }
 
 impl Config {
@@ -136,9 +323,30 @@ impl Config {
         }
     }
 
+    /// Returns the path to the configuration file in Miratope.
+    pub fn config_path() -> ConfigPath {
+        let mut config_path = ConfigPath(Self::config_dir());
+        config_path.0.push(CONF_FILE);
+        config_path
+    }
+
+    /// Reads the configuration file from its default location. See
+    /// [`Self::read`].
+    pub fn load() -> Self {
+        Self::read(Self::config_dir(), Self::config_path())
+    }
+
     /// Attempts to read the configuration from a given path.
     pub fn from_path<T: AsRef<OsStr>>(config_path: T) -> Option<Self> {
-        ron::from_str(&fs::read_to_string(config_path.as_ref()).ok()?).ok()
+        let mut config: Self =
+            ron::from_str(&fs::read_to_string(config_path.as_ref()).ok()?).ok()?;
+
+        // Older configurations stored the background color on its own.
+        if let Some(background_color) = config.background_color.take() {
+            config.scene.background_color = background_color;
+        }
+
+        Some(config)
     }
 
     /// Saves the configuration at a given location.
@@ -193,14 +401,25 @@ fn save_config(
     mut exit: EventReader<'_, '_, AppExit>,
     config_path: Res<'_, ConfigPath>,
 
-    background_color: Res<'_, ClearColor>,
+    scene: Res<'_, SceneSettings>,
     visuals: Res<'_, egui::Visuals>,
+    antialiasing: Res<'_, Antialiasing>,
+    recenter_on_load: Res<'_, RecenterOnLoad>,
+    metrics_on_save: Res<'_, MetricsOnSave>,
+    fit_section: Res<'_, FitSection>,
+    element_cap: Res<'_, ElementCap>,
 ) {
     // If the application is being exited:
     if exit.iter().next().is_some() {
         let config = Config {
-            background_color: BgColor::new(background_color.as_ref()),
+            scene: scene.clone(),
             light_mode: LightMode(!visuals.dark_mode),
+            antialiasing: *antialiasing,
+            recenter_on_load: *recenter_on_load,
+            metrics_on_save: *metrics_on_save,
+            fit_section: *fit_section,
+            element_cap: *element_cap,
+            background_color: None,
         };
 
         config.save(&config_path.0);
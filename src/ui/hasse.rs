@@ -0,0 +1,183 @@
+//! Shows the Hasse diagram of the element lattice of a polytope.
+
+use crate::Concrete;
+
+use bevy_egui::egui::{self, CtxRef, Ui};
+use miratope_core::abs::Ranked;
+use vec_like::VecLike;
+
+/// The greatest number of elements for which the diagram is drawn, so that it
+/// stays readable.
+pub const MAX_NODES: usize = 250;
+
+/// The horizontal space between two elements of the same rank, in pixels.
+const NODE_SPACING: f32 = 16.0;
+
+/// The vertical space between two ranks, in pixels.
+const RANK_SPACING: f32 = 60.0;
+
+/// The radius of the dots drawn at the elements, in pixels.
+const NODE_RADIUS: f32 = 4.0;
+
+/// The greatest width of the diagram, in pixels.
+const MAX_WIDTH: f32 = 800.0;
+
+/// The element lattice of a polytope, as the subelements of each element.
+pub struct HasseDiagram {
+    /// The indices of the subelements of every element, by rank.
+    subs: Vec<Vec<Vec<usize>>>,
+}
+
+impl HasseDiagram {
+    /// Reads off the element lattice of a polytope, or returns `None` if it
+    /// has too many elements to draw.
+    pub fn new(polytope: &Concrete) -> Option<Self> {
+        if polytope.el_count_iter().sum::<usize>() > MAX_NODES {
+            return None;
+        }
+
+        Some(Self {
+            subs: polytope
+                .ranks()
+                .iter()
+                .map(|list| list.iter().map(|el| el.subs.as_inner().clone()).collect())
+                .collect(),
+        })
+    }
+
+    /// The position of an element in the diagram, with the greatest rank at
+    /// the top.
+    fn pos(&self, rect: egui::Rect, rank: usize, idx: usize) -> egui::Pos2 {
+        let count = self.subs[rank].len() as f32;
+        let top = self.subs.len() - 1 - rank;
+        egui::pos2(
+            rect.left() + rect.width() * (idx as f32 + 0.5) / count,
+            rect.top() + NODE_RADIUS + top as f32 * RANK_SPACING,
+        )
+    }
+
+    /// Draws the diagram, with a selected element highlighted. Returns the
+    /// element that was clicked on, if any.
+    fn draw(&self, ui: &mut Ui, selected: Option<(usize, usize)>) -> Option<(usize, usize)> {
+        let widest = self.subs.iter().map(Vec::len).max().unwrap_or(1) as f32;
+        let size = egui::vec2(
+            (widest * NODE_SPACING).clamp(200.0, MAX_WIDTH),
+            (self.subs.len() - 1) as f32 * RANK_SPACING + 2.0 * NODE_RADIUS,
+        );
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+        let painter = ui.painter();
+
+        let visuals = ui.visuals();
+        let line = egui::Stroke::new(1.0, visuals.widgets.noninteractive.fg_stroke.color);
+        let fill = visuals.text_color();
+        let highlight = visuals.selection.bg_fill;
+
+        // Draws the cover relations.
+        for (rank, list) in self.subs.iter().enumerate().skip(1) {
+            for (idx, subs) in list.iter().enumerate() {
+                let stroke = if selected == Some((rank, idx)) {
+                    egui::Stroke::new(2.0, highlight)
+                } else {
+                    line
+                };
+
+                for &sub in subs {
+                    painter.line_segment(
+                        [self.pos(rect, rank, idx), self.pos(rect, rank - 1, sub)],
+                        stroke,
+                    );
+                }
+            }
+        }
+
+        // Draws the elements.
+        for (rank, list) in self.subs.iter().enumerate() {
+            for idx in 0..list.len() {
+                let color = if selected == Some((rank, idx)) {
+                    highlight
+                } else {
+                    fill
+                };
+
+                painter.circle_filled(self.pos(rect, rank, idx), NODE_RADIUS, color);
+            }
+        }
+
+        // Finds the element closest to the click.
+        if !response.clicked() {
+            return None;
+        }
+
+        let click = response.interact_pointer_pos()?;
+        let mut closest = None;
+        let mut closest_dist = 2.0 * NODE_RADIUS;
+        for (rank, list) in self.subs.iter().enumerate() {
+            for idx in 0..list.len() {
+                let dist = self.pos(rect, rank, idx).distance(click);
+                if dist <= closest_dist {
+                    closest = Some((rank, idx));
+                    closest_dist = dist;
+                }
+            }
+        }
+
+        closest
+    }
+}
+
+/// The window showing the Hasse diagram of the polytope on screen.
+#[derive(Default)]
+pub struct HasseWindow {
+    /// Whether the window is open.
+    pub open: bool,
+
+    /// The diagram, if the polytope is small enough to draw it.
+    diagram: Option<HasseDiagram>,
+
+    /// The element that was last clicked on, as its rank and index.
+    selected: Option<(usize, usize)>,
+}
+
+impl HasseWindow {
+    /// Opens the window and reads off the element lattice of a polytope.
+    pub fn open(&mut self, polytope: &Concrete) {
+        self.open = true;
+        self.diagram = HasseDiagram::new(polytope);
+        self.selected = None;
+    }
+
+    /// Shows the window, if it's open. Returns the element that was clicked
+    /// on, as its rank and index, if any.
+    pub fn show(&mut self, ctx: &CtxRef) -> Option<(usize, usize)> {
+        let diagram = &self.diagram;
+        let selected = &mut self.selected;
+        let mut clicked = None;
+
+        egui::Window::new("Hasse diagram")
+            .open(&mut self.open)
+            .resizable(false)
+            .show(ctx, |ui| match diagram {
+                Some(diagram) => {
+                    clicked = diagram.draw(ui, *selected);
+
+                    match *selected {
+                        Some((rank, idx)) => {
+                            ui.label(format!("Selected: rank {}, index {}", rank, idx))
+                        }
+                        None => ui.label("Click on an element to highlight it."),
+                    };
+                }
+                None => {
+                    ui.label(format!(
+                        "The polytope has more than {} elements, too many to draw.",
+                        MAX_NODES
+                    ));
+                }
+            });
+
+        if clicked.is_some() {
+            *selected = clicked;
+        }
+        clicked
+    }
+}
@@ -0,0 +1,134 @@
+//! Shows a histogram of the edge lengths of a polytope.
+
+use crate::{Concrete, Float};
+
+use bevy_egui::egui::{self, CtxRef, Ui};
+use miratope_core::{conc::ConcretePolytope, float::Float as Float2};
+
+/// The greatest number of bars in the histogram.
+const MAX_BUCKETS: usize = 20;
+
+/// The size of the histogram in pixels.
+const HISTOGRAM_SIZE: [f32; 2] = [240.0, 100.0];
+
+/// The edge lengths of a polytope, sorted into buckets of equal width.
+pub struct Histogram {
+    /// The least edge length.
+    pub min: Float,
+
+    /// The greatest edge length.
+    pub max: Float,
+
+    /// The mean edge length.
+    pub mean: Float,
+
+    /// The number of edges in each bucket.
+    pub buckets: Vec<usize>,
+}
+
+impl Histogram {
+    /// Sorts some edge lengths into buckets, or returns `None` if there are
+    /// none. The number of buckets grows with the number of edges, and all of
+    /// the lengths go in a single bucket if they're all equal.
+    pub fn new(lengths: &[Float]) -> Option<Self> {
+        let first = *lengths.first()?;
+        let (min, max) = lengths
+            .iter()
+            .fold((first, first), |(min, max), &len| (min.min(len), max.max(len)));
+        let mean = lengths.iter().sum::<Float>() / lengths.len() as Float;
+
+        let count = if max - min > Float::EPS * max {
+            ((lengths.len() as Float).sqrt().ceil() as usize).clamp(1, MAX_BUCKETS)
+        } else {
+            1
+        };
+
+        let mut buckets = vec![0; count];
+        for &len in lengths {
+            let idx = if count == 1 {
+                0
+            } else {
+                (((len - min) / (max - min) * count as Float) as usize).min(count - 1)
+            };
+            buckets[idx] += 1;
+        }
+
+        Some(Self {
+            min,
+            max,
+            mean,
+            buckets,
+        })
+    }
+
+    /// Draws the bars of the histogram.
+    fn draw(&self, ui: &mut Ui) {
+        let (rect, _) = ui.allocate_exact_size(HISTOGRAM_SIZE.into(), egui::Sense::hover());
+        let painter = ui.painter();
+        let color = ui.visuals().selection.bg_fill;
+
+        let tallest = *self.buckets.iter().max().unwrap_or(&1) as f32;
+        let width = rect.width() / self.buckets.len() as f32;
+
+        for (idx, &count) in self.buckets.iter().enumerate() {
+            let left = rect.left() + idx as f32 * width;
+            let top = rect.bottom() - rect.height() * count as f32 / tallest;
+
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::pos2(left + 1.0, top),
+                    egui::pos2(left + width - 1.0, rect.bottom()),
+                ),
+                0.0,
+                color,
+            );
+        }
+    }
+}
+
+/// The window showing the edge lengths of the polytope on screen.
+#[derive(Default)]
+pub struct EdgeLengthWindow {
+    /// Whether the window is open.
+    pub open: bool,
+
+    /// The histogram of the edge lengths, if the polytope has any edges.
+    histogram: Option<Histogram>,
+}
+
+impl EdgeLengthWindow {
+    /// Opens the window and computes the edge lengths of a polytope.
+    pub fn open(&mut self, polytope: &Concrete) {
+        self.open = true;
+        self.histogram = Histogram::new(&polytope.edge_lengths());
+    }
+
+    /// Shows the window, if it's open.
+    pub fn show(&mut self, ctx: &CtxRef) {
+        let histogram = &self.histogram;
+
+        egui::Window::new("Edge lengths")
+            .open(&mut self.open)
+            .resizable(false)
+            .show(ctx, |ui| match histogram {
+                Some(histogram) => {
+                    histogram.draw(ui);
+
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{:.4}", histogram.min));
+                        ui.with_layout(egui::Layout::right_to_left(), |ui| {
+                            ui.label(format!("{:.4}", histogram.max));
+                        });
+                    });
+
+                    ui.separator();
+                    ui.label(format!("Min: {}", histogram.min));
+                    ui.label(format!("Max: {}", histogram.max));
+                    ui.label(format!("Mean: {}", histogram.mean));
+                }
+                None => {
+                    ui.label("The polytope has no edges.");
+                }
+            });
+    }
+}
@@ -0,0 +1,155 @@
+//! A legend listing the colors of the faces of the polytope on screen, and
+//! which faces have each of them.
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use miratope_core::conc::Rgba;
+use vec_like::VecLike;
+
+use crate::Concrete;
+
+/// The side length of the color swatches, in points.
+const SWATCH_SIZE: f32 = 14.0;
+
+/// The plugin in charge of the color legend.
+pub struct LegendPlugin;
+
+impl Plugin for LegendPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ColorLegend>()
+            .add_system(show_legend.system().after("show_top_panel"));
+    }
+}
+
+/// Returns the name of a polygon with a given number of sides, in plural.
+fn polygon_name(sides: usize) -> String {
+    match sides {
+        3 => "triangles".to_string(),
+        4 => "quadrilaterals".to_string(),
+        5 => "pentagons".to_string(),
+        6 => "hexagons".to_string(),
+        7 => "heptagons".to_string(),
+        8 => "octagons".to_string(),
+        9 => "enneagons".to_string(),
+        10 => "decagons".to_string(),
+        12 => "dodecagons".to_string(),
+        n => format!("{}-gons", n),
+    }
+}
+
+/// A color, and the faces that have it.
+struct LegendEntry {
+    /// The color of the faces.
+    color: Rgba,
+
+    /// The number of faces of this color with each number of sides.
+    sides: BTreeMap<usize, usize>,
+}
+
+impl LegendEntry {
+    /// Describes the faces with the color, like `8 triangles, 6 octagons`.
+    fn description(&self) -> String {
+        let faces: Vec<_> = self
+            .sides
+            .iter()
+            .map(|(&sides, &count)| format!("{} {}", count, polygon_name(sides)))
+            .collect();
+
+        faces.join(", ")
+    }
+}
+
+/// The legend for the face colors of the polytope on screen.
+#[derive(Default)]
+pub struct ColorLegend {
+    /// Whether the legend is shown.
+    pub shown: bool,
+
+    /// The colors in the legend, in the order they first appear in, or `None`
+    /// if they need to be read off of the polytope again.
+    entries: Option<Vec<LegendEntry>>,
+}
+
+impl ColorLegend {
+    /// Groups the faces of a polytope by their colors.
+    fn entries(poly: &Concrete) -> Vec<LegendEntry> {
+        let colors = match poly.face_colors() {
+            Some(colors) => colors,
+            None => return Vec::new(),
+        };
+        let mut entries: Vec<LegendEntry> = Vec::new();
+
+        for (idx, &color) in colors.iter().enumerate() {
+            let entry = match entries.iter().position(|entry| entry.color == color) {
+                Some(pos) => &mut entries[pos],
+                None => {
+                    entries.push(LegendEntry {
+                        color,
+                        sides: BTreeMap::new(),
+                    });
+                    entries.last_mut().unwrap()
+                }
+            };
+
+            *entry.sides.entry(poly[(3, idx)].subs.len()).or_default() += 1;
+        }
+
+        entries
+    }
+}
+
+/// The system that shows the color legend, reading the colors off of the
+/// polytope again whenever it changes.
+fn show_legend(
+    egui_ctx: Res<'_, EguiContext>,
+    mut legend: ResMut<'_, ColorLegend>,
+    polies: Query<'_, '_, &Concrete>,
+    changed_polies: Query<'_, '_, (), Changed<Concrete>>,
+) {
+    if changed_polies.iter().next().is_some() && legend.entries.is_some() {
+        legend.entries = None;
+    }
+
+    if !legend.shown {
+        return;
+    }
+
+    let legend = &mut *legend;
+    let entries = match polies.iter().next() {
+        Some(poly) => legend
+            .entries
+            .get_or_insert_with(|| ColorLegend::entries(poly)),
+        None => return,
+    };
+
+    egui::Window::new("Color legend")
+        .open(&mut legend.shown)
+        .resizable(false)
+        .anchor(egui::Align2::LEFT_BOTTOM, [10.0, -10.0])
+        .show(egui_ctx.ctx(), |ui| {
+            if entries.is_empty() {
+                ui.label("The polytope has no face colors.");
+            }
+
+            for entry in entries.iter() {
+                ui.horizontal(|ui| {
+                    let [r, g, b, a] = entry
+                        .color
+                        .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+                    let (rect, _) = ui.allocate_exact_size(
+                        egui::vec2(SWATCH_SIZE, SWATCH_SIZE),
+                        egui::Sense::hover(),
+                    );
+                    ui.painter().rect_filled(
+                        rect,
+                        2.0,
+                        egui::Color32::from_rgba_unmultiplied(r, g, b, a),
+                    );
+
+                    ui.label(entry.description());
+                });
+            }
+        });
+}
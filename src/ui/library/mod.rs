@@ -3,12 +3,12 @@
 use std::{
     ffi::{OsStr, OsString},
     fs, io,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
-use super::{config::LibPath, main_window::PolyName};
+use super::{config::{LibPath, RecenterOnLoad}, main_window::PolyName};
 use crate::Concrete;
-use miratope_core::file::FromFile;
+use miratope_core::file::{self, off::GZ_EXT, FromFile};
 use special::*;
 
 use bevy::prelude::*;
@@ -27,7 +27,9 @@ impl Plugin for LibraryPlugin {
 
         // The library must be shown after the top panel, to avoid incorrect
         // positioning.
-        app.insert_resource(library).add_system(
+        app.insert_resource(library)
+            .init_resource::<LibraryReveal>()
+            .add_system(
             show_library
                 .system()
                 .label("show_library")
@@ -36,6 +38,67 @@ impl Plugin for LibraryPlugin {
     }
 }
 
+/// The file that's highlighted in the library, usually because it was opened
+/// through the file dialog.
+#[derive(Default)]
+pub struct LibraryReveal {
+    /// The path to the highlighted file.
+    path: Option<PathBuf>,
+
+    /// Whether the library should open the folders containing the file and
+    /// scroll to it on the next frame.
+    jump: bool,
+}
+
+impl LibraryReveal {
+    /// Highlights a file in the library, and jumps to it.
+    pub fn reveal<U: AsRef<Path>>(&mut self, path: U) {
+        let path = path.as_ref();
+        self.path = Some(fs::canonicalize(path).unwrap_or_else(|_| path.to_owned()));
+        self.jump = true;
+    }
+
+    /// Returns whether the library should open the folders up to a given path.
+    fn jump_to(&self, path: &Path) -> bool {
+        self.jump && self.path.as_ref().map_or(false, |p| p.starts_with(path))
+    }
+
+    /// Returns whether a given path is the highlighted one.
+    fn is_highlighted(&self, path: &Path) -> bool {
+        self.path.as_deref() == Some(path)
+    }
+}
+
+/// Opens the platform file manager at the folder containing a given path, or
+/// at the path itself if it is a folder.
+pub fn open_containing_folder<U: AsRef<Path>>(path: U) {
+    let path = path.as_ref();
+    let folder = if path.is_dir() {
+        path
+    } else {
+        path.parent().unwrap_or(path)
+    };
+
+    if let Err(err) = webbrowser::open(&folder.to_string_lossy()) {
+        eprintln!("Could not open folder: {}", err);
+    }
+}
+
+/// Shows a popup with the actions for a library entry whenever it's
+/// right-clicked.
+fn context_menu(ui: &mut Ui, response: &egui::Response, path: &Path) {
+    let popup_id = response.id.with("context_menu");
+    if response.secondary_clicked() {
+        ui.memory().toggle_popup(popup_id);
+    }
+
+    egui::popup::popup_below_widget(ui, popup_id, response, |ui| {
+        if ui.button("Open containing folder").clicked() {
+            open_containing_folder(path);
+        }
+    });
+}
+
 /// The result of showing the Miratope library in a particular frame.
 pub enum ShowResult {
     /// Nothing happened this frame.
@@ -183,7 +246,10 @@ impl Library {
                 // Adds a new file.
                 else {
                     let ext = path.extension();
-                    if ext == Some(OsStr::new("off")) || ext == Some(OsStr::new("ggb")) {
+                    if ext == Some(OsStr::new("off"))
+                        || ext == Some(OsStr::new("ggb"))
+                        || ext == Some(OsStr::new(GZ_EXT))
+                    {
                         contents.push(Self::new_file(path));
                     }
                 }
@@ -194,7 +260,7 @@ impl Library {
     }
 
     /// Shows the library in a given `Ui`, starting from a given path.
-    pub fn show(&mut self, ui: &mut Ui, path: PathBuf) -> ShowResult {
+    pub fn show(&mut self, ui: &mut Ui, path: PathBuf, reveal: &LibraryReveal) -> ShowResult {
         match self {
             // Shows a collapsing drop-down, and loads the folder in case it's clicked.
             Self::UnloadedFolder { name, .. } => {
@@ -203,34 +269,49 @@ impl Library {
                     contents: Self::folder_contents(&path).unwrap(),
                 };
 
-                self.show(ui, path)
+                self.show(ui, path, reveal)
             }
 
             // Shows a drop-down with all of the files and folders.
-            Self::LoadedFolder { name, contents, .. } => ui
-                .collapsing(name.clone(), |ui| {
+            Self::LoadedFolder { name, contents, .. } => {
+                let mut header = egui::CollapsingHeader::new(name.clone());
+                if reveal.jump_to(&path) {
+                    header = header.open(Some(true));
+                }
+
+                let collapsing = header.show(ui, |ui| {
                     let mut res = ShowResult::None;
 
                     for lib in contents.iter_mut() {
                         let mut new_path = path.clone();
                         new_path.push(lib.path_name());
-                        res |= lib.show(ui, new_path);
+                        res |= lib.show(ui, new_path, reveal);
                     }
 
                     res
-                })
-                .body_returned
-                .unwrap_or_default(),
+                });
+
+                context_menu(ui, &collapsing.header_response, &path);
+                collapsing.body_returned.unwrap_or_default()
+            }
 
             // Shows a button that loads the file if clicked.
             Self::File { name, .. } => {
-                let label = PathBuf::from(name as &_)
-                    .file_stem()
-                    .unwrap()
-                    .to_string_lossy()
-                    .into_owned();
+                let label = file::file_stem(name.as_str()).unwrap_or_default().to_string();
 
-                if ui.button(label).clicked() {
+                let response = if reveal.is_highlighted(&path) {
+                    let response = ui.selectable_label(true, label);
+                    if reveal.jump {
+                        response.scroll_to_me(egui::Align::Center);
+                    }
+                    response
+                } else {
+                    ui.button(label)
+                };
+
+                context_menu(ui, &response, &path);
+
+                if response.clicked() {
                     ShowResult::Load(path.into_os_string())
                 } else {
                     ShowResult::None
@@ -249,7 +330,9 @@ fn show_library(
     mut query: Query<'_, '_, &mut Concrete>,
     mut poly_name: ResMut<'_, PolyName>,
     mut library: ResMut<'_, Option<Library>>,
+    mut reveal: ResMut<'_, LibraryReveal>,
     lib_path: Res<'_, LibPath>,
+    recenter_on_load: Res<'_, RecenterOnLoad>,
 ) {
     // Shows the polytope library.
     if let Some(library) = library.as_mut() {
@@ -258,17 +341,24 @@ fn show_library(
             .max_width(450.0)
             .show(egui_ctx.ctx(), |ui| {
                 egui::containers::ScrollArea::auto_sized().show(ui, |ui| {
-                    match library.show(ui, PathBuf::from(lib_path.as_ref())) {
+                    let res = library.show(ui, PathBuf::from(lib_path.as_ref()), &reveal);
+
+                    // We only jump to a revealed file once.
+                    if reveal.jump {
+                        reveal.jump = false;
+                    }
+
+                    match res {
                         // No action needs to be taken.
                         ShowResult::None => {}
 
                         // Loads a selected file.
                         ShowResult::Load(file) => match Concrete::from_path(&file) {
-                            Ok(q) => {
+                            Ok(mut q) => {
+                                recenter_on_load.apply(&mut q);
                                 *query.iter_mut().next().unwrap() = q;
-                                let path_buf = PathBuf::from(file);
-                                let file_name = path_buf.file_name().unwrap().to_str().unwrap();
-                                poly_name.0 = file_name[..file_name.len()-4].into();
+                                reveal.reveal(&file);
+                                poly_name.0 = file::file_stem(&file).unwrap().into();
                             },
                             Err(err) => eprintln!("File open failed: {}", err),
                         },
@@ -1,9 +1,9 @@
 //! The systems that update the main window.
 
 use super::right_panel::ElementTypesRes;
-use super::{camera::ProjectionType, top_panel::SectionState};
-use crate::mesh::Renderable;
-use crate::Concrete;
+use super::{camera::{Orientation4D, ProjectionType}, config::SceneSettings, top_panel::{SectionDirection, SectionFilled, SectionState}};
+use crate::mesh::{self, DepthCue, EdgeSubdivisions, ExplodeFacets, HiddenFacets, PolytopeColors, Renderable, ShownRanks, WireframeDetail};
+use crate::{Concrete, Hyperplane};
 
 use bevy::prelude::*;
 use bevy_egui::EguiSettings;
@@ -18,8 +18,18 @@ impl Plugin for MainWindowPlugin {
     fn build(&self, app: &mut App) {
         app.add_system_to_stage(CoreStage::PreUpdate, update_visible.system())
             .add_system(update_scale_factor.system())
+            .add_system(update_depth_cue.system())
+            .add_system(update_ghost.system())
             .add_system_to_stage(CoreStage::PostUpdate, update_changed_polytopes.system())
-            .init_resource::<PolyName>();
+            .add_system_to_stage(CoreStage::PostUpdate, update_overlays.system())
+            .init_resource::<PolyName>()
+            .init_resource::<DepthCue>()
+            .init_resource::<EdgeSubdivisions>()
+            .init_resource::<WireframeDetail>()
+            .init_resource::<ExplodeFacets>()
+            .init_resource::<ShownRanks>()
+            .init_resource::<Overlays>()
+            .init_resource::<Ghost>();
     }
 }
 
@@ -31,11 +41,130 @@ impl Default for PolyName {
     }
 }
 
+/// A reference drawn together with the polytope.
+#[derive(Clone, Copy)]
+pub enum Overlay {
+    /// The coordinate axes.
+    Axes,
+
+    /// A grid on the ground plane.
+    Grid,
+
+    /// A Petrie polygon of the polytope.
+    Petrie,
+
+    /// A marker on a vertex of the polytope.
+    Vertex,
+
+    /// A faint copy of the polytope before the last operation.
+    Ghost,
+
+    /// The edges of an element selected in the Hasse diagram.
+    Element,
+
+    /// The polytope being sliced in the cross-section view, together with
+    /// the slicing hyperplanes.
+    SectionPlane,
+}
+
+/// Which overlays are shown.
+#[derive(Default)]
+pub struct Overlays {
+    /// Whether the coordinate axes are shown.
+    pub axes: bool,
+
+    /// Whether the ground grid is shown.
+    pub grid: bool,
+
+    /// Whether a Petrie polygon is highlighted.
+    pub petrie: bool,
+
+    /// The index of the highlighted vertex, if any.
+    pub vertex: Option<usize>,
+
+    /// Whether the polytope before the last operation is shown faintly behind
+    /// the current one.
+    pub ghost: bool,
+
+    /// The rank and index of the element highlighted from the Hasse diagram,
+    /// if any.
+    pub element: Option<(usize, usize)>,
+
+    /// Whether the whole polytope and the slicing hyperplanes are shown
+    /// instead of the cross-section, while the cross-section view is open.
+    pub section_plane: bool,
+}
+
+impl Overlays {
+    /// Returns whether a given overlay is shown.
+    pub fn shows(&self, overlay: Overlay) -> bool {
+        match overlay {
+            Overlay::Axes => self.axes,
+            Overlay::Grid => self.grid,
+            Overlay::Petrie => self.petrie,
+            Overlay::Vertex => self.vertex.is_some(),
+            Overlay::Ghost => self.ghost,
+            Overlay::Element => self.element.is_some(),
+            Overlay::SectionPlane => self.section_plane,
+        }
+    }
+}
+
+/// The polytopes used to draw the ghost overlay.
+#[derive(Default)]
+pub struct Ghost {
+    /// The polytope before the last operation, if any.
+    pub previous: Option<Concrete>,
+
+    /// The polytope after the last operation, which becomes the ghost on the
+    /// next one.
+    current: Option<Concrete>,
+}
+
+impl Ghost {
+    /// Stops showing the ghost, until the next operation.
+    pub fn clear(&mut self) {
+        self.previous = None;
+    }
+}
+
 pub fn update_visible(
     keyboard: Res<'_, Input<KeyCode>>,
+    section_state: Res<'_, SectionState>,
+    section_filled: Res<'_, SectionFilled>,
+    overlays: Res<'_, Overlays>,
+    mut showing_plane: Local<'_, bool>,
     mut polies_vis: Query<'_, '_, &mut Visible, With<Concrete>>,
-    mut wfs_vis: Query<'_, '_, &mut Visible, Without<Concrete>>,
+    mut wfs_vis: Query<'_, '_, &mut Visible, (Without<Concrete>, Without<Overlay>)>,
 ) {
+    let active = matches!(*section_state, SectionState::Active { .. });
+    let plane = active && overlays.section_plane;
+
+    // Hides the faces of a cross-section that's only shown as a wireframe, and
+    // shows them again once the cross-section is closed.
+    if section_filled.is_changed() || section_state.is_changed() {
+        if let Some(mut visible) = polies_vis.iter_mut().next() {
+            if !section_filled.0 {
+                visible.is_visible = !active;
+            } else if section_filled.is_changed() {
+                visible.is_visible = !plane;
+            }
+        }
+    }
+
+    // Hides the cross-section while the whole polytope and the slicing
+    // hyperplanes are shown instead.
+    if plane != *showing_plane {
+        *showing_plane = plane;
+
+        if let Some(mut visible) = polies_vis.iter_mut().next() {
+            visible.is_visible = !plane && (section_filled.0 || !active);
+        }
+        if let Some(mut visible) = wfs_vis.iter_mut().next() {
+            visible.is_visible = !plane;
+        }
+    }
+
     if keyboard.just_pressed(KeyCode::V) {
         if let Some(mut visible) = polies_vis.iter_mut().next() {
             let vis = visible.is_visible;
@@ -51,6 +180,50 @@ pub fn update_visible(
     }
 }
 
+/// Keeps a copy of the polytope before each operation while the ghost overlay
+/// is on.
+pub fn update_ghost(
+    overlays: Res<'_, Overlays>,
+    section_state: Res<'_, SectionState>,
+    mut ghost: ResMut<'_, Ghost>,
+    polies: Query<'_, '_, &Concrete>,
+    changed_polies: Query<'_, '_, (), Changed<Concrete>>,
+) {
+    // We don't store any copies if they won't be shown.
+    if !overlays.ghost {
+        if ghost.current.is_some() || ghost.previous.is_some() {
+            *ghost = Ghost::default();
+        }
+        return;
+    }
+
+    // Scrubbing through a cross-section isn't an operation.
+    if matches!(*section_state, SectionState::Active { .. }) {
+        return;
+    }
+
+    if let Some(poly) = polies.iter().next() {
+        match &ghost.current {
+            Some(current) => {
+                if changed_polies.iter().next().is_none() {
+                    return;
+                }
+
+                // The polytope is also marked as changed whenever it's merely
+                // redrawn, so we check that it actually changed.
+                if current.vertices != poly.vertices
+                    || !current.el_count_iter().eq(poly.el_count_iter())
+                {
+                    ghost.previous = ghost.current.replace(poly.clone());
+                }
+            }
+
+            // The first copy is taken when the overlay is turned on.
+            None => ghost.current = Some(poly.clone()),
+        }
+    }
+}
+
 /// Resizes the UI when the screen is resized.
 pub fn update_scale_factor(mut egui_settings: ResMut<'_, EguiSettings>, windows: Res<'_, Windows>) {
     if let Some(window) = windows.get_primary() {
@@ -58,10 +231,57 @@ pub fn update_scale_factor(mut egui_settings: ResMut<'_, EguiSettings>, windows:
     }
 }
 
-/// Updates polytopes after an operation.
+/// Keeps the camera position and background color used for depth cueing up to
+/// date, and recolors the polytope whenever the depth cueing changes.
+pub fn update_depth_cue(
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    mut depth_cue: ResMut<'_, DepthCue>,
+    scene: Res<'_, SceneSettings>,
+    cameras: Query<'_, '_, &GlobalTransform, With<PerspectiveProjection>>,
+    polies: Query<'_, '_, (&PolytopeColors, &Handle<Mesh>, &Children), With<Concrete>>,
+    wfs: Query<'_, '_, &Handle<Mesh>, Without<Concrete>>,
+) {
+    if depth_cue.enabled {
+        let mut new_cue = depth_cue.clone();
+        new_cue.background = scene.background_color.rgb();
+
+        // The camera only matters when we measure depth by distance to it.
+        if !new_cue.use_w {
+            if let Some(camera) = cameras.iter().next() {
+                new_cue.camera = camera.translation.into();
+            }
+        }
+
+        // We only assign on changes, so that the polytope isn't recolored
+        // every frame.
+        if new_cue != *depth_cue {
+            *depth_cue = new_cue;
+        }
+    }
+
+    // Only the colors change, so the meshes don't need to be rebuilt.
+    if depth_cue.is_changed() {
+        for (colors, mesh_handle, children) in polies.iter() {
+            if let Some(mesh) = meshes.get_mut(mesh_handle) {
+                depth_cue.recolor(mesh, &colors.mesh);
+            }
+
+            for child in children.iter() {
+                if let Ok(wf_handle) = wfs.get_component::<Handle<Mesh>>(*child) {
+                    if let Some(wireframe) = meshes.get_mut(wf_handle) {
+                        depth_cue.recolor(wireframe, &colors.wireframe);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Updates polytopes after an operation, after facets are hidden or shown, or
+/// after they're rotated in 4D.
 pub fn update_changed_polytopes(
     mut meshes: ResMut<'_, Assets<Mesh>>,
-    mut polies: Query<'_, '_, (&mut Concrete, &Handle<Mesh>, &Children), Changed<Concrete>>,
+    mut polies: Query<'_, '_, (&mut Concrete, &mut HiddenFacets, &mut PolytopeColors, &Handle<Mesh>, &Children)>,
     wfs: Query<'_, '_, &Handle<Mesh>, Without<Concrete>>,
     mut windows: ResMut<'_, Windows>,
     mut section_state: ResMut<'_, SectionState>,
@@ -69,30 +289,55 @@ pub fn update_changed_polytopes(
     name: Res<'_, PolyName>,
 
     orthogonal: Res<'_, ProjectionType>,
+    depth_cue: Res<'_, DepthCue>,
+    subdivisions: Res<'_, EdgeSubdivisions>,
+    detail: Res<'_, WireframeDetail>,
+    explode: Res<'_, ExplodeFacets>,
+    orientation: Res<'_, Orientation4D>,
+    shown_ranks: Res<'_, ShownRanks>,
 ) {
-    for (mut poly, mesh_handle, children) in polies.iter_mut() {
-        poly.untangle_faces();
-        if cfg!(debug_assertions) {
-            poly.assert_valid();
+    for (mut poly, hidden, mut colors, mesh_handle, children) in polies.iter_mut() {
+        // Hiding or showing facets or rotating in 4D leaves the polytope
+        // itself untouched.
+        let changed = poly.is_changed();
+        if !changed && !hidden.is_changed() && !orientation.is_changed() {
+            continue;
         }
 
-        if !element_types.main_updating {
-            element_types.main = false;
-        } else {
-            element_types.main_updating = false;
+        if changed {
+            poly.untangle_faces();
+            if cfg!(debug_assertions) {
+                poly.assert_valid();
+            }
+
+            if !element_types.main_updating {
+                element_types.main = false;
+            } else {
+                element_types.main_updating = false;
+            }
         }
 
-        *meshes.get_mut(mesh_handle).unwrap() = poly.mesh(*orthogonal);
+        // The facets may be drawn pushed apart or hidden, and rotated in 4D.
+        let exploded = explode.apply(&poly, &hidden);
+        let shown = exploded.as_ref().unwrap_or(&*poly);
+        let rotated = orientation.apply(shown);
+        let shown = rotated.as_ref().unwrap_or(shown);
+
+        let (mesh, mesh_colors) = shown_ranks.mesh(shown, *orthogonal, &depth_cue);
+        *meshes.get_mut(mesh_handle).unwrap() = mesh;
+        colors.mesh = mesh_colors;
 
         // Updates all wireframes.
         for child in children.iter() {
             if let Ok(wf_handle) = wfs.get_component::<Handle<Mesh>>(*child) {
-                *meshes.get_mut(wf_handle).unwrap() = poly.wireframe(*orthogonal);
+                let (wireframe, wireframe_colors) = shown.wireframe_with_colors(*orthogonal, &depth_cue, subdivisions.0, &shown_ranks, &detail);
+                *meshes.get_mut(wf_handle).unwrap() = wireframe;
+                colors.wireframe = wireframe_colors;
             }
         }
 
         // We reset the cross-section view if we didn't use it to change the polytope.
-        if !section_state.is_changed() {
+        if changed && !section_state.is_changed() {
             section_state.close();
         }
 
@@ -103,3 +348,99 @@ pub fn update_changed_polytopes(
 
     }
 }
+
+/// Shows or hides the overlays, and redraws them whenever the polytope or the
+/// projection changes, so that they're projected in the same way.
+#[allow(clippy::too_many_arguments)]
+pub fn update_overlays(
+    mut meshes: ResMut<'_, Assets<Mesh>>,
+    overlays: Res<'_, Overlays>,
+    ghost: Res<'_, Ghost>,
+    orientation: Res<'_, Orientation4D>,
+    projection_type: Res<'_, ProjectionType>,
+    (section_state, section_direction): (Res<'_, SectionState>, Res<'_, Vec<SectionDirection>>),
+    polies: Query<'_, '_, &Concrete>,
+    changed_polies: Query<'_, '_, (), Changed<Concrete>>,
+    mut overlay_query: Query<'_, '_, (&Overlay, &Handle<Mesh>, &mut Visible)>,
+) {
+    if !overlays.is_changed()
+        && !ghost.is_changed()
+        && !orientation.is_changed()
+        && !projection_type.is_changed()
+        && !section_state.is_changed()
+        && !section_direction.is_changed()
+        && changed_polies.iter().next().is_none()
+    {
+        return;
+    }
+
+    if let Some(poly) = polies.iter().next() {
+        // The overlays are rotated in 4D together with the polytope.
+        let rotated = orientation.apply(poly);
+        let poly = rotated.as_ref().unwrap_or(poly);
+
+        for (&overlay, mesh_handle, mut visible) in overlay_query.iter_mut() {
+            visible.is_visible = overlays.shows(overlay);
+
+            // There's no ghost until the first operation.
+            if let Overlay::Ghost = overlay {
+                visible.is_visible &= ghost.previous.is_some();
+            }
+
+            // The slicing hyperplanes are only shown while slicing.
+            if let Overlay::SectionPlane = overlay {
+                visible.is_visible &= matches!(*section_state, SectionState::Active { .. });
+            }
+
+            // Hidden overlays don't need to be redrawn.
+            if visible.is_visible {
+                *meshes.get_mut(mesh_handle).unwrap() = match overlay {
+                    Overlay::Axes => mesh::axes_mesh(poly, *projection_type),
+                    Overlay::Grid => mesh::grid_mesh(poly, *projection_type),
+                    Overlay::Petrie => mesh::petrie_mesh(poly, *projection_type),
+                    Overlay::Vertex => {
+                        mesh::vertex_mesh(poly, overlays.vertex.unwrap(), *projection_type)
+                    }
+                    Overlay::Element => {
+                        let (rank, idx) = overlays.element.unwrap();
+                        mesh::element_mesh(poly, rank, idx, *projection_type)
+                    }
+                    Overlay::Ghost => {
+                        let previous = ghost.previous.as_ref().unwrap();
+                        orientation
+                            .apply(previous)
+                            .as_ref()
+                            .unwrap_or(previous)
+                            .mesh(*projection_type, &Default::default())
+                    }
+                    Overlay::SectionPlane => match &*section_state {
+                        SectionState::Active {
+                            original_polytope,
+                            hyperplane_pos,
+                            flatten,
+                            ..
+                        } => {
+                            // Flattening moves the later hyperplanes out of
+                            // the original coordinates.
+                            let count = if *flatten { 1 } else { hyperplane_pos.len() };
+                            let planes: Vec<_> = section_direction
+                                .iter()
+                                .zip(hyperplane_pos)
+                                .take(count)
+                                .map(|(direction, &pos)| Hyperplane::new(direction.0.clone(), pos))
+                                .collect();
+
+                            mesh::section_plane_mesh(
+                                original_polytope,
+                                &planes,
+                                &orientation,
+                                *projection_type,
+                            )
+                        }
+                        SectionState::Inactive => mesh::empty_mesh(),
+                    },
+                };
+            }
+        }
+    }
+}
@@ -1,17 +1,29 @@
 //! All of the code that configures the UI.
 
-use crate::{Point, EPS};
+use crate::{Float, Point, EPS};
 use approx::abs_diff_eq;
 use bevy_egui::egui::{self, Ui, Widget};
+use miratope_core::expr;
 
 pub mod camera;
 pub mod config;
+pub mod hasse;
+pub mod histogram;
+pub mod legend;
 pub mod library;
 pub mod main_window;
 pub mod memory;
+pub mod name;
+pub mod palette;
+pub mod picking;
 pub mod window;
 pub mod top_panel;
 pub mod right_panel;
+pub mod screenshot;
+pub mod session;
+pub mod status_bar;
+pub mod tabs;
+pub mod wiki;
 
 /// All of the plugins specific to Miratope.
 pub struct MiratopePlugins;
@@ -24,8 +36,12 @@ impl bevy::prelude::PluginGroup for MiratopePlugins {
             .add(window::WindowPlugin)
             .add(library::LibraryPlugin)
             .add(main_window::MainWindowPlugin)
+            .add(picking::PickingPlugin)
+            .add(legend::LegendPlugin)
+            .add(tabs::TabsPlugin)
             .add(top_panel::TopPanelPlugin)
-            .add(right_panel::RightPanelPlugin);
+            .add(right_panel::RightPanelPlugin)
+            .add(status_bar::StatusBarPlugin);
     }
 }
 
@@ -58,6 +74,102 @@ impl<'a> Widget for PointWidget<'a> {
     }
 }
 
+/// A number that's typed in as an expression, like `pi/5` or `sqrt(2)`.
+#[derive(Clone)]
+pub struct ExprValue {
+    /// The text typed in.
+    text: String,
+
+    /// The value of the last expression that could be evaluated.
+    value: Float,
+
+    /// The error in the text typed in, if any.
+    error: Option<String>,
+}
+
+impl Default for ExprValue {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+impl ExprValue {
+    /// Initializes a new expression with a given value.
+    pub fn new(value: Float) -> Self {
+        Self {
+            text: value.to_string(),
+            value,
+            error: None,
+        }
+    }
+
+    /// Returns the value of the last expression that could be evaluated.
+    pub fn value(&self) -> Float {
+        self.value
+    }
+}
+
+/// A widget that sets a number by typing in an expression. Invalid
+/// expressions are reported next to it, and leave the previous value.
+pub struct ExprWidget<'a> {
+    label: String,
+    expr: &'a mut ExprValue,
+
+    /// Whether negative values are rejected.
+    non_negative: bool,
+}
+
+impl<'a> ExprWidget<'a> {
+    /// The width of the text field.
+    const WIDTH: f32 = 80.0;
+
+    /// Initializes a new expression widget with a given label.
+    pub fn new(expr: &'a mut ExprValue, label: impl ToString) -> Self {
+        Self {
+            label: label.to_string(),
+            expr,
+            non_negative: false,
+        }
+    }
+
+    /// Rejects negative values, like those of a radius.
+    pub fn non_negative(mut self) -> Self {
+        self.non_negative = true;
+        self
+    }
+}
+
+impl<'a> Widget for ExprWidget<'a> {
+    fn ui(self, ui: &mut Ui) -> egui::Response {
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.expr.text).desired_width(Self::WIDTH),
+            );
+
+            if response.changed() {
+                match expr::eval(&self.expr.text) {
+                    Ok(value) if self.non_negative && value < 0.0 => {
+                        self.expr.error = Some("the value can't be negative".to_string())
+                    }
+                    Ok(value) => {
+                        self.expr.value = value;
+                        self.expr.error = None;
+                    }
+                    Err(err) => self.expr.error = Some(err.to_string()),
+                }
+            }
+
+            ui.label(self.label);
+            if let Some(error) = &self.expr.error {
+                ui.colored_label(egui::Color32::RED, error);
+            }
+
+            response
+        })
+        .inner
+    }
+}
+
 /// A widget that sets up a point of unit norm.
 pub struct UnitPointWidget<'a>(PointWidget<'a>);
 
@@ -107,3 +219,57 @@ impl<'a> Widget for UnitPointWidget<'a> {
         .response
     }
 }
+
+/// A widget that sets a direction in 3D space, by dragging a point inside a
+/// circle. The circle represents the hemisphere facing the viewer.
+pub struct DirectionWidget<'a> {
+    label: String,
+    direction: &'a mut [f32; 3],
+}
+
+impl<'a> DirectionWidget<'a> {
+    /// The radius of the circle in the widget.
+    const RADIUS: f32 = 32.0;
+
+    /// Initializes a new direction widget with a given label.
+    pub fn new(direction: &'a mut [f32; 3], label: impl ToString) -> Self {
+        Self {
+            label: label.to_string(),
+            direction,
+        }
+    }
+}
+
+impl<'a> Widget for DirectionWidget<'a> {
+    fn ui(self, ui: &mut Ui) -> egui::Response {
+        ui.horizontal(|ui| {
+            let size = egui::Vec2::splat(2.0 * Self::RADIUS);
+            let (mut response, painter) = ui.allocate_painter(size, egui::Sense::drag());
+            let center = response.rect.center();
+
+            // Moves the direction to wherever the circle was dragged.
+            if let Some(pos) = response.interact_pointer_pos() {
+                let mut offset = (pos - center) / Self::RADIUS;
+                if offset.length() > 1.0 {
+                    offset = offset.normalized();
+                }
+
+                let z = (1.0 - offset.length_sq()).max(0.0).sqrt();
+                *self.direction = [offset.x, -offset.y, z];
+                response.mark_changed();
+            }
+
+            // Draws the circle and the current direction.
+            let visuals = ui.visuals();
+            let [x, y, _] = *self.direction;
+            let point = center + Self::RADIUS * egui::vec2(x, -y);
+            painter.circle_stroke(center, Self::RADIUS, visuals.widgets.noninteractive.bg_stroke);
+            painter.line_segment([center, point], visuals.widgets.inactive.fg_stroke);
+            painter.circle_filled(point, 4.0, visuals.text_color());
+
+            ui.label(self.label);
+            response
+        })
+        .inner
+    }
+}
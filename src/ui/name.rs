@@ -0,0 +1,386 @@
+//! Splits the name of a polytope into the operations that built it.
+
+use bevy_egui::egui::{self, CtxRef, Ui};
+
+/// The operations whose names are written right before the name of the
+/// polytope they're applied to, rather than as "Operation of polytope".
+const PREFIXES: [&str; 8] = [
+    "Rectified ",
+    "Truncated ",
+    "Expanded ",
+    "Beveled ",
+    "Chamfered ",
+    "Reflected ",
+    "Rotated ",
+    "Open ",
+];
+
+/// The separator between an operation and the names it's applied to.
+const OF: &str = " of ";
+
+/// The words for the number of components of a compound, starting from two.
+const COUNT_WORDS: [&str; 11] = [
+    "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven", "twelve",
+];
+
+/// The endings of element names and their plurals. Anything else is made
+/// plural by adding an "s" or "es".
+const PLURALS: [(&str, &str); 8] = [
+    ("hedron", "hedra"),
+    ("choron", "chora"),
+    ("teron", "tera"),
+    ("peton", "peta"),
+    ("exon", "exa"),
+    ("zetton", "zetta"),
+    ("yotton", "yotta"),
+    ("simplex", "simplices"),
+];
+
+/// The start of the name of a compound.
+const COMPOUND_OF: &str = "Compound of ";
+
+/// Returns the word for a number of components, or the number itself if it's
+/// too large.
+fn count_word(count: usize) -> String {
+    match COUNT_WORDS.get(count.wrapping_sub(2)) {
+        Some(word) => word.to_string(),
+        None => count.to_string(),
+    }
+}
+
+/// Reads a number of components written by [`count_word`].
+fn parse_count(word: &str) -> Option<usize> {
+    match COUNT_WORDS.iter().position(|&w| w == word) {
+        Some(idx) => Some(idx + 2),
+        None => word.parse().ok(),
+    }
+}
+
+/// Returns a name with its first letter in lowercase, as it's written in the
+/// middle of a sentence.
+fn lowercase_first(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Returns the plural of the name of a polytope, as it's written in the middle
+/// of a sentence. In names like `Dual of cube`, the operation is made plural
+/// instead.
+fn plural(name: &str) -> String {
+    let name = &lowercase_first(name);
+
+    if let Some(idx) = find_top_level(name, OF) {
+        return format!("{}{}", plural(&name[..idx]), &name[idx..]);
+    }
+
+    for (singular, ending) in PLURALS {
+        if let Some(stem) = name.strip_suffix(singular) {
+            return format!("{}{}", stem, ending);
+        }
+    }
+
+    if ["s", "x", "ch", "sh"].iter().any(|end| name.ends_with(end)) {
+        format!("{}es", name)
+    } else {
+        format!("{}s", name)
+    }
+}
+
+/// Reads a name of the form `Compound of five tetrahedra` into the number of
+/// components and their name in plural.
+fn parse_multiple(name: &str) -> Option<(usize, &str)> {
+    let rest = name.strip_prefix(COMPOUND_OF)?;
+    let idx = rest.find(' ')?;
+    Some((parse_count(&rest[..idx])?, &rest[idx + 1..]))
+}
+
+/// Returns the name of the compound of two polytopes with given names. Copies
+/// of a single polytope are counted, as in `Compound of five tetrahedra`, and
+/// anything else is listed, as in `Compound of (cube, octahedron)`.
+pub fn compound_name(a: &str, b: &str) -> String {
+    // The number of copies and the name in plural of either polytope.
+    let (count_a, plural_a) = parse_multiple(a).unwrap_or((1, ""));
+    let (count_b, plural_b) = parse_multiple(b).unwrap_or((1, ""));
+
+    let merged = match (count_a, count_b) {
+        (1, 1) if plural(a) == plural(b) => Some((2, plural(a))),
+        (1, _) if plural_b == plural(a) => Some((count_b + 1, plural_b.to_string())),
+        (_, 1) if plural_a == plural(b) => Some((count_a + 1, plural_a.to_string())),
+        (_, _) if count_a > 1 && plural_a == plural_b => {
+            Some((count_a + count_b, plural_a.to_string()))
+        }
+        _ => None,
+    };
+
+    match merged {
+        Some((count, names)) => format!("{}{} {}", COMPOUND_OF, count_word(count), names),
+        None => format!("{}({}, {})", COMPOUND_OF, a, b),
+    }
+}
+
+/// Returns the index of the first occurrence of a pattern in a string that
+/// isn't enclosed in parentheses.
+fn find_top_level(name: &str, pat: &str) -> Option<usize> {
+    let mut depth = 0usize;
+
+    for (idx, c) in name.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && name[idx..].starts_with(pat) => return Some(idx),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Splits a list of names of the form `(a, b, ...)`, or returns `None` if the
+/// string isn't such a list.
+fn split_list(list: &str) -> Option<Vec<&str>> {
+    let mut inner = list.strip_prefix('(')?.strip_suffix(')')?;
+
+    // Makes sure the parentheses at both ends match each other.
+    let mut depth = 0usize;
+    for c in inner.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.checked_sub(1)?,
+            _ => {}
+        }
+    }
+
+    let mut names = Vec::new();
+    while let Some(idx) = find_top_level(inner, ", ") {
+        names.push(&inner[..idx]);
+        inner = &inner[idx + 2..];
+    }
+    names.push(inner);
+
+    Some(names)
+}
+
+/// The name of a polytope, read as the operation that built it applied to the
+/// names of the polytopes it was built from.
+pub struct NameTree {
+    /// The operation, or the entire name if it wasn't built by one.
+    label: String,
+
+    /// The names of the polytopes the operation was applied to.
+    children: Vec<NameTree>,
+}
+
+impl NameTree {
+    /// Reads off the operations in a name, like `Prism of (Dual of cube)` or
+    /// `Duoprism of (square, triangle)`.
+    pub fn parse(name: &str) -> Self {
+        let name = name.trim();
+
+        // Names of the form "Operated polytope". These come first, since in
+        // `Truncated prism of cube`, the truncation is applied last.
+        for prefix in PREFIXES {
+            if let Some(rest) = name.strip_prefix(prefix) {
+                return Self {
+                    label: prefix.trim_end().to_string(),
+                    children: vec![Self::parse(rest)],
+                };
+            }
+        }
+
+        // Names of the form "Operation of polytope".
+        if let Some(idx) = find_top_level(name, OF) {
+            let rest = &name[idx + OF.len()..];
+
+            return Self {
+                label: name[..idx].to_string(),
+                children: match split_list(rest) {
+                    Some(names) => names.into_iter().map(Self::parse).collect(),
+                    None => vec![Self::parse(rest)],
+                },
+            };
+        }
+
+        Self {
+            label: name.to_string(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Shows the name as a tree of collapsible headers.
+    fn show(&self, ui: &mut Ui, idx: usize) {
+        if self.children.is_empty() {
+            ui.label(&self.label);
+        } else {
+            // The index makes headers for equal operations distinct.
+            egui::CollapsingHeader::new(&self.label)
+                .id_source(idx)
+                .default_open(true)
+                .show(ui, |ui| {
+                    for (idx, child) in self.children.iter().enumerate() {
+                        child.show(ui, idx);
+                    }
+                });
+        }
+    }
+}
+
+/// The window showing the structure of the name of the polytope on screen.
+#[derive(Default)]
+pub struct NameWindow {
+    /// Whether the window is open.
+    pub open: bool,
+
+    /// The structure of the name, when the window was opened.
+    tree: Option<NameTree>,
+}
+
+impl NameWindow {
+    /// Opens the window and reads off the structure of a name.
+    pub fn open(&mut self, name: &str) {
+        self.open = true;
+        self.tree = Some(NameTree::parse(name));
+    }
+
+    /// Shows the window, if it's open.
+    pub fn show(&mut self, ctx: &CtxRef) {
+        let tree = &self.tree;
+
+        egui::Window::new("Name structure")
+            .open(&mut self.open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if let Some(tree) = tree {
+                    tree.show(ui, 0);
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a name tree as `label(child, child)`, so that it's easily
+    /// compared.
+    fn structure(tree: &NameTree) -> String {
+        if tree.children.is_empty() {
+            tree.label.clone()
+        } else {
+            let children: Vec<_> = tree.children.iter().map(structure).collect();
+            format!("{}({})", tree.label, children.join(", "))
+        }
+    }
+
+    /// Parses a name and writes its structure.
+    fn parse(name: &str) -> String {
+        structure(&NameTree::parse(name))
+    }
+
+    /// Checks that names are split into the operations that built them.
+    #[test]
+    fn name_tree() {
+        assert_eq!(parse("cube"), "cube");
+        assert_eq!(parse("Dual of cube"), "Dual(cube)");
+        assert_eq!(parse("Prism of (Dual of cube)"), "Prism(Dual(cube))");
+        assert_eq!(
+            parse("Duoprism of (square, triangle)"),
+            "Duoprism(square, triangle)"
+        );
+        assert_eq!(
+            parse("Compound of (cube, Dual of (Pyramid of square))"),
+            "Compound(cube, Dual(Pyramid(square)))"
+        );
+    }
+
+    /// Checks the plurals of a few names.
+    #[test]
+    fn plurals() {
+        assert_eq!(plural("cube"), "cubes");
+        assert_eq!(plural("Cube"), "cubes");
+        assert_eq!(plural("tetrahedron"), "tetrahedra");
+        assert_eq!(plural("tesseractic choron"), "tesseractic chora");
+        assert_eq!(plural("5-simplex"), "5-simplices");
+        assert_eq!(plural("hexagonal prism"), "hexagonal prisms");
+        assert_eq!(plural("Dual of cube"), "duals of cube");
+        assert_eq!(
+            plural("Duoprism of (square, triangle)"),
+            "duoprisms of (square, triangle)"
+        );
+    }
+
+    /// Checks that the number of copies in a compound is read back.
+    #[test]
+    fn multiples() {
+        for count in 2..20 {
+            assert_eq!(parse_count(&count_word(count)), Some(count));
+        }
+
+        assert_eq!(
+            parse_multiple("Compound of five tetrahedra"),
+            Some((5, "tetrahedra"))
+        );
+        assert_eq!(parse_multiple("Compound of 20 cubes"), Some((20, "cubes")));
+        assert_eq!(parse_multiple("Compound of (cube, octahedron)"), None);
+        assert_eq!(parse_multiple("cube"), None);
+    }
+
+    /// Checks the names of compounds, including compounds of compounds.
+    #[test]
+    fn compounds() {
+        assert_eq!(compound_name("Cube", "cube"), "Compound of two cubes");
+        assert_eq!(
+            compound_name("Compound of two cubes", "Cube"),
+            "Compound of three cubes"
+        );
+        assert_eq!(
+            compound_name("tetrahedron", "Compound of four tetrahedra"),
+            "Compound of five tetrahedra"
+        );
+        assert_eq!(
+            compound_name("Compound of two cubes", "Compound of three cubes"),
+            "Compound of five cubes"
+        );
+        assert_eq!(
+            compound_name("cube", "octahedron"),
+            "Compound of (cube, octahedron)"
+        );
+        assert_eq!(
+            compound_name("Compound of two cubes", "octahedron"),
+            "Compound of (Compound of two cubes, octahedron)"
+        );
+        assert_eq!(
+            compound_name("Compound of (cube, octahedron)", "cube"),
+            "Compound of (Compound of (cube, octahedron), cube)"
+        );
+        assert_eq!(
+            parse(&compound_name(
+                "Compound of (cube, octahedron)",
+                "Dual of cube"
+            )),
+            "Compound(Compound(cube, octahedron), Dual(cube))"
+        );
+    }
+
+    /// Checks that every operation written before the name it's applied to
+    /// is read back.
+    #[test]
+    fn prefixes() {
+        for prefix in PREFIXES {
+            let op = prefix.trim_end();
+            assert_eq!(parse(&format!("{}cube", prefix)), format!("{}(cube)", op));
+        }
+
+        assert_eq!(
+            parse("Rectified Truncated cube"),
+            "Rectified(Truncated(cube))"
+        );
+        assert_eq!(parse("Chamfered Prism of cube"), "Chamfered(Prism(cube))");
+        assert_eq!(
+            parse("Pyramid of Expanded Beveled cube"),
+            "Pyramid(Expanded(Beveled(cube)))"
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! A command palette, which runs any registered action by typing part of its
+//! name.
+
+use std::sync::Arc;
+
+use super::top_panel::ExpensiveOp;
+use crate::Concrete;
+
+use bevy_egui::egui::{self, CtxRef};
+
+/// The greatest number of matching commands shown at once.
+const MAX_SHOWN: usize = 12;
+
+/// An operation applied to the polytope on screen and to its name.
+pub type Operation = Arc<dyn Fn(&mut Concrete, &mut String) + Send + Sync>;
+
+/// The event sent when a window is opened from the command palette. It stores
+/// the name of the window.
+pub struct OpenWindow(pub &'static str);
+
+/// What happens when a command is picked from the palette.
+#[derive(Clone)]
+pub enum Action {
+    /// Applies an operation to the polytope on screen and to its name.
+    Apply(Operation),
+
+    /// Runs an operation that asks for confirmation on large polytopes.
+    Expensive(ExpensiveOp),
+
+    /// Opens the window with the same name as the command.
+    Open,
+}
+
+/// A command that can be run from the palette.
+#[derive(Clone)]
+pub struct Command {
+    /// The name of the command.
+    pub name: &'static str,
+
+    /// What the command does.
+    pub action: Action,
+}
+
+impl Command {
+    /// The text shown for the command. Like in the menus, commands that open
+    /// a window end with an ellipsis.
+    fn label(&self) -> String {
+        match self.action {
+            Action::Open => format!("{}...", self.name),
+            _ => self.name.to_string(),
+        }
+    }
+}
+
+/// Every command that can be run from the palette.
+#[derive(Default)]
+pub struct CommandRegistry(Vec<Command>);
+
+impl CommandRegistry {
+    /// Registers a command with a given name.
+    pub fn register(&mut self, name: &'static str, action: Action) -> &mut Self {
+        self.0.push(Command { name, action });
+        self
+    }
+
+    /// Registers a command that applies an operation to the polytope on screen.
+    pub fn register_op<F>(&mut self, name: &'static str, op: F) -> &mut Self
+    where
+        F: Fn(&mut Concrete, &mut String) + Send + Sync + 'static,
+    {
+        self.register(name, Action::Apply(Arc::new(op)))
+    }
+
+    /// Returns the indices of the commands matching a query, best matches
+    /// first.
+    fn matches(&self, query: &str) -> Vec<usize> {
+        let mut scores: Vec<_> = self
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, command)| Some((fuzzy_score(&command.label(), query)?, idx)))
+            .collect();
+
+        scores.sort_unstable();
+        scores.into_iter().map(|(_, idx)| idx).collect()
+    }
+}
+
+/// Scores how well a name matches a query, if the characters of the query
+/// appear in order within the name. Lower scores are better matches: the
+/// score adds up the positions of the first and last matched characters, so
+/// that matches close to the start and to each other come first.
+fn fuzzy_score(name: &str, query: &str) -> Option<usize> {
+    let name = name.to_lowercase();
+    let mut chars = name.chars().enumerate();
+    let mut first = None;
+    let mut last = 0;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let (idx, _) = chars.find(|&(_, c)| c == q)?;
+        first.get_or_insert(idx);
+        last = idx;
+    }
+
+    Some(match first {
+        Some(first) => first + last,
+        None => 0,
+    })
+}
+
+/// The state of the command palette.
+#[derive(Default)]
+pub struct CommandPalette {
+    /// Whether the palette is open.
+    pub open: bool,
+
+    /// The text typed into the palette.
+    query: String,
+
+    /// The index of the highlighted command among the matching ones.
+    selected: usize,
+}
+
+impl CommandPalette {
+    /// Opens the palette if it's closed, and closes it otherwise.
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    /// Shows the palette, if it's open. Returns the command that was picked,
+    /// if any.
+    pub fn show(&mut self, ctx: &CtxRef, commands: &CommandRegistry) -> Option<Command> {
+        if !self.open {
+            return None;
+        }
+
+        let matches = commands.matches(&self.query);
+        let mut picked = None;
+
+        egui::Window::new("Command palette")
+            .collapsible(false)
+            .resizable(false)
+            .title_bar(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 40.0])
+            .show(ctx, |ui| {
+                ui.text_edit_singleline(&mut self.query).request_focus();
+
+                // Moves the highlight with the arrow keys.
+                let input = ui.input();
+                let last = matches.len().min(MAX_SHOWN).saturating_sub(1);
+                if input.key_pressed(egui::Key::ArrowDown) {
+                    self.selected = (self.selected + 1).min(last);
+                } else if input.key_pressed(egui::Key::ArrowUp) {
+                    self.selected = self.selected.saturating_sub(1);
+                } else {
+                    self.selected = self.selected.min(last);
+                }
+
+                let enter = input.key_pressed(egui::Key::Enter);
+                let escape = input.key_pressed(egui::Key::Escape);
+
+                ui.separator();
+                if matches.is_empty() {
+                    ui.label("No matching commands.");
+                }
+
+                for (i, &idx) in matches.iter().take(MAX_SHOWN).enumerate() {
+                    let label = commands.0[idx].label();
+                    if ui.selectable_label(i == self.selected, label).clicked()
+                        || (enter && i == self.selected)
+                    {
+                        picked = Some(idx);
+                    }
+                }
+
+                if escape {
+                    self.open = false;
+                }
+            });
+
+        let command = commands.0[picked?].clone();
+        self.open = false;
+        Some(command)
+    }
+}
@@ -0,0 +1,263 @@
+//! Picks the elements of the polytope on screen by clicking on them.
+
+use bevy::{prelude::*, render::camera::Camera};
+use bevy_egui::EguiContext;
+use miratope_core::abs::Ranked;
+use vec_like::VecLike;
+
+use super::{
+    camera::{Orientation4D, ProjectionType},
+    main_window::Overlays,
+};
+use crate::{mesh::projected_vertices, Concrete};
+
+/// How far from a vertex or an edge a click can be, in pixels, and still pick
+/// it.
+const PICK_RADIUS: f32 = 8.;
+
+/// How far the mouse can move between being pressed and released, in pixels,
+/// for the press to count as a click rather than a drag.
+const CLICK_TOLERANCE: f32 = 4.;
+
+/// The plugin in charge of picking elements with the mouse.
+pub struct PickingPlugin;
+
+impl Plugin for PickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Picking>()
+            // Like the camera, we only pick after the library has been shown,
+            // so that we know whether mouse input should register.
+            .add_system(pick.system().after("show_library"));
+    }
+}
+
+/// Which ranks of elements clicking on the polytope picks.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PickMode {
+    /// Picks vertices, then edges, then faces, whichever is found first.
+    Auto,
+
+    /// Only picks vertices.
+    Vertex,
+
+    /// Only picks edges, even when a face is in front of them.
+    Edge,
+
+    /// Only picks faces.
+    Face,
+}
+
+impl Default for PickMode {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+impl PickMode {
+    /// Every pick mode, in the order they're listed in the UI.
+    pub const ALL: [Self; 4] = [Self::Auto, Self::Vertex, Self::Edge, Self::Face];
+
+    /// The name of the mode, as shown in the UI.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "Auto",
+            Self::Vertex => "Vertex",
+            Self::Edge => "Edge",
+            Self::Face => "Face",
+        }
+    }
+
+    /// The ranks of the elements that can be picked, in order of priority.
+    fn ranks(self) -> &'static [usize] {
+        match self {
+            Self::Auto => &[1, 2, 3],
+            Self::Vertex => &[1],
+            Self::Edge => &[2],
+            Self::Face => &[3],
+        }
+    }
+}
+
+/// Whether clicking on the polytope picks its elements, and which ones.
+#[derive(Default)]
+pub struct Picking {
+    /// Whether clicking picks elements. Dragging always rotates the camera.
+    pub enabled: bool,
+
+    /// Which ranks of elements are picked.
+    pub mode: PickMode,
+}
+
+/// Returns the distance from a point to the segment between two others.
+fn segment_distance(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    let t = if len_sq > 0. {
+        ((p - a).dot(ab) / len_sq).clamp(0., 1.)
+    } else {
+        0.
+    };
+
+    p.distance(a + t * ab)
+}
+
+/// The vertices of the polytope on screen, as seen from the camera.
+struct ScreenVertices {
+    /// The position of every vertex on the screen, in pixels, or `None` if
+    /// it's behind the camera.
+    pos: Vec<Option<Vec2>>,
+
+    /// The distance from every vertex to the camera.
+    depth: Vec<f32>,
+}
+
+impl ScreenVertices {
+    /// Returns the screen positions of the endpoints of an edge, if both are
+    /// visible.
+    fn edge(&self, poly: &Concrete, idx: usize) -> Option<(Vec2, Vec2)> {
+        let subs = &poly[(2, idx)].subs;
+        Some((self.pos[subs[0]]?, self.pos[subs[1]]?))
+    }
+
+    /// Returns the average distance from the endpoints of some edges to the
+    /// camera.
+    fn edge_depth<'a, I: Iterator<Item = &'a usize>>(&self, poly: &Concrete, edges: I) -> f32 {
+        let mut sum = 0.;
+        let mut count = 0;
+
+        for &edge in edges {
+            for &v in poly[(2, edge)].subs.iter() {
+                sum += self.depth[v];
+                count += 1;
+            }
+        }
+
+        sum / count.max(1) as f32
+    }
+
+    /// Returns whether a point lies on a face, by the even-odd rule applied to
+    /// its edges. This also works for faces made out of several polygons.
+    fn face_contains(&self, poly: &Concrete, idx: usize, p: Vec2) -> bool {
+        let mut inside = false;
+
+        for &edge in poly[(3, idx)].subs.iter() {
+            let (a, b) = match self.edge(poly, edge) {
+                Some(edge) => edge,
+                None => return false,
+            };
+
+            if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+                inside = !inside;
+            }
+        }
+
+        inside
+    }
+
+    /// Finds the element of a given rank under the cursor that's closest to
+    /// the camera, if any.
+    fn pick(&self, poly: &Concrete, rank: usize, cursor: Vec2) -> Option<usize> {
+        if rank > poly.rank() {
+            return None;
+        }
+
+        let depths = (0..poly[rank].len()).filter_map(|idx| {
+            let depth = match rank {
+                1 => {
+                    (self.pos[idx]?.distance(cursor) < PICK_RADIUS).then(|| self.depth[idx])?
+                }
+                2 => {
+                    let (a, b) = self.edge(poly, idx)?;
+                    (segment_distance(cursor, a, b) < PICK_RADIUS)
+                        .then(|| self.edge_depth(poly, [idx].iter()))?
+                }
+                _ => self
+                    .face_contains(poly, idx, cursor)
+                    .then(|| self.edge_depth(poly, poly[(3, idx)].subs.iter()))?,
+            };
+
+            Some((idx, depth))
+        });
+
+        depths
+            .min_by(|(_, d0), (_, d1)| d0.partial_cmp(d1).unwrap())
+            .map(|(idx, _)| idx)
+    }
+}
+
+/// The system that highlights the element clicked on, when picking is
+/// enabled. Clicking on empty space clears the highlight.
+#[allow(clippy::too_many_arguments)]
+fn pick(
+    mouse_button: Res<'_, Input<MouseButton>>,
+    windows: Res<'_, Windows>,
+    egui_ctx: Res<'_, EguiContext>,
+    picking: Res<'_, Picking>,
+    orientation: Res<'_, Orientation4D>,
+    projection_type: Res<'_, ProjectionType>,
+    mut overlays: ResMut<'_, Overlays>,
+    mut pressed_at: Local<'_, Option<Vec2>>,
+    cameras: Query<'_, '_, (&Camera, &GlobalTransform), With<PerspectiveProjection>>,
+    polies: Query<'_, '_, &Concrete>,
+) {
+    if !picking.enabled {
+        return;
+    }
+
+    let cursor = match windows.get_primary().and_then(Window::cursor_position) {
+        Some(cursor) => cursor,
+        None => return,
+    };
+
+    // Clicks on the UI don't pick anything.
+    if mouse_button.just_pressed(MouseButton::Left) {
+        *pressed_at = (!egui_ctx.ctx().wants_pointer_input()).then(|| cursor);
+    }
+
+    // Only clicks pick elements, since dragging rotates the camera.
+    if !mouse_button.just_released(MouseButton::Left) {
+        return;
+    }
+    match pressed_at.take() {
+        Some(pressed) if pressed.distance(cursor) <= CLICK_TOLERANCE => {}
+        _ => return,
+    }
+
+    let (camera, camera_tf) = match cameras.iter().next() {
+        Some(camera) => camera,
+        None => return,
+    };
+    let poly = match polies.iter().next() {
+        Some(poly) => poly,
+        None => return,
+    };
+
+    // The vertices are projected in the same way as they're drawn.
+    let rotated = orientation.apply(poly);
+    let poly = rotated.as_ref().unwrap_or(poly);
+    let world: Vec<_> = projected_vertices(poly, *projection_type)
+        .iter()
+        .map(|v| Vec3::new(v[0] as f32, v[1] as f32, v[2] as f32))
+        .collect();
+
+    let screen = ScreenVertices {
+        pos: world
+            .iter()
+            .map(|&v| camera.world_to_screen(&windows, camera_tf, v))
+            .collect(),
+        depth: world
+            .iter()
+            .map(|&v| v.distance(camera_tf.translation))
+            .collect(),
+    };
+
+    let picked = picking
+        .mode
+        .ranks()
+        .iter()
+        .find_map(|&rank| Some((rank, screen.pick(poly, rank, cursor)?)));
+
+    if overlays.element != picked {
+        overlays.element = picked;
+    }
+}
@@ -1,6 +1,6 @@
 //! Contains all code related to the right side panel.
 
-use crate::Concrete;
+use crate::{Concrete, Float, Hypersphere};
 
 use bevy::prelude::*;
 use bevy_egui::{
@@ -10,7 +10,9 @@ use bevy_egui::{
 use miratope_core::{conc::{element_types::{EL_NAMES, EL_SUFFIXES}, ConcretePolytope}, Polytope, abs::Ranked, geometry::{Subspace, Point, Vector}};
 use vec_like::VecLike;
 
-use super::{top_panel::{SectionDirection, SectionState}, main_window::PolyName};
+use crate::mesh::HiddenFacets;
+
+use super::{top_panel::{SectionDirection, SectionState}, main_window::{Overlays, PolyName}, ExprValue, ExprWidget, PointWidget};
 
 #[derive(Clone, Copy, Debug)]
 struct ElementTypeWithData {
@@ -27,7 +29,7 @@ struct ElementTypeWithData {
     fig_facets: usize,
 
     /// The circumradius of the element, or distance from the origin if it's a vertex.
-    radius: Option<f64>,
+    radius: Option<Float>,
 }
 
 #[derive(Clone)]
@@ -45,6 +47,9 @@ pub struct ElementTypesRes {
     /// The element types.
     types: Vec<Vec<ElementTypeWithData>>,
 
+    /// The index of the type of every facet.
+    facet_types: Vec<usize>,
+
     /// The components.
     components: Option<Vec<Concrete>>,
 
@@ -62,6 +67,7 @@ impl Default for ElementTypesRes {
             poly: Concrete::nullitope(),
             poly_name: "nullitope".to_string(),
             types: Vec::new(),
+            facet_types: Vec::new(),
             components: None,
             main: true,
             main_updating: false,
@@ -74,7 +80,13 @@ impl ElementTypesRes {
         let mut poly = poly.clone();
         poly.element_sort();
 
-        let plain_types = poly.element_types();
+        let (plain_types, type_of_element) = poly.element_types_common();
+        let facet_types = poly
+            .rank()
+            .checked_sub(1)
+            .and_then(|r| type_of_element.get(r))
+            .cloned()
+            .unwrap_or_default();
         let mut types_with_data = Vec::new();
     
         for (r, types) in plain_types.clone().into_iter().enumerate() {
@@ -120,6 +132,7 @@ impl ElementTypesRes {
             poly: poly.clone(),
             poly_name,
             types: types_with_data,
+            facet_types,
             components: None,
             main: true,
             main_updating: false,
@@ -132,12 +145,277 @@ impl ElementTypesRes {
     }
 }
 
+/// A step taken while exploring the element lattice of a polytope.
+struct NavStep {
+    /// The polytope we stepped down from.
+    parent: Concrete,
+
+    /// The name of the polytope we stepped down from.
+    parent_name: String,
+
+    /// A label for the element we stepped into.
+    label: String,
+}
+
+/// Stores the path taken while navigating into the facets and vertex figures
+/// of a polytope, so that navigating up returns the exact parent.
+#[derive(Default)]
+pub struct LatticeNav {
+    /// The steps taken so far, from the outermost polytope inwards.
+    stack: Vec<NavStep>,
+
+    /// The index of the element to step into next.
+    idx: usize,
+}
+
+impl LatticeNav {
+    /// Returns the breadcrumb of the path taken so far.
+    fn breadcrumb(&self, name: &str) -> String {
+        match self.stack.first() {
+            Some(first) => {
+                let mut breadcrumb = first.parent_name.clone();
+                for step in &self.stack {
+                    breadcrumb.push_str(" › ");
+                    breadcrumb.push_str(&step.label);
+                }
+                breadcrumb
+            }
+            None => name.to_string(),
+        }
+    }
+
+    /// Steps down into a facet of the polytope.
+    fn facet(&mut self, p: &mut Concrete, poly_name: &mut PolyName) {
+        let rank = p.rank();
+        if rank < 2 {
+            return;
+        }
+
+        p.element_sort();
+        match p.element(rank - 1, self.idx) {
+            Some(mut facet) => {
+                facet.flatten();
+                facet.recenter();
+                let label = format!("facet {}", self.idx);
+                self.push(p, facet, poly_name, label);
+            }
+            None => eprintln!("Facet failed: no facet with index {}", self.idx),
+        }
+    }
+
+    /// Steps down into a vertex figure of the polytope.
+    fn verf(&mut self, p: &mut Concrete, poly_name: &mut PolyName) {
+        p.element_sort();
+
+        match p.element_fig(1, self.idx) {
+            Ok(Some(mut figure)) => {
+                figure.flatten();
+                figure.recenter();
+                let label = format!("verf {}", self.idx);
+                self.push(p, figure, poly_name, label);
+            }
+            Ok(None) => eprintln!("Verf failed: no vertex with index {}", self.idx),
+            Err(err) => eprintln!("Verf failed: {}", err),
+        }
+    }
+
+    /// Replaces the polytope by an element, and records the step.
+    fn push(&mut self, p: &mut Concrete, element: Concrete, poly_name: &mut PolyName, label: String) {
+        let parent = std::mem::replace(p, element);
+        let parent_name = std::mem::take(&mut poly_name.0);
+        poly_name.0 = format!("{} of {}", label, parent_name);
+
+        self.stack.push(NavStep {
+            parent,
+            parent_name,
+            label,
+        });
+        self.idx = 0;
+    }
+
+    /// Steps back up into the parent polytope.
+    fn up(&mut self, p: &mut Concrete, poly_name: &mut PolyName) {
+        if let Some(step) = self.stack.pop() {
+            *p = step.parent;
+            poly_name.0 = step.parent_name;
+            self.idx = 0;
+        }
+    }
+
+    /// Steps back up into the outermost polytope.
+    fn top(&mut self, p: &mut Concrete, poly_name: &mut PolyName) {
+        if !self.stack.is_empty() {
+            let step = self.stack.swap_remove(0);
+            self.stack.clear();
+            *p = step.parent;
+            poly_name.0 = step.parent_name;
+            self.idx = 0;
+        }
+    }
+}
+
+/// Shows the element lattice explorer, and handles its hotkeys.
+fn show_explorer(
+    ui: &mut egui::Ui,
+    mut p: Mut<'_, Concrete>,
+    poly_name: &mut PolyName,
+    nav: &mut LatticeNav,
+    keyboard: &Input<KeyCode>,
+    hotkeys: bool,
+) {
+    let rank = p.rank();
+    let facet_count = if rank >= 2 { p.facet_count() } else { 0 };
+    let max_idx = facet_count.max(p.vertex_count()).saturating_sub(1);
+    nav.idx = nav.idx.min(max_idx);
+
+    ui.heading("Explorer");
+    ui.label(nav.breadcrumb(&poly_name.0));
+
+    ui.horizontal(|ui| {
+        if ui.add(egui::Button::new("Top").enabled(!nav.stack.is_empty())).clicked() {
+            nav.top(&mut p, poly_name);
+        }
+
+        if ui.add(egui::Button::new("Up").enabled(!nav.stack.is_empty())).clicked()
+            || (hotkeys && keyboard.just_pressed(KeyCode::Up))
+        {
+            nav.up(&mut p, poly_name);
+        }
+
+        ui.add(egui::DragValue::new(&mut nav.idx).clamp_range(0..=max_idx));
+
+        if ui.add(egui::Button::new("Facet").enabled(nav.idx < facet_count)).clicked()
+            || (hotkeys && keyboard.just_pressed(KeyCode::Down))
+        {
+            nav.facet(&mut p, poly_name);
+        }
+
+        if ui.add(egui::Button::new("Verf").enabled(rank >= 2 && nav.idx < p.vertex_count())).clicked() {
+            nav.verf(&mut p, poly_name);
+        }
+    });
+
+    // Cycles through the elements.
+    if hotkeys {
+        if keyboard.just_pressed(KeyCode::Right) && nav.idx < max_idx {
+            nav.idx += 1;
+        }
+        if keyboard.just_pressed(KeyCode::Left) && nav.idx > 0 {
+            nav.idx -= 1;
+        }
+    }
+}
+
+/// Stores the point used to look up the nearest vertex of the polytope, which
+/// is also the center of the polar dual.
+pub struct VertexSearch {
+    /// The point to compare against the vertices.
+    point: Point<Float>,
+
+    /// The radius of the sphere the polar dual is taken with.
+    radius: ExprValue,
+}
+
+impl Default for VertexSearch {
+    fn default() -> Self {
+        Self {
+            point: Point::zeros(0),
+            radius: ExprValue::new(1.0),
+        }
+    }
+}
+
+/// Shows the vertex search, which finds and highlights the vertex closest to
+/// some given coordinates, along with its incidences.
+fn show_vertex_search(
+    ui: &mut egui::Ui,
+    p: &Concrete,
+    search: &mut VertexSearch,
+    overlays: &mut ResMut<'_, Overlays>,
+) {
+    let dim = p.dim_or();
+    if search.point.len() != dim {
+        search.point = Point::zeros(dim);
+    }
+
+    ui.heading("Vertex search");
+
+    ui.horizontal(|ui| {
+        ui.add(PointWidget::new(&mut search.point, ""));
+
+        if ui.button("Find").clicked() {
+            overlays.vertex = p.nearest_vertex(&search.point);
+        }
+
+        if ui.add(egui::Button::new("Clear").enabled(overlays.vertex.is_some())).clicked() {
+            overlays.vertex = None;
+        }
+    });
+
+    // The highlighted vertex might be gone if the polytope changed.
+    if let Some(idx) = overlays.vertex.filter(|&idx| idx < p.vertex_count()) {
+        let coords: Vec<_> = p.vertices[idx].iter().map(|x| format!("{:.6}", x)).collect();
+        ui.label(format!("Vertex {}: ({})", idx, coords.join(", ")));
+
+        if p.rank() >= 2 {
+            let edges = &p.abs[(1, idx)].sups;
+            let neighbors: Vec<_> = edges
+                .iter()
+                .filter_map(|&edge| p.abs[(2, edge)].subs.iter().find(|&&v| v != idx))
+                .map(usize::to_string)
+                .collect();
+            let edges: Vec<_> = edges.iter().map(usize::to_string).collect();
+
+            ui.label(format!("Edges: {}", edges.join(", ")));
+            ui.label(format!("Neighbors: {}", neighbors.join(", ")));
+        }
+    }
+}
+
+/// Shows the controls that take the polar dual of the polytope about the point
+/// in the vertex search, rather than about the origin.
+fn show_polar_dual(
+    ui: &mut egui::Ui,
+    mut p: Mut<'_, Concrete>,
+    poly_name: &mut PolyName,
+    search: &mut VertexSearch,
+) {
+    ui.horizontal(|ui| {
+        // Non-centered polytopes are best dualized about their gravicenter.
+        if ui.button("Gravicenter").clicked() {
+            if let Some(gravicenter) = p.gravicenter() {
+                search.point = gravicenter;
+            }
+        }
+
+        ui.add(ExprWidget::new(&mut search.radius, "Radius").non_negative());
+    });
+
+    if ui
+        .button("Dual about here")
+        .on_hover_text("Reciprocates about the point in the vertex search")
+        .clicked()
+    {
+        let sphere = Hypersphere::with_radius(search.point.clone(), search.radius.value());
+
+        match p.try_dual_mut_with(&sphere) {
+            Ok(_) => {
+                poly_name.0 = format!("Dual of {}", poly_name.0);
+                println!("Dual succeeded.");
+            }
+            Err(err) => eprintln!("Dual failed: {}. Try moving the center off of it.", err),
+        }
+    }
+}
+
 /// The plugin in charge of everything on the right panel.
 pub struct RightPanelPlugin;
 
 impl Plugin for RightPanelPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<ElementTypesRes>()
+            .init_resource::<LatticeNav>()
+            .init_resource::<VertexSearch>()
             // The top panel must be shown first.
             .add_system(
                 show_right_panel
@@ -154,10 +432,15 @@ pub fn show_right_panel(
     // Info about the application state.
     egui_ctx: Res<'_, EguiContext>,
     mut query: Query<'_, '_, &mut Concrete>,
+    mut hidden_facets: Query<'_, '_, &mut HiddenFacets>,
     mut poly_name: ResMut<'_, PolyName>,
+    keyboard: Res<'_, Input<KeyCode>>,
 
     // The Miratope resources controlled by the right panel.
     mut element_types: ResMut<'_, ElementTypesRes>,
+    mut nav: ResMut<'_, LatticeNav>,
+    mut vertex_search: ResMut<'_, VertexSearch>,
+    mut overlays: ResMut<'_, Overlays>,
     mut section_direction: ResMut<'_, Vec<SectionDirection>>,
     section_state: Res<'_, SectionState>
 ) {
@@ -173,6 +456,11 @@ pub fn show_right_panel(
                         element_types.main = true;
                         *element_types = element_types.from_poly(p, poly_name.0.clone());
                     }
+
+                    // The facet indices may no longer be the same.
+                    for mut hidden in hidden_facets.iter_mut() {
+                        *hidden = HiddenFacets::default();
+                    }
                 }
     
                 if ui.add(egui::Button::new("Load").enabled(!element_types.main)).clicked() {
@@ -187,7 +475,26 @@ pub fn show_right_panel(
 
             ui.separator();
 
+            // Arrow keys shouldn't navigate while typing.
+            let hotkeys = !egui_ctx.ctx().wants_keyboard_input();
+            if let Some(p) = query.iter_mut().next() {
+                show_explorer(ui, p, &mut poly_name, &mut nav, &keyboard, hotkeys);
+            }
+
+            ui.separator();
+
+            if let Some(p) = query.iter_mut().next() {
+                show_vertex_search(ui, &p, &mut vertex_search, &mut overlays);
+                show_polar_dual(ui, p, &mut poly_name, &mut vertex_search);
+            }
+
+            ui.separator();
+
             if element_types.active {
+                // Facets can only be hidden if the types are of a polytope
+                // with as many facets as the one on screen.
+                let screen_facets = query.iter().next().map(|p| p.facet_count());
+
                 egui::containers::ScrollArea::auto_sized().show(ui, |ui| {
                     for (r, types) in element_types.types.clone().into_iter().enumerate().skip(1) {
                         let poly = &element_types.poly;
@@ -201,8 +508,16 @@ pub fn show_right_panel(
                             if rank > EL_NAMES.len() {format!("{}-elements", r-1)}
                             else {EL_NAMES[r].to_string()})
                         );
-                        for t in types {
+                        // Whether the facets of each type can be hidden.
+                        let hideable = r + 1 == rank
+                            && rank >= 4
+                            && screen_facets == Some(element_types.facet_types.len());
+
+                        for (type_idx, t) in types.into_iter().enumerate() {
                             let i = t.example;
+                            let of_type: Vec<_> = (0..element_types.facet_types.len())
+                                .filter(|&idx| element_types.facet_types[idx] == type_idx)
+                                .collect();
 
                             ui.horizontal(|ui| {
 
@@ -247,6 +562,21 @@ pub fn show_right_panel(
                                     }
                                 }
 
+                                // Moves the element's centroid to the origin. This
+                                // only makes sense if the types are of the polytope
+                                // on screen.
+                                if ui
+                                    .add(egui::Button::new("Recenter here").enabled(element_types.main))
+                                    .clicked()
+                                {
+                                    if let Some(mut p) = query.iter_mut().next() {
+                                        match poly.element(r, i).and_then(|element| element.gravicenter()) {
+                                            Some(center) => p.recenter_with(&center),
+                                            None => eprintln!("Recenter failed: no element at rank {}, index {}", r, i),
+                                        }
+                                    }
+                                }
+
                                 if let SectionState::Active{..} = section_state.clone() {
                                     if section_direction[0].0.len() == rank-1 { // Checks if the sliced polytope and the polytope the types are of have the same rank.
                                         if ui.button("Align slice").clicked() {
@@ -268,7 +598,49 @@ pub fn show_right_panel(
                                         else {format!("radius {:.10}", radius)}
                                     );
                                 }
+
+                                // Hides or shows every facet of this type at once.
+                                if hideable {
+                                    if let Some(mut hidden) = hidden_facets.iter_mut().next() {
+                                        let was_shown = of_type.iter().all(|&idx| !hidden.is_hidden(idx));
+                                        let mut shown = was_shown;
+                                        ui.checkbox(&mut shown, "Shown");
+
+                                        if shown != was_shown {
+                                            for &idx in &of_type {
+                                                hidden.set(element_types.facet_types.len(), idx, !shown);
+                                            }
+                                        }
+                                    }
+                                }
                             });
+
+                            // Hides or shows the facets of this type one by one.
+                            if hideable {
+                                if let Some(mut hidden) = hidden_facets.iter_mut().next() {
+                                    egui::CollapsingHeader::new("Facets")
+                                        .id_source((r, type_idx))
+                                        .show(ui, |ui| {
+                                            ui.horizontal_wrapped(|ui| {
+                                                for &idx in &of_type {
+                                                    let mut shown = !hidden.is_hidden(idx);
+                                                    if ui.checkbox(&mut shown, idx.to_string()).changed() {
+                                                        hidden.set(element_types.facet_types.len(), idx, !shown);
+                                                    }
+                                                }
+                                            });
+                                        });
+                                }
+                            }
+                        }
+
+                        // Shows every hidden facet again.
+                        if r + 1 == rank {
+                            if let Some(mut hidden) = hidden_facets.iter_mut().next() {
+                                if hidden.0.contains(&true) && ui.button("Show all facets").clicked() {
+                                    *hidden = HiddenFacets::default();
+                                }
+                            }
                         }
 
                         ui.separator();
@@ -0,0 +1,353 @@
+//! Renders the polytope into images, the same way it's drawn on screen, so
+//! that it can be saved as a screenshot or as the frames of an animation.
+
+use std::{fs::File, io, path::Path};
+
+use super::camera::{Orientation4D, ProjectionType};
+use crate::{
+    mesh::{
+        DepthCue, ExplodeFacets, HiddenFacets, MeshColors, Renderable, ShownRanks, WireframeDetail,
+    },
+    Concrete,
+};
+
+use bevy::{
+    prelude::*,
+    render::{camera::Camera, mesh::Indices, pipeline::PrimitiveTopology},
+};
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, Frame, ImageResult, RgbaImage,
+};
+
+/// The file extension for GIF animations.
+pub const GIF_EXT: &str = "gif";
+
+/// The file extension for PNG images.
+pub const PNG_EXT: &str = "png";
+
+/// How much farther from the camera than a face a line may be while still
+/// being drawn over it, relative to its distance. Otherwise, the edges would
+/// flicker in and out of the faces they bound.
+const LINE_BIAS: f32 = 1e-3;
+
+/// The point of view and the lighting from which a screenshot is taken.
+#[derive(Clone, Copy)]
+pub struct View {
+    /// The width of the image in pixels.
+    width: u32,
+
+    /// The height of the image in pixels.
+    height: u32,
+
+    /// Takes the world coordinates of a point to clip space.
+    view_proj: Mat4,
+
+    /// The position of the light.
+    light: Vec3,
+
+    /// The brightness of the ambient light.
+    ambient: f32,
+
+    /// The background color.
+    background: [f32; 3],
+}
+
+impl View {
+    /// Takes the view from a camera, lit by a light at a given position, and
+    /// with the given ambient light and background color. The image is as
+    /// large as the given window.
+    pub fn new(
+        window: &Window,
+        camera: &Camera,
+        camera_tf: &GlobalTransform,
+        light: &GlobalTransform,
+        ambient: f32,
+        background: [f32; 3],
+    ) -> Self {
+        Self {
+            width: window.physical_width().max(1),
+            height: window.physical_height().max(1),
+            view_proj: camera.projection_matrix * camera_tf.compute_matrix().inverse(),
+            light: light.translation,
+            ambient,
+            background,
+        }
+    }
+
+    /// Renders a polytope from this view, drawn in a given style.
+    pub fn render(&self, poly: &Concrete, style: &DrawStyle<'_>) -> RgbaImage {
+        let mut screenshot = Screenshot::new(self);
+
+        // The facets may be drawn pushed apart or hidden, and rotated in 4D.
+        let exploded = style.explode.apply(poly, style.hidden);
+        let shown = exploded.as_ref().unwrap_or(poly);
+        let rotated = style.orientation.apply(shown);
+        let shown = rotated.as_ref().unwrap_or(shown);
+
+        if let Some(material) = style.mesh_material {
+            let (mesh, colors) =
+                style
+                    .shown_ranks
+                    .mesh(shown, style.projection_type, style.depth_cue);
+            screenshot.draw(&mesh, &colors, style.depth_cue, material);
+        }
+
+        if let Some(material) = style.wireframe_material {
+            let (wireframe, colors) = shown.wireframe_with_colors(
+                style.projection_type,
+                style.depth_cue,
+                style.subdivisions,
+                style.shown_ranks,
+                style.detail,
+            );
+            screenshot.draw(&wireframe, &colors, style.depth_cue, material);
+        }
+
+        screenshot.image
+    }
+
+    /// Returns the position of a point on the image, and its distance to the
+    /// camera, or `None` if it's behind the camera.
+    fn project(&self, point: [f32; 3]) -> Option<Vec3> {
+        let clip = self.view_proj * Vec3::from(point).extend(1.0);
+        if clip.w <= f32::EPSILON {
+            return None;
+        }
+
+        let (x, y) = (clip.x / clip.w, clip.y / clip.w);
+        Some(Vec3::new(
+            (x + 1.0) / 2.0 * self.width as f32,
+            (1.0 - y) / 2.0 * self.height as f32,
+            clip.w,
+        ))
+    }
+}
+
+/// Everything that determines how the polytope is drawn on screen.
+#[derive(Clone, Copy)]
+pub struct DrawStyle<'a> {
+    /// How the polytope is projected into 3D.
+    pub projection_type: ProjectionType,
+
+    /// How the far away vertices are faded.
+    pub depth_cue: &'a DepthCue,
+
+    /// The elements that are drawn.
+    pub shown_ranks: &'a ShownRanks,
+
+    /// The number of segments each edge is split into.
+    pub subdivisions: usize,
+
+    /// How many edges are drawn.
+    pub detail: &'a WireframeDetail,
+
+    /// How far the facets are pushed apart.
+    pub explode: &'a ExplodeFacets,
+
+    /// The facets that aren't drawn.
+    pub hidden: &'a HiddenFacets,
+
+    /// The orientation of the polytope in 4D.
+    pub orientation: &'a Orientation4D,
+
+    /// The material of the faces, or `None` if they're hidden.
+    pub mesh_material: Option<&'a StandardMaterial>,
+
+    /// The material of the wireframe, or `None` if it's hidden.
+    pub wireframe_material: Option<&'a StandardMaterial>,
+}
+
+/// An image being rendered, along with the distance from the camera to what's
+/// drawn on each pixel.
+struct Screenshot<'a> {
+    /// The view from which the image is rendered.
+    view: &'a View,
+
+    /// The image itself.
+    image: RgbaImage,
+
+    /// The distance from the camera to what's drawn on each pixel.
+    depth: Vec<f32>,
+}
+
+impl<'a> Screenshot<'a> {
+    /// Initializes an image filled with the background color.
+    fn new(view: &'a View) -> Self {
+        let [r, g, b] = view.background.map(to_u8);
+
+        Self {
+            view,
+            image: RgbaImage::from_pixel(view.width, view.height, image::Rgba([r, g, b, 255])),
+            depth: vec![f32::INFINITY; (view.width * view.height) as usize],
+        }
+    }
+
+    /// Draws a mesh with a given material, faded by depth like on screen. The
+    /// faces are lit in the same way as in the shader of the no-cull pipeline.
+    fn draw(
+        &mut self,
+        mesh: &Mesh,
+        colors: &MeshColors,
+        depth_cue: &DepthCue,
+        material: &StandardMaterial,
+    ) {
+        let indices: Vec<usize> = match mesh.indices() {
+            Some(Indices::U16(indices)) => indices.iter().map(|&idx| idx as usize).collect(),
+            Some(Indices::U32(indices)) => indices.iter().map(|&idx| idx as usize).collect(),
+            None => return,
+        };
+
+        let positions = colors.positions();
+        let base = material.base_color.as_rgba_f32();
+        let vertex_colors: Vec<[f32; 3]> = depth_cue
+            .colors(colors)
+            .iter()
+            .map(|color| [0, 1, 2].map(|i| color[i] * base[i]))
+            .collect();
+
+        match mesh.primitive_topology() {
+            PrimitiveTopology::TriangleList => {
+                for triangle in indices.chunks_exact(3) {
+                    let [a, b, c] = [triangle[0], triangle[1], triangle[2]];
+                    if [a, b, c].iter().any(|&idx| idx >= positions.len()) {
+                        continue;
+                    }
+
+                    let [p, q, r] = [a, b, c].map(|idx| Vec3::from(positions[idx]));
+                    let normal = (q - p).cross(r - p).normalize_or_zero();
+                    let diffuse = normal.dot(self.view.light.normalize_or_zero()).abs();
+                    let light = (self.view.ambient + diffuse).min(1.0);
+
+                    self.triangle(
+                        [a, b, c].map(|idx| positions[idx]),
+                        [a, b, c].map(|idx| vertex_colors[idx].map(|x| x * light)),
+                    );
+                }
+            }
+
+            PrimitiveTopology::LineList => {
+                for line in indices.chunks_exact(2) {
+                    let [a, b] = [line[0], line[1]];
+                    if a < positions.len() && b < positions.len() {
+                        self.line(
+                            [positions[a], positions[b]],
+                            [vertex_colors[a], vertex_colors[b]],
+                        );
+                    }
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    /// Paints a pixel, unless something closer to the camera was already
+    /// drawn there.
+    fn paint(&mut self, x: i64, y: i64, depth: f32, color: [f32; 3]) {
+        if x < 0 || y < 0 || x >= self.view.width as i64 || y >= self.view.height as i64 {
+            return;
+        }
+
+        let idx = y as usize * self.view.width as usize + x as usize;
+        if depth < self.depth[idx] {
+            self.depth[idx] = depth;
+            let [r, g, b] = color.map(to_u8);
+            self.image
+                .put_pixel(x as u32, y as u32, image::Rgba([r, g, b, 255]));
+        }
+    }
+
+    /// Fills a triangle, interpolating the colors of its vertices.
+    fn triangle(&mut self, points: [[f32; 3]; 3], colors: [[f32; 3]; 3]) {
+        let [p, q, r] = match [0, 1, 2].map(|i| self.view.project(points[i])) {
+            [Some(p), Some(q), Some(r)] => [p, q, r],
+            _ => return,
+        };
+
+        let area = (q.x - p.x) * (r.y - p.y) - (q.y - p.y) * (r.x - p.x);
+        if area.abs() <= f32::EPSILON {
+            return;
+        }
+
+        let min_x = p.x.min(q.x).min(r.x).floor().max(0.0) as i64;
+        let max_x = p.x.max(q.x).max(r.x).ceil().min(self.view.width as f32) as i64;
+        let min_y = p.y.min(q.y).min(r.y).floor().max(0.0) as i64;
+        let max_y = p.y.max(q.y).max(r.y).ceil().min(self.view.height as f32) as i64;
+
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                // The barycentric coordinates of the center of the pixel.
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                let u = ((q.x - px) * (r.y - py) - (q.y - py) * (r.x - px)) / area;
+                let v = ((r.x - px) * (p.y - py) - (r.y - py) * (p.x - px)) / area;
+                let w = 1.0 - u - v;
+
+                if u >= 0.0 && v >= 0.0 && w >= 0.0 {
+                    let depth = u * p.z + v * q.z + w * r.z;
+                    let color =
+                        [0, 1, 2].map(|i| u * colors[0][i] + v * colors[1][i] + w * colors[2][i]);
+                    self.paint(x, y, depth, color);
+                }
+            }
+        }
+    }
+
+    /// Draws a line one pixel wide, interpolating the colors of its endpoints.
+    fn line(&mut self, points: [[f32; 3]; 2], colors: [[f32; 3]; 2]) {
+        let (p, q) = match (self.view.project(points[0]), self.view.project(points[1])) {
+            (Some(p), Some(q)) => (p, q),
+            _ => return,
+        };
+
+        let steps = (q.x - p.x).abs().max((q.y - p.y).abs()).ceil().max(1.0) as usize;
+        for k in 0..=steps {
+            let t = k as f32 / steps as f32;
+            let point = p.lerp(q, t);
+            let color = [0, 1, 2].map(|i| colors[0][i] * (1.0 - t) + colors[1][i] * t);
+            self.paint(
+                point.x.floor() as i64,
+                point.y.floor() as i64,
+                point.z * (1.0 - LINE_BIAS),
+                color,
+            );
+        }
+    }
+}
+
+/// Converts a color component between 0 and 1 into a byte.
+fn to_u8(c: f32) -> u8 {
+    (c.max(0.0).min(1.0) * 255.0).round() as u8
+}
+
+/// Saves the frames of an animation. If the path is that of a GIF file, they're
+/// saved as a looping animation with a given number of frames per second.
+/// Otherwise, they're saved as a sequence of PNG images numbered from 1, next
+/// to the given path.
+pub fn save_animation(path: &Path, frames: Vec<RgbaImage>, fps: u32) -> ImageResult<()> {
+    if path.extension().and_then(|ext| ext.to_str()) == Some(GIF_EXT) {
+        let mut encoder = GifEncoder::new(File::create(path)?);
+        encoder.set_repeat(Repeat::Infinite)?;
+
+        let delay = Delay::from_numer_denom_ms(1000, fps.max(1));
+        encoder.encode_frames(
+            frames
+                .into_iter()
+                .map(|frame| Frame::from_parts(frame, 0, 0, delay)),
+        )
+    } else {
+        let stem = path
+            .file_stem()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing file name"))?
+            .to_string_lossy();
+
+        // Pads the numbers so that the images are listed in order.
+        let digits = frames.len().to_string().len();
+        for (idx, frame) in frames.iter().enumerate() {
+            let name = format!("{} {:0width$}.{}", stem, idx + 1, PNG_EXT, width = digits);
+            frame.save(path.with_file_name(name))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+//! Saves and loads the whole state of the application: the polytope on
+//! screen, the memory, and the view settings.
+
+use std::{ffi::OsStr, fs};
+
+use super::{camera::ProjectionType, config::SceneSettings, memory::Memory};
+use crate::Concrete;
+
+use bevy::prelude::*;
+use miratope_core::file::{off::OffOptions, FromFile};
+use serde::{Deserialize, Serialize};
+
+/// The extension of session files.
+pub const SESSION_EXT: &str = "ron";
+
+/// A serializable version of a `Transform`.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct TransformData {
+    /// The translation of the transform.
+    translation: [f32; 3],
+
+    /// The rotation of the transform, as a quaternion.
+    rotation: [f32; 4],
+
+    /// The scale of the transform.
+    scale: [f32; 3],
+}
+
+impl From<&Transform> for TransformData {
+    fn from(tf: &Transform) -> Self {
+        Self {
+            translation: tf.translation.into(),
+            rotation: tf.rotation.into(),
+            scale: tf.scale.into(),
+        }
+    }
+}
+
+impl From<TransformData> for Transform {
+    fn from(data: TransformData) -> Self {
+        Self {
+            translation: data.translation.into(),
+            rotation: Quat::from_array(data.rotation),
+            scale: data.scale.into(),
+        }
+    }
+}
+
+/// Everything needed to pick up exactly where the user left off. Polytopes are
+/// stored as OFF files.
+#[derive(Serialize, Deserialize)]
+pub struct Session {
+    /// The polytope on screen.
+    pub polytope: String,
+
+    /// The name of the polytope on screen.
+    pub name: String,
+
+    /// The memory slots, along with their labels.
+    pub memory: Vec<Option<(String, Option<String>)>>,
+
+    /// The projection type.
+    pub projection_type: ProjectionType,
+
+    /// The background and lighting of the scene.
+    pub scene: SceneSettings,
+
+    /// Whether light mode is enabled.
+    pub light_mode: bool,
+
+    /// The transforms of the camera anchor and the camera.
+    pub camera: (TransformData, TransformData),
+}
+
+/// An error while saving or loading a session.
+#[derive(Debug)]
+pub enum SessionError {
+    /// Some polytope couldn't be written or read as an OFF file.
+    Off(String),
+
+    /// The session file couldn't be serialized or deserialized.
+    Ron(ron::Error),
+
+    /// The session file couldn't be written or read.
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off(err) => write!(f, "OFF error: {}", err),
+            Self::Ron(err) => write!(f, "RON error: {}", err),
+            Self::Io(err) => write!(f, "IO error: {}", err),
+        }
+    }
+}
+
+impl From<ron::Error> for SessionError {
+    fn from(err: ron::Error) -> Self {
+        Self::Ron(err)
+    }
+}
+
+impl From<std::io::Error> for SessionError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// The result of saving or loading a session.
+pub type SessionResult<T> = Result<T, SessionError>;
+
+/// Writes a polytope as an OFF file.
+fn poly_to_off(poly: &Concrete) -> SessionResult<String> {
+    poly.to_off(OffOptions::default())
+        .map_err(|err| SessionError::Off(err.to_string()))
+}
+
+/// Reads a polytope from an OFF file.
+fn poly_from_off(src: &str) -> SessionResult<Concrete> {
+    Concrete::from_off(src).map_err(|err| SessionError::Off(err.to_string()))
+}
+
+impl Session {
+    /// Captures a session from the state of the application.
+    pub fn new(
+        poly: &Concrete,
+        name: &str,
+        memory: &Memory,
+        projection_type: ProjectionType,
+        scene: &SceneSettings,
+        light_mode: bool,
+        camera: (&Transform, &Transform),
+    ) -> SessionResult<Self> {
+        let memory = memory
+            .iter()
+            .map(|slot| match slot {
+                Some((poly, label)) => Ok(Some((poly_to_off(poly)?, label.clone()))),
+                None => Ok(None),
+            })
+            .collect::<SessionResult<_>>()?;
+
+        Ok(Self {
+            polytope: poly_to_off(poly)?,
+            name: name.to_string(),
+            memory,
+            projection_type,
+            scene: scene.clone(),
+            light_mode,
+            camera: (camera.0.into(), camera.1.into()),
+        })
+    }
+
+    /// Reads back the polytope on screen.
+    pub fn polytope(&self) -> SessionResult<Concrete> {
+        poly_from_off(&self.polytope)
+    }
+
+    /// Reads back the memory slots.
+    pub fn memory(&self) -> SessionResult<Memory> {
+        self.memory
+            .iter()
+            .map(|slot| match slot {
+                Some((src, label)) => Ok(Some((poly_from_off(src)?, label.clone()))),
+                None => Ok(None),
+            })
+            .collect::<SessionResult<_>>()
+            .map(Memory)
+    }
+
+    /// Attempts to read a session from a given path.
+    pub fn from_path<T: AsRef<OsStr>>(path: T) -> SessionResult<Self> {
+        Ok(ron::from_str(&fs::read_to_string(path.as_ref())?)?)
+    }
+
+    /// Saves the session at a given location.
+    pub fn save<T: AsRef<OsStr>>(&self, path: T) -> SessionResult<()> {
+        fs::write(path.as_ref(), ron::to_string(self)?)?;
+        Ok(())
+    }
+}
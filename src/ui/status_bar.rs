@@ -0,0 +1,60 @@
+//! A status bar at the bottom of the screen, showing information about the
+//! coordinates of the polytope on screen.
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::Concrete;
+
+/// The plugin in charge of the status bar.
+pub struct StatusBarPlugin;
+
+impl Plugin for StatusBarPlugin {
+    fn build(&self, app: &mut App) {
+        // The status bar must be shown before the side panels, so that it
+        // spans the whole window.
+        app.init_resource::<StatusBar>().add_system(
+            show_status_bar
+                .system()
+                .after("show_top_panel")
+                .before("show_library")
+                .before("show_right_panel"),
+        );
+    }
+}
+
+/// The information shown on the status bar.
+#[derive(Default)]
+pub struct StatusBar {
+    /// Whether the polytope on screen has exact coordinates, or `None` if
+    /// this needs to be read off of the polytope again.
+    exact: Option<bool>,
+}
+
+/// The system that shows the status bar, checking the polytope again whenever
+/// it changes.
+fn show_status_bar(
+    egui_ctx: Res<'_, EguiContext>,
+    mut status: ResMut<'_, StatusBar>,
+    polies: Query<'_, '_, &Concrete>,
+    changed_polies: Query<'_, '_, (), Changed<Concrete>>,
+) {
+    if changed_polies.iter().next().is_some() {
+        status.exact = None;
+    }
+
+    let exact = match polies.iter().next() {
+        Some(poly) => *status.exact.get_or_insert_with(|| poly.is_exact()),
+        None => return,
+    };
+
+    egui::TopBottomPanel::bottom("status_bar").show(egui_ctx.ctx(), |ui| {
+        if exact {
+            ui.label("Exact coordinates").on_hover_text(
+                "The polytope was built from primitives by operations that keep its coordinates exact.",
+            );
+        } else {
+            ui.label("Floating-point coordinates");
+        }
+    });
+}
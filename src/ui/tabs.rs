@@ -0,0 +1,317 @@
+//! Tabs, each of which has its own polytope, camera, cross-section and
+//! operation history. Only the tab on screen lives in the usual resources, the
+//! others are stored away until they're switched to.
+
+use std::mem;
+
+use bevy::{prelude::*, render::camera::Camera};
+use bevy_egui::{egui, EguiContext};
+
+use super::{
+    camera::{FitRadius, Orientation4D},
+    main_window::{Ghost, PolyName},
+    top_panel::{SectionDirection, SectionState},
+    window::LastOperation,
+};
+use crate::Concrete;
+
+/// The greatest number of characters of the name of a polytope shown on its
+/// tab.
+const MAX_LABEL_LEN: usize = 24;
+
+/// The plugin in charge of the tabs.
+pub struct TabsPlugin;
+
+impl Plugin for TabsPlugin {
+    fn build(&self, app: &mut App) {
+        // The tab bar goes right below the top panel, and must be shown before
+        // the side panels so that it spans the entire window.
+        app.init_resource::<Tabs>().add_system(
+            show_tabs
+                .system()
+                .label("show_tabs")
+                .after("show_top_panel")
+                .before("show_library")
+                .before("show_right_panel"),
+        );
+    }
+}
+
+/// Everything that belongs to a tab that isn't on screen.
+struct Workspace {
+    /// The polytope in the tab.
+    polytope: Concrete,
+
+    /// The name of the polytope.
+    name: PolyName,
+
+    /// The transforms of the camera anchor and the camera.
+    camera: (Transform, Transform),
+
+    /// The orientation of the polytope in 4D.
+    orientation: Orientation4D,
+
+    /// The state of the cross-section view.
+    section_state: SectionState,
+
+    /// The directions of the cross-section sliders.
+    section_direction: Vec<SectionDirection>,
+
+    /// The last operation applied to the polytope.
+    last_operation: LastOperation,
+
+    /// The polytope before the last operation.
+    ghost: Ghost,
+}
+
+/// A change to the tabs, made once the UI has been shown.
+#[derive(Clone, Copy)]
+pub enum TabRequest {
+    /// Opens a new tab with a copy of the polytope on screen, and switches to
+    /// it.
+    New,
+
+    /// Switches to the tab with a given index.
+    Switch(usize),
+
+    /// Closes the tab with a given index.
+    Close(usize),
+}
+
+/// The tabs that are open.
+pub struct Tabs {
+    /// The workspace of every tab, or `None` for the tab on screen.
+    workspaces: Vec<Option<Workspace>>,
+
+    /// The index of the tab on screen.
+    active: usize,
+
+    /// The change to make to the tabs, if any.
+    request: Option<TabRequest>,
+}
+
+impl Default for Tabs {
+    fn default() -> Self {
+        Self {
+            workspaces: vec![None],
+            active: 0,
+            request: None,
+        }
+    }
+}
+
+impl Tabs {
+    /// Asks for a change to the tabs, which is made once the UI is shown.
+    pub fn request(&mut self, request: TabRequest) {
+        self.request = Some(request);
+    }
+
+    /// Returns the number of tabs.
+    fn len(&self) -> usize {
+        self.workspaces.len()
+    }
+}
+
+/// The resources that store the state of the tab on screen.
+struct Screen<'a> {
+    /// The polytope on screen.
+    polytope: &'a mut Concrete,
+
+    /// The name of the polytope on screen.
+    name: &'a mut PolyName,
+
+    /// The transforms of the camera anchor and the camera.
+    camera: (&'a mut Transform, &'a mut Transform),
+
+    /// The orientation of the polytope in 4D.
+    orientation: &'a mut Orientation4D,
+
+    /// The state of the cross-section view.
+    section_state: &'a mut SectionState,
+
+    /// The directions of the cross-section sliders.
+    section_direction: &'a mut Vec<SectionDirection>,
+
+    /// The last operation applied to the polytope.
+    last_operation: &'a mut LastOperation,
+
+    /// The polytope before the last operation.
+    ghost: &'a mut Ghost,
+}
+
+impl<'a> Screen<'a> {
+    /// Returns a workspace with a copy of the polytope and camera on screen,
+    /// and everything else reset.
+    fn copy(&self) -> Workspace {
+        Workspace {
+            polytope: self.polytope.clone(),
+            name: PolyName(self.name.0.clone()),
+            camera: (*self.camera.0, *self.camera.1),
+            orientation: Default::default(),
+            section_state: Default::default(),
+            section_direction: Default::default(),
+            last_operation: Default::default(),
+            ghost: Default::default(),
+        }
+    }
+
+    /// Puts a workspace on screen, and stores the one that was on screen in
+    /// its place.
+    fn swap(&mut self, workspace: &mut Workspace) {
+        mem::swap(self.polytope, &mut workspace.polytope);
+        mem::swap(self.name, &mut workspace.name);
+        mem::swap(self.camera.0, &mut workspace.camera.0);
+        mem::swap(self.camera.1, &mut workspace.camera.1);
+        mem::swap(self.orientation, &mut workspace.orientation);
+        mem::swap(self.section_state, &mut workspace.section_state);
+        mem::swap(self.section_direction, &mut workspace.section_direction);
+        mem::swap(self.last_operation, &mut workspace.last_operation);
+        mem::swap(self.ghost, &mut workspace.ghost);
+    }
+}
+
+impl Tabs {
+    /// Switches to the tab with a given index.
+    fn switch(&mut self, screen: &mut Screen<'_>, idx: usize) {
+        if idx == self.active {
+            return;
+        }
+
+        if let Some(mut workspace) = self.workspaces[idx].take() {
+            screen.swap(&mut workspace);
+            self.workspaces[self.active] = Some(workspace);
+            self.active = idx;
+        }
+    }
+
+    /// Makes a change to the tabs.
+    fn apply(&mut self, screen: &mut Screen<'_>, request: TabRequest) {
+        match request {
+            TabRequest::New => {
+                self.workspaces.push(Some(screen.copy()));
+                self.switch(screen, self.len() - 1);
+            }
+            TabRequest::Switch(idx) => self.switch(screen, idx),
+            TabRequest::Close(idx) => {
+                // The last tab can't be closed.
+                if self.len() == 1 {
+                    return;
+                }
+
+                if idx == self.active {
+                    self.switch(screen, if idx == 0 { 1 } else { idx - 1 });
+                }
+
+                self.workspaces.remove(idx);
+                if idx < self.active {
+                    self.active -= 1;
+                }
+            }
+        }
+    }
+
+    /// Shows the tab bar. The tab on screen is labeled by the name of the
+    /// polytope on screen.
+    fn show(&mut self, ui: &mut egui::Ui, name: &str) {
+        ui.horizontal(|ui| {
+            for idx in 0..self.len() {
+                let name = match &self.workspaces[idx] {
+                    Some(workspace) => &workspace.name.0,
+                    None => name,
+                };
+                let mut label: String = name.chars().take(MAX_LABEL_LEN).collect();
+                if name.chars().count() > MAX_LABEL_LEN {
+                    label.push('…');
+                }
+
+                if ui
+                    .selectable_label(idx == self.active, label)
+                    .on_hover_text(name)
+                    .clicked()
+                {
+                    self.request(TabRequest::Switch(idx));
+                }
+
+                if ui.small_button("×").on_hover_text("Close tab").clicked() {
+                    self.request(TabRequest::Close(idx));
+                }
+
+                ui.separator();
+            }
+
+            if ui.small_button("+").on_hover_text("New tab").clicked() {
+                self.request(TabRequest::New);
+            }
+        });
+    }
+}
+
+/// The system that shows the tab bar whenever there's more than one tab, and
+/// switches between tabs.
+#[allow(clippy::too_many_arguments)]
+fn show_tabs(
+    egui_ctx: Res<'_, EguiContext>,
+    mut tabs: ResMut<'_, Tabs>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut transforms: Query<'_, '_, &mut Transform>,
+    cameras: Query<'_, '_, (Entity, &Parent), With<Camera>>,
+    mut fit_radius: ResMut<'_, FitRadius>,
+    (mut poly_name, mut orientation, mut section_state, mut section_direction, mut last_operation, mut ghost): (
+        ResMut<'_, PolyName>,
+        ResMut<'_, Orientation4D>,
+        ResMut<'_, SectionState>,
+        ResMut<'_, Vec<SectionDirection>>,
+        ResMut<'_, LastOperation>,
+        ResMut<'_, Ghost>,
+    ),
+) {
+    if tabs.len() > 1 {
+        egui::TopBottomPanel::top("tabs").show(egui_ctx.ctx(), |ui| {
+            tabs.show(ui, &poly_name.0);
+        });
+    }
+
+    let request = match tabs.request.take() {
+        Some(request) => request,
+        None => return,
+    };
+
+    let (cam, anchor) = match cameras.iter().next() {
+        Some(camera) => camera,
+        None => return,
+    };
+    let mut polytope = match query.iter_mut().next() {
+        Some(polytope) => polytope,
+        None => return,
+    };
+
+    // The transforms are copied out and written back, since the camera and
+    // its anchor can't be borrowed mutably from the same query at once.
+    let (mut anchor_tf, mut cam_tf) = match (transforms.get(anchor.0), transforms.get(cam)) {
+        (Ok(anchor_tf), Ok(cam_tf)) => (*anchor_tf, *cam_tf),
+        _ => return,
+    };
+
+    let mut screen = Screen {
+        polytope: &mut *polytope,
+        name: &mut *poly_name,
+        camera: (&mut anchor_tf, &mut cam_tf),
+        orientation: &mut *orientation,
+        section_state: &mut *section_state,
+        section_direction: &mut *section_direction,
+        last_operation: &mut *last_operation,
+        ghost: &mut *ghost,
+    };
+    tabs.apply(&mut screen, request);
+
+    if let Ok(mut tf) = transforms.get_mut(anchor.0) {
+        *tf = anchor_tf;
+    }
+    if let Ok(mut tf) = transforms.get_mut(cam) {
+        *tf = cam_tf;
+    }
+
+    // The camera isn't automatically fit to the polytope that was switched
+    // to, so that it keeps its own view.
+    fit_radius.0 = None;
+}
@@ -2,12 +2,12 @@
 
 use std::path::PathBuf;
 
-use super::{camera::ProjectionType, memory::Memory, window::{Window, *}, UnitPointWidget, main_window::PolyName};
-use crate::{Concrete, Float, Hyperplane, Point, Vector};
+use super::{camera::{CameraInputEvent, Orientation4D, ProjectionType}, config::{Antialiasing, ElementCap, FitSection, LightMode, MetricsOnSave, RecenterOnLoad, SceneSettings}, hasse::HasseWindow, histogram::EdgeLengthWindow, legend::ColorLegend, session::{Session, SESSION_EXT}, tabs::{TabRequest, Tabs}, library::LibraryReveal, memory::Memory, name::{compound_name, NameWindow}, picking::{PickMode, Picking}, palette::{Action, CommandPalette, CommandRegistry, OpenWindow}, window::{Window, *}, DirectionWidget, UnitPointWidget, main_window::{Ghost, Overlay, Overlays, PolyName}, screenshot::{self, DrawStyle, View, GIF_EXT, PNG_EXT}, wiki::WikiTarget};
+use crate::{mesh::{DepthCue, EdgeSubdivisions, ExplodeFacets, HiddenFacets, ShownRanks, WireframeDetail}, Concrete, Float, Hyperplane, Hypersphere, Point, Vector, EPS};
 
-use bevy::prelude::*;
+use bevy::{prelude::*, render::camera::Camera, window::FileDragAndDrop};
 use bevy_egui::{egui::{self, menu, Ui}, EguiContext};
-use miratope_core::{conc::{ConcretePolytope, faceting::GroupEnum, symmetry::Vertices}, file::FromFile, float::Float as Float2, Polytope, abs::Ranked};
+use miratope_core::{conc::{ConcretePolytope, RecenterMode, faceting::GroupEnum, metrics::Metrics, symmetry::Vertices}, file::{self, ggb::GGB_EXT, incidence::CSV_EXT, lattice::LATTICE_EXT, off::{OffOptions, GZ_EXT}, ply::PLY_EXT, svg::SVG_EXT, FromFile}, float::Float as Float2, geometry::{Matrix, Subspace}, Polytope, abs::Ranked};
 
 /// The plugin in charge of everything on the top panel.
 pub struct TopPanelPlugin;
@@ -17,12 +17,28 @@ impl Plugin for TopPanelPlugin {
         app.init_resource::<FileDialogState>()
             .init_resource::<SectionState>()
             .init_resource::<Vec<SectionDirection>>()
+            .init_resource::<SectionFilled>()
+            .init_resource::<SectionAnimation>()
+            .init_resource::<Tolerance>()
+            .init_resource::<UnitEdge>()
+            .init_resource::<ConfirmThreshold>()
+            .init_resource::<ConwayNotation>()
+            .init_resource::<PendingOp>()
+            .init_resource::<ExplodedSimplices>()
             .init_resource::<Memory>()
             .init_resource::<ShowMemory>()
             .init_resource::<ShowHelp>()
+            .init_resource::<EdgeLengthWindow>()
+            .init_resource::<HasseWindow>()
+            .init_resource::<NameWindow>()
             .init_resource::<ExportMemory>()
+            .init_resource::<CommandPalette>()
             .init_non_send_resource::<FileDialogToken>()
-            .add_system(file_dialog.system())
+            // Files are loaded into new tabs once they've been opened.
+            .add_system(file_dialog.system().after("show_tabs"))
+            .add_system(file_drop.system())
+            .add_system(forget_exploded.system().after("show_top_panel"))
+            .add_startup_system(register_commands.system())
             // Windows must be the first thing shown.
             .add_system(
                 show_top_panel
@@ -55,6 +71,10 @@ pub enum SectionState {
         /// Whether we're not updating the cross-section.
         lock: bool,
 
+        /// The symmetries of the original polytope, if the slicing
+        /// hyperplanes snap to symmetric positions.
+        symmetry: Option<Vec<Matrix<Float>>>,
+
         /// Whether to update the polytope. This is a bodge.
         update: bool,
     },
@@ -90,7 +110,7 @@ impl SectionState {
 		}
     }
 
-    pub fn open(&mut self, original_polytope: Concrete, name: String, minmax: Vec<(f64, f64)>) {
+    pub fn open(&mut self, original_polytope: Concrete, name: String, minmax: Vec<(Float, Float)>) {
         *self = SectionState::Active {
             original_polytope,
             original_name: name,
@@ -98,6 +118,7 @@ impl SectionState {
             hyperplane_pos: minmax.clone().into_iter().map(|m| (m.0 + m.1) / 2.0).collect(),
             flatten: true,
             lock: false,
+            symmetry: None,
             update: false,
         }
     }
@@ -112,6 +133,7 @@ impl Clone for SectionState {
 				hyperplane_pos,
 				flatten,
 				lock,
+                symmetry,
                 update,
 			} = self{
 				
@@ -122,6 +144,7 @@ impl Clone for SectionState {
 				hyperplane_pos: hyperplane_pos.clone(),
 				flatten: *flatten,
 				lock: *lock,
+                symmetry: symmetry.clone(),
                 update: *update,
 			}
 		}
@@ -146,6 +169,114 @@ impl Default for SectionDirection {
     }
 }
 
+/// Stores whether the faces of the cross-section are shown, or only its
+/// wireframe.
+pub struct SectionFilled(pub bool);
+
+impl Default for SectionFilled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// The settings for exporting the cross-sections swept by the last slider as
+/// an animation.
+pub struct SectionAnimation {
+    /// The number of frames in the animation.
+    pub frames: usize,
+
+    /// The number of frames shown per second.
+    pub fps: u32,
+}
+
+impl Default for SectionAnimation {
+    fn default() -> Self {
+        Self {
+            frames: 60,
+            fps: 20,
+        }
+    }
+}
+
+/// The tolerance used by geometric predicates, such as slicing, convexity
+/// tests and coplanarity checks, as a power of 10.
+pub struct Tolerance(pub i32);
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self(-7)
+    }
+}
+
+impl Tolerance {
+    /// Returns the tolerance as a number.
+    pub fn value(&self) -> Float {
+        Float::powi(10.0, self.0)
+    }
+}
+
+/// The polytope on screen and its name, stored while it's shown exploded into
+/// simplices. They're forgotten once the exploded polytope is changed, so that
+/// turning the option off doesn't undo whatever was done since.
+#[derive(Default)]
+pub struct ExplodedSimplices(Option<Exploded>);
+
+/// A polytope shown exploded into simplices.
+struct Exploded {
+    /// The polytope before it was exploded.
+    original: Concrete,
+
+    /// The name of the polytope before it was exploded.
+    name: String,
+
+    /// The exploded polytope, to tell whether it's been changed since.
+    simplices: Concrete,
+}
+
+/// Forgets the polytope from before it was exploded into simplices once the
+/// polytope on screen is changed.
+fn forget_exploded(
+    mut exploded: ResMut<'_, ExplodedSimplices>,
+    changed_polies: Query<'_, '_, &Concrete, Changed<Concrete>>,
+) {
+    if let (Some(poly), Some(current)) = (changed_polies.iter().next(), &exploded.0) {
+        // The polytope is also marked as changed whenever it's merely
+        // redrawn, so we check that it actually changed.
+        if current.simplices.vertices != poly.vertices
+            || !current.simplices.el_count_iter().eq(poly.el_count_iter())
+        {
+            exploded.0 = None;
+        }
+    }
+}
+
+/// How far apart the simplices of an exploded polytope are pushed, as a
+/// fraction of their distance to the gravicenter.
+const EXPLODE_FACTOR: Float = 0.5;
+
+/// Stores whether volumes and surface areas are reported for a unit edge
+/// length, rather than as they are.
+#[derive(Default)]
+pub struct UnitEdge(pub bool);
+
+/// The number of elements above which expensive operations ask for
+/// confirmation before running.
+pub struct ConfirmThreshold(pub usize);
+
+impl Default for ConfirmThreshold {
+    fn default() -> Self {
+        Self(10_000)
+    }
+}
+
+/// The sequence of Conway operators typed into the Operations menu.
+#[derive(Default)]
+pub struct ConwayNotation(pub String);
+
+/// Stores the expensive operation that's waiting for confirmation, if any.
+#[derive(Default)]
+pub struct PendingOp(pub Option<ExpensiveOp>);
+
 /// Stores whether the memory window is shown.
 pub struct ShowMemory(bool);
 
@@ -186,16 +317,90 @@ impl FileDialogToken {
     fn new_file_dialog() -> rfd::FileDialog {
         rfd::FileDialog::new()
             .add_filter("OFF File", &["off"])
+            .add_filter("Gzipped OFF File", &[GZ_EXT])
     }
 
     /// Returns the path given by an open file dialog.
     fn pick_file(&self) -> Option<PathBuf> {
-        Self::new_file_dialog().pick_file()
+        Self::new_file_dialog()
+            .add_filter("Lattice File", &[LATTICE_EXT])
+            .pick_file()
     }
 
     /// Returns the path given by a save file dialog.
     fn save_file(&self, name: &str) -> Option<PathBuf> {
-        Self::new_file_dialog().set_file_name(name).save_file()
+        Self::new_file_dialog()
+            .add_filter("GeoGebra File", &[GGB_EXT])
+            .add_filter("PLY File", &[PLY_EXT])
+            .set_file_name(name)
+            .save_file()
+    }
+
+    /// Returns the path given by a save file dialog for lattice files.
+    fn save_lattice(&self, name: &str) -> Option<PathBuf> {
+        rfd::FileDialog::new()
+            .add_filter("Lattice File", &[LATTICE_EXT])
+            .set_file_name(name)
+            .save_file()
+    }
+
+    /// Returns the path given by a save file dialog for SVG images.
+    fn save_svg(&self, name: &str) -> Option<PathBuf> {
+        rfd::FileDialog::new()
+            .add_filter("SVG Image", &[SVG_EXT])
+            .set_file_name(name)
+            .save_file()
+    }
+
+    /// Returns the path given by a save file dialog for screenshots.
+    fn save_png(&self, name: &str) -> Option<PathBuf> {
+        rfd::FileDialog::new()
+            .add_filter("PNG Image", &[PNG_EXT])
+            .set_file_name(name)
+            .save_file()
+    }
+
+    /// Returns the path given by a save file dialog for animations, which are
+    /// saved either as a GIF or as numbered PNG images.
+    fn save_animation(&self, name: &str) -> Option<PathBuf> {
+        rfd::FileDialog::new()
+            .add_filter("GIF Animation", &[GIF_EXT])
+            .add_filter("PNG Images", &[PNG_EXT])
+            .set_file_name(name)
+            .save_file()
+    }
+
+    /// Returns the path given by a save file dialog for incidence matrices.
+    fn save_csv(&self, name: &str) -> Option<PathBuf> {
+        rfd::FileDialog::new()
+            .add_filter("CSV File", &[CSV_EXT])
+            .set_file_name(name)
+            .save_file()
+    }
+
+    /// Returns the path given by a save file dialog for Stella-compatible OFF
+    /// files.
+    fn save_stella(&self, name: &str) -> Option<PathBuf> {
+        rfd::FileDialog::new()
+            .add_filter("Stella-compatible OFF File", &["off"])
+            .set_file_name(name)
+            .save_file()
+    }
+
+    /// Auxiliary function to create a new file dialog for sessions.
+    fn new_session_dialog() -> rfd::FileDialog {
+        rfd::FileDialog::new()
+            .add_filter("Miratope session", &[SESSION_EXT])
+    }
+
+    /// Returns the path given by an open session dialog.
+    fn pick_session(&self) -> Option<PathBuf> {
+        Self::new_session_dialog().pick_file()
+    }
+
+    /// Returns the path given by a save session dialog.
+    fn save_session(&self) -> Option<PathBuf> {
+        Self::new_session_dialog().set_file_name("session").save_file()
     }
 }
 
@@ -209,6 +414,34 @@ enum FileDialogMode {
 
     /// We're showing a file dialog to save a file.
     Save,
+
+    /// We're showing a file dialog to export the lattice of the polytope.
+    ExportLattice,
+
+    /// We're showing a file dialog to export the projection of the polytope
+    /// onto the xy-plane as an SVG image.
+    ExportSvg,
+
+    /// We're showing a file dialog to export the polytope as an OFF file that
+    /// Stella can open.
+    ExportStella,
+
+    /// We're showing a file dialog to export the incidence matrix between the
+    /// elements of two given ranks as a CSV file.
+    ExportIncidence(usize, usize),
+
+    /// We're showing a file dialog to save a screenshot of the polytope.
+    Screenshot,
+
+    /// We're showing a file dialog to export the cross-sections swept by the
+    /// last slider as an animation.
+    ExportSection,
+
+    /// We're showing a file dialog to open a session.
+    OpenSession,
+
+    /// We're showing a file dialog to save the session.
+    SaveSession,
 }
 
 /// The file dialog is disabled by default.
@@ -241,6 +474,58 @@ impl FileDialogState {
         self.name = Some(name);
     }
 
+    /// Changes the file dialog mode to [`FileDialogMode::ExportLattice`], and
+    /// loads the name of the file.
+    pub fn export_lattice(&mut self, name: String) {
+        self.mode = FileDialogMode::ExportLattice;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::ExportSvg`], and loads
+    /// the name of the file.
+    pub fn export_svg(&mut self, name: String) {
+        self.mode = FileDialogMode::ExportSvg;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::ExportStella`], and
+    /// loads the name of the file.
+    pub fn export_stella(&mut self, name: String) {
+        self.mode = FileDialogMode::ExportStella;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::ExportIncidence`] with
+    /// the given ranks, and loads the name of the file.
+    pub fn export_incidence(&mut self, name: String, r0: usize, r1: usize) {
+        self.mode = FileDialogMode::ExportIncidence(r0, r1);
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::Screenshot`], and
+    /// loads the name of the file.
+    pub fn screenshot(&mut self, name: String) {
+        self.mode = FileDialogMode::Screenshot;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::ExportSection`], and
+    /// loads the name of the file.
+    pub fn export_section(&mut self, name: String) {
+        self.mode = FileDialogMode::ExportSection;
+        self.name = Some(name);
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::OpenSession`].
+    pub fn open_session(&mut self) {
+        self.mode = FileDialogMode::OpenSession;
+    }
+
+    /// Changes the file dialog mode to [`FileDialogMode::SaveSession`].
+    pub fn save_session(&mut self) {
+        self.mode = FileDialogMode::SaveSession;
+    }
+
     /// Gets the name of the file dialog.
     pub fn unwrap_name(&self) -> &str {
         self.name.as_ref().unwrap()
@@ -251,8 +536,42 @@ impl FileDialogState {
 pub fn file_dialog(
     mut query: Query<'_, '_, &mut Concrete>,
     mut name: ResMut<'_, PolyName>,
+    mut reveal: ResMut<'_, LibraryReveal>,
     file_dialog_state: Res<'_, FileDialogState>,
     file_dialog: NonSend<'_, FileDialogToken>,
+
+    mut memory: ResMut<'_, Memory>,
+    mut projection_type: ResMut<'_, ProjectionType>,
+    mut scene: ResMut<'_, SceneSettings>,
+    mut visuals: ResMut<'_, egui::Visuals>,
+    mut transforms: Query<'_, '_, &mut Transform>,
+    cameras: Query<'_, '_, (Entity, &Parent), With<Camera>>,
+    (section_state, section_direction, section_animation, tolerance, recenter_on_load, metrics_on_save): (
+        Res<'_, SectionState>,
+        Res<'_, Vec<SectionDirection>>,
+        Res<'_, SectionAnimation>,
+        Res<'_, Tolerance>,
+        Res<'_, RecenterOnLoad>,
+        Res<'_, MetricsOnSave>,
+    ),
+
+    // Everything that screenshots are rendered with.
+    (windows, materials, depth_cue, shown_ranks, subdivisions, detail, explode, orientation): (
+        Res<'_, Windows>,
+        Res<'_, Assets<StandardMaterial>>,
+        Res<'_, DepthCue>,
+        Res<'_, ShownRanks>,
+        Res<'_, EdgeSubdivisions>,
+        Res<'_, WireframeDetail>,
+        Res<'_, ExplodeFacets>,
+        Res<'_, Orientation4D>,
+    ),
+    (views, lights, polies, wfs): (
+        Query<'_, '_, (&Camera, &GlobalTransform)>,
+        Query<'_, '_, &GlobalTransform, With<PointLight>>,
+        Query<'_, '_, (&Visible, &Handle<StandardMaterial>, &HiddenFacets, &Children), With<Concrete>>,
+        Query<'_, '_, (&Visible, &Handle<StandardMaterial>), (Without<Concrete>, Without<Overlay>)>,
+    ),
 ) {
     if file_dialog_state.is_changed() {
         match file_dialog_state.mode {
@@ -260,22 +579,161 @@ pub fn file_dialog(
             FileDialogMode::Save => {
                 if let Some(path) = file_dialog.save_file(file_dialog_state.unwrap_name()) {
                     if let Some(p) = query.iter_mut().next() {
-                        if let Err(err) = p.con().to_path(&path, Default::default()) {
+                        // GGB and PLY files are written from the 3D projection
+                        // on screen.
+                        let ext = path.extension().and_then(|ext| ext.to_str());
+                        if ext == Some(GGB_EXT) || ext == Some(PLY_EXT) {
+                            let vertices = crate::mesh::projected_vertices(&p, *projection_type);
+                            let contents = if ext == Some(GGB_EXT) {
+                                p.to_ggb_with(&vertices)
+                            } else {
+                                p.to_ply_with(&vertices)
+                            };
+
+                            if let Err(err) = std::fs::write(&path, contents) {
+                                eprintln!("File saving failed: {}", err);
+                            }
+                        } else if let Err(err) = p.con().to_path(
+                            &path,
+                            OffOptions {
+                                metrics: metrics_on_save.0,
+                                ..Default::default()
+                            },
+                        ) {
                             eprintln!("File saving failed: {}", err);
                         }
                     }
                 }
             }
 
+            // We want to export the lattice of the polytope.
+            FileDialogMode::ExportLattice => {
+                if let Some(path) = file_dialog.save_lattice(file_dialog_state.unwrap_name()) {
+                    if let Some(p) = query.iter().next() {
+                        if let Err(err) = std::fs::write(&path, p.to_abstract_text()) {
+                            eprintln!("Lattice export failed: {}", err);
+                        }
+                    }
+                }
+            }
+
+            // We want to export the projection of the polytope as an image.
+            FileDialogMode::ExportSvg => {
+                if let Some(path) = file_dialog.save_svg(file_dialog_state.unwrap_name()) {
+                    if let Some(p) = query.iter().next() {
+                        if let Err(err) = std::fs::write(&path, p.to_svg_xy()) {
+                            eprintln!("SVG export failed: {}", err);
+                        }
+                    }
+                }
+            }
+
+            // We want to export the polytope in the OFF dialect Stella reads.
+            FileDialogMode::ExportStella => {
+                if let Some(path) = file_dialog.save_stella(file_dialog_state.unwrap_name()) {
+                    if let Some(p) = query.iter().next() {
+                        match p.to_stella_off() {
+                            Ok(off) => {
+                                if let Err(err) = std::fs::write(&path, off) {
+                                    eprintln!("Stella export failed: {}", err);
+                                }
+                            }
+                            Err(err) => eprintln!("Stella export failed: {}", err),
+                        }
+                    }
+                }
+            }
+
+            // We want to export an incidence matrix of the polytope.
+            FileDialogMode::ExportIncidence(r0, r1) => {
+                if let Some(path) = file_dialog.save_csv(file_dialog_state.unwrap_name()) {
+                    if let Some(p) = query.iter().next() {
+                        match p.to_incidence_csv(r0, r1) {
+                            Some(csv) => {
+                                if let Err(err) = std::fs::write(&path, csv) {
+                                    eprintln!("Incidence export failed: {}", err);
+                                }
+                            }
+                            None => eprintln!(
+                                "Incidence export failed: the polytope has no elements of those ranks."
+                            ),
+                        }
+                    }
+                }
+            }
+
+            // We want to save a screenshot, or to export the cross-sections as
+            // an animation. Both are rendered as the polytope is shown on
+            // screen.
+            FileDialogMode::Screenshot | FileDialogMode::ExportSection => {
+                let is_screenshot = matches!(file_dialog_state.mode, FileDialogMode::Screenshot);
+                let name = file_dialog_state.unwrap_name();
+                let path = if is_screenshot {
+                    file_dialog.save_png(name)
+                } else {
+                    file_dialog.save_animation(name)
+                };
+
+                let view = match (windows.get_primary(), views.iter().next(), lights.iter().next()) {
+                    (Some(window), Some((camera, camera_tf)), Some(light)) => Some(View::new(
+                        window,
+                        camera,
+                        camera_tf,
+                        light,
+                        scene.ambient,
+                        scene.background_color.rgb(),
+                    )),
+                    _ => None,
+                };
+
+                if let (Some(path), Some(view), Some(p), Some((visible, material, hidden, children))) =
+                    (path, view, query.iter().next(), polies.iter().next())
+                {
+                    // Hidden meshes don't show up in screenshots either.
+                    let wireframe = children.iter().find_map(|child| wfs.get(*child).ok());
+                    let style = DrawStyle {
+                        projection_type: *projection_type,
+                        depth_cue: &depth_cue,
+                        shown_ranks: &shown_ranks,
+                        subdivisions: subdivisions.0,
+                        detail: &detail,
+                        explode: &explode,
+                        hidden,
+                        orientation: &orientation,
+                        mesh_material: materials.get(material).filter(|_| visible.is_visible),
+                        wireframe_material: wireframe.and_then(|(visible, material)| {
+                            materials.get(material).filter(|_| visible.is_visible)
+                        }),
+                    };
+
+                    if is_screenshot {
+                        if let Err(err) = view.render(p, &style).save(&path) {
+                            eprintln!("Screenshot failed: {}", err);
+                        }
+                    } else if let Some(frames) = section_frames(
+                        &section_state,
+                        &section_direction,
+                        section_animation.frames,
+                        tolerance.value(),
+                    ) {
+                        let images = frames.iter().map(|frame| view.render(frame, &style)).collect();
+                        if let Err(err) = screenshot::save_animation(&path, images, section_animation.fps) {
+                            eprintln!("Animation export failed: {}", err);
+                        }
+                    }
+                }
+            }
+
             // We want to open a file.
             FileDialogMode::Open => {
                 if let Some(path) = file_dialog.pick_file() {
                     if let Some(mut p) = query.iter_mut().next() {
                         match Concrete::from_path(&path) {
-                            Ok(q) => {
+                            Ok(mut q) => {
+                                recenter_on_load.apply(&mut q);
                                 *p = q;
-                                let file_name = path.file_name().unwrap().to_str().unwrap();
-                                name.0 = file_name[..file_name.len()-4].into();
+                                reveal.reveal(&path);
+                                name.0 = file::file_stem(&path).unwrap().into();
                             }
                             Err(err) => eprintln!("File open failed: {}", err),
                         }
@@ -283,78 +741,575 @@ pub fn file_dialog(
                 }
             }
 
+            // We want to save the session.
+            FileDialogMode::SaveSession => {
+                if let Some(path) = file_dialog.save_session() {
+                    if let (Some(p), Some((cam, anchor))) =
+                        (query.iter().next(), cameras.iter().next())
+                    {
+                        let (anchor_tf, cam_tf) = match (transforms.get(anchor.0), transforms.get(cam)) {
+                            (Ok(anchor_tf), Ok(cam_tf)) => (anchor_tf, cam_tf),
+                            _ => return,
+                        };
+
+                        let session = Session::new(
+                            &p,
+                            &name.0,
+                            &memory,
+                            *projection_type,
+                            &scene,
+                            !visuals.dark_mode,
+                            (anchor_tf, cam_tf),
+                        );
+
+                        match session.and_then(|session| session.save(&path)) {
+                            Ok(()) => println!("Saved session!"),
+                            Err(err) => eprintln!("Session saving failed: {}", err),
+                        }
+                    }
+                }
+            }
+
+            // We want to load a session.
+            FileDialogMode::OpenSession => {
+                if let Some(path) = file_dialog.pick_session() {
+                    let loaded = Session::from_path(&path).and_then(|session| {
+                        let poly = session.polytope()?;
+                        let slots = session.memory()?;
+                        Ok((session, poly, slots))
+                    });
+
+                    match loaded {
+                        Ok((session, poly, slots)) => {
+                            if let Some(mut p) = query.iter_mut().next() {
+                                *p = poly;
+                            }
+                            name.0 = session.name;
+                            *memory = slots;
+                            *projection_type = session.projection_type;
+                            *scene = session.scene;
+                            *visuals = LightMode(session.light_mode).visuals();
+
+                            if let Some((cam, anchor)) = cameras.iter().next() {
+                                if let Ok(mut anchor_tf) = transforms.get_mut(anchor.0) {
+                                    *anchor_tf = session.camera.0.into();
+                                }
+                                if let Ok(mut cam_tf) = transforms.get_mut(cam) {
+                                    *cam_tf = session.camera.1.into();
+                                }
+                            }
+                        }
+                        Err(err) => eprintln!("Session loading failed: {}", err),
+                    }
+                }
+            }
+
             // There's nothing to do with the file dialog this frame.
             FileDialogMode::Disabled => {}
         }
     }
 }
 
+/// The system that loads files dropped onto the window. Holding Shift adds the
+/// dropped polytope to the one on screen as a compound, instead of replacing
+/// it.
+pub fn file_drop(
+    mut events: EventReader<'_, '_, FileDragAndDrop>,
+    keyboard: Res<'_, Input<KeyCode>>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    mut name: ResMut<'_, PolyName>,
+    mut reveal: ResMut<'_, LibraryReveal>,
+    mut section_state: ResMut<'_, SectionState>,
+    recenter_on_load: Res<'_, RecenterOnLoad>,
+) {
+    for event in events.iter() {
+        let path = match event {
+            FileDragAndDrop::DroppedFile { path_buf, .. } => path_buf,
+            _ => continue,
+        };
+
+        if let Some(mut p) = query.iter_mut().next() {
+            match Concrete::from_path(path) {
+                Ok(mut q) => {
+                    let file_name = file::file_stem(path).unwrap_or_default().to_string();
+
+                    if keyboard.pressed(KeyCode::LShift) || keyboard.pressed(KeyCode::RShift) {
+                        if p.rank() != q.rank() || p.dim_or() != q.dim_or() {
+                            eprintln!("Can't add a polytope of a different rank or dimension.");
+                            continue;
+                        }
+
+                        p.comp_append(q);
+                        name.0 = compound_name(&name.0, &file_name);
+                    } else {
+                        recenter_on_load.apply(&mut q);
+                        *p = q;
+                        reveal.reveal(path);
+                        name.0 = file_name;
+                    }
+
+                    section_state.close();
+                }
+                Err(err) => eprintln!("File open failed: {}", err),
+            }
+        }
+    }
+}
+
+/// The name of the coordinate axis with a given index.
+fn axis_name(idx: usize) -> String {
+    match idx {
+        0 => "x".to_string(),
+        1 => "y".to_string(),
+        2 => "z".to_string(),
+        3 => "w".to_string(),
+        _ => format!("axis {}", idx + 1),
+    }
+}
+
 /// Whether the hotkey to enable "advanced" options is enabled.
 pub fn advanced(keyboard: &Input<KeyCode>) -> bool {
     keyboard.pressed(KeyCode::LControl) || keyboard.pressed(KeyCode::RControl)
 }
 
-/// All of the windows that can be shown on screen, as mutable resources.
-pub type EguiWindows<'a> = (
-    ResMut<'a, DualWindow>,
-    ResMut<'a, PyramidWindow>,
-    ResMut<'a, PrismWindow>,
-    ResMut<'a, TegumWindow>,
-    ResMut<'a, AntiprismWindow>,
-    ResMut<'a, DuopyramidWindow>,
-    ResMut<'a, DuoprismWindow>,
-    ResMut<'a, DuotegumWindow>,
-    ResMut<'a, DuocombWindow>,
-    ResMut<'a, StarWindow>,
-    ResMut<'a, CompoundWindow>,
-    ResMut<'a, TruncateWindow>,
-    ResMut<'a, ScaleWindow>,
-    ResMut<'a, FacetingSettings>,
-	ResMut<'a, RotateWindow>,
-	ResMut<'a, PlaneWindow>,
-);
+/// The operations in the top panel that only make sense for polytopes of
+/// certain ranks or dimensions.
+#[derive(Clone, Copy)]
+enum GuardedOp {
+    /// Builds the Petrial.
+    Petrial,
 
-macro_rules! element_sort {
-    ($p:ident) => {
-        if !$p.abs().sorted() {
-            $p.element_sort();
+    /// Builds a Petrie polygon.
+    PetriePolygon,
+
+    /// Builds the Kleetope.
+    Kleetope,
+
+    /// Chamfers a polyhedron.
+    Chamfer,
+
+    /// Applies one of the truncations among Conway's operators.
+    Truncate,
+
+    /// Fuses coplanar facets.
+    FuseFacets,
+
+    /// Opens the cross-section view.
+    CrossSection,
+
+    /// Rotates the polytope.
+    Rotate,
+
+    /// Drops the last coordinate of the polytope.
+    Project,
+
+    /// Computes the genus of the surface of a polyhedron.
+    SurfaceGenus,
+
+    /// Computes the angular defects at the vertices of a polyhedron.
+    AngularDefects,
+
+    /// Checks whether the faces of a polyhedron can be oriented consistently.
+    CheckOrientability,
+
+    /// Removes a facet.
+    RemoveFacet,
+
+    /// Glues a cap onto a facet.
+    Augment,
+
+    /// Splits the polytope into its facets.
+    ExplodeToFacets,
+}
+
+impl GuardedOp {
+    /// Returns why the operation can't be applied to a polytope with a given
+    /// rank and dimension, or `None` if it can.
+    fn disabled_reason(self, rank: usize, dim: usize) -> Option<&'static str> {
+        match self {
+            Self::Petrial if rank != 4 => Some("Petrials are only defined for polyhedra."),
+            Self::PetriePolygon if rank < 3 => {
+                Some("Petrie polygons are only defined for polygons and higher.")
+            }
+            Self::Kleetope if rank < 3 => {
+                Some("Kleetopes are only defined for polygons and higher.")
+            }
+            Self::Chamfer if rank != 4 => Some("Chamfering is only supported for polyhedra."),
+            Self::Truncate if rank < 3 => {
+                Some("Truncating is only supported for polygons and higher.")
+            }
+            Self::FuseFacets if rank < 3 => Some("The polytope has no facets to fuse."),
+            Self::CrossSection if rank < 4 => {
+                Some("Slicing polytopes of rank less than 3 is not supported.")
+            }
+            Self::Rotate if dim < 2 => Some("Polytopes in less than 2D cannot be rotated."),
+            Self::Project if dim == 0 => Some("Polytopes in 0D cannot be projected."),
+            Self::SurfaceGenus if rank != 4 => Some("Surface genus is only defined for polyhedra."),
+            Self::AngularDefects if rank != 4 => {
+                Some("Angular defects are only defined for polyhedra.")
+            }
+            Self::CheckOrientability if rank != 4 => {
+                Some("Orientability can only be checked for polyhedra.")
+            }
+            Self::RemoveFacet if rank < 3 => {
+                Some("Removing facets is only supported for polygons and higher.")
+            }
+            Self::Augment if rank < 3 => Some("Augmenting is only supported for polygons and higher."),
+            Self::ExplodeToFacets if rank < 3 => {
+                Some("Splitting into facets is only supported for polygons and higher.")
+            }
+            _ => None,
         }
-    };
+    }
 }
 
-/// The system that shows the top panel.
-#[allow(clippy::too_many_arguments)]
-pub fn show_top_panel(
-    // Info about the application state.
-    egui_ctx: Res<'_, EguiContext>,
-    mut query: Query<'_, '_, &mut Concrete>,
-    keyboard: Res<'_, Input<KeyCode>>,
+/// The operations in the top panel that can take a long time on large
+/// polytopes, and thus ask for confirmation first.
+#[derive(Clone, Copy)]
+pub enum ExpensiveOp {
+    /// Builds the dual.
+    Dual,
 
-    // The Miratope resources controlled by the top panel.
-    mut section_state: ResMut<'_, SectionState>,
-    mut section_direction: ResMut<'_, Vec<SectionDirection>>,
-    mut file_dialog_state: ResMut<'_, FileDialogState>,
-    mut projection_type: ResMut<'_, ProjectionType>,
-    mut poly_name: ResMut<'_, PolyName>,
-    mut memory: ResMut<'_, Memory>,
-    mut show_memory: ResMut<'_, ShowMemory>,
-    mut show_help: ResMut<'_, ShowHelp>,
-    mut export_memory: ResMut<'_, ExportMemory>,
-    mut background_color: ResMut<'_, ClearColor>,
+    /// Builds the Petrial.
+    Petrial,
 
-    mut visuals: ResMut<'_, egui::Visuals>,
+    /// Builds the Kleetope.
+    Kleetope,
 
-    // The different windows that can be shown.
-    (
-        mut dual_window,
-        mut pyramid_window,
-        mut prism_window,
-        mut tegum_window,
-        mut antiprism_window,
-        mut duopyramid_window,
-        mut duoprism_window,
-        mut duotegum_window,
-        mut duocomb_window,
+    /// Fuses coplanar facets.
+    FuseFacets,
+}
+
+impl ExpensiveOp {
+    /// Every expensive operation.
+    const ALL: [Self; 4] = [Self::Dual, Self::Petrial, Self::Kleetope, Self::FuseFacets];
+
+    /// The name of the operation, as shown in the confirmation dialog.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Dual => "Dual",
+            Self::Petrial => "Petrial",
+            Self::Kleetope => "Kleetope",
+            Self::FuseFacets => "Identify coplanar facets",
+        }
+    }
+
+    /// A rough estimate of the number of elements the operation has to build,
+    /// given the element counts of the polytope.
+    fn estimated_cost(self, el_counts: &[usize]) -> usize {
+        let total: usize = el_counts.iter().sum();
+        match self {
+            // Every facet is replaced by a pyramid over it. Such a pyramid has
+            // twice as many elements as the facet, which has fewer than the
+            // polytope, so there's at most twice as many per facet.
+            Self::Kleetope => 2 * total * el_counts.iter().rev().nth(1).copied().unwrap_or(0),
+            _ => total,
+        }
+    }
+
+    /// Records the operation with a given tolerance, so that it can be
+    /// repeated.
+    fn remember(self, last_operation: &mut LastOperation, tolerance: Float) {
+        last_operation.set(self.name(), move |p, poly_name| {
+            self.apply(p, poly_name, tolerance)
+        });
+    }
+
+    /// Applies the operation to a polytope, updating its name.
+    fn apply(self, p: &mut Concrete, poly_name: &mut String, tolerance: Float) {
+        match self {
+            Self::Dual => match p.try_dual_mut() {
+                Ok(_) => {
+                    *poly_name = format!("Dual of {}", poly_name);
+                    println!("Dual succeeded.")
+                }
+                Err(err) => eprintln!("Dual failed: {}", err),
+            },
+            Self::Petrial => {
+                if p.petrial_mut() {
+                    *poly_name = format!("Petrial of {}", poly_name);
+                    println!("Petrial succeeded.");
+                } else {
+                    eprintln!("Petrial failed.");
+                }
+            }
+            Self::Kleetope => {
+                *p = p.kis();
+                *poly_name = format!("Kleetope of {}", poly_name);
+            }
+            Self::FuseFacets => {
+                *p = p.fuse_facets_with(tolerance);
+                println!("Fuse succeeded!");
+            }
+        }
+    }
+}
+
+/// Builds a pyramid out of a polytope, updating its name.
+fn pyramid(p: &mut Concrete, name: &mut String) {
+    *p = p.pyramid();
+    *name = format!("Pyramid of {}", name);
+}
+
+/// Builds a tegum out of a polytope, updating its name.
+fn tegum(p: &mut Concrete, name: &mut String) {
+    *p = p.tegum();
+    *name = format!("Tegum of {}", name);
+}
+
+/// Converts a polytope into its ditope, updating its name.
+fn ditope(p: &mut Concrete, name: &mut String) {
+    p.ditope_mut();
+    *name = format!("Ditope of {}", name);
+    println!("Ditope succeeded!");
+}
+
+/// Converts a polytope into its hosotope, updating its name.
+fn hosotope(p: &mut Concrete, name: &mut String) {
+    p.hosotope_mut();
+    *name = format!("Hosotope of {}", name);
+    println!("Hosotope succeeded!");
+}
+
+/// Reverses the orientation of a polytope.
+fn reverse_orientation(p: &mut Concrete, _: &mut String) {
+    p.reverse_orientation();
+    println!("Orientation reversed!");
+}
+
+/// Embeds a polytope into one more dimension.
+fn embed(p: &mut Concrete, _: &mut String) {
+    p.embed();
+    println!("Embedded into {}D.", p.dim_or());
+}
+
+/// Projects a polytope into one less dimension, warning if any vertices
+/// coincide afterwards.
+fn project(p: &mut Concrete, _: &mut String) {
+    if p.project() {
+        println!("Projected into {}D. Some vertices now coincide.", p.dim_or());
+    } else {
+        println!("Projected into {}D.", p.dim_or());
+    }
+}
+
+/// Builds the ambo of a polytope, updating its name.
+fn ambo(p: &mut Concrete, name: &mut String) {
+    *p = p.ambo();
+    *name = format!("Rectified {}", name);
+}
+
+/// Builds the truncation of a polytope, updating its name.
+fn truncate(p: &mut Concrete, name: &mut String) {
+    *p = p.truncate();
+    *name = format!("Truncated {}", name);
+}
+
+/// Builds the expansion of a polytope, updating its name.
+fn expand(p: &mut Concrete, name: &mut String) {
+    *p = p.expand();
+    *name = format!("Expanded {}", name);
+}
+
+/// Builds the bevel of a polytope, updating its name.
+fn bevel(p: &mut Concrete, name: &mut String) {
+    *p = p.bevel();
+    *name = format!("Beveled {}", name);
+}
+
+/// Builds the chamfer of a polytope, updating its name.
+fn chamfer(p: &mut Concrete, name: &mut String) {
+    *p = p.chamfer();
+    *name = format!("Chamfered {}", name);
+}
+
+/// Builds the characteristic orthoscheme of a polytope, updating its name.
+/// Warns if the polytope isn't regular, since then the orthoscheme isn't a
+/// fundamental domain of its symmetry group.
+fn orthoscheme(p: &mut Concrete, name: &mut String) {
+    if !p.is_regular() {
+        println!("The polytope isn't regular, so this isn't a fundamental domain.");
+    }
+
+    if let Some(q) = p.orthoscheme() {
+        *p = q;
+        *name = format!("Orthoscheme of {}", name);
+    }
+}
+
+/// Applies a sequence of Conway operators to a polytope, updating its name.
+fn conway(p: &mut Concrete, name: &mut String, notation: &str) {
+    match p.conway(notation) {
+        Ok(q) => {
+            *p = q;
+            *name = format!("{} {}", notation.trim(), name);
+            println!("Conway operators succeeded.");
+        }
+        Err(err) => eprintln!("Conway operators failed: {}", err),
+    }
+}
+
+/// The system that adds the operations of the top panel to the command
+/// palette. The windows add themselves.
+fn register_commands(mut commands: ResMut<'_, CommandRegistry>) {
+    for op in ExpensiveOp::ALL {
+        commands.register(op.name(), Action::Expensive(op));
+    }
+
+    commands
+        .register_op("Pyramid", pyramid)
+        .register_op("Tegum", tegum)
+        .register_op("Ditope", ditope)
+        .register_op("Hosotope", hosotope)
+        .register_op("Reverse orientation", reverse_orientation)
+        .register_op("Embed", embed)
+        .register_op("Project", project)
+        .register_op("Ambo", ambo)
+        .register_op("Truncate", truncate)
+        .register_op("Expand", expand)
+        .register_op("Bevel", bevel)
+        .register_op("Chamfer", chamfer)
+        .register_op("Orthoscheme", orthoscheme);
+}
+
+/// Adds a button that's disabled whenever `reason` is `Some`, in which case
+/// the reason is shown as a tooltip. Returns whether the button was clicked.
+fn guarded_button(ui: &mut Ui, text: &str, reason: Option<&str>) -> bool {
+    let response = ui.add(egui::Button::new(text).enabled(reason.is_none()));
+
+    match reason {
+        Some(reason) => {
+            response.on_hover_text(reason);
+            false
+        }
+        None => response.clicked(),
+    }
+}
+
+/// All of the windows that can be shown on screen, as mutable resources.
+pub type EguiWindows<'a> = (
+    ResMut<'a, DualWindow>,
+    ResMut<'a, PyramidWindow>,
+    ResMut<'a, PrismWindow>,
+    ResMut<'a, TegumWindow>,
+    ResMut<'a, AntiprismWindow>,
+    ResMut<'a, DuopyramidWindow>,
+    ResMut<'a, DuoprismWindow>,
+    ResMut<'a, DuotegumWindow>,
+    ResMut<'a, DuocombWindow>,
+    ResMut<'a, StarWindow>,
+    ResMut<'a, CompoundWindow>,
+    ResMut<'a, TruncateWindow>,
+    ResMut<'a, ScaleWindow>,
+    ResMut<'a, FacetingSettings>,
+	ResMut<'a, RotateWindow>,
+	ResMut<'a, PlaneWindow>,
+);
+
+/// The windows that didn't fit in [`EguiWindows`], since system parameters
+/// can only be tuples of up to 16 elements.
+pub type MoreEguiWindows<'a> = (
+    ResMut<'a, KisWindow>,
+    ResMut<'a, CdWindow>,
+    ResMut<'a, EdgeLengthWindow>,
+    ResMut<'a, PolygonWindow>,
+    ResMut<'a, RegularWindow>,
+    ResMut<'a, ReflectWindow>,
+    ResMut<'a, RemoveFacetWindow>,
+    ResMut<'a, AugmentWindow>,
+    ResMut<'a, HasseWindow>,
+    ResMut<'a, OrbitWindow>,
+    ResMut<'a, CompareWindow>,
+    ResMut<'a, LaceWindow>,
+    ResMut<'a, NameWindow>,
+    ResMut<'a, ChamferWindow>,
+    ResMut<'a, UniformFamilyWindow>,
+);
+
+macro_rules! element_sort {
+    ($p:ident) => {
+        if !$p.abs().sorted() {
+            $p.element_sort();
+        }
+    };
+}
+
+/// Returns the centers and radii of a polytope, only flagging it as changed
+/// when they have to be calculated again.
+fn metrics<'a>(p: &'a mut Mut<'_, Concrete>) -> &'a Metrics {
+    if p.metrics().is_none() {
+        p.metrics_mut();
+    }
+
+    p.metrics().unwrap()
+}
+
+/// The system that shows the top panel.
+#[allow(clippy::too_many_arguments)]
+pub fn show_top_panel(
+    // Info about the application state.
+    egui_ctx: Res<'_, EguiContext>,
+    mut query: Query<'_, '_, &mut Concrete>,
+    keyboard: Res<'_, Input<KeyCode>>,
+
+    // The Miratope resources controlled by the top panel.
+    mut section_state: ResMut<'_, SectionState>,
+    mut section_direction: ResMut<'_, Vec<SectionDirection>>,
+    mut file_dialog_state: ResMut<'_, FileDialogState>,
+    mut projection_type: ResMut<'_, ProjectionType>,
+    mut poly_name: ResMut<'_, PolyName>,
+    mut memory: ResMut<'_, Memory>,
+    mut show_memory: ResMut<'_, ShowMemory>,
+    mut show_help: ResMut<'_, ShowHelp>,
+    mut export_memory: ResMut<'_, ExportMemory>,
+    (mut scene, mut depth_cue, mut overlays, mut subdivisions, mut exploded, mut explode_facets, mut antialiasing, mut orientation, mut shown_ranks, mut picking, mut legend, mut tabs, (mut recenter_on_load, mut metrics_on_save), mut wireframe_detail, mut fit_section): (
+        ResMut<'_, SceneSettings>,
+        ResMut<'_, DepthCue>,
+        ResMut<'_, Overlays>,
+        ResMut<'_, EdgeSubdivisions>,
+        ResMut<'_, ExplodedSimplices>,
+        ResMut<'_, ExplodeFacets>,
+        ResMut<'_, Antialiasing>,
+        ResMut<'_, Orientation4D>,
+        ResMut<'_, ShownRanks>,
+        ResMut<'_, Picking>,
+        ResMut<'_, ColorLegend>,
+        ResMut<'_, Tabs>,
+        (ResMut<'_, RecenterOnLoad>, ResMut<'_, MetricsOnSave>),
+        ResMut<'_, WireframeDetail>,
+        ResMut<'_, FitSection>,
+    ),
+
+    (mut visuals, mut section_filled, mut tolerance, mut cam_inputs, mut unit_edge, mut confirm_threshold, mut element_cap, mut conway_notation, mut pending_op, mut ghost, (mut numpy_edges, mut incidence_ranks), mut section_animation, mut last_operation, mut palette, commands, mut open_events): (
+        ResMut<'_, egui::Visuals>,
+        ResMut<'_, SectionFilled>,
+        ResMut<'_, Tolerance>,
+        EventWriter<'_, '_, CameraInputEvent>,
+        ResMut<'_, UnitEdge>,
+        ResMut<'_, ConfirmThreshold>,
+        ResMut<'_, ElementCap>,
+        ResMut<'_, ConwayNotation>,
+        ResMut<'_, PendingOp>,
+        ResMut<'_, Ghost>,
+        (Local<'_, bool>, Local<'_, [usize; 2]>),
+        ResMut<'_, SectionAnimation>,
+        ResMut<'_, LastOperation>,
+        ResMut<'_, CommandPalette>,
+        Res<'_, CommandRegistry>,
+        EventWriter<'_, '_, OpenWindow>,
+    ),
+
+    // The different windows that can be shown.
+    (
+        mut dual_window,
+        mut pyramid_window,
+        mut prism_window,
+        mut tegum_window,
+        mut antiprism_window,
+        mut duopyramid_window,
+        mut duoprism_window,
+        mut duotegum_window,
+        mut duocomb_window,
         mut star_window,
         mut compound_window,
         mut truncate_window,
@@ -363,11 +1318,140 @@ pub fn show_top_panel(
 		mut rotate_window,
 		mut plane_window,
     ): EguiWindows<'_>,
+    (mut kis_window, mut cd_window, mut edge_length_window, mut polygon_window, mut regular_window, mut reflect_window, mut remove_facet_window, mut augment_window, mut hasse_window, mut orbit_window, mut compare_window, mut lace_window, mut name_window, mut chamfer_window, mut uniform_family_window): MoreEguiWindows<'_>,
 ) {
+    // The rank and dimension of the polytope on screen, used to disable the
+    // operations that don't apply to it.
+    let (rank, dim) = query
+        .iter_mut()
+        .next()
+        .map_or((0, 0), |p| (p.rank(), p.dim_or()));
+    let guard = |op: GuardedOp| op.disabled_reason(rank, dim);
+
+    // The element counts of the polytope on screen, used to decide whether
+    // expensive operations need confirmation.
+    let el_counts: Vec<usize> = query
+        .iter_mut()
+        .next()
+        .map_or_else(Vec::new, |p| p.el_count_iter().collect());
+
+    // The orbit window can't read the settings by itself.
+    if orbit_window.max_elements() != element_cap.get() {
+        orbit_window.set_max_elements(element_cap.get());
+    }
+    let element_count: usize = el_counts.iter().sum();
+
+    // Asks for confirmation before running an expensive operation.
+    if let Some(op) = pending_op.0 {
+        let mut proceed = false;
+        let mut cancel = false;
+
+        egui::Window::new("Confirm operation")
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_ctx.ctx(), |ui| {
+                ui.label(format!(
+                    "{} on a polytope with {} elements.",
+                    op.name(),
+                    element_count
+                ));
+                ui.label(format!(
+                    "This will build roughly {} elements, and may take a while.",
+                    op.estimated_cost(&el_counts)
+                ));
+
+                ui.horizontal(|ui| {
+                    proceed = ui.button("Proceed").clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+
+        if proceed {
+            if let Some(mut p) = query.iter_mut().next() {
+                op.apply(&mut p, &mut poly_name.0, tolerance.value());
+                op.remember(&mut last_operation, tolerance.value());
+            }
+        }
+        if proceed || cancel {
+            pending_op.0 = None;
+        }
+    }
+
+    // The expensive operation clicked on this frame, if any.
+    let mut requested_op = None;
+
+    // Whether to repeat the last operation on this frame.
+    let mut repeat =
+        !egui_ctx.ctx().wants_keyboard_input() && keyboard.just_pressed(KeyCode::Period);
+
+    // Runs the command picked from the command palette, if any.
+    if advanced(&keyboard) && keyboard.just_pressed(KeyCode::P) {
+        palette.toggle();
+    }
+    if let Some(command) = palette.show(egui_ctx.ctx(), &commands) {
+        match command.action {
+            Action::Apply(op) => {
+                if let Some(mut p) = query.iter_mut().next() {
+                    op(&mut p, &mut poly_name.0);
+                    last_operation.set(command.name, move |p, name| op(p, name));
+                }
+            }
+            Action::Expensive(op) => requested_op = Some(op),
+            Action::Open => open_events.send(OpenWindow(command.name)),
+        }
+    }
+
     // The top bar.
     egui::TopBottomPanel::top("top_panel").show(egui_ctx.ctx(), |ui| {
         menu::bar(ui, |ui| {
             
+            // Builds basic polytopes from scratch.
+            menu::menu(ui, "New", |ui| {
+                if ui.button("Point").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        *p = Concrete::point();
+                        poly_name.0 = "point".to_string();
+                    }
+                }
+
+                if ui.button("Dyad").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        *p = Concrete::dyad();
+                        poly_name.0 = "dyad".to_string();
+                    }
+                }
+
+                if ui.button("Polygon...").clicked() {
+                    polygon_window.open();
+                }
+
+                ui.separator();
+
+                // Simplices, hypercubes and orthoplexes of any dimension.
+                if ui.button("Regular polytope...").clicked() {
+                    regular_window.open();
+                }
+
+                // The regular polychora without analogs in other dimensions.
+                for (name, diagram) in [
+                    ("24-cell", "x3o4o3o"),
+                    ("120-cell", "x5o3o3o"),
+                    ("600-cell", "o5o3o3x"),
+                ] {
+                    if ui.button(name).clicked() {
+                        if let Some(mut p) = query.iter_mut().next() {
+                            match Concrete::from_cd(diagram) {
+                                Ok(q) => {
+                                    *p = q;
+                                    poly_name.0 = name.to_string();
+                                }
+                                Err(err) => eprintln!("Could not build the {}: {}", name, err),
+                            }
+                        }
+                    }
+                }
+            });
+
             // Operations on files.
             menu::menu(ui, "File", |ui| {
                 // Loads a file.
@@ -375,11 +1459,130 @@ pub fn show_top_panel(
                     file_dialog_state.open();
                 }
 
+                // Loads a file into a new tab, keeping the polytope on screen
+                // in its own tab.
+                if ui.button("Open in new tab").clicked() {
+                    tabs.request(TabRequest::New);
+                    file_dialog_state.open();
+                }
+
+                // Opens a new tab with a copy of the polytope on screen.
+                if ui.button("New tab").clicked() {
+                    tabs.request(TabRequest::New);
+                }
+
+                // Whether loaded polytopes are moved to the origin, or keep
+                // the coordinates in their files.
+                let mut recenter = recenter_on_load.0;
+                ui.checkbox(&mut recenter, "Recenter on load");
+                if recenter != recenter_on_load.0 {
+                    recenter_on_load.0 = recenter;
+                }
+
                 // Saves a file.
                 if ui.button("Save").clicked() {
                     file_dialog_state.save("polytope".to_string());
                 }
 
+                // Whether saved OFF files note the polytope's hyperspheres.
+                let mut metrics = metrics_on_save.0;
+                ui.checkbox(&mut metrics, "Save hyperspheres")
+                    .on_hover_text("Notes the circumsphere, bounding sphere and insphere in the comments of OFF files.");
+                if metrics != metrics_on_save.0 {
+                    metrics_on_save.0 = metrics;
+                }
+
+                // Saves only the incidences of the polytope.
+                if ui.button("Export lattice").clicked() {
+                    file_dialog_state.export_lattice("polytope".to_string());
+                }
+
+                // Saves the projection of the polytope onto the xy-plane.
+                if ui
+                    .button("Export SVG")
+                    .on_hover_text("Use View → Coxeter plane first for the standard 2D diagram.")
+                    .clicked()
+                {
+                    file_dialog_state.export_svg(poly_name.0.clone());
+                }
+
+                // Saves the polytope as it's shown on screen.
+                if ui.button("Save screenshot").clicked() {
+                    file_dialog_state.screenshot(poly_name.0.clone());
+                }
+
+                // Saves the polytope as an OFF file that Stella can open.
+                if ui
+                    .button("Export for Stella")
+                    .on_hover_text("Only polyhedra and polychora can be opened in Stella.")
+                    .clicked()
+                {
+                    file_dialog_state.export_stella(poly_name.0.clone());
+                }
+
+                // Copies the coordinates as a Python literal, for use with
+                // NumPy.
+                ui.horizontal(|ui| {
+                    if ui.button("Copy as numpy").clicked() {
+                        if let Some(p) = query.iter_mut().next() {
+                            ui.output().copied_text = p.to_numpy(*numpy_edges);
+                            println!("Copied the coordinates to the clipboard.");
+                        }
+                    }
+
+                    ui.checkbox(&mut *numpy_edges, "With edges");
+                });
+
+                // Saves the incidences between the elements of two ranks, which
+                // are picked by their dimension.
+                ui.horizontal(|ui| {
+                    let max_dim = query
+                        .iter()
+                        .next()
+                        .map_or(0, |p| p.rank().saturating_sub(2));
+
+                    if ui
+                        .button("Export incidence matrix")
+                        .on_hover_text("Rows and columns are elements of the two dimensions, in order.")
+                        .clicked()
+                    {
+                        let [d0, d1] = *incidence_ranks;
+                        file_dialog_state.export_incidence(
+                            format!("{} incidences", poly_name.0),
+                            d0 + 1,
+                            d1 + 1,
+                        );
+                    }
+
+                    for dim in incidence_ranks.iter_mut() {
+                        ui.add(egui::DragValue::new(dim).clamp_range(0..=max_dim).prefix("dim "));
+                    }
+                });
+
+                ui.separator();
+
+                // Saves everything, so that it can be loaded back later.
+                if ui.button("Save session").clicked() {
+                    file_dialog_state.save_session();
+                }
+
+                // Loads everything from a saved session.
+                if ui.button("Load session").clicked() {
+                    file_dialog_state.open_session();
+                }
+
+                ui.separator();
+
+                // Builds a polytope from a Coxeter diagram.
+                if ui.button("From Coxeter diagram...").clicked() {
+                    cd_window.open();
+                }
+
+                // Builds the hull of the orbit of a point under some group.
+                if ui.button("From symmetry orbit...").clicked() {
+                    orbit_window.open();
+                }
+
                 if ui.button("Export all memory slots").clicked() {
                     export_memory.0 = true;
                     export_memory.1 = 0;
@@ -429,14 +1632,327 @@ pub fn show_top_panel(
                         p.set_changed();
                     }
                 }
+
+                // Frames the polytope so that it fills the view.
+                if ui.button("Fit to view").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        if let Some(event) = CameraInputEvent::fit_to(&p, *projection_type) {
+                            cam_inputs.send(event);
+                        }
+                    }
+                }
+
+                // Whether the camera follows the size of cross-sections.
+                let mut fit = fit_section.0;
+                ui.checkbox(&mut fit, "Fit view to cross-sections");
+                if fit != fit_section.0 {
+                    fit_section.0 = fit;
+                }
+
+                // Undoes any rotation into the fourth dimension.
+                if ui
+                    .add(egui::Button::new("Reset 4D orientation").enabled(!orientation.is_identity()))
+                    .on_hover_text("Right-drag to rotate in the xw and yw planes.")
+                    .clicked()
+                {
+                    orientation.reset();
+                }
+
+                ui.separator();
+
+                // We edit a copy, so that the scene only updates on changes.
+                let mut new_scene = scene.clone();
+                let mut changed = false;
+
+                ui.horizontal(|ui| {
+                    changed |= new_scene.background_color.color_edit_button(ui);
+                    ui.label("Background color");
+                });
+
+                changed |= ui
+                    .add(egui::Slider::new(&mut new_scene.ambient, 0.0..=1.0).text("Ambient light"))
+                    .changed();
+
+                changed |= ui
+                    .add(DirectionWidget::new(&mut new_scene.light_direction, "Key light direction"))
+                    .changed();
+
+                if ui.button("Reset scene").clicked() {
+                    new_scene = Default::default();
+                    changed = true;
+                }
+
+                if changed {
+                    *scene = new_scene;
+                }
+
+                // Trades performance for smoother edges.
+                let mut samples = antialiasing.0;
+                ui.horizontal(|ui| {
+                    ui.label("Antialiasing");
+                    for &n in Antialiasing::SAMPLES.iter() {
+                        ui.radio_value(&mut samples, n, format!("{}x", n));
+                    }
+                })
+                .response
+                .on_hover_text("Applies after restarting Miratope.");
+                if samples != antialiasing.0 {
+                    antialiasing.0 = samples;
+                }
+
+                ui.separator();
+
+                // Fades the far away parts of the polytope.
+                let mut new_cue = depth_cue.clone();
+                ui.checkbox(&mut new_cue.enabled, "Depth cueing");
+                ui.add(
+                    egui::Slider::new(&mut new_cue.intensity, 0.0..=1.0)
+                        .text("Depth cue intensity"),
+                );
+                ui.checkbox(&mut new_cue.use_w, "Depth cue by w-coordinate");
+
+                if new_cue != *depth_cue {
+                    *depth_cue = new_cue;
+                }
+
+                // Splits the edges so that they curve under projection.
+                let mut new_subdivisions = subdivisions.0;
+                ui.add(
+                    egui::Slider::new(&mut new_subdivisions, 1..=32)
+                        .text("Edge subdivisions"),
+                );
+                if new_subdivisions != subdivisions.0 {
+                    subdivisions.0 = new_subdivisions;
+
+                    // Forces an update on all polytopes.
+                    if let Some(mut p) = query.iter_mut().next() {
+                        p.set_changed();
+                    }
+                }
+
+                // Draws fewer edges for polytopes with many of them.
+                let mut new_detail = *wireframe_detail;
+                ui.add(
+                    egui::Slider::new(&mut new_detail.quality, 0.0..=1.0)
+                        .text("Wireframe quality"),
+                )
+                .on_hover_text(format!(
+                    "Only the longest looking edges are drawn once there's more than {}.",
+                    new_detail.threshold
+                ));
+                if new_detail != *wireframe_detail {
+                    *wireframe_detail = new_detail;
+
+                    if let Some(mut p) = query.iter_mut().next() {
+                        p.set_changed();
+                    }
+                }
+
+                // Chooses which ranks of elements are drawn.
+                let mut new_ranks = *shown_ranks;
+                ui.horizontal(|ui| {
+                    ui.label("Show:");
+                    ui.checkbox(&mut new_ranks.vertices, "Vertices");
+                    ui.checkbox(&mut new_ranks.edges, "Edges");
+                    ui.checkbox(&mut new_ranks.faces, "Faces");
+                    ui.checkbox(&mut new_ranks.cells, "Cells")
+                        .on_hover_text("Draws the cells shrunk toward their centers, when the faces are hidden.");
+                });
+                if new_ranks != *shown_ranks {
+                    *shown_ranks = new_ranks;
+
+                    // Forces an update on all polytopes.
+                    if let Some(mut p) = query.iter_mut().next() {
+                        p.set_changed();
+                    }
+                }
+
+                // Chooses what clicking on the polytope highlights.
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut picking.enabled, "Pick:")
+                        .on_hover_text("Clicking on the polytope highlights the element under the cursor.");
+                    for mode in PickMode::ALL {
+                        ui.radio_value(&mut picking.mode, mode, mode.label());
+                    }
+                });
+
+                // Pushes the facets apart, without changing the polytope.
+                let mut new_explode = explode_facets.0;
+                ui.add(egui::Slider::new(&mut new_explode, 0.0..=2.0).text("Explode"));
+                if new_explode != explode_facets.0 {
+                    explode_facets.0 = new_explode;
+
+                    // Forces an update on all polytopes.
+                    if let Some(mut p) = query.iter_mut().next() {
+                        p.set_changed();
+                    }
+                }
+
+                ui.separator();
+
+                // Toggles the axes, the ground grid and the Petrie polygon.
+                let mut show_axes = overlays.axes;
+                let mut show_grid = overlays.grid;
+                let mut show_petrie = overlays.petrie;
+                ui.checkbox(&mut show_axes, "Show axes");
+                ui.checkbox(&mut show_grid, "Show ground grid");
+                ui.checkbox(&mut show_petrie, "Highlight Petrie polygon");
+                if show_axes != overlays.axes
+                    || show_grid != overlays.grid
+                    || show_petrie != overlays.petrie
+                {
+                    overlays.axes = show_axes;
+                    overlays.grid = show_grid;
+                    overlays.petrie = show_petrie;
+                }
+
+                // Only the first three axes are drawn.
+                if show_axes && dim > 3 {
+                    let hidden: Vec<_> = (3..dim).map(axis_name).collect();
+                    ui.label(format!(
+                        "Showing x, y, z; {} {}.",
+                        hidden.join(", "),
+                        if projection_type.is_orthogonal() {
+                            "dropped"
+                        } else {
+                            "projected in perspective"
+                        }
+                    ));
+                }
+
+                // Lists the face colors and the faces that have them.
+                ui.checkbox(&mut legend.shown, "Show color legend");
+
+                // Shows the polytope before the last operation faintly behind
+                // the current one.
+                let mut show_ghost = overlays.ghost;
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut show_ghost, "Show ghost of previous");
+
+                    if ui
+                        .add(egui::Button::new("Clear").enabled(ghost.previous.is_some()))
+                        .clicked()
+                    {
+                        ghost.clear();
+                    }
+                });
+                if show_ghost != overlays.ghost {
+                    overlays.ghost = show_ghost;
+                }
+
+                // Shows the element lattice, whose elements can be picked to
+                // highlight them.
+                if ui.button("Hasse diagram").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        hasse_window.open(&p);
+                        overlays.element = None;
+                    }
+                }
+
+                ui.separator();
+
+                // Shows the polytope split into simplices, pushed apart.
+                let mut explode = exploded.0.is_some();
+                ui.checkbox(&mut explode, "Explode into simplices");
+                if explode != exploded.0.is_some() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match exploded.0.take() {
+                            Some(Exploded { original, name, .. }) => {
+                                *p = original;
+                                poly_name.0 = name;
+                            }
+                            None => {
+                                element_sort!(p);
+                                let simplices = p.exploded_simplices(EXPLODE_FACTOR);
+                                let original = std::mem::replace(&mut *p, simplices.clone());
+                                exploded.0 = Some(Exploded {
+                                    original,
+                                    name: poly_name.0.clone(),
+                                    simplices,
+                                });
+                                poly_name.0 = format!("Simplices of {}", poly_name.0);
+                            }
+                        }
+                    }
+                }
+
+                ui.separator();
+
+                // Rotates the polytope so that its Petrie polygon projects
+                // onto the screen as a regular polygon, and optionally exports
+                // this view as an SVG image.
+                let coxeter = guarded_button(ui, "Coxeter plane", guard(GuardedOp::PetriePolygon));
+                let coxeter_svg =
+                    guarded_button(ui, "Export Coxeter plane SVG", guard(GuardedOp::PetriePolygon));
+                if coxeter || coxeter_svg {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        let plane = p
+                            .first_petrie_polygon()
+                            .and_then(|petrie| p.coxeter_plane(&petrie));
+
+                        match plane {
+                            Some(plane) => {
+                                p.align_plane(&plane);
+                                cam_inputs.send(CameraInputEvent::FaceXy);
+                                *projection_type = ProjectionType::Orthogonal;
+
+                                if coxeter_svg {
+                                    file_dialog_state.export_svg(poly_name.0.clone());
+                                } else {
+                                    println!("Aligned to the Coxeter plane.");
+                                }
+                            }
+                            None => eprintln!("The Petrie polygon doesn't span a plane."),
+                        }
+                    }
+                }
+
+                ui.separator();
+
+                // The tolerance for slicing, convexity and coplanarity.
+                let mut exponent = tolerance.0;
+                ui.add(
+                    egui::Slider::new(&mut exponent, -15..=-1)
+                        .text("Tolerance")
+                        .prefix("1e"),
+                );
+                if exponent != tolerance.0 {
+                    tolerance.0 = exponent;
+                }
+
+                // The number of elements above which expensive operations ask
+                // for confirmation.
+                ui.add(
+                    egui::DragValue::new(&mut confirm_threshold.0)
+                        .speed(100.0)
+                        .prefix("Confirm above: ")
+                        .suffix(" elements"),
+                );
+
+                // The number of elements above which convex hulls and
+                // facetings give up.
+                ui.add(
+                    egui::DragValue::new(&mut element_cap.0)
+                        .speed(1000.0)
+                        .prefix("Abort above: ")
+                        .suffix(" elements"),
+                )
+                .on_hover_text("0 for no limit");
             });
 
             // Prints out properties about the loaded polytope.
             menu::menu(ui, "Properties", |ui| {
+                // Shows the operations the name of the polytope is built from.
+                if ui.button("Name structure").clicked() {
+                    name_window.open(&poly_name.0);
+                }
+
                 // Determines the circumsphere of the polytope.
                 if ui.button("Circumsphere").clicked() {
-                    if let Some(p) = query.iter_mut().next() {
-                        match p.circumsphere() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        let metrics = metrics(&mut p);
+
+                        match &metrics.circumsphere {
                             Some(sphere) => println!(
                                 "The circumradius is {} and the circumcenter is {}.",
                                 sphere.radius(),
@@ -444,31 +1960,183 @@ pub fn show_top_panel(
                             ),
                             None => println!("The polytope has no circumsphere."),
                         }
+                        if let Some(sphere) = &metrics.bounding_sphere {
+                            println!(
+                                "The smallest hypersphere around the polytope has radius {} and center {}.",
+                                sphere.radius(),
+                                sphere.center
+                            );
+                        }
+                    }
+                }
+
+                // Determines the largest hypersphere within the polytope.
+                if ui.button("Insphere").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match &metrics(&mut p).insphere {
+                            Some(sphere) => println!(
+                                "The largest hypersphere within the polytope has radius {} and center {}.",
+                                sphere.radius(),
+                                sphere.center
+                            ),
+                            None => println!("The polytope isn't convex or has no volume."),
+                        }
+                    }
+                }
+
+                // Determines whether the polytope is convex.
+                if ui.button("Convexity").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        if p.is_convex_with(tolerance.value()) {
+                            println!("The polytope is convex.");
+                        } else {
+                            println!("The polytope is not convex.");
+                        }
+                    }
+                }
+
+                // Determines whether the polytope is orientable.
+                if ui.button("Orientability").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        element_sort!(p);
+
+                        if p.orientable() {
+                            println!("The polytope is orientable.");
+                        } else {
+                            println!("The polytope is not orientable.");
+                        }
+                    }
+                }
+
+                // Whether to report volumes for a unit edge length.
+                let mut normalize = unit_edge.0;
+                ui.checkbox(&mut normalize, "Unit edge length");
+                if normalize != unit_edge.0 {
+                    unit_edge.0 = normalize;
+                }
+
+                // Gets the volume of the polytope.
+                if ui.button("Volume").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        element_sort!(p);
+
+                        if unit_edge.0 {
+                            match p.unit_edge_volume() {
+                                Some(vol) => println!("The volume for a unit edge is {}.", vol),
+                                None => println!("The polytope has no volume for a unit edge."),
+                            }
+                        } else if let Some(vol) = p.volume() {
+                            println!("The volume is {}.", vol);
+                        } else {
+                            println!("The polytope has no volume.");
+                        }
+                    }
+                }
+
+                // Gets the average of the vertices and the center of mass of
+                // the polytope, which differ for non-uniform polytopes.
+                if ui.button("Centers").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        let metrics = metrics(&mut p);
+
+                        if let Some(centroid) = &metrics.gravicenter {
+                            println!("The vertex centroid is {}.", centroid);
+                        }
+                        match &metrics.volume_centroid {
+                            Some(center) => println!("The center of mass is {}.", center),
+                            None => println!("The polytope has no center of mass."),
+                        }
+                    }
+                }
+
+                // Gets the surface area of the polytope.
+                if ui.button("Surface area").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        if unit_edge.0 {
+                            match p.unit_edge_surface_area() {
+                                Some(area) => {
+                                    println!("The surface area for a unit edge is {}.", area)
+                                }
+                                None => println!("The polytope has no surface area for a unit edge."),
+                            }
+                        } else if let Some(area) = p.surface_area() {
+                            println!("The surface area is {}.", area);
+                        } else {
+                            println!("The polytope has no surface area.");
+                        }
+                    }
+                }
+
+                // Shows the distribution of the edge lengths.
+                if ui.button("Edge lengths").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        edge_length_window.open(&p);
                     }
                 }
 
-                // Determines whether the polytope is orientable.
-                if ui.button("Orientability").clicked() {
+                // Gets the genus of the surface of a polyhedron.
+                if guarded_button(ui, "Surface genus", guard(GuardedOp::SurfaceGenus)) {
                     if let Some(mut p) = query.iter_mut().next() {
                         element_sort!(p);
 
-                        if p.orientable() {
-                            println!("The polytope is orientable.");
-                        } else {
-                            println!("The polytope is not orientable.");
+                        match p.abs.surface_genus() {
+                            Some(genus) if p.orientable() => {
+                                println!("The surface is orientable with genus {}.", genus)
+                            }
+                            Some(genus) => {
+                                println!("The surface is non-orientable with genus {}.", genus)
+                            }
+                            None => println!("The surface isn't a closed 2-manifold."),
                         }
                     }
                 }
 
-                // Gets the volume of the polytope.
-                if ui.button("Volume").clicked() {
+                // Lists the angular defect at every vertex, and checks their
+                // sum against Descartes' theorem.
+                if guarded_button(ui, "Angular defects", guard(GuardedOp::AngularDefects)) {
                     if let Some(mut p) = query.iter_mut().next() {
                         element_sort!(p);
 
-                        if let Some(vol) = p.volume() {
-                            println!("The volume is {}.", vol);
-                        } else {
-                            println!("The polytope has no volume.");
+                        if let Some(defects) = p.angular_defects() {
+                            for (idx, defect) in defects.iter().enumerate() {
+                                println!("Vertex {}: {}", idx, defect);
+                            }
+
+                            let total: Float = defects.iter().sum();
+                            let expected = Float::TAU * p.abs.euler_characteristic() as Float;
+                            println!(
+                                "The total angular defect is {}, or {}π.",
+                                total,
+                                total / Float::PI
+                            );
+                            if (total - expected).abs() > tolerance.value() {
+                                println!(
+                                    "This doesn't match 2π times the Euler characteristic, {}.",
+                                    expected
+                                );
+                            }
+                        }
+                    }
+                }
+
+                // Gets the number of connected components of the polytope.
+                if ui.button("Component count").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        match p.abs.component_count() {
+                            1 => println!("The polytope has 1 component."),
+                            n => println!("The polytope has {} components.", n),
+                        }
+                    }
+                }
+
+                // Counts the ridges shared by more than two facets, which
+                // break orientability and most exports.
+                if ui.button("Non-manifold ridges").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        match p.abs.non_manifold_ridges().len() {
+                            0 => println!("Every ridge is shared by at most two facets."),
+                            1 => println!("1 ridge is shared by more than two facets."),
+                            n => println!("{} ridges are shared by more than two facets.", n),
                         }
                     }
                 }
@@ -476,7 +2144,7 @@ pub fn show_top_panel(
                 // Gets the number of flags of the polytope.
                 if ui.button("Flag count").clicked() {
                     if let Some(p) = query.iter_mut().next() {
-                        println!("The polytope has {} flags.", p.flags().count())
+                        println!("The polytope has {} flags.", p.flag_count())
                     }
                 }
 
@@ -484,19 +2152,75 @@ pub fn show_top_panel(
                 if advanced(&keyboard) {
                     if ui.button("Rotation symmetry group").clicked() {
                         if let Some(mut p) = query.iter_mut().next() {
-                            let group = p.get_rotation_group().unwrap().0;
-                            println!("Rotation symmetry order {}", group.count());
+                            match p.get_rotation_group() {
+                                Some((group, _)) => {
+                                    println!("Rotation symmetry order {}", group.count())
+                                }
+                                None => println!("The symmetry group couldn't be computed."),
+                            }
                         }
                     }
                 } else {
                     if ui.button("Symmetry group").clicked() {
                         if let Some(mut p) = query.iter_mut().next() {
-                            let group = p.get_symmetry_group().unwrap().0;
-                            println!("Symmetry order {}", group.count());
+                            match p.get_symmetry_group() {
+                                Some((group, _)) => {
+                                    let order = group.count();
+                                    println!("Symmetry order {}", order);
+
+                                    // A polytope is regular iff its symmetries
+                                    // act transitively on its flags.
+                                    if order == p.flag_count() {
+                                        println!("The polytope is regular.");
+                                    }
+                                }
+                                None => println!("The symmetry group couldn't be computed."),
+                            }
                         }
                     }
                 }
 				
+                // Gets the Schläfli symbol of a regular polytope.
+                if ui.button("Schläfli symbol").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match p.schlafli_symbol() {
+                            Some(symbol) => println!("The Schläfli symbol is {}.", symbol),
+                            None => println!("The polytope isn't regular."),
+                        }
+                    }
+                }
+
+                // Guesses which well-known polytope this is.
+                if ui.button("Identify").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        match p.identify() {
+                            Some(guess) => println!("{}", guess),
+                            None => println!("There are no well-known polytopes of this rank to compare against."),
+                        }
+                    }
+                }
+
+                // Groups the facets by combinatorial type.
+                if ui.button("Facet types").clicked() {
+                    if let Some(p) = query.iter_mut().next() {
+                        let types = p.abs.facet_types();
+                        println!("The polytope has {} facet type(s):", types.len());
+                        for t in types {
+                            println!(
+                                "{} × facet {}, with element counts {:?}",
+                                t.count,
+                                t.example,
+                                t.facet.el_count_iter().collect::<Vec<_>>()
+                            );
+                        }
+                    }
+                }
+
+                // Checks whether two polytopes are isomorphic.
+                if ui.button("Compare...").clicked() {
+                    compare_window.open();
+                }
+
                 // Gets if it is a compound.
                 if ui.button("Is compound").clicked() {
                     if let Some(mut p) = query.iter_mut().next() {
@@ -524,10 +2248,21 @@ pub fn show_top_panel(
 
             menu::menu(ui, "Transform", |ui| {
             
-                if ui.button("Scale to unit edge length").clicked() {
-                    let mut p = query.iter_mut().next().unwrap();
-                    let e_l = (&p.vertices[p.abs[2][0].subs[0]] - &p.vertices[p.abs[2][0].subs[1]]).norm();
-                    p.scale(1.0/e_l);
+                // Scales a polytope so that its edges have unit length, or
+                // unit mean length if they aren't all equal.
+                if ui.button("Normalize edges").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        let equilateral = p.is_equilateral();
+                        match p.normalize_edges() {
+                            Some(k) => {
+                                if !equilateral {
+                                    println!("Warning: the edges aren't all equal, the mean edge length was used.");
+                                }
+                                println!("Scaled the polytope by a factor of {}.", k);
+                            }
+                            None => println!("The polytope has no edges."),
+                        }
+                    }
                 }
 
                 if ui.button("Scale to unit circumradius").clicked() {
@@ -547,31 +2282,42 @@ pub fn show_top_panel(
                 
                 ui.separator();
 
-                // Moves a polytope so that the circumcenter is at the origin.
-                if ui.button("Recenter by circumcenter").clicked() {
-                    let mut p = query.iter_mut().next().unwrap();
-                    match p.circumsphere() {
-                        Some(sphere) => {
-                            p.recenter_with(&sphere.center);
+                // Moves a polytope so that some center is at the origin.
+                egui::CollapsingHeader::new("Recenter")
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        for (mode, text) in [
+                            (RecenterMode::Centroid, "Vertex centroid"),
+                            (RecenterMode::CenterOfMass, "Center of mass"),
+                            (RecenterMode::Circumcenter, "Circumcenter"),
+                            (RecenterMode::BoundingBoxCenter, "Bounding box center"),
+                        ] {
+                            if ui.button(text).clicked() {
+                                if let Some(mut p) = query.iter_mut().next() {
+                                    if mode == RecenterMode::Circumcenter && p.circumsphere().is_none() {
+                                        println!("The vertices aren't cospherical, using the least-squares circumcenter.");
+                                    }
+                                    if mode == RecenterMode::CenterOfMass {
+                                        element_sort!(p);
+                                        if p.volume_centroid().is_none() {
+                                            println!("The polytope has no center of mass.");
+                                        }
+                                    }
+                                    p.recenter_by(mode);
+                                }
+                            }
                         }
-                        None => println!("The polytope has no circumsphere."),
-                    }
-                }
-                
-                // Moves a polytope so that the gravicenter is at the origin.
-                if ui.button("Recenter by gravicenter").clicked() {
-                    query.iter_mut().next().unwrap().recenter();
-                }
+                    });
 				
 				ui.separator();
 				
 				// Rotates a polytope around the origin.
-				if ui.button("Rotate...").clicked() {
+				if guarded_button(ui, "Rotate...", guard(GuardedOp::Rotate)) {
 					rotate_window.open();
 				}
 				
 				//Rotates a polytope around the origin along a given plane intersecting the origin.
-				if ui.button("Rotate with plane...").clicked() {
+				if guarded_button(ui, "Rotate with plane...", guard(GuardedOp::Rotate)) {
 					plane_window.open();
 				}
 				
@@ -579,39 +2325,38 @@ pub fn show_top_panel(
 
             // Operations on polytopes.
             menu::menu(ui, "Operations", |ui| {
+                // Applies the last operation again.
+                let repeat_label = match last_operation.name() {
+                    Some(name) => format!("Repeat {} (.)", name),
+                    None => "Repeat (.)".to_string(),
+                };
+                if ui
+                    .add(egui::Button::new(repeat_label).enabled(last_operation.name().is_some()))
+                    .clicked()
+                {
+                    repeat = true;
+                }
+
+                ui.separator();
+
                 // Converts the active polytope into its dual.
                 if advanced(&keyboard) {
                     if ui.button("Dual...").clicked() {
                         dual_window.open();
                     }
-                } else if let Some(mut p) = query.iter_mut().next() {
-                    if ui.button("Dual").clicked() {
-                        match p.try_dual_mut() {
-                            Ok(_) => {
-                                poly_name.0 = format!("Dual of {}", poly_name.0);
-                                println!("Dual succeeded.")
-                            },
-                            Err(err) => eprintln!("Dual failed: {}", err),
-                        }
-                    }
+                } else if ui.button("Dual").clicked() {
+                    requested_op = Some(ExpensiveOp::Dual);
                 }
 
                 ui.separator();
 
                 // Converts the active polytope into its Petrial.
-                if ui.button("Petrial").clicked() {
-                    if let Some(mut p) = query.iter_mut().next() {
-                        if p.petrial_mut() {
-                            poly_name.0 = format!("Petrial of {}", poly_name.0);
-                            println!("Petrial succeeded.");
-                        } else {
-                            eprintln!("Petrial failed.");
-                        }
-                    }
+                if guarded_button(ui, "Petrial", guard(GuardedOp::Petrial)) {
+                    requested_op = Some(ExpensiveOp::Petrial);
                 }
 
                 // Converts the active polytope into its Petrie polygon.
-                if ui.button("Petrie polygon").clicked() {
+                if guarded_button(ui, "Petrie polygon", guard(GuardedOp::PetriePolygon)) {
                     if let Some(mut p) = query.iter_mut().next() {
                         p.element_sort();
                         let flag = p.first_flag();
@@ -635,8 +2380,8 @@ pub fn show_top_panel(
                     }
                 } else if let Some(mut p) = query.iter_mut().next() {
                     if ui.button("Pyramid").clicked() {
-                        *p = p.pyramid();
-                        poly_name.0 = format!("Pyramid of {}", poly_name.0);
+                        pyramid(&mut p, &mut poly_name.0);
+                        last_operation.set("Pyramid", pyramid);
                     }
                 }
 
@@ -646,10 +2391,29 @@ pub fn show_top_panel(
                         prism_window.open();
                     }
                 } else if let Some(mut p) = query.iter_mut().next() {
-                    if ui.button("Prism").clicked() {
-                        *p = p.prism();
-                        poly_name.0 = format!("Prism of {}", poly_name.0);
-                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Prism").clicked() {
+                            let height = *prism_window.height_mut();
+                            let prism = move |p: &mut Concrete, name: &mut String| {
+                                *p = p.prism_with(height);
+                                *name = format!("Prism of {}", name);
+                            };
+                            prism(&mut p, &mut poly_name.0);
+                            last_operation.set("Prism", prism);
+                        }
+
+                        ui.add(
+                            egui::DragValue::new(prism_window.height_mut())
+                                .speed(0.01)
+                                .clamp_range(0.0..=Float::MAX),
+                        )
+                        .on_hover_text("Height");
+                    });
+                }
+
+                // Makes a lace prism out of the current polytope.
+                if ui.button("Lace prism...").clicked() {
+                    lace_window.open();
                 }
 
                 // Makes a tegum out of the current polytope.
@@ -659,8 +2423,8 @@ pub fn show_top_panel(
                     }
                 } else if let Some(mut p) = query.iter_mut().next() {
                     if ui.button("Tegum").clicked() {
-                        *p = p.tegum();
-                        poly_name.0 = format!("Tegum of {}", poly_name.0);
+                        tegum(&mut p, &mut poly_name.0);
+                        last_operation.set("Tegum", tegum);
                     }
                 }
 
@@ -670,32 +2434,186 @@ pub fn show_top_panel(
                         antiprism_window.open();
                     }
                 } else if let Some(mut p) = query.iter_mut().next() {
-                    if ui.button("Antiprism").clicked() {
-                        match p.try_antiprism() {
-                            Ok(q) => {
-                                *p = q;
-                                poly_name.0 = format!("Antiprism of {}", poly_name.0);
-                            },
-                            Err(err) => eprintln!("Antiprism failed: {}", err),
+                    ui.horizontal(|ui| {
+                        if ui.button("Antiprism").clicked() {
+                            let height = *antiprism_window.height_mut();
+                            let antiprism = move |p: &mut Concrete, name: &mut String| {
+                                let sphere = Hypersphere::unit(p.dim().unwrap_or(1));
+                                match p.try_antiprism_with(&sphere, height) {
+                                    Ok(q) => {
+                                        *p = q;
+                                        *name = format!("Antiprism of {}", name);
+                                    },
+                                    Err(err) => eprintln!("Antiprism failed: {}", err),
+                                }
+                            };
+                            antiprism(&mut p, &mut poly_name.0);
+                            last_operation.set("Antiprism", antiprism);
+                        }
+
+                        ui.add(egui::DragValue::new(antiprism_window.height_mut()).speed(0.01))
+                            .on_hover_text("Height");
+                    });
+                }
+
+                // Raises a pyramid over every facet of the current polytope.
+                if advanced(&keyboard) {
+                    if guarded_button(ui, "Kleetope...", guard(GuardedOp::Kleetope)) {
+                        if let Some(p) = query.iter_mut().next() {
+                            kis_window.set_height(p.kis_height());
+                        }
+                        kis_window.open();
+                    }
+                } else if guarded_button(ui, "Kleetope", guard(GuardedOp::Kleetope)) {
+                    requested_op = Some(ExpensiveOp::Kleetope);
+                }
+
+                // Insets every face and replaces every edge with a hexagon.
+                if guarded_button(ui, "Chamfer...", guard(GuardedOp::Chamfer)) {
+                    chamfer_window.open();
+                }
+
+                // Reverses the orientation of the active polytope.
+                if ui.button("Reverse orientation").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        reverse_orientation(&mut p, &mut poly_name.0);
+                    }
+                }
+
+                // Checks whether the faces of the active polyhedron can be
+                // oriented consistently. Exported files use this orientation.
+                if guarded_button(
+                    ui,
+                    "Check orientability",
+                    guard(GuardedOp::CheckOrientability),
+                ) {
+                    if let Some(p) = query.iter().next() {
+                        if p.oriented_face_cycles().is_some() {
+                            println!(
+                                "The faces can be oriented consistently. Exported files will use this orientation."
+                            );
+                        } else {
+                            println!(
+                                "The polytope can't be oriented: it's non-orientable, or it isn't a closed surface."
+                            );
                         }
                     }
                 }
 
+                // Opens the window to reflect the polytope across a hyperplane.
+                if ui.button("Reflect...").clicked() {
+                    reflect_window.open();
+                }
+
+                // Appends a zero coordinate to every vertex.
+                if ui
+                    .button("Embed")
+                    .on_hover_text("Moves the polytope into one more dimension.")
+                    .clicked()
+                {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        embed(&mut p, &mut poly_name.0);
+                        last_operation.set("Embed", embed);
+                    }
+                }
+
+                // Drops the last coordinate of every vertex.
+                if guarded_button(ui, "Project", guard(GuardedOp::Project)) {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        project(&mut p, &mut poly_name.0);
+                        last_operation.set("Project", project);
+                    }
+                }
+
                 // Converts the active polytope into its ditope.
                 if ui.button("Ditope").clicked() {
                     if let Some(mut p) = query.iter_mut().next() {
-                        p.ditope_mut();
-                        poly_name.0 = format!("Ditope of {}", poly_name.0);
-                        println!("Ditope succeeded!");
+                        ditope(&mut p, &mut poly_name.0);
+                    }
+                }
+
+                // Builds the simplex spanned by the centers of a flag.
+                if ui
+                    .button("Orthoscheme")
+                    .on_hover_text("The fundamental domain of a regular polytope.")
+                    .clicked()
+                {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        orthoscheme(&mut p, &mut poly_name.0);
+                        last_operation.set("Orthoscheme", orthoscheme);
+                    }
+                }
+
+                // Offers every Wythoffian polytope with the same symmetry as
+                // the active regular polytope.
+                if ui
+                    .button("Uniform family...")
+                    .on_hover_text(
+                        "Polytopes sharing the symmetry of a regular polytope. \
+                        The symmetries of other polytopes aren't detected.",
+                    )
+                    .clicked()
+                {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match p.schlafli_symbol() {
+                            Some(symbol) => uniform_family_window.load(symbol),
+                            None => println!(
+                                "The polytope isn't regular. Only the families of regular polytopes are detected."
+                            ),
+                        }
+                    }
+                }
+
+                // Opens the window to remove a facet from the active polytope.
+                if guarded_button(ui, "Remove facet...", guard(GuardedOp::RemoveFacet)) {
+                    remove_facet_window.open();
+                }
+
+                // Removes facets until every ridge is shared by at most two
+                // of them.
+                if ui
+                    .button("Remove non-manifold facets")
+                    .on_hover_text("Keeps the first two facets at every ridge shared by more than two.")
+                    .clicked()
+                {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match p.remove_non_manifold_facets() {
+                            0 => println!("No facets needed to be removed."),
+                            1 => println!("Removed 1 facet."),
+                            n => println!("Removed {} facets.", n),
+                        }
+                    }
+                }
+
+                // Opens the window to glue a cap onto a facet.
+                if guarded_button(ui, "Augment...", guard(GuardedOp::Augment)) {
+                    augment_window.open();
+                }
+
+                // Stores every facet of the active polytope, flattened and
+                // recentered, in its own memory slot.
+                if guarded_button(ui, "Explode to facets", guard(GuardedOp::ExplodeToFacets)) {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        let rank = p.rank();
+                        p.element_sort();
+
+                        for idx in 0..p.el_count(rank - 1) {
+                            if let Some(mut facet) = p.element(rank - 1, idx) {
+                                facet.flatten();
+                                facet.recenter();
+                                memory.push((facet, Some(format!("facet {} of {}", idx, poly_name.0))));
+                            }
+                        }
+
+                        show_memory.0 = true;
+                        println!("Split into {} facets.", p.el_count(rank - 1));
                     }
                 }
 
                 // Converts the active polytope into its hosotope.
                 if ui.button("Hosotope").clicked() {
                     if let Some(mut p) = query.iter_mut().next() {
-                        p.hosotope_mut();
-                        poly_name.0 = format!("Hosotope of {}", poly_name.0);
-                        println!("Hosotope succeeded!");
+                        hosotope(&mut p, &mut poly_name.0);
                     }
                 }
                 
@@ -736,19 +2654,91 @@ pub fn show_top_panel(
                 if ui.button("Truncate...").clicked() {
                     truncate_window.open();
                 }
+
+                // Conway's operators, named by their letters in his notation.
+                ui.collapsing("Conway operators", |ui| {
+                    if ui.button("Dual (d)").clicked() {
+                        requested_op = Some(ExpensiveOp::Dual);
+                    }
+                    if guarded_button(ui, "Kis (k)", guard(GuardedOp::Kleetope)) {
+                        requested_op = Some(ExpensiveOp::Kleetope);
+                    }
+
+                    let ops: [(&str, &'static str, fn(&mut Concrete, &mut String), GuardedOp); 5] = [
+                        ("Ambo (a)", "Ambo", ambo, GuardedOp::Truncate),
+                        ("Truncate (t)", "Truncate", truncate, GuardedOp::Truncate),
+                        ("Expand (e)", "Expand", expand, GuardedOp::Truncate),
+                        ("Bevel (b)", "Bevel", bevel, GuardedOp::Truncate),
+                        ("Chamfer (c)", "Chamfer", chamfer, GuardedOp::Chamfer),
+                    ];
+                    for (label, name, op, guarded) in ops {
+                        if guarded_button(ui, label, guard(guarded)) {
+                            if let Some(mut p) = query.iter_mut().next() {
+                                op(&mut p, &mut poly_name.0);
+                                last_operation.set(name, op);
+                            }
+                        }
+                    }
+
+                    // Applies several operators at once, right to left.
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut conway_notation.0)
+                                .desired_width(60.0),
+                        )
+                        .on_hover_text("Letters of the operators, applied right to left, like \"tk\"");
+
+                        if ui.button("Apply").clicked() {
+                            if let Some(mut p) = query.iter_mut().next() {
+                                let notation = conway_notation.0.clone();
+                                conway(&mut p, &mut poly_name.0, &notation);
+                                last_operation.set("Conway operators", move |p, name| {
+                                    conway(p, name, &notation)
+                                });
+                            }
+                        }
+                    });
+                });
 				
                 ui.separator();
 
-                if ui.button("Identify coplanar facets").clicked() {
+                if guarded_button(ui, "Identify coplanar facets", guard(GuardedOp::FuseFacets)) {
+                    requested_op = Some(ExpensiveOp::FuseFacets);
+                }
+
+                // Merges the vertices that lie on top of each other.
+                if ui.button("Weld vertices").clicked() {
+                    if let Some(mut p) = query.iter_mut().next() {
+                        match p.weld_vertices(tolerance.value()) {
+                            0 => println!("No vertices were close enough to weld."),
+                            1 => println!("Welded 1 vertex."),
+                            n => println!("Welded {} vertices.", n),
+                        }
+                    }
+                }
+
+                // Projects the polytope into the subspace spanned by its vertices.
+                if ui.button("Flatten").clicked() {
                     if let Some(mut p) = query.iter_mut().next() {
-                        *p = p.fuse_facets();
-                        println!("Fuse succeeded!");
+                        let dim = p.dim_or();
+                        let subspace = Subspace::from_points(p.vertices.iter());
+
+                        if p.vertices.is_empty() || subspace.is_full_rank() {
+                            println!("The polytope is already in its lowest dimension.");
+                        } else {
+                            p.flatten_into(&subspace);
+                            println!("Flattened from {}D into {}D.", dim, subspace.rank());
+                        }
                     }
                 }
             });
 
-            // Toggles cross-section mode.
-            if ui.button("Cross-section").clicked() {
+            // Toggles cross-section mode. The view can always be closed.
+            let section_guard = match *section_state {
+                SectionState::Active { .. } => None,
+                SectionState::Inactive => guard(GuardedOp::CrossSection),
+            };
+            if guarded_button(ui, "Cross-section", section_guard) {
                 match section_state.as_mut() {
                     // The view is active, but will be inactivated.
                     SectionState::Active {
@@ -812,6 +2802,7 @@ pub fn show_top_panel(
                             faceting_settings.only_below_vertex,
                             if faceting_settings.max_facet_types == 0 {None} else {Some(faceting_settings.max_facet_types)},
                             if faceting_settings.max_per_hyperplane == 0 {None} else {Some(faceting_settings.max_per_hyperplane)},
+                            element_cap.get(),
                             faceting_settings.uniform,
                             faceting_settings.compounds,
                             faceting_settings.mark_fissary,
@@ -821,11 +2812,23 @@ pub fn show_top_panel(
                             faceting_settings.save_to_file,
                             faceting_settings.file_path.clone(),
                         );
-                        for faceting in facetings {
-                            memory.push(faceting);
+
+                        match facetings {
+                            Ok(facetings) => {
+                                faceting_settings.aborted = None;
+                                for faceting in facetings {
+                                    memory.push(faceting);
+                                }
+                            }
+                            Err(err) => faceting_settings.aborted = Some(err.report),
                         }
                     }
                 }
+
+                // Reports how far the last enumeration got if it gave up.
+                if let Some(report) = &faceting_settings.aborted {
+                    ui.colored_label(egui::Color32::RED, report);
+                }
                 
                 ui.separator();
 
@@ -838,6 +2841,29 @@ pub fn show_top_panel(
                 show_memory.0 = !show_memory.0;
             }
             memory.show(&mut query, &mut poly_name, &egui_ctx, &mut show_memory.0);
+            edge_length_window.show(egui_ctx.ctx());
+            name_window.show(egui_ctx.ctx());
+
+            // Highlights the element picked in the Hasse diagram, until the
+            // window is closed.
+            let hasse_open = hasse_window.open;
+            if let Some(element) = hasse_window.show(egui_ctx.ctx()) {
+                overlays.element = Some(element);
+            } else if hasse_open && !hasse_window.open && overlays.element.is_some() {
+                overlays.element = None;
+            }
+
+            // Looks up the polytope on the web.
+            menu::menu(ui, "Wiki", |ui| {
+                for target in WikiTarget::ALL {
+                    if ui.button(target.label()).clicked() {
+                        let link = target.link(&poly_name.0);
+                        if let Err(err) = webbrowser::open(&link) {
+                            eprintln!("Could not open {}: {}", link, err);
+                        }
+                    }
+                }
+            });
 
             if ui.button("Help").clicked() {
                 show_help.0 = !show_help.0;
@@ -847,70 +2873,137 @@ pub fn show_top_panel(
                 .resizable(false)
                 .show(egui_ctx.ctx(), |ui| {
                     ui.heading("Hotkeys");
-                    ui.label("V: toggle faces\nB: toggle wireframe");
+                    ui.label("V: toggle faces\nB: toggle wireframe\nPeriod: repeat last operation\nCtrl+P: command palette");
                     ui.separator();
                     ui.heading("Camera");
-                    ui.label("WSADRF: move\nQE: roll\nX: reset\nHold Ctrl: move faster\nHold Shift: move slower");
+                    ui.label("WSADRF: move\nQE: roll\nX: reset\nZ: fit to view\nRight drag: rotate in 4D, or like left drag below 4D\nClick: highlight element, if picking is on\nHold Ctrl: move faster\nHold Shift: move slower");
                     ui.separator();
                     ui.heading("UI");
                     ui.label("Hold Ctrl: extra options in some menus\nHold Shift: move number sliders slower");
+                    ui.separator();
+                    ui.heading("Explorer");
+                    ui.label("Down: go into facet\nUp: go back to parent\nLeft/Right: change element index");
+                    ui.separator();
+                    ui.heading("Cross-section");
+                    ui.label("[ and ]: step the last slice back and forth\nHold Shift: take larger steps");
                 });
 
             // Background color picker.
-
-            // The current background color.
-            let [r, g, b, a] = background_color.0.as_rgba_f32().map(|c| (c * 255.0) as u8);
-            let color = egui::Color32::from_rgba_premultiplied(r, g, b, a);
-
-            // The new background color.
-            let mut new_color = color;
-            egui::color_picker::color_edit_button_srgba(
-                ui,
-                &mut new_color,
-                egui::color_picker::Alpha::Opaque,
-            );
-
-            // Updates the background color if necessary.
-            if color != new_color {
-                background_color.0 = Color::rgb(
-                    new_color.r() as f32 / 255.0,
-                    new_color.g() as f32 / 255.0,
-                    new_color.b() as f32 / 255.0,
-                );
+            let mut background_color = scene.background_color.clone();
+            if background_color.color_edit_button(ui) {
+                scene.background_color = background_color;
             }
 
             // Light/dark mode toggle.
             if let Some(new_visuals) = visuals.light_dark_small_toggle_button(ui) {
                 *visuals = new_visuals;
             }
+
+            // The rank and dimension of the polytope on screen.
+            ui.separator();
+            ui.label(format!("Rank {}, {}D", rank as isize - 1, dim));
         });
 
+        // Repeats the last operation.
+        if repeat {
+            if let Some(mut p) = query.iter_mut().next() {
+                last_operation.repeat(&mut p, &mut poly_name.0);
+            }
+        }
+
+        // Runs the expensive operation that was clicked, or asks for
+        // confirmation if the polytope is large.
+        if let Some(op) = requested_op {
+            if element_count > confirm_threshold.0 {
+                pending_op.0 = Some(op);
+            } else if let Some(mut p) = query.iter_mut().next() {
+                op.apply(&mut p, &mut poly_name.0, tolerance.value());
+                op.remember(&mut last_operation, tolerance.value());
+            }
+        }
+
         // Shows secondary views below the menu bar.
-        show_views(ui, query, &mut poly_name, section_state, section_direction);
+        show_views(
+            ui,
+            query,
+            &mut poly_name,
+            section_state,
+            section_direction,
+            &mut section_filled,
+            &mut section_animation,
+            &mut overlays,
+            &mut file_dialog_state,
+            &keyboard,
+            tolerance.value(),
+        );
     });
 }
 
+/// The fraction of the range of a cross-section slider that a hotkey steps
+/// through.
+const SCRUB_STEP: Float = 0.01;
+
+/// The fraction of the range of a cross-section slider that a hotkey steps
+/// through while holding Shift.
+const SCRUB_STEP_LARGE: Float = 0.1;
+
+/// Returns how far the hotkeys step the last cross-section slider on this
+/// frame, as a fraction of its range. The arrow keys are left to the element
+/// explorer, so the brackets are used instead.
+fn scrub_step(keyboard: &Input<KeyCode>) -> Float {
+    let step = if keyboard.pressed(KeyCode::LShift) || keyboard.pressed(KeyCode::RShift) {
+        SCRUB_STEP_LARGE
+    } else {
+        SCRUB_STEP
+    };
+
+    let mut total = 0.0;
+    if keyboard.just_pressed(KeyCode::RBracket) {
+        total += step;
+    }
+    if keyboard.just_pressed(KeyCode::LBracket) {
+        total -= step;
+    }
+    total
+}
+
 /// Shows any secondary views that are active. Currently, just shows the
 /// cross-section view.
+#[allow(clippy::too_many_arguments)]
 fn show_views(
     ui: &mut Ui,
     mut query: Query<'_, '_, &mut Concrete>,
     poly_name: &mut ResMut<'_, PolyName>,
     mut section_state: ResMut<'_, SectionState>,
-    mut section_direction: ResMut<'_, Vec<SectionDirection>>
+    mut section_direction: ResMut<'_, Vec<SectionDirection>>,
+    section_filled: &mut ResMut<'_, SectionFilled>,
+    section_animation: &mut ResMut<'_, SectionAnimation>,
+    overlays: &mut ResMut<'_, Overlays>,
+    file_dialog_state: &mut ResMut<'_, FileDialogState>,
+    keyboard: &Input<KeyCode>,
+    tolerance: Float,
 ) {
     // The cross-section settings.
     if let SectionState::Active {
+        original_polytope,
         minmax,
         hyperplane_pos,
         flatten,
         lock,
+        symmetry,
         ..
     } = (*section_state).clone()
     {
         ui.label("Cross section settings:");
         ui.spacing_mut().slider_width = ui.available_width() / 3.0;
 
+        // How far to step the last slider, unless we're typing.
+        let scrub = if ui.ctx().wants_keyboard_input() {
+            0.0
+        } else {
+            scrub_step(keyboard)
+        };
+
         // Sets the slider range to the range of x coordinates in the polytope.
         let mut i = 0;
 
@@ -920,11 +3013,34 @@ fn show_views(
 			ui.add(
 				egui::Slider::new(
 					&mut new_hyperplane_pos,
-					(minmax[i].0 + 0.0000001)..=(minmax[i].1 - 0.0000001), // We do this to avoid empty slices.
+					(minmax[i].0 + tolerance)..=(minmax[i].1 - tolerance), // We do this to avoid empty slices.
 				)
 				.text("Slice depth")
 				.prefix("pos: "),
-			);
+			)
+			.on_hover_text("Press [ and ] to step through the slices of the last slider.");
+
+			// Steps the last slider with the hotkeys.
+			#[allow(clippy::float_cmp)]
+			if i + 1 == hyperplane_pos.len() && scrub != 0.0 {
+				new_hyperplane_pos = (new_hyperplane_pos + scrub * (minmax[i].1 - minmax[i].0))
+					.min(minmax[i].1 - tolerance)
+					.max(minmax[i].0 + tolerance);
+			}
+
+			// Snaps the slicing depth to symmetric positions. Flattening moves
+			// the later cross-sections out of the original coordinates.
+			if let Some(symmetry) = &symmetry {
+				if i == 0 || !flatten {
+					new_hyperplane_pos = snap_to_symmetry(
+						symmetry,
+						&section_direction[..=i],
+						&hyperplane_pos[..i],
+						new_hyperplane_pos,
+						minmax[i].1 - minmax[i].0,
+					);
+				}
+			}
 
 			// Updates the slicing depth.
 			#[allow(clippy::float_cmp)]
@@ -936,6 +3052,16 @@ fn show_views(
 				}
 			}
 
+			// Shows where the slicing hyperplane actually is.
+			let point = &section_direction[i].0 * hyperplane_pos[i];
+			let coords: Vec<_> = point.iter().map(|x| format!("{:.3}", x)).collect();
+			let fraction = (hyperplane_pos[i] - minmax[i].0) / (minmax[i].1 - minmax[i].0);
+			ui.label(format!(
+				"Through ({}), {:.0}% of the way across",
+				coords.join(", "),
+				100.0 * fraction.clamp(0.0, 1.0)
+			));
+
 			let mut new_direction = section_direction[i].0.clone();
 
 			ui.horizontal(|ui| {
@@ -946,7 +3072,7 @@ fn show_views(
 				));
 
 				if ui.button("Diagonal").clicked() {
-					new_direction = Point::from_element(new_direction.len(), 1.0/(new_direction.len() as f64).sqrt());
+					new_direction = Point::from_element(new_direction.len(), 1.0/(new_direction.len() as Float).sqrt());
 				}
 			});
 			
@@ -959,6 +3085,12 @@ fn show_views(
 			i = i + 1;
 		}
 
+        // The element counts of the current cross-section.
+        if let Some(p) = query.iter_mut().next() {
+            let counts: Vec<_> = p.el_count_iter().map(|count| count.to_string()).collect();
+            ui.label(format!("Element counts: {}", counts.join(", ")));
+        }
+
         ui.horizontal(|ui| {
             // Makes the current cross-section into the main polytope.
             if ui.button("Make main").clicked() {
@@ -1001,6 +3133,23 @@ fn show_views(
                 }
             }
 
+            // Only changes how the cross-section is rendered, so that it
+            // doesn't have to be sliced again.
+            let mut new_filled = section_filled.0;
+            ui.add(egui::Checkbox::new(&mut new_filled, "Filled"));
+            if section_filled.0 != new_filled {
+                section_filled.0 = new_filled;
+            }
+
+            // Switches between the slice and the whole polytope together with
+            // the slicing hyperplanes.
+            let mut new_plane = overlays.section_plane;
+            ui.add(egui::Checkbox::new(&mut new_plane, "Show plane"))
+                .on_hover_text("Show the whole polytope and where it's being sliced, instead of the slice.");
+            if overlays.section_plane != new_plane {
+                overlays.section_plane = new_plane;
+            }
+
             let mut new_lock = lock;
             ui.add(egui::Checkbox::new(&mut new_lock, "Lock"));
 
@@ -1012,6 +3161,46 @@ fn show_views(
                     unreachable!()
                 }
             }
+
+            let snap = symmetry.is_some();
+            let mut new_snap = snap;
+            ui.add(egui::Checkbox::new(&mut new_snap, "Snap to symmetry"))
+                .on_hover_text("Snaps the slices to the positions fixed by the symmetries of the polytope.");
+
+            // Detects the symmetries of the polytope when snapping is enabled.
+            if snap != new_snap {
+                let new_symmetry = new_snap.then(|| {
+                    let group: Vec<_> = original_polytope
+                        .clone()
+                        .get_symmetry_group()
+                        .map(|(group, _)| group.collect())
+                        .unwrap_or_default();
+
+                    if group.len() <= 1 {
+                        println!("No symmetry found, the slices will move freely.");
+                    }
+                    group
+                });
+
+                if let SectionState::Active { symmetry, .. } = section_state.as_mut() {
+                    *symmetry = new_symmetry;
+                } else {
+                    unreachable!()
+                }
+            }
+        });
+
+        // Exports the cross-sections swept by the last slider as an animation.
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut section_animation.frames).clamp_range(2..=1000));
+            ui.label("Frames");
+            ui.add(egui::DragValue::new(&mut section_animation.fps).clamp_range(1..=60));
+            ui.label("FPS");
+
+            if ui.button("Export animation...").clicked() {
+                let name = poly_name.0.clone();
+                file_dialog_state.export_section(format!("{} animation", name));
+            }
         });
     }
 
@@ -1033,6 +3222,7 @@ fn show_views(
             flatten,
             lock,
             update,
+            ..
         } = section_state.as_mut() {
             *update = false;
 
@@ -1042,35 +3232,144 @@ fn show_views(
             }
 
             if let Some(mut p) = query.iter_mut().next() {
-                let mut r = original_polytope.clone();
-				let mut i = 0;
-                while i < hyperplane_pos.len() {
-					let hyp_pos = hyperplane_pos[i];
-
-					if let Some(dim) = r.dim() {
-						let hyperplane = Hyperplane::new(section_direction[i].0.clone(), hyp_pos);
-						minmax[i] = r
-							.minmax(section_direction[i].0.clone())
-							.unwrap_or((-1.0, 1.0));
-
-						minmax[i].0 += f64::EPS;
-						let mut slice = r.cross_section(&hyperplane);
-
-						if *flatten {
-							slice.flatten_into(&hyperplane.subspace);
-							slice.recenter_with(
-								&hyperplane.flatten(&hyperplane.project(&Point::zeros(dim))),
-							);
-						}
-
-						r = slice;
-					}
-					i += 1;
-				}
-				*p = r;
+                *p = slice_section(
+                    original_polytope,
+                    &section_direction,
+                    hyperplane_pos,
+                    *flatten,
+                    tolerance,
+                    minmax,
+                );
             }
 
             poly_name.0 = format!("Slice of {}", original_name);
         }
     }
 }
+
+/// How close a slicing hyperplane has to get to a symmetric position to snap
+/// to it, as a fraction of the range of its slider.
+const SNAP_DISTANCE: Float = 0.05;
+
+/// Snaps the position of the last of some slicing hyperplanes to a symmetric
+/// position, if it's close enough to one.
+///
+/// A hyperplane is in a symmetric position when some symmetry that preserves
+/// the previous slicing hyperplanes swaps its two sides, like a reflection
+/// through a mirror orthogonal to it. Since symmetries fix the origin, the only
+/// such position is the one through it.
+fn snap_to_symmetry(
+    symmetry: &[Matrix<Float>],
+    section_direction: &[SectionDirection],
+    hyperplane_pos: &[Float],
+    pos: Float,
+    range: Float,
+) -> Float {
+    if pos.abs() > SNAP_DISTANCE * range {
+        return pos;
+    }
+
+    let (direction, previous) = section_direction.split_last().unwrap();
+    let maps_to = |m: &Matrix<Float>, d: &Vector, e: &Vector| {
+        m.nrows() == d.len() && (m * d - e).norm() < EPS.sqrt()
+    };
+
+    // Whether some symmetry preserves the previous hyperplanes and reverses
+    // the last one.
+    let symmetric = symmetry.iter().any(|m| {
+        maps_to(m, &direction.0, &-&direction.0)
+            && previous.iter().zip(hyperplane_pos).all(|(d, &p)| {
+                maps_to(m, &d.0, &d.0) || (p.abs() < EPS && maps_to(m, &d.0, &-&d.0))
+            })
+    });
+
+    if symmetric {
+        0.0
+    } else {
+        pos
+    }
+}
+
+/// Slices a polytope successively by hyperplanes with the given normals and
+/// positions, as in the cross-section view. Also updates the slider ranges.
+fn slice_section(
+    original_polytope: &Concrete,
+    section_direction: &[SectionDirection],
+    hyperplane_pos: &[Float],
+    flatten: bool,
+    tolerance: Float,
+    minmax: &mut [(Float, Float)],
+) -> Concrete {
+    let mut r = original_polytope.clone();
+
+    for (i, &hyp_pos) in hyperplane_pos.iter().enumerate() {
+        if let Some(dim) = r.dim() {
+            let hyperplane = Hyperplane::new(section_direction[i].0.clone(), hyp_pos);
+            minmax[i] = r
+                .minmax(section_direction[i].0.clone())
+                .unwrap_or((-1.0, 1.0));
+
+            minmax[i].0 += tolerance;
+            let mut slice = r.cross_section(&hyperplane);
+
+            // Slicing through ridges can leave coplanar facets.
+            if slice.rank() >= 3 {
+                slice.merge_coplanar_facets(tolerance);
+            }
+
+            if flatten {
+                slice.flatten_into(&hyperplane.subspace);
+                slice.recenter_with(&hyperplane.flatten(&hyperplane.project(&Point::zeros(dim))));
+            }
+
+            r = slice;
+        }
+    }
+
+    r
+}
+
+/// Slices the original polytope of the cross-section view at evenly spaced
+/// positions of the last slider, keeping the others in place. Returns `None`
+/// if the view is inactive.
+fn section_frames(
+    section_state: &SectionState,
+    section_direction: &[SectionDirection],
+    frames: usize,
+    tolerance: Float,
+) -> Option<Vec<Concrete>> {
+    if let SectionState::Active {
+        original_polytope,
+        minmax,
+        hyperplane_pos,
+        flatten,
+        ..
+    } = section_state
+    {
+        let last = hyperplane_pos.len().checked_sub(1)?;
+        let mut minmax = minmax.clone();
+        let mut hyperplane_pos = hyperplane_pos.clone();
+
+        // The same range as the slider, to avoid empty slices.
+        let (min, max) = (minmax[last].0 + tolerance, minmax[last].1 - tolerance);
+
+        Some(
+            (0..frames)
+                .map(|k| {
+                    let t = k as Float / (frames.max(2) - 1) as Float;
+                    hyperplane_pos[last] = min + (max - min) * t;
+                    slice_section(
+                        original_polytope,
+                        section_direction,
+                        &hyperplane_pos,
+                        *flatten,
+                        tolerance,
+                        &mut minmax,
+                    )
+                })
+                .collect(),
+        )
+    } else {
+        None
+    }
+}
@@ -0,0 +1,97 @@
+//! Builds links to the pages of polytopes on different websites.
+
+use crate::WIKI_LINK;
+
+/// The websites on which a polytope can be looked up.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WikiTarget {
+    /// The [Polytope Wiki](https://polytope.miraheze.org/wiki/).
+    PolytopeWiki,
+
+    /// The English [Wikipedia](https://en.wikipedia.org/wiki/).
+    Wikipedia,
+
+    /// Jonathan Bowers' [polytope site](https://polytope.net/hedrondude/).
+    Bowers,
+}
+
+impl WikiTarget {
+    /// All of the possible targets, in the order they're shown in the menu.
+    pub const ALL: [Self; 3] = [Self::PolytopeWiki, Self::Wikipedia, Self::Bowers];
+
+    /// The label for the target in the Wiki menu.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::PolytopeWiki => "Polytope Wiki",
+            Self::Wikipedia => "Wikipedia",
+            Self::Bowers => "Bowers' site",
+        }
+    }
+
+    /// The URL that a page title gets appended to, if the site has one.
+    fn page_link(self) -> Option<&'static str> {
+        match self {
+            Self::PolytopeWiki => Some(WIKI_LINK),
+            Self::Wikipedia => Some("https://en.wikipedia.org/wiki/"),
+            Self::Bowers => None,
+        }
+    }
+
+    /// The URL that a search query gets appended to.
+    fn search_link(self) -> &'static str {
+        match self {
+            Self::PolytopeWiki => "https://polytope.miraheze.org/w/index.php?search=",
+            Self::Wikipedia => "https://en.wikipedia.org/w/index.php?search=",
+            Self::Bowers => "https://duckduckgo.com/?q=site%3Apolytope.net+",
+        }
+    }
+
+    /// Returns the link to the page of a polytope with a given name. If the
+    /// polytope can't have a page, we link to a search instead.
+    pub fn link(self, name: &str) -> String {
+        let name = name.trim();
+
+        match self.page_link() {
+            Some(page_link) if has_page(name) => {
+                // Page titles start with a capital letter and use underscores.
+                let mut chars = name.chars();
+                let title: String = chars
+                    .next()
+                    .into_iter()
+                    .flat_map(char::to_uppercase)
+                    .chain(chars)
+                    .map(|c| if c == ' ' { '_' } else { c })
+                    .collect();
+
+                format!("{}{}", page_link, encode(&title))
+            }
+            _ => format!("{}{}", self.search_link(), encode(name)),
+        }
+    }
+}
+
+/// Returns whether a name could be the title of a page, rather than a name
+/// generated by Miratope after some operation.
+fn has_page(name: &str) -> bool {
+    !(name.is_empty()
+        || name == "default"
+        || name.contains(" of ")
+        || name.starts_with("polytope "))
+}
+
+/// Percent-encodes a string to be used in a URL.
+fn encode(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                res.push(b as char)
+            }
+            b' ' => res.push('+'),
+            _ => res.push_str(&format!("%{:02X}", b)),
+        }
+    }
+
+    res
+}
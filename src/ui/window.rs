@@ -7,11 +7,13 @@ use std::marker::PhantomData;
 
 use super::{
     memory::{slot_label, Memory},
-    PointWidget,
+    name::compound_name,
+    palette::{Action, CommandRegistry, OpenWindow},
+    ExprValue, ExprWidget, PointWidget, UnitPointWidget,
 };
-use crate::{Concrete, Float, Hypersphere, Point, ui::main_window::PolyName};
+use crate::{Concrete, Float, Hyperplane, Hypersphere, Point, ui::main_window::PolyName};
 
-use miratope_core::{conc::ConcretePolytope, Polytope, geometry::Subspace};
+use miratope_core::{conc::{symmetry::SchlafliSymbol, CapType, ConcretePolytope}, Polytope, float::Float as _, geometry::{Matrix, Subspace}, group::{gen_iter::GenIter, Group}};
 
 use bevy::prelude::*;
 use bevy_egui::{
@@ -37,14 +39,49 @@ pub enum ShowResult {
     Ok,
 }
 
+/// An operation applied to the polytope on screen and to its name.
+type Operation = Box<dyn Fn(&mut Concrete, &mut String) + Send + Sync>;
+
+/// The last operation applied to the polytope on screen, so that it can be
+/// repeated with the same parameters.
+#[derive(Default)]
+pub struct LastOperation(Option<(&'static str, Operation)>);
+
+impl LastOperation {
+    /// Records an operation with a given name.
+    pub fn set<F: Fn(&mut Concrete, &mut String) + Send + Sync + 'static>(
+        &mut self,
+        name: &'static str,
+        op: F,
+    ) {
+        self.0 = Some((name, Box::new(op)));
+    }
+
+    /// The name of the last operation, if any.
+    pub fn name(&self) -> Option<&'static str> {
+        self.0.as_ref().map(|(name, _)| *name)
+    }
+
+    /// Applies the last operation again, if any.
+    pub fn repeat(&self, polytope: &mut Concrete, name: &mut String) {
+        if let Some((_, op)) = &self.0 {
+            op(polytope, name);
+        }
+    }
+}
+
 /// The plugin controlling all of these windows.
 pub struct WindowPlugin;
 
 impl Plugin for WindowPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugin(DualWindow::plugin())
+        app.init_resource::<LastOperation>()
+            .init_resource::<CommandRegistry>()
+            .add_event::<OpenWindow>()
+            .add_plugin(DualWindow::plugin())
             .add_plugin(PyramidWindow::plugin())
             .add_plugin(PrismWindow::plugin())
+            .add_plugin(LaceWindow::plugin())
             .add_plugin(TegumWindow::plugin())
             .add_plugin(AntiprismWindow::plugin())
             .add_plugin(DuopyramidWindow::plugin())
@@ -53,11 +90,22 @@ impl Plugin for WindowPlugin {
             .add_plugin(DuocombWindow::plugin())
             .add_plugin(StarWindow::plugin())
             .add_plugin(CompoundWindow::plugin())
+            .add_plugin(CompareWindow::plugin())
             .add_plugin(TruncateWindow::plugin())
             .add_plugin(ScaleWindow::plugin())
+            .add_plugin(ReflectWindow::plugin())
             .add_plugin(FacetingSettings::plugin())
 			.add_plugin(RotateWindow::plugin())
-			.add_plugin(PlaneWindow::plugin());
+			.add_plugin(PlaneWindow::plugin())
+            .add_plugin(KisWindow::plugin())
+            .add_plugin(ChamferWindow::plugin())
+            .add_plugin(RemoveFacetWindow::plugin())
+            .add_plugin(AugmentWindow::plugin())
+            .add_plugin(CdWindow::plugin())
+            .add_plugin(OrbitWindow::plugin())
+            .add_plugin(PolygonWindow::plugin())
+            .add_plugin(RegularWindow::plugin())
+            .add_plugin(UniformFamilyWindow::plugin());
     }
 }
 
@@ -116,13 +164,37 @@ pub trait Window: Send + Sync + Default {
     fn close(&mut self) {
         *self.is_open_mut() = false;
     }
+
+    /// The system that opens the window when it's picked from the command
+    /// palette.
+    fn open_system(mut self_: ResMut<'_, Self>, mut events: EventReader<'_, '_, OpenWindow>)
+    where
+        Self: 'static,
+    {
+        if events.iter().any(|OpenWindow(name)| *name == Self::NAME) {
+            self_.open();
+        }
+    }
+}
+
+/// Adds the command that opens a window to the command palette, along with
+/// the system that opens it.
+fn add_to_palette<T: Window + 'static>(app: &mut App) {
+    app.world
+        .get_resource_or_insert_with(CommandRegistry::default)
+        .register(T::NAME, Action::Open);
+    app.add_system(T::open_system.system());
 }
 
 /// Implements the common methods of [`PlainWindow`] and [`UpdateWindow`]. Note
 /// that this can't be put in a common trait since some of the methods here have
 /// the same names but belong to different traits and have different defaults.
+///
+/// Windows whose parameters depend on the dimension of the polytope pass the
+/// name of the method that updates them, so that repeating their operation on
+/// a polytope of another dimension first resizes the parameters.
 macro_rules! impl_show {
-    () => {
+    ($($update:ident)?) => {
         /// Shows the window on screen.
         fn show(&mut self, ctx: &CtxRef) -> ShowResult {
             let mut open = self.is_open();
@@ -150,16 +222,35 @@ macro_rules! impl_show {
             egui_ctx: Res<'_, EguiContext>,
             mut query: Query<'_, '_, &mut Concrete>,
             mut poly_name: ResMut<'_, PolyName>,
+            mut last_operation: ResMut<'_, LastOperation>,
         ) where
             Self: 'static,
         {
             match self_.show(egui_ctx.ctx()) {
                 ShowResult::Ok => {
+                    let mut applied = false;
                     for mut polytope in query.iter_mut() {
-                        self_.action(polytope.as_mut());
+                        applied |= self_.try_action(polytope.as_mut());
                     }
-                    self_.name_action(&mut poly_name.0);
-                    self_.close()
+                    if applied {
+                        self_.name_action(&mut poly_name.0);
+                    }
+                    self_.close();
+
+                    // Remembers the parameters the window was applied with.
+                    let window = self_.clone();
+                    last_operation.set(Self::NAME, move |polytope, name| {
+                        $(
+                            let dim = polytope.dim_or();
+                            let mut window = window.clone();
+                            if window.dim() != dim {
+                                window.$update(dim);
+                            }
+                        )?
+                        if window.try_action(polytope) {
+                            window.name_action(name);
+                        }
+                    });
                 }
                 ShowResult::Close => self_.close(),
                 ShowResult::Reset => self_.reset(),
@@ -171,10 +262,17 @@ macro_rules! impl_show {
 
 /// A window that doesn't depend on any resources other than itself, and that
 /// doesn't need to be updated when the polytope is changed.
-pub trait PlainWindow: Window {
+pub trait PlainWindow: Window + Clone {
     /// Applies the action of the window to the polytope.
     fn action(&self, polytope: &mut Concrete);
 
+    /// Applies the action of the window to the polytope, and returns whether
+    /// it succeeded. The polytope is only renamed if it did.
+    fn try_action(&self, polytope: &mut Concrete) -> bool {
+        self.action(polytope);
+        true
+    }
+
     /// Applies an action to the polytope name.
     fn name_action(&self, name: &mut String);
 
@@ -203,14 +301,22 @@ impl<T: PlainWindow + 'static> Plugin for PlainWindowPlugin<T> {
     fn build(&self, app: &mut App) {
         app.init_resource::<T>()
             .add_system(T::show_system.system().label("show_windows"));
+        add_to_palette::<T>(app);
     }
 }
 
 /// A window that doesn't depend on any resources other than itself, but needs
 /// to be updated when the dimension of the polytope is changed.
-pub trait UpdateWindow: Window {
+pub trait UpdateWindow: Window + Clone {
     /// Applies the action of the window to the polytope.
     fn action(&self, polytope: &mut Concrete);
+
+    /// Applies the action of the window to the polytope, and returns whether
+    /// it succeeded. The polytope is only renamed if it did.
+    fn try_action(&self, polytope: &mut Concrete) -> bool {
+        self.action(polytope);
+        true
+    }
     
     /// Applies an action to the polytope name.
     fn name_action(&self, name: &mut String);
@@ -231,7 +337,7 @@ pub trait UpdateWindow: Window {
         self.open();
     }
 
-    impl_show!();
+    impl_show!(update);
 
     /// Updates the window when the dimension of the polytope is updated.
     fn update(&mut self, dim: usize);
@@ -264,6 +370,7 @@ impl<T: UpdateWindow + 'static> Plugin for UpdateWindowPlugin<T> {
         app.insert_resource(T::default())
             .add_system(T::show_system.system().label("show_windows"))
             .add_system(T::update_system.system().label("show_windows"));
+        add_to_palette::<T>(app);
     }
 }
 
@@ -368,6 +475,71 @@ impl<T: MemoryWindow + 'static> Plugin for MemoryWindowPlugin<T> {
     fn build(&self, app: &mut App) {
         app.init_resource::<T>()
             .add_system(T::show_system.system().label("show_windows"));
+        add_to_palette::<T>(app);
+    }
+}
+
+/// Shows a drop-down for each of two slots, to select a polytope either from
+/// memory or the currently loaded one.
+fn slot_dropdowns(slots: &mut [Slot; 2], ui: &mut Ui, memory: &Memory) {
+    const SELECT: &str = "Select";
+
+    // Iterates over both slots.
+    for (slot_idx, selected) in slots.iter_mut().enumerate() {
+        // The text for the selected option.
+        let selected_text = match selected {
+            // Nothing has been selected.
+            Slot::None => SELECT.to_string(),
+
+            // The loaded polytope is selected.
+            Slot::Loaded => LOADED_LABEL.to_string(),
+
+            // Something is selected from the memory.
+            Slot::Memory(selected_idx) => match memory[*selected_idx].as_ref() {
+                // Whatever was previously selected got deleted off the memory.
+                None => {
+                    *selected = Slot::None;
+                    SELECT.to_string()
+                }
+
+                // Shows the name of the selected polytope.
+                Some(_) => slot_label(*selected_idx),
+            },
+        };
+
+        // The drop-down for selecting polytopes, either from memory or the
+        // currently loaded one.
+        egui::ComboBox::from_label(format!("#{}", slot_idx + 1))
+            .selected_text(selected_text)
+            .width(200.0)
+            .show_ui(ui, |ui| {
+                // The currently loaded polytope.
+                let mut loaded_selected = false;
+
+                ui.selectable_value(&mut loaded_selected, true, LOADED_LABEL);
+
+                // If the value was changed, update it.
+                if loaded_selected {
+                    *selected = Slot::Loaded;
+                }
+
+                // The polytopes in memory.
+                for (slot_idx, _) in memory
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, s)| s.as_ref().map(|s| (idx, s)))
+                {
+                    // This value couldn't be selected by the user.
+                    let mut slot_inner = None;
+
+                    ui.selectable_value(&mut slot_inner, Some(slot_idx), slot_label(slot_idx));
+
+                    // If the value was changed, update it.
+                    if let Some(idx) = slot_inner {
+                        *selected = Slot::Memory(idx);
+                    }
+                }
+            });
     }
 }
 
@@ -413,66 +585,9 @@ pub trait DuoWindow: Window {
     /// Builds the window to be shown on screen.
     fn build(&mut self, _: &mut Ui, _: &Concrete, _: &Memory) {}
 
+    /// Shows the drop-downs that select the polytopes.
     fn build_dropdowns(&mut self, ui: &mut Ui, memory: &Memory) {
-        const SELECT: &str = "Select";
-
-        // Iterates over both slots.
-        for (slot_idx, selected) in self.slots_mut().iter_mut().enumerate() {
-            // The text for the selected option.
-            let selected_text = match selected {
-                // Nothing has been selected.
-                Slot::None => SELECT.to_string(),
-
-                // The loaded polytope is selected.
-                Slot::Loaded => LOADED_LABEL.to_string(),
-
-                // Something is selected from the memory.
-                Slot::Memory(selected_idx) => match memory[*selected_idx].as_ref() {
-                    // Whatever was previously selected got deleted off the memory.
-                    None => {
-                        *selected = Slot::None;
-                        SELECT.to_string()
-                    }
-
-                    // Shows the name of the selected polytope.
-                    Some(_) => slot_label(*selected_idx),
-                },
-            };
-
-            // The drop-down for selecting polytopes, either from memory or the
-            // currently loaded one.
-            egui::ComboBox::from_label(format!("#{}", slot_idx + 1))
-                .selected_text(selected_text)
-                .width(200.0)
-                .show_ui(ui, |ui| {
-                    // The currently loaded polytope.
-                    let mut loaded_selected = false;
-
-                    ui.selectable_value(&mut loaded_selected, true, LOADED_LABEL);
-
-                    // If the value was changed, update it.
-                    if loaded_selected {
-                        *selected = Slot::Loaded;
-                    }
-
-                    // The polytopes in memory.
-                    for (slot_idx, _) in memory
-                        .iter()
-                        .enumerate()
-                        .filter_map(|(idx, s)| s.as_ref().map(|s| (idx, s)))
-                    {
-                        // This value couldn't be selected by the user.
-                        let mut slot_inner = None;
-
-                        ui.selectable_value(&mut slot_inner, Some(slot_idx), slot_label(slot_idx));
-
-                        // If the value was changed, update it.
-                        if let Some(idx) = slot_inner {
-                            *selected = Slot::Memory(idx);
-                        }
-                    }
-                });
-        }
+        slot_dropdowns(self.slots_mut(), ui, memory);
     }
 
     /// Resets a window to its default state.
@@ -541,10 +656,12 @@ impl<T: DuoWindow + 'static> Plugin for DuoWindowPlugin<T> {
     fn build(&self, app: &mut App) {
         app.init_resource::<T>()
             .add_system(T::show_system.system().label("show_windows"));
+        add_to_palette::<T>(app);
     }
 }
 
 /// A window that allows the user to build a dual with a specified hypersphere.
+#[derive(Clone)]
 pub struct DualWindow {
     /// Whether the window is open.
     open: bool,
@@ -553,7 +670,7 @@ pub struct DualWindow {
     center: Point,
 
     /// The radius of the sphere.
-    radius: Float,
+    radius: ExprValue,
 }
 
 impl Default for DualWindow {
@@ -561,7 +678,7 @@ impl Default for DualWindow {
         Self {
             open: false,
             center: Point::zeros(0),
-            radius: 1.0,
+            radius: ExprValue::new(1.0),
         }
     }
 }
@@ -580,7 +697,7 @@ impl Window for DualWindow {
 
 impl UpdateWindow for DualWindow {
     fn action(&self, polytope: &mut Concrete) {
-        let sphere = Hypersphere::with_radius(self.center.clone(), self.radius);
+        let sphere = Hypersphere::with_radius(self.center.clone(), self.radius.value());
 
         if let Err(err) = polytope.try_dual_mut_with(&sphere) {
             eprintln!("Dual failed: {}", err);
@@ -593,16 +710,7 @@ impl UpdateWindow for DualWindow {
 
     fn build(&mut self, ui: &mut Ui) {
         ui.add(PointWidget::new(&mut self.center, "Center"));
-
-        ui.horizontal(|ui| {
-            ui.add(
-                egui::DragValue::new(&mut self.radius)
-                    .speed(0.01)
-                    .clamp_range(0.0..=Float::MAX),
-            );
-
-            ui.label("Radius");
-        });
+        ui.add(ExprWidget::new(&mut self.radius, "Radius").non_negative());
     }
 
     fn dim(&self) -> usize {
@@ -612,7 +720,6 @@ impl UpdateWindow for DualWindow {
     fn default_with(dim: usize) -> Self {
         Self {
             center: Point::zeros(dim),
-            radius: 1.0,
             ..Default::default()
         }
     }
@@ -623,6 +730,7 @@ impl UpdateWindow for DualWindow {
 }
 
 /// A window that allows the user to build a pyramid with a specified apex.
+#[derive(Clone)]
 pub struct PyramidWindow {
     /// Whether the window is open.
     open: bool,
@@ -697,6 +805,7 @@ impl UpdateWindow for PyramidWindow {
 }
 
 /// Allows the user to build a prism with a given height.
+#[derive(Clone)]
 pub struct PrismWindow {
     /// Whether the window is open.
     open: bool,
@@ -705,6 +814,14 @@ pub struct PrismWindow {
     height: Float,
 }
 
+impl PrismWindow {
+    /// A mutable reference to the height of the prism, so that it can also be
+    /// set from the Operations menu.
+    pub fn height_mut(&mut self) -> &mut Float {
+        &mut self.height
+    }
+}
+
 impl Window for PrismWindow {
     const NAME: &'static str = "Prism";
 
@@ -747,34 +864,77 @@ impl Default for PrismWindow {
     }
 }
 
-/// Allows the user to build a tegum with the specified apices and a height.
-pub struct TegumWindow {
+/// Allows the user to build a lace prism, whose top base is a scaled copy of
+/// the polytope.
+#[derive(Clone)]
+pub struct LaceWindow {
     /// Whether the window is open.
     open: bool,
 
-    /// The offset of the apices from the origin.
-    offset: Point,
+    /// The scale of the top base.
+    top_scale: Float,
 
-    /// The height of the tegum (the distance between both apices).
+    /// The height of the lace prism.
     height: Float,
+}
 
-    /// How much the apices are offset up or down.
-    height_offset: Float,
+impl Window for LaceWindow {
+    const NAME: &'static str = "Lace prism";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
 }
 
-impl Default for TegumWindow {
+impl PlainWindow for LaceWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        *polytope = polytope.lace(self.top_scale, self.height);
+    }
+
+    fn name_action(&self, name: &mut String) {
+        *name = format!("Lace prism of {}", name);
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        // A scale of 0 would collapse the top into a point.
+        ui.add(egui::Slider::new(&mut self.top_scale, 0.01..=2.0).text("Top scale"));
+        ui.add(egui::Slider::new(&mut self.height, 0.0..=2.0).text("Height"));
+    }
+}
+
+impl Default for LaceWindow {
     fn default() -> Self {
         Self {
             open: false,
-            offset: Point::zeros(0),
+            top_scale: 0.5,
             height: 1.0,
-            height_offset: 0.0,
         }
     }
 }
 
-impl Window for TegumWindow {
-    const NAME: &'static str = "Tegum";
+/// Allows the user to build a Kleetope with a given height.
+#[derive(Clone)]
+pub struct KisWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The height of the pyramids over the facets.
+    height: Float,
+}
+
+impl KisWindow {
+    /// Sets the height of the pyramids over the facets.
+    pub fn set_height(&mut self, height: Float) {
+        self.height = height;
+    }
+}
+
+impl Window for KisWindow {
+    const NAME: &'static str = "Kleetope";
 
     fn is_open(&self) -> bool {
         self.open
@@ -785,121 +945,733 @@ impl Window for TegumWindow {
     }
 }
 
-impl UpdateWindow for TegumWindow {
+impl PlainWindow for KisWindow {
     fn action(&self, polytope: &mut Concrete) {
-        let half_height = self.height / 2.0;
-
-        *polytope = polytope.tegum_with(
-            self.offset.push(self.height_offset + half_height),
-            self.offset.push(self.height_offset - half_height),
-        );
+        *polytope = polytope.kis_with(self.height);
     }
 
     fn name_action(&self, name: &mut String) {
-        *name = format!("Tegum of {}", name);
+        *name = format!("Kleetope of {}", name);
     }
 
     fn build(&mut self, ui: &mut Ui) {
-        ui.add(PointWidget::new(&mut self.offset, "Offset"));
-
-        ui.horizontal(|ui| {
-            ui.add(
-                egui::DragValue::new(&mut self.height)
-                    .speed(0.01)
-                    .clamp_range(0.0..=Float::MAX),
-            );
-            ui.label("Height");
-        });
-
         ui.horizontal(|ui| {
-            ui.add(egui::DragValue::new(&mut self.height_offset).speed(0.01));
-            ui.label("Height offset");
+            ui.label("Height:");
+            ui.add(egui::DragValue::new(&mut self.height).speed(0.01));
         });
     }
+}
 
-    fn dim(&self) -> usize {
-        self.offset.len()
-    }
-
-    fn default_with(dim: usize) -> Self {
+impl Default for KisWindow {
+    fn default() -> Self {
         Self {
-            offset: Point::zeros(dim),
-            height: 1.0,
-            ..Default::default()
+            open: false,
+            height: 0.5,
         }
     }
+}
 
-    fn update(&mut self, dim: usize) {
-        resize(&mut self.offset, dim);
+/// Allows the user to chamfer a polyhedron with a given depth.
+#[derive(Clone)]
+pub struct ChamferWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// How far the faces are inset towards their centroids.
+    depth: Float,
+}
+
+impl Window for ChamferWindow {
+    const NAME: &'static str = "Chamfer";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
     }
 }
 
-/// Allows the user to select an antiprism from a specified hypersphere and a
-/// given height.
-pub struct AntiprismWindow {
-    /// The info about the hypersphere we use to get from one base to another.
-    dual: DualWindow,
+impl PlainWindow for ChamferWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        *polytope = polytope.chamfer_with(self.depth);
+    }
 
-    /// The height of the antiprism.
-    height: Float,
+    fn name_action(&self, name: &mut String) {
+        *name = format!("Chamfered {}", name);
+    }
 
-    /// Whether the antiprism is a retroprism.
-    retroprism: bool,
+    fn build(&mut self, ui: &mut Ui) {
+        ui.add(egui::Slider::new(&mut self.depth, 0.0..=1.0).text("Depth"));
+    }
 }
 
-impl Default for AntiprismWindow {
+impl Default for ChamferWindow {
     fn default() -> Self {
         Self {
-            dual: Default::default(),
-            height: 1.0,
-            retroprism: false,
+            open: false,
+            depth: 0.5,
         }
     }
 }
 
-impl Window for AntiprismWindow {
-    const NAME: &'static str = "Antiprism";
+/// Allows the user to remove a facet from the polytope, leaving it open.
+#[derive(Clone, Default)]
+pub struct RemoveFacetWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The index of the facet to remove.
+    facet: usize,
+}
+
+impl Window for RemoveFacetWindow {
+    const NAME: &'static str = "Remove facet";
 
     fn is_open(&self) -> bool {
-        self.dual.open
+        self.open
     }
 
     fn is_open_mut(&mut self) -> &mut bool {
-        &mut self.dual.open
+        &mut self.open
     }
 }
 
-impl UpdateWindow for AntiprismWindow {
+impl PlainWindow for RemoveFacetWindow {
     fn action(&self, polytope: &mut Concrete) {
-        let radius = self.dual.radius;
-        let mut squared_radius = radius * radius;
-        if self.retroprism {
-            squared_radius *= -1.0;
-        }
-
-        let sphere = Hypersphere::with_squared_radius(self.dual.center.clone(), squared_radius);
+        self.try_action(polytope);
+    }
 
-        match polytope.try_antiprism_with(&sphere, self.height) {
-            Ok(antiprism) => *polytope = antiprism,
-            Err(err) => eprintln!("Antiprism failed: {}", err),
+    fn try_action(&self, polytope: &mut Concrete) -> bool {
+        let removed = polytope.remove_facet(self.facet);
+        if removed {
+            println!("Facet {} removed.", self.facet);
+        } else {
+            eprintln!("Facet {} can't be removed.", self.facet);
         }
+
+        removed
     }
 
     fn name_action(&self, name: &mut String) {
-        *name = format!("Dual of {}", name);
+        *name = format!("Open {}", name);
     }
 
     fn build(&mut self, ui: &mut Ui) {
-        ui.add(PointWidget::new(&mut self.dual.center, "Center"));
-
         ui.horizontal(|ui| {
-            ui.add(
-                egui::DragValue::new(&mut self.dual.radius)
-                    .speed(0.01)
-                    .clamp_range(0.0..=Float::MAX),
-            );
-            ui.label("Radius");
+            ui.label("Facet:");
+            ui.add(egui::DragValue::new(&mut self.facet).speed(0.1));
         });
+    }
+}
+
+/// The cap that the [`AugmentWindow`] glues onto a facet.
+#[derive(Clone, Copy, PartialEq)]
+enum AugmentCap {
+    /// A cap built over the facet itself.
+    Built(CapType),
+
+    /// A polytope from memory, which must already be in place.
+    Memory(usize),
+}
+
+/// Allows the user to glue a cap onto a facet of the polytope.
+pub struct AugmentWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The index of the facet to augment.
+    facet: usize,
+
+    /// The cap to glue onto the facet.
+    cap: AugmentCap,
+
+    /// The height of the built caps.
+    height: Float,
+
+    /// A copy of the selected polytope from memory, if any.
+    memory_cap: Option<Concrete>,
+}
+
+impl Default for AugmentWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            facet: 0,
+            cap: AugmentCap::Built(CapType::Pyramid),
+            height: 0.5,
+            memory_cap: None,
+        }
+    }
+}
+
+impl Window for AugmentWindow {
+    const NAME: &'static str = "Augment";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl MemoryWindow for AugmentWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        let augmented = match self.cap {
+            AugmentCap::Built(cap_type) => polytope.augment(self.facet, cap_type, self.height),
+            AugmentCap::Memory(_) => self
+                .memory_cap
+                .as_ref()
+                .and_then(|cap| polytope.augment_with(self.facet, cap, crate::EPS)),
+        };
+
+        match augmented {
+            Some(p) => {
+                *polytope = p;
+                println!("Augmentation succeeded.");
+            }
+            None => eprintln!("Augmentation failed: the cap doesn't fit onto facet {}.", self.facet),
+        }
+    }
+
+    fn build(&mut self, ui: &mut Ui, memory: &Memory) {
+        ui.horizontal(|ui| {
+            ui.label("Facet:");
+            ui.add(egui::DragValue::new(&mut self.facet).speed(0.1));
+        });
+
+        ui.separator();
+
+        ui.label("Cap:");
+        ui.radio_value(&mut self.cap, AugmentCap::Built(CapType::Pyramid), "Pyramid");
+        ui.radio_value(&mut self.cap, AugmentCap::Built(CapType::Prism), "Prism");
+
+        if let AugmentCap::Built(_) = self.cap {
+            ui.horizontal(|ui| {
+                ui.label("Height:");
+                ui.add(egui::DragValue::new(&mut self.height).speed(0.01));
+            });
+        }
+
+        // Whatever was previously selected might have been deleted off the
+        // memory.
+        if let AugmentCap::Memory(idx) = self.cap {
+            if memory[idx].is_none() {
+                self.cap = AugmentCap::Built(CapType::Pyramid);
+                self.memory_cap = None;
+            }
+        }
+
+        let selected_text = match self.cap {
+            AugmentCap::Memory(idx) => slot_label(idx),
+            AugmentCap::Built(_) => "Select".to_string(),
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("From memory:");
+            egui::ComboBox::from_label("")
+                .selected_text(selected_text)
+                .width(200.0)
+                .show_ui(ui, |ui| {
+                    for (slot_idx, (poly, _)) in memory
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(idx, s)| s.as_ref().map(|s| (idx, s)))
+                    {
+                        let mut slot_inner = None;
+                        ui.selectable_value(&mut slot_inner, Some(slot_idx), slot_label(slot_idx));
+
+                        // We only copy the polytope when the selection changes.
+                        if slot_inner.is_some() && self.cap != AugmentCap::Memory(slot_idx) {
+                            self.cap = AugmentCap::Memory(slot_idx);
+                            self.memory_cap = Some(poly.clone());
+                        }
+                    }
+                });
+        });
+    }
+}
+
+/// Allows the user to build a polytope from a Coxeter diagram.
+#[derive(Clone)]
+pub struct CdWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The Coxeter diagram, as a string.
+    diagram: String,
+}
+
+impl Window for CdWindow {
+    const NAME: &'static str = "Coxeter diagram";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for CdWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        self.try_action(polytope);
+    }
+
+    fn try_action(&self, polytope: &mut Concrete) -> bool {
+        match Concrete::from_cd(&self.diagram) {
+            Ok(poly) => {
+                *polytope = poly;
+                true
+            }
+            Err(err) => {
+                eprintln!("Coxeter diagram failed: {}", err);
+                false
+            }
+        }
+    }
+
+    fn name_action(&self, name: &mut String) {
+        *name = self.diagram.clone();
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Diagram:");
+            ui.text_edit_singleline(&mut self.diagram);
+        });
+    }
+}
+
+impl Default for CdWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            diagram: "x4o3o".to_string(),
+        }
+    }
+}
+
+/// Allows the user to build a regular polygon from scratch.
+#[derive(Clone)]
+pub struct PolygonWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The number of sides of the polygon.
+    n: usize,
+}
+
+impl Window for PolygonWindow {
+    const NAME: &'static str = "Polygon";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for PolygonWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        *polytope = Concrete::polygon(self.n);
+    }
+
+    fn name_action(&self, name: &mut String) {
+        *name = format!("{}-gon", self.n);
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(&mut self.n)
+                    .speed(0.05)
+                    .clamp_range(3..=1000),
+            );
+            ui.label("Sides");
+        });
+    }
+}
+
+impl Default for PolygonWindow {
+    fn default() -> Self {
+        Self { open: false, n: 3 }
+    }
+}
+
+/// The families of regular polytopes that exist in every dimension.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RegularFamily {
+    /// The simplices.
+    Simplex,
+
+    /// The hypercubes.
+    Hypercube,
+
+    /// The orthoplexes.
+    Orthoplex,
+}
+
+impl RegularFamily {
+    /// The name of a member of the family, without the dimension.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Simplex => "simplex",
+            Self::Hypercube => "cube",
+            Self::Orthoplex => "orthoplex",
+        }
+    }
+}
+
+/// Allows the user to build a simplex, hypercube, or orthoplex of any
+/// dimension.
+#[derive(Clone)]
+pub struct RegularWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The family of the polytope.
+    family: RegularFamily,
+
+    /// The dimension of the polytope.
+    dim: usize,
+}
+
+impl Window for RegularWindow {
+    const NAME: &'static str = "Regular polytope";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for RegularWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        let rank = self.dim + 1;
+
+        *polytope = match self.family {
+            RegularFamily::Simplex => Concrete::simplex(rank),
+            RegularFamily::Hypercube => Concrete::hypercube(rank),
+            RegularFamily::Orthoplex => Concrete::orthoplex(rank),
+        };
+    }
+
+    fn name_action(&self, name: &mut String) {
+        *name = format!("{}-{}", self.dim, self.family.name());
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.radio_value(&mut self.family, RegularFamily::Simplex, "Simplex");
+        ui.radio_value(&mut self.family, RegularFamily::Hypercube, "Hypercube");
+        ui.radio_value(&mut self.family, RegularFamily::Orthoplex, "Orthoplex");
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(&mut self.dim)
+                    .speed(0.05)
+                    .clamp_range(1..=12),
+            );
+            ui.label("Dimension");
+        });
+    }
+}
+
+impl Default for RegularWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            family: RegularFamily::Hypercube,
+            dim: 3,
+        }
+    }
+}
+
+/// Allows the user to build any of the Wythoffian polytopes sharing the
+/// symmetry of a regular polytope, by picking which nodes of its Coxeter
+/// diagram are ringed.
+///
+/// The symmetry is only detected through the Schläfli symbol of the polytope,
+/// so the families of non-regular polytopes, even uniform ones, aren't found.
+#[derive(Clone, Default)]
+pub struct UniformFamilyWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The Schläfli symbol of the regular polytope whose symmetry we use.
+    symbol: Option<SchlafliSymbol>,
+
+    /// Which nodes of the diagram are ringed.
+    rings: Vec<bool>,
+}
+
+impl UniformFamilyWindow {
+    /// Opens the window for the family of a regular polytope with a given
+    /// Schläfli symbol, with the first node ringed.
+    pub fn load(&mut self, symbol: SchlafliSymbol) {
+        self.rings = vec![false; symbol.node_count()];
+        self.rings[0] = true;
+        self.symbol = Some(symbol);
+        self.open();
+    }
+
+    /// The Coxeter diagram of the selected polytope, if any.
+    fn diagram(&self) -> Option<String> {
+        self.symbol
+            .as_ref()
+            .map(|symbol| symbol.diagram(&self.rings))
+    }
+}
+
+impl Window for UniformFamilyWindow {
+    const NAME: &'static str = "Uniform family";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl PlainWindow for UniformFamilyWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        self.try_action(polytope);
+    }
+
+    fn try_action(&self, polytope: &mut Concrete) -> bool {
+        if let Some(diagram) = self.diagram() {
+            match Concrete::from_cd(&diagram) {
+                Ok(poly) => {
+                    *polytope = poly;
+                    return true;
+                }
+                Err(err) => eprintln!("Wythoff construction failed: {}", err),
+            }
+        }
+
+        false
+    }
+
+    fn reset(&mut self) {
+        // We keep the symmetry, and only reset the ringed nodes.
+        match self.symbol.take() {
+            Some(symbol) => self.load(symbol),
+            None => self.open(),
+        }
+    }
+
+    fn name_action(&self, name: &mut String) {
+        if let Some(diagram) = self.diagram() {
+            *name = diagram;
+        }
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        let symbol = match &self.symbol {
+            Some(symbol) => symbol.clone(),
+            None => {
+                ui.label("Load a regular polytope to see its family.");
+                ui.label("Only the symmetries of regular polytopes are detected.");
+                return;
+            }
+        };
+
+        ui.label(format!("Symmetry of {}", symbol));
+        let n = symbol.node_count();
+
+        // Every nonempty set of ringed nodes, one row per number of rings.
+        egui::containers::ScrollArea::auto_sized().show(ui, |ui| {
+            egui::Grid::new("uniform_family").show(ui, |ui| {
+                for count in 1..=n {
+                    ui.label(format!("{} ringed", count));
+
+                    for mask in 1..(1usize << n) {
+                        if mask.count_ones() as usize != count {
+                            continue;
+                        }
+
+                        let rings: Vec<_> = (0..n).map(|i| mask & (1 << i) != 0).collect();
+                        if ui
+                            .selectable_label(rings == self.rings, symbol.diagram(&rings))
+                            .clicked()
+                        {
+                            self.rings = rings;
+                        }
+                    }
+
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
+
+/// Allows the user to build a tegum with the specified apices and a height.
+#[derive(Clone)]
+pub struct TegumWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The offset of the apices from the origin.
+    offset: Point,
+
+    /// The height of the tegum (the distance between both apices).
+    height: Float,
+
+    /// How much the apices are offset up or down.
+    height_offset: Float,
+}
+
+impl Default for TegumWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            offset: Point::zeros(0),
+            height: 1.0,
+            height_offset: 0.0,
+        }
+    }
+}
+
+impl Window for TegumWindow {
+    const NAME: &'static str = "Tegum";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl UpdateWindow for TegumWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        let half_height = self.height / 2.0;
+
+        *polytope = polytope.tegum_with(
+            self.offset.push(self.height_offset + half_height),
+            self.offset.push(self.height_offset - half_height),
+        );
+    }
+
+    fn name_action(&self, name: &mut String) {
+        *name = format!("Tegum of {}", name);
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.add(PointWidget::new(&mut self.offset, "Offset"));
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::DragValue::new(&mut self.height)
+                    .speed(0.01)
+                    .clamp_range(0.0..=Float::MAX),
+            );
+            ui.label("Height");
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut self.height_offset).speed(0.01));
+            ui.label("Height offset");
+        });
+    }
+
+    fn dim(&self) -> usize {
+        self.offset.len()
+    }
+
+    fn default_with(dim: usize) -> Self {
+        Self {
+            offset: Point::zeros(dim),
+            height: 1.0,
+            ..Default::default()
+        }
+    }
+
+    fn update(&mut self, dim: usize) {
+        resize(&mut self.offset, dim);
+    }
+}
+
+/// Allows the user to select an antiprism from a specified hypersphere and a
+/// given height.
+#[derive(Clone)]
+pub struct AntiprismWindow {
+    /// The info about the hypersphere we use to get from one base to another.
+    dual: DualWindow,
+
+    /// The height of the antiprism.
+    height: Float,
+
+    /// Whether the antiprism is a retroprism.
+    retroprism: bool,
+}
+
+impl Default for AntiprismWindow {
+    fn default() -> Self {
+        Self {
+            dual: Default::default(),
+            height: 1.0,
+            retroprism: false,
+        }
+    }
+}
+
+impl AntiprismWindow {
+    /// A mutable reference to the height of the antiprism, so that it can also
+    /// be set from the Operations menu.
+    pub fn height_mut(&mut self) -> &mut Float {
+        &mut self.height
+    }
+}
+
+impl Window for AntiprismWindow {
+    const NAME: &'static str = "Antiprism";
+
+    fn is_open(&self) -> bool {
+        self.dual.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.dual.open
+    }
+}
+
+impl UpdateWindow for AntiprismWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        let radius = self.dual.radius.value();
+        let mut squared_radius = radius * radius;
+        if self.retroprism {
+            squared_radius *= -1.0;
+        }
+
+        let sphere = Hypersphere::with_squared_radius(self.dual.center.clone(), squared_radius);
+
+        match polytope.try_antiprism_with(&sphere, self.height) {
+            Ok(antiprism) => *polytope = antiprism,
+            Err(err) => eprintln!("Antiprism failed: {}", err),
+        }
+    }
+
+    fn name_action(&self, name: &mut String) {
+        *name = format!("Antiprism of {}", name);
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.add(PointWidget::new(&mut self.dual.center, "Center"));
+        ui.add(ExprWidget::new(&mut self.dual.radius, "Radius").non_negative());
 
         ui.horizontal(|ui| {
             ui.add(egui::DragValue::new(&mut self.height).speed(0.01));
@@ -1369,7 +2141,7 @@ impl DuoWindow for CompoundWindow {
             Slot::None => "".to_string(),
         };
 
-        *name = format!("Compound of ({}, {})", name_a, name_b);
+        *name = compound_name(&name_a, &name_b);
     }
 
     fn slots(&self) -> [Slot; 2] {
@@ -1381,8 +2153,118 @@ impl DuoWindow for CompoundWindow {
     }
 }
 
-/// A window to configure a truncation of the polytope.
+/// A window that compares two polytopes, either from memory or the currently
+/// loaded one, and reports whether they're combinatorially isomorphic.
 #[derive(Default)]
+pub struct CompareWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The slots that are currently selected.
+    slots: [Slot; 2],
+
+    /// Whether to also check whether the polytopes are congruent.
+    congruence: bool,
+
+    /// The result of the last comparison.
+    verdict: Option<String>,
+}
+
+impl CompareWindow {
+    /// Compares two polytopes, and returns the verdict.
+    fn compare(&self, p: &Concrete, q: &Concrete) -> String {
+        let mut p = p.clone();
+        p.element_sort();
+        let mut q = q.clone();
+        q.element_sort();
+
+        if !p.abs.is_isomorphic(&q.abs) {
+            "The polytopes are not isomorphic.".to_string()
+        } else if !self.congruence {
+            "The polytopes are isomorphic.".to_string()
+        } else if p.is_congruent(&q, Float::EPS) {
+            "The polytopes are isomorphic and congruent.".to_string()
+        } else {
+            "The polytopes are isomorphic, but not congruent.".to_string()
+        }
+    }
+
+    /// Shows the window on screen. Comparing never changes the polytopes, so
+    /// unlike a [`DuoWindow`], this only needs to read them.
+    fn show(&mut self, ctx: &CtxRef, polytope: &Concrete, memory: &Memory) {
+        let mut open = self.is_open();
+
+        egui::Window::new(Self::NAME)
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                slot_dropdowns(&mut self.slots, ui, memory);
+                ui.checkbox(&mut self.congruence, "Also check congruence");
+
+                let [i, j] = self.slots;
+                let (p, q) = (i.to_poly(memory, polytope), j.to_poly(memory, polytope));
+                let compare =
+                    ui.add(egui::Button::new("Compare").enabled(p.is_some() && q.is_some()));
+
+                if compare.clicked() {
+                    if let (Some(p), Some(q)) = (p, q) {
+                        let verdict = self.compare(p, q);
+                        println!("{}", verdict);
+                        self.verdict = Some(verdict);
+                    }
+                }
+
+                if let Some(verdict) = &self.verdict {
+                    ui.label(verdict);
+                }
+            });
+
+        self.open = open;
+    }
+
+    /// The system that shows the window.
+    fn show_system(
+        mut self_: ResMut<'_, Self>,
+        egui_ctx: Res<'_, EguiContext>,
+        query: Query<'_, '_, &Concrete>,
+        memory: Res<'_, Memory>,
+    ) {
+        if let Some(polytope) = query.iter().next() {
+            self_.show(egui_ctx.ctx(), polytope, &memory);
+        }
+    }
+
+    /// A plugin that adds the window as a resource and the system to show it.
+    pub fn plugin() -> CompareWindowPlugin {
+        CompareWindowPlugin
+    }
+}
+
+impl Window for CompareWindow {
+    const NAME: &'static str = "Compare";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+/// A plugin that adds all of the necessary systems for the [`CompareWindow`].
+pub struct CompareWindowPlugin;
+
+impl Plugin for CompareWindowPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CompareWindow>()
+            .add_system(CompareWindow::show_system.system().label("show_windows"));
+        add_to_palette::<CompareWindow>(app);
+    }
+}
+
+/// A window to configure a truncation of the polytope.
+#[derive(Clone, Default)]
 pub struct TruncateWindow {
     /// Whether the window is open.
     open: bool,
@@ -1394,7 +2276,7 @@ pub struct TruncateWindow {
     truncate_type: Vec<bool>,
 
     /// The weights applied to the coordinates. Intuitively, the truncation depths.
-    depth: Vec<f64>,
+    depth: Vec<Float>,
 }
 
 impl Window for TruncateWindow {
@@ -1454,14 +2336,35 @@ impl UpdateWindow for TruncateWindow {
     }
 }
 
-/// A window that scales a polytope.
-#[derive(Default)]
+/// A window that scales a polytope, either uniformly or along each axis.
+#[derive(Clone)]
 pub struct ScaleWindow {
     /// Whether the window is open.
     open: bool,
 
+    /// The dimension of the polytope.
+    dim: usize,
+
     /// The scale factor.
-    scale: f64,
+    scale: ExprValue,
+
+    /// Whether each axis gets its own scale factor.
+    per_axis: bool,
+
+    /// The scale factors along each axis.
+    factors: Vec<ExprValue>,
+}
+
+impl Default for ScaleWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            dim: 0,
+            scale: ExprValue::new(1.0),
+            per_axis: false,
+            factors: Vec::new(),
+        }
+    }
 }
 
 impl Window for ScaleWindow {
@@ -1476,21 +2379,142 @@ impl Window for ScaleWindow {
     }
 }
 
-impl PlainWindow for ScaleWindow {
+impl UpdateWindow for ScaleWindow {
     fn action(&self, polytope: &mut Concrete) {
-        polytope.scale(self.scale);
+        if !self.per_axis {
+            polytope.scale(self.scale.value());
+        } else if self.factors.len() == polytope.dim_or() {
+            // Nonuniform scaling is just a diagonal linear transformation.
+            let factors = self.factors.iter().map(ExprValue::value).collect();
+            let m = Matrix::from_diagonal(&Point::from_vec(factors));
+            *polytope = polytope.clone().apply(&m);
+        } else {
+            println!("The scale factors don't match the dimension of the polytope.");
+        }
     }
 
     fn name_action(&self, _name: &mut String) {}
 
     fn build(&mut self, ui: &mut Ui) {
+        ui.add(egui::Checkbox::new(&mut self.per_axis, "Scale each axis separately"));
+
+        if self.per_axis {
+            for (i, factor) in self.factors.iter_mut().enumerate() {
+                ui.add(ExprWidget::new(factor, format!("Axis {}", i + 1)));
+            }
+        } else {
+            ui.add(ExprWidget::new(&mut self.scale, "Scale factor"));
+        }
+    }
+
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    fn default_with(dim: usize) -> Self {
+        Self {
+            dim,
+            factors: vec![ExprValue::new(1.0); dim],
+            ..Default::default()
+        }
+    }
+
+    fn update(&mut self, dim: usize) {
+        if self.dim != dim {
+            self.dim = dim;
+            self.factors = vec![ExprValue::new(1.0); dim];
+        }
+    }
+}
+
+/// Returns the unit vector along the first axis, or the empty vector in 0D.
+fn first_axis(dim: usize) -> Point {
+    let mut axis = Point::zeros(dim);
+    if dim != 0 {
+        axis[0] = 1.0;
+    }
+    axis
+}
+
+/// Allows the user to reflect a polytope across a hyperplane.
+#[derive(Clone)]
+pub struct ReflectWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The dimension of the polytope.
+    dim: usize,
+
+    /// The normal vector of the hyperplane.
+    normal: Point,
+
+    /// The distance from the hyperplane to the origin.
+    pos: Float,
+}
+
+impl Default for ReflectWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            dim: 0,
+            normal: Point::zeros(0),
+            pos: 0.0,
+        }
+    }
+}
+
+impl Window for ReflectWindow {
+    const NAME: &'static str = "Reflect";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl UpdateWindow for ReflectWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        if self.dim == 0 {
+            println!("A polytope in 0D can't be reflected.");
+        } else {
+            polytope.reflect(&Hyperplane::new(self.normal.clone(), self.pos));
+        }
+    }
+
+    fn name_action(&self, name: &mut String) {
+        *name = format!("Reflected {}", name);
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.add(UnitPointWidget::new(&mut self.normal, "Normal"));
+
         ui.horizontal(|ui| {
-            ui.add(
-                egui::DragValue::new(&mut self.scale)
-                    .speed(0.01)
-            );
+            ui.add(egui::DragValue::new(&mut self.pos).speed(0.01));
+            ui.label("Distance from origin");
         });
     }
+
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    fn default_with(dim: usize) -> Self {
+        Self {
+            dim,
+            normal: first_axis(dim),
+            ..Default::default()
+        }
+    }
+
+    fn update(&mut self, dim: usize) {
+        if self.dim != dim {
+            self.dim = dim;
+            self.normal = first_axis(dim);
+        }
+    }
 }
 
 /// Where to get the symmetry group for faceting
@@ -1528,25 +2552,25 @@ pub struct FacetingSettings {
     pub do_min_edge_length: bool,
 
     /// The minimum edge length.
-    pub min_edge_length: f64,
+    pub min_edge_length: Float,
 
     /// Whether to use a maximum edge length.
     pub do_max_edge_length: bool,
 
     /// The maximum edge length.
-    pub max_edge_length: f64,
+    pub max_edge_length: Float,
 
     /// Whether to use a minimum inradius.
     pub do_min_inradius: bool,
 
     /// The minimum inradius.
-    pub min_inradius: f64,
+    pub min_inradius: Float,
 
     /// Whether to use a maximum inradius.
     pub do_max_inradius: bool,
 
     /// The maximum inradius.
-    pub max_inradius: f64,
+    pub max_inradius: Float,
 
     /// Whether to exclude planes passing through the origin.
     pub exclude_hemis: bool,
@@ -1577,6 +2601,9 @@ pub struct FacetingSettings {
 
     /// The path to save to, if saving to file.
     pub file_path: String,
+
+    /// How far the last enumeration got, if it gave up.
+    pub aborted: Option<String>,
 }
 
 impl Default for FacetingSettings {
@@ -1606,6 +2633,7 @@ impl Default for FacetingSettings {
             save_facets: false,
             save_to_file: false,
             file_path: "".to_string(),
+            aborted: None,
         }
     }
 }
@@ -1819,7 +2847,7 @@ impl MemoryWindow for FacetingSettings {
 
 
 /// Rotation window for Transform tab
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct RotateWindow {
     /// Whether the window is open.
     open: bool,
@@ -1828,7 +2856,7 @@ pub struct RotateWindow {
     rank: usize,
 
     /// List of rotations (in radians). Rotates around xy plane, then yz plane, then zw plane, etc.
-    rots: Vec<f64>,
+    rots: Vec<ExprValue>,
 	
 	/// Determines if radians or degrees are used.
 	degcheck: bool,
@@ -1854,7 +2882,7 @@ impl UpdateWindow for RotateWindow {
 			if self.degcheck { //Degrees
 				for ind in 0..self.rank-1 {
 					for v in polytope.vertices_mut() {
-						let theta = self.rots[ind]*0.017453292519943295;
+						let theta = self.rots[ind].value()*0.017453292519943295;
 						
 						let x = v[ind]*theta.cos() - v[ind+1]*theta.sin();
 						let y = v[ind]*theta.sin() + v[ind+1]*theta.cos();
@@ -1866,7 +2894,7 @@ impl UpdateWindow for RotateWindow {
 			else { //Radians
 				for ind in 0..self.rank-1 {
 					for v in polytope.vertices_mut() {
-						let theta = self.rots[ind];
+						let theta = self.rots[ind].value();
 						
 						let x = v[ind]*theta.cos() - v[ind+1]*theta.sin();
 						let y = v[ind]*theta.sin() + v[ind+1]*theta.cos();
@@ -1891,14 +2919,7 @@ impl UpdateWindow for RotateWindow {
     fn build(&mut self, ui: &mut Ui) {
         ui.add(egui::Checkbox::new(&mut self.degcheck, "Use degrees instead of radians"));
 		for r in 0..self.rank-1 {
-            ui.horizontal(|ui| {
-				if self.degcheck {
-					ui.add(egui::DragValue::new(&mut self.rots[r]).speed(1.0).clamp_range(0.0..=360.0));
-				}
-                else{
-					ui.add(egui::DragValue::new(&mut self.rots[r]).speed(0.01).clamp_range(0.0..=6.283185307179586));
-				}
-            });
+            ui.add(ExprWidget::new(&mut self.rots[r], format!("Axes {} and {}", r + 1, r + 2)));
         }
     }
 	
@@ -1909,19 +2930,20 @@ impl UpdateWindow for RotateWindow {
     fn default_with(dim: usize) -> Self {
         Self {
             rank: dim,
-            rots: vec![0.0001; dim], // if this is set to 0 the whole window becomes dark for some reason
+            rots: vec![ExprValue::default(); dim],
             ..Default::default()
         }
     }
 
     fn update(&mut self, dim: usize) {
         self.rank = dim;
-        self.rots = vec![0.0; dim];
+        self.rots = vec![ExprValue::default(); dim];
     }
 }
 
 /// Plane rotation window (Rotate with plane... window)
 
+#[derive(Clone)]
 pub struct PlaneWindow {
     /// Whether the window is open.
     open: bool,
@@ -1930,7 +2952,7 @@ pub struct PlaneWindow {
     rank: usize,
 
     /// Rotation amount (radians).
-    rot: f64,
+    rot: ExprValue,
 	
 	/// Coordinates of points.
 	p1: Point,
@@ -1947,7 +2969,7 @@ impl Default for PlaneWindow {
             open: false,
 			rank: Default::default(),
 			
-			rot: 0.0,
+			rot: ExprValue::default(),
 			
             p1: Point::zeros(0),
 			p2: Point::zeros(0),
@@ -1970,7 +2992,7 @@ impl Window for PlaneWindow {
     }
 }
 
-fn dot(u: &Vec<f64>, v: &Vec<f64>) -> f64 {
+fn dot(u: &Vec<Float>, v: &Vec<Float>) -> Float {
 	let mut sum = 0.0;
 	for i in 0..u.len() {
 		sum += u[i]*v[i];
@@ -1983,17 +3005,17 @@ impl UpdateWindow for PlaneWindow {
 		if self.p1 == Point::zeros(self.rank) || self.p2 == Point::zeros(self.rank) {
 			println!("Points within plane cannot be located at the origin.");
 		}
-		else if self.rot == 0.0 {
+		else if self.rot.value() == 0.0 {
 			println!("Rotated, but the rotation amount was set to 0 so there was no change.");
 		}
 		else {
 			//Step 0: Make plane of orthonormal basis based on input
-			//Make points p1 and p2 into unit Vec<f64> objects.
-			let ss1: f64 = self.p1.iter().map(|&x| x*x).sum();
-			let ss2: f64 = self.p2.iter().map(|&x| x*x).sum();
+			//Make points p1 and p2 into unit Vec<Float> objects.
+			let ss1: Float = self.p1.iter().map(|&x| x*x).sum();
+			let ss2: Float = self.p2.iter().map(|&x| x*x).sum();
 			
-			let mut v1: Vec<f64> = Vec::new();
-			let mut v2: Vec<f64> = Vec::new();
+			let mut v1: Vec<Float> = Vec::new();
+			let mut v2: Vec<Float> = Vec::new();
 			
 			for i in 0..self.rank {
 				v1.push(self.p1[i]/ss1.sqrt());
@@ -2002,7 +3024,7 @@ impl UpdateWindow for PlaneWindow {
 			
 			//Implement Gram-Schmidt process to make vectors orthonormal
 			let prod = dot(&v1,&v2)/dot(&v2,&v2);
-			let mut u2: Vec<f64> = Vec::new();
+			let mut u2: Vec<Float> = Vec::new();
 			for i in 0..v1.len() {
 				u2.push(v2[i] - v1[i] * prod)
 			}
@@ -2020,10 +3042,10 @@ impl UpdateWindow for PlaneWindow {
 			
 			let mut theta = 0.0;
 			if self.degcheck { //theta is the rotation amount in radians, which may or may not need conversion
-				theta = self.rot;
+				theta = self.rot.value();
 			}
 			else {
-				theta = self.rot * 0.017453292519943295;
+				theta = self.rot.value() * 0.017453292519943295;
 			}
 			
 			for v in polytope.vertices_mut() {
@@ -2070,17 +3092,7 @@ impl UpdateWindow for PlaneWindow {
     fn build(&mut self, ui: &mut Ui) {
         ui.add(egui::Checkbox::new(&mut self.degcheck, "Use degrees instead of radians"));
 		
-		ui.horizontal(|ui| {
-			
-			if self.degcheck {
-			   ui.add(egui::DragValue::new(&mut self.rot).speed(1.0).clamp_range::<f64>(0.0..=360.0));
-			}
-			else{
-				ui.add(egui::DragValue::new(&mut self.rot).speed(0.01).clamp_range::<f64>(0.0..=6.283185307179586));
-			}
-			
-			ui.label("Rotation"); 
-        });
+		ui.add(ExprWidget::new(&mut self.rot, "Rotation"));
 		
 		
 		ui.separator();
@@ -2097,7 +3109,6 @@ impl UpdateWindow for PlaneWindow {
     fn default_with(dim: usize) -> Self {
         Self {
             rank: dim,
-			rot: 0.0,
             p1: Point::zeros(dim),
 			p2: Point::zeros(dim),
             ..Default::default()
@@ -2109,4 +3120,219 @@ impl UpdateWindow for PlaneWindow {
         self.p1 = Point::zeros(dim);
 		self.p2 = Point::zeros(dim);
     }
-}
\ No newline at end of file
+}
+/// The largest group the orbit window is willing to generate, so that infinite
+/// groups don't freeze the application.
+const MAX_ORDER: usize = 20_000;
+
+/// A generator for the group in the [`OrbitWindow`].
+#[derive(Clone)]
+pub enum Generator {
+    /// A reflection through the hyperplane with a given normal vector.
+    Reflection(Point),
+
+    /// A rotation by 2π / `n` on the plane spanned by two coordinate axes. If
+    /// both axes are the same, this is the identity.
+    Rotation { axes: [usize; 2], n: u32 },
+}
+
+impl Generator {
+    /// Returns the matrix of the generator, in a given number of dimensions.
+    fn matrix(&self, dim: usize) -> Matrix<Float> {
+        let mut matrix = Matrix::identity(dim, dim);
+
+        match self {
+            Self::Reflection(normal) => matrix -= normal * normal.transpose() * 2.0,
+            Self::Rotation { axes: [i, j], .. } if i == j => {}
+            Self::Rotation { axes: [i, j], n } => {
+                let (s, c) = (Float::TAU / *n as Float).fsin_cos();
+                matrix[(*i, *i)] = c;
+                matrix[(*i, *j)] = -s;
+                matrix[(*j, *i)] = s;
+                matrix[(*j, *j)] = c;
+            }
+        }
+
+        matrix
+    }
+}
+
+/// Allows the user to build the convex hull of the orbit of a point under some
+/// group, either the symmetry group of the polytope on screen, or the one
+/// generated by some reflections and rotations.
+#[derive(Clone)]
+pub struct OrbitWindow {
+    /// Whether the window is open.
+    open: bool,
+
+    /// The dimension of the polytope.
+    dim: usize,
+
+    /// Whether to use the symmetry group of the polytope on screen.
+    own_symmetry: bool,
+
+    /// The generators of the group, if we're not using the symmetry group of
+    /// the polytope.
+    generators: Vec<Generator>,
+
+    /// The point whose orbit we take.
+    seed: Point,
+
+    /// The number of elements above which the hull gives up, if any.
+    max_elements: Option<usize>,
+}
+
+impl Default for OrbitWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            dim: 0,
+            own_symmetry: true,
+            generators: Vec::new(),
+            seed: Point::zeros(0),
+            max_elements: None,
+        }
+    }
+}
+
+impl OrbitWindow {
+    /// Returns the number of elements above which the hull gives up, if any.
+    pub fn max_elements(&self) -> Option<usize> {
+        self.max_elements
+    }
+
+    /// Sets the number of elements above which the hull gives up.
+    pub fn set_max_elements(&mut self, max_elements: Option<usize>) {
+        self.max_elements = max_elements;
+    }
+
+    /// Builds the group to take the orbit under, or returns `None` if it's too
+    /// large or if the symmetry of the polytope couldn't be computed.
+    fn group(&self, polytope: &mut Concrete) -> Option<Group<std::vec::IntoIter<Matrix<Float>>>> {
+        if self.own_symmetry {
+            return polytope.get_symmetry_group().map(|(group, _)| group);
+        }
+
+        let gens = self.generators.iter().map(|g| g.matrix(self.dim)).collect();
+        let elements: Vec<_> = Group::from(GenIter::new(self.dim, gens))
+            .take(MAX_ORDER + 1)
+            .collect();
+
+        if elements.len() > MAX_ORDER {
+            println!("The group has more than {} elements.", MAX_ORDER);
+            None
+        } else {
+            // Safety: the elements were generated as a group.
+            Some(unsafe { Group::new(self.dim, elements.into_iter()) })
+        }
+    }
+}
+
+impl Window for OrbitWindow {
+    const NAME: &'static str = "Symmetry orbit";
+
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_open_mut(&mut self) -> &mut bool {
+        &mut self.open
+    }
+}
+
+impl UpdateWindow for OrbitWindow {
+    fn action(&self, polytope: &mut Concrete) {
+        self.try_action(polytope);
+    }
+
+    fn try_action(&self, polytope: &mut Concrete) -> bool {
+        if self.dim == 0 {
+            println!("Orbits can't be taken in 0D.");
+        } else if let Some(group) = self.group(polytope) {
+            let max_elements = self.max_elements.unwrap_or(usize::MAX);
+            match Concrete::orbit_hull_capped(vec![self.seed.clone()], group, max_elements) {
+                Ok(hull) => {
+                    *polytope = hull;
+                    return true;
+                }
+                Err(err) => println!("Orbit hull aborted: {}.", err),
+            }
+        }
+
+        false
+    }
+
+    fn name_action(&self, name: &mut String) {
+        *name = format!("Orbit hull in {}D", self.dim);
+    }
+
+    fn build(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.own_symmetry, "Use the symmetry of the current polytope");
+
+        if !self.own_symmetry {
+            let dim = self.dim;
+            let mut removed = None;
+
+            for (idx, generator) in self.generators.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    match generator {
+                        Generator::Reflection(normal) => {
+                            ui.add(UnitPointWidget::new(normal, "Reflection"));
+                        }
+                        Generator::Rotation { axes, n } => {
+                            for axis in axes.iter_mut() {
+                                ui.add(egui::DragValue::new(axis).clamp_range(0..=dim.saturating_sub(1)));
+                            }
+                            ui.add(egui::DragValue::new(n).clamp_range(1..=1000));
+                            ui.label("Rotation (axes, order)");
+                        }
+                    }
+
+                    if ui.button("Remove").clicked() {
+                        removed = Some(idx);
+                    }
+                });
+            }
+
+            if let Some(idx) = removed {
+                self.generators.remove(idx);
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("Add reflection").clicked() {
+                    self.generators.push(Generator::Reflection(first_axis(dim)));
+                }
+
+                if ui.add(egui::Button::new("Add rotation").enabled(dim >= 2)).clicked() {
+                    self.generators.push(Generator::Rotation { axes: [0, 1], n: 2 });
+                }
+            });
+        }
+
+        ui.separator();
+        ui.add(PointWidget::new(&mut self.seed, "Seed point"));
+    }
+
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    fn default_with(dim: usize) -> Self {
+        Self {
+            dim,
+            seed: first_axis(dim),
+            ..Default::default()
+        }
+    }
+
+    fn update(&mut self, dim: usize) {
+        if self.dim != dim {
+            *self = Self {
+                open: self.open,
+                own_symmetry: self.own_symmetry,
+                max_elements: self.max_elements,
+                ..Self::default_with(dim)
+            };
+        }
+    }
+}